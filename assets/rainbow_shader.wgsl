@@ -2,6 +2,9 @@
 #import kayak_ui::sample_quad sample_quad
 #import kayak_ui::vertex_output VertexOutput
 
+// Importing `VertexOutput` rather than declaring it keeps this shader in step with the UI's vertex
+// layout. `sample_quad` rounds the corners using `in.border_radius`, which holds all four radii.
+
 fn hsv2rgb(c: vec3<f32>) -> vec3<f32>
 {
     let K = vec4(1.0, 2.0 / 3.0, 1.0 / 3.0, 3.0);
@@ -106,6 +106,24 @@ fn startup(
                                 />
                             </AccordionDetailsBundle>
                         </AccordionContextBundle>
+                        <CollapsibleBundle
+                            collapsible={Collapsible {
+                                title: "Collapsible".into(),
+                                ..Default::default()
+                            }}
+                            styles={KStyle {
+                                top: Units::Pixels(10.0).into(),
+                                ..Default::default()
+                            }}
+                        >
+                            <TextWidgetBundle
+                                text={TextProps {
+                                    content: "A single panel that doesn't need a group around it.".into(),
+                                    size: 14.0,
+                                    ..Default::default()
+                                }}
+                            />
+                        </CollapsibleBundle>
                     </ScrollBoxBundle>
                 </ScrollContextProviderBundle>
             </WindowBundle>
@@ -38,6 +38,7 @@ fn startup(
                                 radius: 30.0,
                                 offset: Vec2::new(0.0, 20.0),
                                 spread: Vec2::new(0.0, -10.0),
+                                inset: false,
                             }
                         ].into(),
                         ..Default::default()
@@ -57,6 +58,7 @@ fn startup(
                                 radius: 12.0,
                                 offset: Vec2::new(0.0, 4.0),
                                 spread: Vec2::new(0.0, 0.0),
+                                inset: false,
                             }
                         ].into(),
                         ..Default::default()
@@ -166,6 +168,18 @@ fn startup(
                         ..Default::default()
                     }}
                 />
+                <BackgroundBundle
+                    styles={KStyle {
+                        background_color: Color::WHITE.into(),
+                        left: Units::Pixels(50.0).into(),
+                        top: Units::Pixels(50.0).into(),
+                        border_radius: Corner::all(6.0).into(),
+                        width: Units::Pixels(182.0).into(),
+                        height: Units::Pixels(182.0).into(),
+                        box_shadow: BoxShadow::from_string("box-shadow: inset rgba(50, 50, 93, 0.25) 0px 30px 60px -12px, inset rgba(0, 0, 0, 0.3) 0px 18px 36px -18px;").into(),
+                        ..Default::default()
+                    }}
+                />
             </ElementBundle>
         </KayakAppBundle>
     };
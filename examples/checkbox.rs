@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let row_styles = KStyle {
+        layout_type: LayoutType::Row.into(),
+        col_between: Units::Pixels(10.0).into(),
+        height: Units::Auto.into(),
+        bottom: Units::Pixels(10.0).into(),
+        ..Default::default()
+    };
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Checkbox and radio button example".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(300.0, 250.0),
+                    ..KWindow::default()
+                }}
+            >
+                <ElementBundle styles={row_styles.clone()}>
+                    <CheckboxBundle />
+                    <CheckboxBundle checkbox={CheckboxProps { checked: true, ..Default::default() }} />
+                    <CheckboxBundle checkbox={CheckboxProps { disabled: true, ..Default::default() }} />
+                </ElementBundle>
+                <RadioContextProviderBundle>
+                    <ElementBundle styles={row_styles}>
+                        <RadioButtonBundle
+                            radio_button={RadioButtonProps {
+                                group_id: "size".into(),
+                                value: "small".into(),
+                                selected: true,
+                                ..Default::default()
+                            }}
+                        />
+                        <RadioButtonBundle
+                            radio_button={RadioButtonProps {
+                                group_id: "size".into(),
+                                value: "medium".into(),
+                                ..Default::default()
+                            }}
+                        />
+                        <RadioButtonBundle
+                            radio_button={RadioButtonProps {
+                                group_id: "size".into(),
+                                value: "large".into(),
+                                ..Default::default()
+                            }}
+                        />
+                    </ElementBundle>
+                </RadioContextProviderBundle>
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn print_changes(
+    mut checkboxes: EventReader<CheckboxChanged>,
+    mut radio_buttons: EventReader<RadioButtonChanged>,
+) {
+    for event in checkboxes.iter() {
+        info!("Checkbox {:?} checked: {}", event.entity, event.checked);
+    }
+    for event in radio_buttons.iter() {
+        info!("Selected {} in group {}", event.value, event.group_id);
+    }
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .add_systems(Update, print_changes)
+        .run()
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Context menu example".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(300.0, 250.0),
+                    ..KWindow::default()
+                }}
+            >
+                <ContextMenuBundle
+                    context_menu={ContextMenuProps {
+                        items: vec!["Cut".into(), "Copy".into(), "Paste".into()],
+                        ..Default::default()
+                    }}
+                >
+                    <TextWidgetBundle
+                        text={TextProps {
+                            content: "Right-click anywhere in this window".into(),
+                            size: 16.0,
+                            ..Default::default()
+                        }}
+                    />
+                </ContextMenuBundle>
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn print_selections(mut selected: EventReader<ContextMenuSelected>) {
+    for event in selected.iter() {
+        info!("Picked {} (item {})", event.value, event.index);
+    }
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .add_systems(Update, print_selections)
+        .run()
+}
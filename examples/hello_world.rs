@@ -1,38 +1,32 @@
 use bevy::prelude::*;
-use kayak_ui::{
-    prelude::{widgets::*, *},
-    CameraUIKayak,
-};
+use kayak_ui::prelude::{widgets::*, *};
 
 fn startup(
     mut commands: Commands,
     mut font_mapping: ResMut<FontMapping>,
     asset_server: Res<AssetServer>,
 ) {
-    let camera_entity = commands
-        .spawn(Camera2dBundle::default())
-        .insert(CameraUIKayak)
-        .id();
-
     font_mapping.set_default(asset_server.load("roboto.kayak_font"));
     // font_mapping.force_subpixel(&asset_server.load("roboto.kayak_font"));
 
-    let mut widget_context = KayakRootContext::new(camera_entity);
-    widget_context.add_plugin(KayakWidgetsContextPlugin);
-    let parent_id = None;
-    rsx! {
-        <KayakAppBundle>
-            <TextWidgetBundle
-                text={TextProps {
-                    content: "Hello World".into(),
-                    size: 20.0,
-                    ..Default::default()
-                }}
-            />
-        </KayakAppBundle>
-    };
-
-    commands.spawn((widget_context, EventDispatcher::default()));
+    KayakRootContext::spawn_with_camera(
+        &mut commands,
+        Camera2dBundle::default(),
+        |widget_context, commands| {
+            let parent_id = None;
+            rsx! {
+                <KayakAppBundle>
+                    <TextWidgetBundle
+                        text={TextProps {
+                            content: "Hello World".into(),
+                            size: 20.0,
+                            ..Default::default()
+                        }}
+                    />
+                </KayakAppBundle>
+            };
+        },
+    );
 }
 
 fn main() {
@@ -0,0 +1,82 @@
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+use kayak_ui::prelude::{widgets::*, *};
+
+/// Builds a bar chart out of one quad per value, colored from blue to green by height
+fn bar_chart(values: &[f32]) -> Mesh {
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    let mut indices = Vec::new();
+    for (i, value) in values.iter().enumerate() {
+        let left = i as f32 + 0.1;
+        let right = i as f32 + 0.9;
+        let start = positions.len() as u32;
+        positions.extend([
+            [left, 0.0, 0.0],
+            [right, 0.0, 0.0],
+            [right, *value, 0.0],
+            [left, *value, 0.0],
+        ]);
+        let top = [0.2, 0.4 + value * 0.6, 1.0 - value * 0.6, 1.0];
+        colors.extend([[0.2, 0.4, 1.0, 1.0], [0.2, 0.4, 1.0, 1.0], top, top]);
+        indices.extend([start, start + 1, start + 2, start, start + 2, start + 3]);
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let chart = meshes.add(bar_chart(&[0.3, 0.8, 0.5, 1.0, 0.65, 0.2]));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Mesh example".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(400.0, 300.0),
+                    ..KWindow::default()
+                }}
+            >
+                <ElementBundle
+                    styles={KStyle {
+                        render_command: RenderCommand::Mesh { handle: chart }.into(),
+                        ..Default::default()
+                    }}
+                />
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .run()
+}
@@ -82,6 +82,7 @@ fn my_widget_render(
                     modal={Modal {
                         title: "Modal".into(),
                         visible: state.show_modal,
+                        dismiss_on_overlay: true,
                         ..Modal::default()
                     }}
                     styles={KStyle {
@@ -158,6 +159,17 @@ fn startup(
     commands.spawn((widget_context, EventDispatcher::default()));
 }
 
+fn hide_dismissed_modal(
+    mut dismissed: EventReader<ModalDismissed>,
+    mut query: Query<&mut MyWidgetState>,
+) {
+    if dismissed.iter().next().is_some() {
+        for mut state in query.iter_mut() {
+            state.show_modal = false;
+        }
+    }
+}
+
 fn main() {
     App::new()
         .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
@@ -168,5 +180,6 @@ fn main() {
             MaterialUIPlugin::<MyUIMaterial>::default(),
         ))
         .add_systems(Startup, startup)
+        .add_systems(Update, hide_dismissed_modal)
         .run()
 }
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let bar_styles = KStyle {
+        bottom: Units::Pixels(10.0).into(),
+        ..Default::default()
+    };
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Progress bar example".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(300.0, 250.0),
+                    ..KWindow::default()
+                }}
+            >
+                <ProgressBarBundle
+                    styles={bar_styles.clone()}
+                    progress_bar={ProgressBarProps { value: 0.4, ..Default::default() }}
+                />
+                <ProgressBarBundle
+                    styles={bar_styles.clone().with_style(KStyle {
+                        background_color: Color::rgba(0.1, 0.1, 0.1, 1.0).into(),
+                        color: Color::rgba(0.4, 0.8, 0.4, 1.0).into(),
+                        ..Default::default()
+                    })}
+                    progress_bar={ProgressBarProps { value: 0.75, ..Default::default() }}
+                />
+                <ProgressBarBundle
+                    styles={bar_styles}
+                    progress_bar={ProgressBarProps { indeterminate: true, ..Default::default() }}
+                />
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .run()
+}
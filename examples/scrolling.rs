@@ -43,22 +43,24 @@ Lorem ipsum dolor sit amet, consectetur adipiscing elit. Cras sed tellus neque.
                 window={KWindow {
                     title: "Simple scrolling example".into(),
                     draggable: true,
+                    resizable: true,
+                    minimizable: true,
+                    maximizable: true,
                     initial_position: Vec2::new(10.0, 10.0),
                     size: Vec2::new(512.0, 512.0),
+                    min_size: Some(Vec2::new(200.0, 150.0)),
                     ..KWindow::default()
                 }}
             >
-                <ScrollContextProviderBundle>
-                    <ScrollBoxBundle>
-                        <TextWidgetBundle
-                            text={TextProps {
-                                content: lorem_ipsum,
-                                size: 14.0,
-                                ..Default::default()
-                            }}
-                        />
-                    </ScrollBoxBundle>
-                </ScrollContextProviderBundle>
+                <ScrollBoxBundle>
+                    <TextWidgetBundle
+                        text={TextProps {
+                            content: lorem_ipsum,
+                            size: 14.0,
+                            ..Default::default()
+                        }}
+                    />
+                </ScrollBoxBundle>
             </WindowBundle>
         </KayakAppBundle>
     };
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Slider example".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(300.0, 250.0),
+                    ..KWindow::default()
+                }}
+            >
+                <SliderBundle
+                    styles={KStyle {
+                        bottom: StyleProp::Value(Units::Pixels(20.0)),
+                        ..Default::default()
+                    }}
+                    slider={SliderProps {
+                        value: 0.5,
+                        ..Default::default()
+                    }}
+                />
+                <SliderBundle
+                    slider={SliderProps {
+                        min: 0.0,
+                        max: 100.0,
+                        step: 10.0,
+                        value: 30.0,
+                        track_height: Units::Pixels(8.0),
+                        ..Default::default()
+                    }}
+                />
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn print_changes(mut changed: EventReader<SliderChanged>) {
+    for event in changed.iter() {
+        info!("Slider {:?} changed to {}", event.entity, event.value);
+    }
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .add_systems(Update, print_changes)
+        .run()
+}
@@ -47,11 +47,7 @@ fn main() {
     App::new()
         .insert_resource(Msaa::Sample8)
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
-        .add_plugins((
-            KayakContextPlugin,
-            KayakWidgets,
-            bevy_svg::prelude::SvgPlugin,
-        ))
+        .add_plugins((KayakContextPlugin, KayakWidgets))
         .add_systems(Startup, startup)
         .run()
 }
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let rows = (0..1000)
+        .map(|index| {
+            vec![
+                format!("Item {}", index),
+                format!("{}", (index * 37) % 101),
+                format!("{:.2}", (index * 13 % 97) as f32 * 1.25),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Table example".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(400.0, 400.0),
+                    ..KWindow::default()
+                }}
+            >
+                <TableBundle
+                    table={TableProps {
+                        columns: vec![
+                            TableColumn {
+                                label: "Name".into(),
+                                ..Default::default()
+                            },
+                            TableColumn {
+                                label: "Count".into(),
+                                width: Units::Pixels(80.0),
+                                ..Default::default()
+                            },
+                            TableColumn {
+                                label: "Price".into(),
+                                width: Units::Pixels(80.0),
+                                ..Default::default()
+                            },
+                        ],
+                        rows,
+                        ..Default::default()
+                    }}
+                />
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn print_sorts(mut sorted: EventReader<TableSorted>) {
+    for event in sorted.iter() {
+        info!("Sorted by column {} ({:?})", event.column, event.direction);
+    }
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .add_systems(Update, print_sorts)
+        .run()
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::{Bundle, Color, Commands, Component, Entity, In, Query, Res};
 use kayak_ui::prelude::{
-    rsx, widgets::BackgroundBundle, ComputedStyles, Edge, KChildren, KStyle, KayakWidgetContext,
-    StyleProp, Units, Widget, WidgetName,
+    rsx, widgets::BackgroundBundle, ComputedStyles, Edge, KChildren, KDisplay, KStyle,
+    KayakWidgetContext, StyleProp, Units, Widget, WidgetName,
 };
 
 use crate::tab_context::TabContext;
@@ -45,8 +45,7 @@ pub fn tab_render(
             .unwrap();
         if let Ok(tab_context) = tab_context_query.get(context_entity) {
             if tab_context.current_index == tab.index {
-                styles.0.height = StyleProp::default();
-                styles.0.width = StyleProp::default();
+                styles.0.display = StyleProp::default();
                 let parent_id = Some(entity);
                 let styles = KStyle {
                     background_color: StyleProp::Value(Color::rgba(0.0781, 0.0898, 0.101, 1.0)),
@@ -59,8 +58,7 @@ pub fn tab_render(
                     <BackgroundBundle styles={styles} children={children.clone()} />
                 };
             } else {
-                styles.0.height = StyleProp::Value(Units::Pixels(0.0));
-                styles.0.width = StyleProp::Value(Units::Pixels(0.0));
+                styles.0.display = KDisplay::None.into();
             }
         }
     }
@@ -70,7 +70,7 @@ pub fn tab_button_render(
                 <KButtonBundle
                     styles={KStyle {
                         background_color: StyleProp::Value(background_color),
-                        border_radius: Corner::all(0.0).into(),
+                        border_radius: Corner::vertical(6.0, 0.0).into(),
                         height: StyleProp::Value(Units::Pixels(25.0)),
                         ..Default::default()
                     }}
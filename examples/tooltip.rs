@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <TooltipProviderBundle>
+                <WindowBundle
+                    window={KWindow {
+                        title: "Tooltip example".into(),
+                        draggable: true,
+                        initial_position: Vec2::new(10.0, 10.0),
+                        size: Vec2::new(300.0, 250.0),
+                        ..KWindow::default()
+                    }}
+                >
+                    <TooltipBundle
+                        tooltip={TooltipProps {
+                            text: "Saves the current document".into(),
+                            ..Default::default()
+                        }}
+                    >
+                        <KButtonBundle
+                            button={KButton {
+                                text: "Save".into(),
+                            }}
+                        />
+                    </TooltipBundle>
+                    <TooltipBundle
+                        tooltip={TooltipProps {
+                            text: "Shows up right away".into(),
+                            delay: 0.0,
+                        }}
+                        styles={KStyle {
+                            top: Units::Pixels(10.0).into(),
+                            ..Default::default()
+                        }}
+                    >
+                        <CheckboxBundle />
+                    </TooltipBundle>
+                </WindowBundle>
+            </TooltipProviderBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .run()
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+) {
+    let camera_entity = commands
+        .spawn((Camera2dBundle::default(), CameraUIKayak))
+        .id();
+
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle>
+            <WindowBundle
+                window={KWindow {
+                    title: "Virtual list example".into(),
+                    draggable: true,
+                    initial_position: Vec2::new(10.0, 10.0),
+                    size: Vec2::new(300.0, 400.0),
+                    ..KWindow::default()
+                }}
+            >
+                <ScrollBoxBundle>
+                    <VirtualListBundle
+                        virtual_list_props={VirtualListProps {
+                            item_count: 100_000,
+                            item_height: 24.0,
+                            item_builder: ItemBuilder::new(|widget_context, mut commands, children, parent_id, index| {
+                                constructor! {
+                                    <TextWidgetBundle
+                                        text={TextProps {
+                                            content: format!("Row {}", index),
+                                            size: 16.0,
+                                            ..Default::default()
+                                        }}
+                                    />
+                                }
+                            }),
+                            ..Default::default()
+                        }}
+                    />
+                </ScrollBoxBundle>
+            </WindowBundle>
+        </KayakAppBundle>
+    };
+
+    commands.spawn((widget_context, EventDispatcher::default()));
+}
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .run()
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use kayak_ui::prelude::{widgets::*, *};
+
+/// Marks the cube that spins underneath the panel
+#[derive(Component)]
+struct Spinner;
+
+fn startup(
+    mut commands: Commands,
+    mut font_mapping: ResMut<FontMapping>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    font_mapping.set_default(asset_server.load("roboto.kayak_font"));
+
+    // The panel is drawn into an image at this size
+    let (panel_camera, panel_image) = WorldPanelBundle::new(&mut images, UVec2::new(512, 128));
+    let camera_entity = commands.spawn(panel_camera).id();
+
+    let mut widget_context = KayakRootContext::new(camera_entity);
+    widget_context.add_plugin(KayakWidgetsContextPlugin);
+    let parent_id = None;
+    rsx! {
+        <KayakAppBundle
+            styles={KStyle {
+                background_color: Color::rgba(0.1, 0.1, 0.15, 0.8).into(),
+                border_radius: Corner::all(24.0).into(),
+                padding: Edge::all(Units::Stretch(1.0)).into(),
+                ..Default::default()
+            }}
+        >
+            <TextWidgetBundle
+                text={TextProps {
+                    size: 64.0,
+                    content: "Spinning cube".into(),
+                    alignment: Alignment::Middle,
+                    ..Default::default()
+                }}
+            />
+        </KayakAppBundle>
+    };
+    commands.spawn((widget_context, EventDispatcher::default()));
+
+    commands.spawn(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.4, 0.2).into()),
+            ..default()
+        },
+        Spinner,
+    ));
+
+    // The panel keeps the aspect ratio of its image and always faces the camera. It isn't a child
+    // of the cube, since billboards are turned in world space.
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Quad::new(Vec2::new(2.0, 0.5)))),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(panel_image),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, 1.25, 0.0),
+            ..default()
+        },
+        Billboard::default(),
+    ));
+
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn spin(time: Res<Time>, mut query: Query<&mut Transform, With<Spinner>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate_y(time.delta_seconds());
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((KayakContextPlugin, KayakWidgets))
+        .add_systems(Startup, startup)
+        .add_systems(Update, spin)
+        .run()
+}
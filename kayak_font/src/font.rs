@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 #[cfg(feature = "bevy_renderer")]
@@ -8,6 +9,8 @@ use bevy::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
+#[cfg(feature = "bevy_renderer")]
+use crate::ttf::runtime::RuntimeGlyphs;
 use crate::utility::{BreakableWord, MISSING, SPACE};
 use crate::{
     utility, Alignment, Glyph, GlyphRect, Grapheme, Line, Sdf, TextLayout, TextProperties,
@@ -22,6 +25,8 @@ pub struct KayakFont {
     pub missing_glyph: Option<char>,
     char_ids: HashMap<char, u32>,
     max_glyph_size: (f32, f32),
+    /// The font that glyphs are rasterized from on demand, if this font was created at runtime
+    pub(crate) runtime: Option<RuntimeGlyphs>,
 }
 
 #[cfg(feature = "bevy_renderer")]
@@ -49,6 +54,16 @@ pub struct KayakFont {
     max_glyph_size: (f32, f32),
 }
 
+/// A run of text measured with its own font and font size
+///
+/// See [`KayakFont::measure_runs`].
+#[derive(Debug, Clone, Copy)]
+pub struct FontRun<'a> {
+    pub content: &'a str,
+    pub font: &'a KayakFont,
+    pub font_size: f32,
+}
+
 impl KayakFont {
     pub fn new(sdf: Sdf, #[cfg(feature = "bevy_renderer")] image_type: ImageType) -> Self {
         let max_glyph_size = sdf.max_glyph_size();
@@ -79,9 +94,19 @@ impl KayakFont {
             missing_glyph,
             char_ids,
             max_glyph_size,
+            #[cfg(feature = "bevy_renderer")]
+            runtime: None,
         }
     }
 
+    /// Adds a glyph to the end of the SDF, making it available by its character.
+    pub(crate) fn push_glyph(&mut self, glyph: Glyph) {
+        self.char_ids
+            .insert(glyph.unicode, self.sdf.glyphs.len() as u32);
+        self.sdf.glyphs.push(glyph);
+        self.max_glyph_size = self.sdf.max_glyph_size();
+    }
+
     pub fn generate_char_ids(&mut self) {
         for (count, glyph) in self.sdf.glyphs.iter().enumerate() {
             self.char_ids.insert(glyph.unicode, count as u32);
@@ -93,21 +118,22 @@ impl KayakFont {
     }
 
     pub fn get_word_width(&self, word: &str, properties: TextProperties) -> f32 {
-        let space_width = self.get_space_width(properties);
-        let tab_width = self.get_tab_width(properties);
-
-        let mut width = 0.0;
-        for c in word.chars() {
-            if utility::is_space(c) {
-                width += space_width;
-            } else if utility::is_tab(c) {
-                width += tab_width;
-            } else if let Some(glyph) = self.get_glyph(c) {
-                width += glyph.advance * properties.font_size;
-            }
-        }
+        word.chars()
+            .map(|c| self.get_char_width(c, properties))
+            .sum()
+    }
 
-        width
+    /// Returns the pixel width of a single character.
+    fn get_char_width(&self, c: char, properties: TextProperties) -> f32 {
+        if utility::is_space(c) {
+            self.get_space_width(properties)
+        } else if utility::is_tab(c) {
+            self.get_tab_width(properties)
+        } else if let Some(glyph) = self.get_glyph(c) {
+            glyph.advance * properties.font_size
+        } else {
+            0.0
+        }
     }
 
     /// Splits up the provided &str into grapheme clusters.
@@ -124,16 +150,186 @@ impl KayakFont {
     /// * `properties`: The text properties to use.
     ///
     pub fn measure(&self, content: &str, properties: TextProperties) -> TextLayout {
-        let space_width = self.get_space_width(properties);
-        let tab_width = self.get_tab_width(properties);
+        Self::measure_runs(
+            &[FontRun {
+                content,
+                font: self,
+                font_size: properties.font_size,
+            }],
+            properties,
+        )
+    }
+
+    /// Shortens each line of the given content that is wider than `max_width`, ending it with an
+    /// ellipsis so that the line fits.
+    ///
+    /// The ellipsis is `…` if the font has a glyph for it, otherwise `...`. Content that already
+    /// fits is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `content`: The textual content to shorten.
+    /// * `max_width`: The width each line must fit within.
+    /// * `properties`: The text properties to use.
+    ///
+    pub fn ellipsize<'a>(
+        &self,
+        content: &'a str,
+        max_width: f32,
+        properties: TextProperties,
+    ) -> Cow<'a, str> {
+        let ellipsis = self.ellipsis();
+        let ellipsis_width = self.get_word_width(ellipsis, properties);
+
+        let mut result = String::new();
+        let mut changed = false;
+        for (index, line) in content.split('\n').enumerate() {
+            if index > 0 {
+                result.push('\n');
+            }
+
+            if self.get_word_width(line, properties) <= max_width {
+                result.push_str(line);
+                continue;
+            }
+
+            changed = true;
+            let mut width = ellipsis_width;
+            for grapheme in self.get_graphemes(line) {
+                width += self.get_word_width(grapheme, properties);
+                if width > max_width {
+                    break;
+                }
+                result.push_str(grapheme);
+            }
+            result.push_str(ellipsis);
+        }
+
+        if changed {
+            Cow::Owned(result)
+        } else {
+            Cow::Borrowed(content)
+        }
+    }
+
+    /// Shortens the given content to the first `max_lines` lines it wraps into, ending the last
+    /// one with an ellipsis so that it fits within `properties.max_size.0`.
+    ///
+    /// Content that already fits within `max_lines` lines is returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `content`: The textual content to shorten.
+    /// * `max_lines`: The number of lines to keep. At least one line is always kept.
+    /// * `properties`: The text properties to use.
+    ///
+    pub fn clamp_lines<'a>(
+        &self,
+        content: &'a str,
+        max_lines: usize,
+        properties: TextProperties,
+    ) -> Cow<'a, str> {
+        let layout = self.measure(content, properties);
+        let max_lines = max_lines.max(1);
+        if layout.total_lines() <= max_lines {
+            return Cow::Borrowed(content);
+        }
+
+        // Line indices count chars, so they need to be converted into byte indices
+        let byte_index = |char_index: usize| {
+            content
+                .char_indices()
+                .nth(char_index)
+                .map(|(index, _)| index)
+                .unwrap_or(content.len())
+        };
+        let last_line = &layout.lines()[max_lines - 1];
+        let start = byte_index(last_line.char_index());
+        let end = byte_index(last_line.char_index() + last_line.total_chars());
+
+        let ellipsis = self.ellipsis();
+        let mut width = self.get_word_width(ellipsis, properties);
+        let mut line = String::new();
+        for grapheme in self.get_graphemes(content[start..end].trim_end()) {
+            width += self.get_word_width(grapheme, properties);
+            if width > properties.max_size.0 {
+                break;
+            }
+            line.push_str(grapheme);
+        }
+
+        let mut result = content[..start].to_string();
+        result.push_str(line.trim_end());
+        result.push_str(ellipsis);
+        Cow::Owned(result)
+    }
+
+    /// The ellipsis used to shorten text: `…` if the font has a glyph for it, otherwise `...`.
+    fn ellipsis(&self) -> &'static str {
+        if self.get_char_id('…').is_some() {
+            "…"
+        } else {
+            "..."
+        }
+    }
+
+    /// Measures text made up of several runs, each with its own font and font size, and
+    /// calculates a layout for all of them together.
+    ///
+    /// Lines wrap across run boundaries as if the runs were a single string. Every line is
+    /// `properties.line_height` tall and all runs share the baseline set by `properties.font_size`.
+    /// Each [`GlyphRect::run`] is the index of the run the glyph came from.
+    ///
+    /// # Arguments
+    ///
+    /// * `runs`: The runs of text to measure, in order.
+    /// * `properties`: The text properties to use.
+    ///
+    pub fn measure_runs(runs: &[FontRun], properties: TextProperties) -> TextLayout {
+        if runs.is_empty() {
+            return TextLayout::new(
+                Vec::new(),
+                vec![Line::new(0)],
+                (0.0, properties.line_height),
+                properties,
+            );
+        }
+
+        let content = runs.iter().map(|run| run.content).collect::<String>();
+        // The byte index each run ends at
+        let run_ends = runs
+            .iter()
+            .scan(0, |end, run| {
+                *end += run.content.len();
+                Some(*end)
+            })
+            .collect::<Vec<_>>();
+        // Finds the run containing the given byte index
+        let run_at = |byte_index: usize| {
+            run_ends
+                .iter()
+                .position(|end| byte_index < *end)
+                .unwrap_or(runs.len() - 1)
+        };
+        let run_properties = |run: usize| TextProperties {
+            font_size: runs[run].font_size,
+            ..properties
+        };
+        // Measures text starting at the given byte index
+        let text_width = |start: usize, text: &str| -> f32 {
+            text.char_indices()
+                .map(|(offset, c)| {
+                    let run = run_at(start + offset);
+                    runs[run].font.get_char_width(c, run_properties(run))
+                })
+                .sum()
+        };
 
         let mut size: (f32, f32) = (0.0, 0.0);
         let mut glyph_rects = Vec::new();
         let mut lines = Vec::new();
-
-        // This is the normalized glyph bounds for all glyphs in the atlas.
-        // It's needed to ensure all glyphs render proportional to each other.
-        let norm_glyph_bounds = self.calc_glyph_size(properties.font_size);
+        // Whether each line ends a paragraph (i.e. ends in a hard break or ends the text)
+        let mut paragraph_ends = Vec::new();
 
         // The current line being calculated
         let mut line = Line::new(0);
@@ -141,7 +337,7 @@ impl KayakFont {
         let mut char_index = 0;
 
         // The word index to break a line before
-        let mut break_index = None;
+        let mut break_index: Option<usize> = None;
         // The word index until attempting to find another line break
         let mut skip_until_index = None;
 
@@ -158,7 +354,18 @@ impl KayakFont {
         //        careful thought and consideration, so it should probably be addressed later.
         //        Once resolved, this comment should be updated accordingly.
 
-        let words = utility::split_breakable_words(content).collect::<Vec<_>>();
+        let words = utility::split_breakable_words(&content).collect::<Vec<_>>();
+        // The byte index each word starts at
+        let word_starts = words
+            .iter()
+            .scan(0, |start, word| {
+                let word_start = *start;
+                *start += word.content.len();
+                Some(word_start)
+            })
+            .collect::<Vec<_>>();
+        let word_width = |index: usize, text: &str| text_width(word_starts[index], text);
+
         for (index, word) in words.iter().enumerate() {
             // Check if this is the last word of the line.
             let mut will_break = break_index.map(|idx| index + 1 == idx).unwrap_or_default();
@@ -169,6 +376,7 @@ impl KayakFont {
                 if idx == index {
                     let next_line = Line::new_after(&line);
                     lines.push(line);
+                    paragraph_ends.push(words[idx - 1].hard_break);
                     line = next_line;
                     break_index = None;
                 }
@@ -180,8 +388,13 @@ impl KayakFont {
                         // Skip finding a line break since we're guaranteed not to find one until `idx`
                     }
                     _ => {
-                        let (next_break, next_skip) =
-                            self.find_next_break(index, line.width(), properties, &words);
+                        let (next_break, next_skip) = Self::find_next_break(
+                            index,
+                            line.width(),
+                            properties,
+                            &words,
+                            &word_width,
+                        );
                         break_index = next_break;
                         skip_until_index = next_skip;
                         will_break |= break_index.map(|idx| index + 1 == idx).unwrap_or_default();
@@ -190,7 +403,7 @@ impl KayakFont {
             }
 
             // === Iterate Grapheme Clusters === //
-            for grapheme_content in word.content.graphemes(true) {
+            for (grapheme_offset, grapheme_content) in word.content.grapheme_indices(true) {
                 let mut grapheme = Grapheme {
                     position: (line.width(), properties.line_height * lines.len() as f32),
                     glyph_index,
@@ -198,10 +411,14 @@ impl KayakFont {
                     ..Default::default()
                 };
 
-                for c in grapheme_content.chars() {
+                for (char_offset, c) in grapheme_content.char_indices() {
                     char_index += 1;
                     grapheme.char_total += 1;
 
+                    let run = run_at(word_starts[index] + grapheme_offset + char_offset);
+                    let font = runs[run].font;
+                    let properties = run_properties(run);
+
                     if utility::is_newline(c) {
                         // Newlines (hard breaks) are already accounted for by the line break algorithm
                         continue;
@@ -210,14 +427,14 @@ impl KayakFont {
                     if utility::is_space(c) {
                         if !will_break {
                             // Don't add the space if we're about to break the line
-                            grapheme.size.0 += space_width;
+                            grapheme.size.0 += font.get_space_width(properties);
                         }
                     } else if utility::is_tab(c) {
-                        grapheme.size.0 += tab_width;
+                        grapheme.size.0 += font.get_tab_width(properties);
                     } else {
-                        let glyph = self.get_glyph(c).or_else(|| {
-                            if let Some(missing) = self.missing_glyph {
-                                self.get_glyph(missing)
+                        let glyph = font.get_glyph(c).or_else(|| {
+                            if let Some(missing) = font.missing_glyph {
+                                font.get_glyph(missing)
                             } else {
                                 None
                             }
@@ -244,8 +461,11 @@ impl KayakFont {
 
                             glyph_rects.push(GlyphRect {
                                 position: (pos_x, pos_y),
-                                size: norm_glyph_bounds,
+                                // The normalized glyph bounds for all glyphs in the atlas.
+                                // It's needed to ensure all glyphs render proportional to each other.
+                                size: font.calc_glyph_size(properties.font_size),
                                 content: glyph.unicode,
+                                run,
                             });
 
                             glyph_index += 1;
@@ -262,16 +482,22 @@ impl KayakFont {
 
         // Push the final line
         lines.push(line);
+        paragraph_ends.push(true);
         size.1 = properties.line_height * lines.len() as f32;
 
         // === Shift Lines & Glyphs === //
-        for line in lines.iter() {
+        for (line, is_paragraph_end) in lines.iter().zip(paragraph_ends) {
             let shift_x = match properties.alignment {
-                Alignment::Start => 0.0,
+                Alignment::Start | Alignment::Justified => 0.0,
                 Alignment::Middle => (properties.max_size.0 - line.width()) / 2.0,
                 Alignment::End => properties.max_size.0 - line.width(),
             };
 
+            if properties.alignment == Alignment::Justified && !is_paragraph_end {
+                Self::justify_line(line, properties.max_size.0, &mut glyph_rects);
+                continue;
+            }
+
             let start = line.glyph_index();
             let end = line.glyph_index() + line.total_glyphs();
 
@@ -284,6 +510,43 @@ impl KayakFont {
         TextLayout::new(glyph_rects, lines, size, properties)
     }
 
+    /// Stretches the gaps between the words of a line so that it fills `max_width`.
+    ///
+    /// Only whitespace followed by another word counts as a gap, so a line containing a
+    /// single word is left as-is.
+    fn justify_line(line: &Line, max_width: f32, glyph_rects: &mut [GlyphRect]) {
+        let graphemes = line.graphemes();
+        let is_gap = |index: usize| {
+            let grapheme = &graphemes[index];
+            grapheme.glyph_total == 0
+                && grapheme.size.0 > 0.0
+                && graphemes
+                    .get(index + 1)
+                    .map(|next| next.glyph_total > 0)
+                    .unwrap_or_default()
+        };
+
+        let gaps = (0..graphemes.len()).filter(|index| is_gap(*index)).count();
+        let extra = max_width - line.width();
+        if gaps == 0 || extra <= 0.0 {
+            return;
+        }
+
+        let gap_width = extra / gaps as f32;
+        let mut shift_x = 0.0;
+        for (index, grapheme) in graphemes.iter().enumerate() {
+            let start = grapheme.glyph_index;
+            let end = grapheme.glyph_index + grapheme.glyph_total;
+            for rect in glyph_rects[start..end].iter_mut() {
+                rect.position.0 += shift_x;
+            }
+
+            if is_gap(index) {
+                shift_x += gap_width;
+            }
+        }
+    }
+
     /// Attempts to find the next line break for a given set of [breakable words](BreakableWord).
     ///
     /// Each line break returned is guaranteed to be a _future_ index. That is, a line break will
@@ -306,13 +569,14 @@ impl KayakFont {
     /// * `line_width`: The current line's current width
     /// * `properties`: The associated text properties
     /// * `words`: The list of breakable words
+    /// * `word_width`: Measures (part of) the word at the given index
     ///
     fn find_next_break(
-        &self,
         curr_index: usize,
         line_width: f32,
         properties: TextProperties,
         words: &[BreakableWord],
+        word_width: &dyn Fn(usize, &str) -> f32,
     ) -> (Option<usize>, Option<usize>) {
         // Line Break Rules:
         //
@@ -360,14 +624,14 @@ impl KayakFont {
             return (None, None);
         };
 
-        let next_trimmed_width = self.get_word_width(next.content.trim_end(), properties);
+        let next_trimmed_width = word_width(next_index, next.content.trim_end());
 
         // 2.
         if next_trimmed_width > properties.max_size.0 {
             return (Some(next_index), None);
         }
 
-        let curr_width = self.get_word_width(curr.content, properties);
+        let curr_width = word_width(curr_index, curr.content);
 
         // 3.
         if next_trimmed_width + curr_width > properties.max_size.0 {
@@ -394,7 +658,7 @@ impl KayakFont {
         let mut best_break_index = next_index;
 
         while let Some(peek) = words.get(peek_index) {
-            chain_width += self.get_word_width(peek.content, properties);
+            chain_width += word_width(peek_index, peek.content);
 
             if peek.content.ends_with(char::is_whitespace) {
                 // End of joined chain
@@ -452,6 +716,68 @@ impl KayakFont {
             .and_then(|index| self.sdf.glyphs.get(*index as usize))
     }
 
+    /// Splits text into runs by the font that draws them, given a font stack in order of
+    /// preference.
+    ///
+    /// Each character is drawn by the first font that has a glyph for it. Whitespace and control
+    /// characters stay in the current run, and characters that no font has a glyph for fall back
+    /// to the first font. Returns the index of each run's font in `fonts` along with its content.
+    ///
+    /// The runs can be measured with [`KayakFont::measure_runs`].
+    pub fn fallback_runs<'a>(content: &'a str, fonts: &[&KayakFont]) -> Vec<(usize, &'a str)> {
+        let mut runs = Vec::new();
+        let mut run_start = 0;
+        let mut current = None;
+        for (index, c) in content.char_indices() {
+            let font_index = if c.is_whitespace() || c.is_control() {
+                current.unwrap_or_default()
+            } else {
+                fonts
+                    .iter()
+                    .position(|font| font.get_char_id(c).is_some())
+                    .unwrap_or_default()
+            };
+            if let Some(current) = current.filter(|current| *current != font_index) {
+                runs.push((current, &content[run_start..index]));
+                run_start = index;
+            }
+            current = Some(font_index);
+        }
+        if let Some(current) = current {
+            runs.push((current, &content[run_start..]));
+        }
+
+        runs
+    }
+
+    /// Measures text drawn with a font stack, in order of preference.
+    ///
+    /// Characters missing from the first font are measured with the later font that draws them,
+    /// as split by [`KayakFont::fallback_runs`].
+    pub fn measure_with_fallbacks(
+        content: &str,
+        fonts: &[&KayakFont],
+        properties: TextProperties,
+    ) -> TextLayout {
+        let [font, ..] = fonts else {
+            return Self::measure_runs(&[], properties);
+        };
+        let runs = Self::fallback_runs(content, fonts);
+        if runs.iter().all(|(font_index, _)| *font_index == 0) {
+            return font.measure(content, properties);
+        }
+
+        let font_runs = runs
+            .iter()
+            .map(|(font_index, content)| FontRun {
+                content,
+                font: fonts[*font_index],
+                font_size: properties.font_size,
+            })
+            .collect::<Vec<_>>();
+        Self::measure_runs(&font_runs, properties)
+    }
+
     /// Calculates the appropriate glyph size for a desired font size.
     ///
     /// This glyph size can then be used to provide a normalized size across all glyphs
@@ -6,4 +6,6 @@ pub struct GlyphRect {
     pub position: (f32, f32),
     pub size: (f32, f32),
     pub content: char,
+    /// The index of the run this glyph belongs to (see [`KayakFont::measure_runs`](crate::KayakFont::measure_runs))
+    pub run: usize,
 }
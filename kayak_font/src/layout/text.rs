@@ -9,6 +9,10 @@ pub enum Alignment {
     Start,
     Middle,
     End,
+    /// Stretches the space between words so that each wrapped line fills the max width.
+    ///
+    /// The last line of each paragraph is aligned to the start.
+    Justified,
 }
 
 /// Properties to control text layout.
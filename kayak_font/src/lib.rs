@@ -20,20 +20,24 @@ pub mod bevy;
 
 #[cfg(test)]
 mod tests {
-    use crate::{Alignment, ImageType, KayakFont, Sdf, TextProperties};
+    use crate::{Alignment, FontRun, ImageType, KayakFont, Sdf, TextProperties};
 
     fn make_font() -> KayakFont {
+        make_font_without(&[])
+    }
+
+    /// Makes a font that has no glyphs for the given characters
+    fn make_font_without(missing: &[char]) -> KayakFont {
         let bytes = std::fs::read("assets/roboto.kayak_font")
             .expect("a `roboto.kayak_font` file in the `assets/` directory of this crate");
+        let mut sdf = Sdf::from_bytes(&bytes);
+        sdf.glyphs.retain(|glyph| !missing.contains(&glyph.unicode));
 
         #[cfg(feature = "bevy_renderer")]
-        return KayakFont::new(
-            Sdf::from_bytes(&bytes),
-            ImageType::Atlas(bevy::asset::Handle::default()),
-        );
+        return KayakFont::new(sdf, ImageType::Atlas(bevy::asset::Handle::default()));
 
         #[cfg(not(feature = "bevy_renderer"))]
-        return KayakFont::new(Sdf::from_bytes(&bytes));
+        return KayakFont::new(sdf);
     }
 
     fn make_properties() -> TextProperties {
@@ -138,4 +142,145 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn justified_lines_should_fill_max_width() {
+        let content = "Hello world!\nHow is everyone on this super-awesome rock doing today?";
+        let font = make_font();
+        let properties = make_properties();
+        let start = font.measure(content, properties);
+        let justified = font.measure(
+            content,
+            TextProperties {
+                alignment: Alignment::Justified,
+                ..properties
+            },
+        );
+
+        for (index, line) in start.lines().iter().enumerate() {
+            let first = line.glyph_index();
+            let last = first + line.total_glyphs() - 1;
+            let first_shift =
+                justified.glyphs()[first].position.0 - start.glyphs()[first].position.0;
+            let last_shift = justified.glyphs()[last].position.0 - start.glyphs()[last].position.0;
+
+            assert_eq!(0.0, first_shift);
+            if index == 1 || index == 2 {
+                // Wrapped lines are stretched to the max width
+                let expected = properties.max_size.0 - line.width();
+                assert!((expected - last_shift).abs() < 0.01);
+            } else {
+                // Lines ending a paragraph are left as-is
+                assert_eq!(0.0, last_shift);
+            }
+        }
+    }
+
+    #[test]
+    fn runs_should_wrap_across_boundaries() {
+        let content = "Hello world!\nHow is everyone on this super-awesome rock doing today?";
+        let font = make_font();
+        let properties = make_properties();
+        let whole = font.measure(content, properties);
+        let (first, second) = content.split_at(content.find("one").unwrap());
+        let runs = [first, second].map(|content| FontRun {
+            content,
+            font: &font,
+            font_size: properties.font_size,
+        });
+        let split = KayakFont::measure_runs(&runs, properties);
+
+        assert_eq!(whole.total_lines(), split.total_lines());
+        for (a, b) in whole.glyphs().iter().zip(split.glyphs()) {
+            assert_eq!(a.position, b.position);
+        }
+
+        // Whitespace has no glyphs
+        let first_glyphs = first.split_whitespace().collect::<String>().len();
+        assert!(split.glyphs()[..first_glyphs]
+            .iter()
+            .all(|glyph| glyph.run == 0));
+        assert!(split.glyphs()[first_glyphs..]
+            .iter()
+            .all(|glyph| glyph.run == 1));
+    }
+
+    #[test]
+    fn fallback_runs_should_use_first_font_with_glyph() {
+        let primary = make_font_without(&['w', 'd']);
+        let fallback = make_font();
+        let fonts = [&primary, &fallback];
+
+        let runs = KayakFont::fallback_runs("Hello world! wow", &fonts);
+        assert_eq!(
+            runs,
+            vec![
+                (0, "Hello "),
+                (1, "w"),
+                (0, "orl"),
+                (1, "d"),
+                (0, "! "),
+                (1, "w"),
+                (0, "o"),
+                (1, "w"),
+            ]
+        );
+
+        // Characters no font has stay with the first font
+        let missing = make_font_without(&['w', 'd']);
+        assert_eq!(
+            KayakFont::fallback_runs("wd", &[&primary, &missing]),
+            vec![(0, "wd")]
+        );
+
+        let properties = make_properties();
+        let font_runs = runs
+            .iter()
+            .map(|(font, content)| FontRun {
+                content,
+                font: fonts[*font],
+                font_size: properties.font_size,
+            })
+            .collect::<Vec<_>>();
+        let layout = KayakFont::measure_runs(&font_runs, properties);
+        assert_eq!(layout.glyphs().len(), "Helloworld!wow".len());
+        for glyph in layout.glyphs() {
+            let font = fonts[runs[glyph.run].0];
+            assert!(font.get_char_id(glyph.content).is_some());
+        }
+    }
+
+    #[test]
+    fn ellipsize_should_fit_max_width() {
+        let content = "Hello world!\nHi";
+        let font = make_font();
+        let properties = make_properties();
+        let max_width = font.get_word_width("Hello wor", properties);
+
+        let ellipsized = font.ellipsize(content, max_width, properties);
+        let first_line = ellipsized.lines().next().unwrap();
+        assert!(first_line.len() < "Hello world!".len());
+        assert!(font.get_word_width(first_line, properties) <= max_width);
+        assert!(ellipsized.ends_with("\nHi"));
+
+        let unchanged = font.ellipsize(content, 1000.0, properties);
+        assert_eq!(content, unchanged);
+    }
+
+    #[test]
+    fn clamp_lines_should_limit_lines() {
+        let content = "How is everyone on this super-awesome rock doing today? I hope everyone is doing well.";
+        let font = make_font();
+        let properties = make_properties();
+        assert!(font.measure(content, properties).total_lines() > 2);
+
+        let clamped = font.clamp_lines(content, 2, properties);
+        let layout = font.measure(&clamped, properties);
+        assert_eq!(2, layout.total_lines());
+        assert!(clamped.ends_with('…') || clamped.ends_with("..."));
+        assert!(layout.size().0 <= properties.max_size.0);
+
+        let unchanged = font.clamp_lines(content, 10, properties);
+        assert_eq!(content, unchanged);
+    }
 }
@@ -1,7 +1,10 @@
 #![allow(clippy::needless_question_mark, clippy::question_mark)]
+use ab_glyph::FontArc;
 use bevy::{
     asset::{AssetLoader, LoadContext, LoadedAsset},
-    render::render_resource::{Extent3d, TextureFormat},
+    render::render_resource::{
+        Extent3d, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+    },
     utils::{BoxedFuture, HashMap},
 };
 
@@ -13,11 +16,25 @@ use nanoserde::DeJson;
 
 use crate::{
     msdf::{self, bitmap::FloatRGBBmp, shape::Shape, ttf_parser::ShapeBuilder, vector::Vector2},
+    ttf::runtime::{runtime_atlas, RuntimeGlyphs},
     Glyph, ImageType, KayakFont, Rect, Sdf,
 };
+/// File extensions that are loaded directly as font files rather than as a `.kttf` config
+const RAW_FONT_EXTENSIONS: &[&str] = &["ttf", "otf"];
+
+/// Loads a [`KayakFont`] by generating its MSDF glyphs from a TTF/OTF font.
+///
+/// This accepts either a `.kttf` config file or a font file named `*.kayak.ttf`/`*.kayak.otf`.
+/// Font files start out without any glyphs and rasterize each one the first time it's measured.
+/// A `.kttf` file rasterizes its character range up front (caching it to disk), and rasterizes
+/// any other character on demand.
+///
+/// Plain `.ttf`/`.otf` files can't be loaded here: Bevy picks a single loader per extension and
+/// `bevy_text` already registers one for them, so claiming them would break either Bevy's text or
+/// these fonts depending on plugin order. Use `FontMapping::add_ttf` for those instead.
 pub struct TTFLoader;
 
-#[derive(DeJson, Default, Debug, Clone)]
+#[derive(DeJson, Debug, Clone)]
 pub struct Kttf {
     file: String,
     char_range_start: String,
@@ -26,6 +43,18 @@ pub struct Kttf {
     offset_y: Option<f32>,
 }
 
+impl Default for Kttf {
+    fn default() -> Self {
+        Self {
+            file: String::new(),
+            char_range_start: "0x20".to_string(),
+            char_range_end: "0x7f".to_string(),
+            offset_x: None,
+            offset_y: None,
+        }
+    }
+}
+
 impl AssetLoader for TTFLoader {
     fn load<'a>(
         &'a self,
@@ -39,14 +68,41 @@ impl AssetLoader for TTFLoader {
                 .downcast_ref::<FileAssetIo>()
                 .unwrap();
 
-            let kttf: Kttf =
-                nanoserde::DeJson::deserialize_json(std::str::from_utf8(bytes).unwrap()).unwrap();
+            // Raw TTF/OTF files are rasterized with the default settings and are never cached to
+            // disk. A `.kttf` file points to the font file and configures how it gets rasterized.
+            let is_raw_font = load_context
+                .path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| RAW_FONT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                .unwrap_or_default();
+
+            let (kttf, font_bytes) = if is_raw_font {
+                (Kttf::default(), bytes.to_vec())
+            } else {
+                let kttf: Kttf =
+                    nanoserde::DeJson::deserialize_json(std::str::from_utf8(bytes).unwrap())
+                        .unwrap();
+                let font_bytes = load_context.read_asset_bytes(kttf.file.clone()).await?;
+                (kttf, font_bytes)
+            };
+
+            let font = FontArc::try_from_vec(font_bytes.clone())?;
+
+            if is_raw_font {
+                let (sdf, image) = runtime_atlas(&font, &kttf);
+                let image_handle =
+                    load_context.set_labeled_asset("font_image", LoadedAsset::new(image));
+                let mut kayak_font = KayakFont::new(sdf, ImageType::Array(image_handle));
+                kayak_font.runtime = Some(RuntimeGlyphs::new(font, kttf));
+                load_context.set_default_asset(LoadedAsset::new(kayak_font));
+                return Ok(());
+            }
 
             let char_range_start =
                 u32::from_str_radix(kttf.char_range_start.trim_start_matches("0x"), 16)?;
             let char_range_end =
                 u32::from_str_radix(kttf.char_range_end.trim_start_matches("0x"), 16)?;
-            let font_bytes = load_context.read_asset_bytes(kttf.file.clone()).await?;
 
             let mut cache_path = std::path::PathBuf::from(load_context.path());
             let file_name = load_context
@@ -212,6 +268,13 @@ impl AssetLoader for TTFLoader {
                 }
             };
 
+            // Characters without a glyph don't take up a layer, so the unused layers at the end
+            // are dropped. Glyphs rasterized on demand are then added as the layers right after.
+            let has_glyphs = !glyphs.is_empty();
+            let layer_count = glyphs.len().max(1) as u32;
+            let mut image_bytes = image_bytes;
+            image_bytes.truncate(size_x * size_y * 4 * layer_count as usize);
+
             let mut sdf = Sdf::default();
             sdf.glyphs = glyphs;
             sdf.atlas.font_size = size_x as f32;
@@ -219,30 +282,38 @@ impl AssetLoader for TTFLoader {
             let mut image = bevy::prelude::Image::new(
                 Extent3d {
                     width: size_x as u32,
-                    height: image_height,
+                    height: size_y as u32 * layer_count,
                     depth_or_array_layers: 1,
                 },
                 bevy::render::render_resource::TextureDimension::D2,
                 image_bytes,
                 TextureFormat::Rgba8Unorm,
             );
-            image.reinterpret_stacked_2d_as_array(char_count);
+            image.reinterpret_stacked_2d_as_array(layer_count);
+            // A single layer would otherwise be viewed as a plain 2D texture
+            image.texture_view_descriptor = Some(TextureViewDescriptor {
+                dimension: Some(TextureViewDimension::D2Array),
+                ..Default::default()
+            });
             let image_handle =
                 load_context.set_labeled_asset("font_image", LoadedAsset::new(image));
 
-            let font = KayakFont::new(sdf, ImageType::Array(image_handle));
-            load_context.set_default_asset(LoadedAsset::new(font));
+            let mut kayak_font = KayakFont::new(sdf, ImageType::Array(image_handle));
+            if has_glyphs {
+                kayak_font.runtime = Some(RuntimeGlyphs::new(font, kttf.clone()));
+            }
+            load_context.set_default_asset(LoadedAsset::new(kayak_font));
 
             Ok(())
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        &["kttf"]
+        &["kttf", "kayak.ttf", "kayak.otf"]
     }
 }
 
-fn calculate_plane(
+pub(crate) fn calculate_plane(
     loaded_file: &Kttf,
     shape: &mut Shape,
     geometry_scale: f32,
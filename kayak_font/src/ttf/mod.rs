@@ -1 +1,2 @@
 pub(crate) mod loader;
+pub(crate) mod runtime;
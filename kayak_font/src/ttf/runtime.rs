@@ -0,0 +1,300 @@
+use std::fmt::{Debug, Formatter};
+
+use ab_glyph::{Font, FontArc, OutlineCurve, Point};
+use bevy::{
+    prelude::{Assets, Image},
+    render::{
+        render_resource::{
+            Extent3d, FilterMode, SamplerDescriptor, TextureDimension, TextureFormat,
+            TextureUsages, TextureViewDescriptor, TextureViewDimension,
+        },
+        texture::ImageSampler,
+    },
+    utils::HashSet,
+};
+
+use crate::{
+    msdf::{self, bitmap::FloatRGBBmp, ttf_parser::ShapeBuilder, vector::Vector2},
+    ttf::loader::{calculate_plane, Kttf},
+    utility::{self, SPACE},
+    Glyph, ImageType, KayakFont, Rect, Sdf,
+};
+
+/// The size of a single glyph layer, matching the glyphs generated by the TTF loader
+const GLYPH_SIZE: (usize, usize) = (64, 128);
+const PX_RANGE: f64 = 8.0;
+
+/// The source of a runtime [`KayakFont`], used to rasterize glyphs the first time they are needed
+#[derive(Clone)]
+pub(crate) struct RuntimeGlyphs {
+    font: FontArc,
+    /// The settings glyphs are rasterized with, such as their offsets
+    config: Kttf,
+    /// Characters that the font has no glyph for, so they are never rasterized again
+    unavailable: HashSet<char>,
+}
+
+impl RuntimeGlyphs {
+    pub(crate) fn new(font: FontArc, config: Kttf) -> Self {
+        Self {
+            font,
+            config,
+            unavailable: HashSet::default(),
+        }
+    }
+}
+
+impl Debug for RuntimeGlyphs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuntimeGlyphs")
+            .field("config", &self.config)
+            .field("unavailable", &self.unavailable)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RuntimeGlyphs {
+    fn eq(&self, other: &Self) -> bool {
+        // `FontArc` can't be compared, so the set of glyphs is compared instead
+        self.unavailable == other.unavailable
+    }
+}
+
+impl KayakFont {
+    /// Creates a font from a TTF/OTF font whose glyphs are rasterized on demand.
+    ///
+    /// The font starts out with only a space glyph. Call [`KayakFont::rasterize_glyphs`] before
+    /// measuring text to add the glyphs it uses to the font and to its texture.
+    pub fn from_ttf(font: FontArc, images: &mut Assets<Image>) -> Self {
+        let config = Kttf::default();
+        let (sdf, image) = runtime_atlas(&font, &config);
+        let mut kayak_font = KayakFont::new(sdf, ImageType::Array(images.add(image)));
+        kayak_font.runtime = Some(RuntimeGlyphs::new(font, config));
+        kayak_font
+    }
+
+    /// Returns true if this is a runtime font that has yet to rasterize some of the given
+    /// characters.
+    pub fn needs_glyphs(&self, content: &str) -> bool {
+        let Some(runtime) = &self.runtime else {
+            return false;
+        };
+
+        content.chars().any(|c| self.is_missing(runtime, c))
+    }
+
+    fn is_missing(&self, runtime: &RuntimeGlyphs, c: char) -> bool {
+        !(utility::is_space(c) || c.is_control())
+            && self.get_char_id(c).is_none()
+            && !runtime.unavailable.contains(&c)
+    }
+
+    /// Rasterizes the glyphs for any characters in `content` that haven't been rasterized yet,
+    /// appending them to the font's texture.
+    ///
+    /// This does nothing for fonts that weren't created with [`KayakFont::from_ttf`].
+    /// Returns true if any glyph was added.
+    pub fn rasterize_glyphs(&mut self, content: &str, images: &mut Assets<Image>) -> bool {
+        if !self.needs_glyphs(content) {
+            return false;
+        }
+
+        let Some(image) = images.get_mut(self.image.get()) else {
+            return false;
+        };
+
+        let mut runtime = self.runtime.take().unwrap();
+        let mut added = false;
+        for c in content.chars() {
+            if !self.is_missing(&runtime, c) {
+                continue;
+            }
+
+            if let Some((glyph, pixels)) = rasterize_glyph(&runtime.font, &runtime.config, c) {
+                image.data.extend(pixels);
+                image.texture_descriptor.size.depth_or_array_layers += 1;
+                self.push_glyph(glyph);
+                added = true;
+            } else {
+                runtime.unavailable.insert(c);
+            }
+        }
+        self.runtime = Some(runtime);
+
+        added
+    }
+}
+
+/// Creates the SDF and the single-layer texture that a runtime font starts out with, holding only
+/// a space glyph
+pub(crate) fn runtime_atlas(font: &FontArc, config: &Kttf) -> (Sdf, Image) {
+    let (width, height) = GLYPH_SIZE;
+    let mut image = Image::new(
+        Extent3d {
+            width: width as u32,
+            height: height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        Vec::new(),
+        TextureFormat::Rgba8Unorm,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::COPY_SRC;
+    image.sampler_descriptor = ImageSampler::Descriptor(SamplerDescriptor {
+        label: Some("Present Sampler"),
+        mag_filter: FilterMode::Linear,
+        min_filter: FilterMode::Linear,
+        ..Default::default()
+    });
+    // A single layer would otherwise be viewed as a plain 2D texture
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::D2Array),
+        ..Default::default()
+    });
+
+    let space = rasterize_glyph(font, config, SPACE).unwrap_or_else(|| {
+        (
+            Glyph {
+                unicode: SPACE,
+                advance: 0.25,
+                atlas_bounds: None,
+                plane_bounds: None,
+            },
+            vec![0; width * height * 4],
+        )
+    });
+    image.data = space.1;
+
+    let mut sdf = Sdf::default();
+    sdf.glyphs = vec![space.0];
+    sdf.atlas.font_size = width as f32;
+
+    (sdf, image)
+}
+
+/// Generates the MSDF glyph for the given character, along with the RGBA pixels of its layer.
+///
+/// Returns `None` if the font has no glyph for the character.
+fn rasterize_glyph(font: &FontArc, config: &Kttf, c: char) -> Option<(Glyph, Vec<u8>)> {
+    let (width, height) = GLYPH_SIZE;
+    let glyph_id = font.glyph_id(c);
+    if glyph_id.0 == 0 {
+        return None;
+    }
+
+    let units_per_em = font.units_per_em()? as f64;
+    let pixel_scale = width as f64 / units_per_em;
+    let mut builder = ShapeBuilder::default();
+    builder.pixel_scale = pixel_scale;
+
+    let outline = font.outline(glyph_id);
+    let (x_max, y_max) = match &outline {
+        // The bounds are stored top-left to bottom-right, so y is flipped
+        Some(outline) => (
+            outline.bounds.min.x.max(outline.bounds.max.x) as f64,
+            outline.bounds.min.y.max(outline.bounds.max.y) as f64,
+        ),
+        None => (width as f64, height as f64),
+    };
+    if let Some(outline) = &outline {
+        build_shape(&mut builder, &outline.curves);
+    }
+
+    let mut shape = builder.build();
+    shape.inverse_y_axis = true;
+
+    let scale = Vector2::new(1.0, 1.0);
+    let range = PX_RANGE / scale.x.min(scale.y);
+    let (translation, plane) = calculate_plane(
+        config,
+        &mut shape,
+        pixel_scale as f32,
+        1.0,
+        PX_RANGE as f32,
+        1.0,
+    );
+    let advance = font.h_advance_unscaled(glyph_id) / units_per_em as f32;
+
+    let mut pixels = vec![0; width * height * 4];
+    if outline.is_some() {
+        let mut output = FloatRGBBmp::new(width, height);
+        msdf::edge_coloring::simple(&mut shape, 3.0, 0);
+        msdf::gen::generate_msdf(
+            &mut output,
+            &shape,
+            range,
+            scale,
+            translation + Vector2::new(0.0, width as f64 * 1.25),
+            1.111_111_111_111_111_2,
+        );
+
+        let right = (translation.x + x_max * pixel_scale).floor().max(0.0) as usize;
+        let bottom = (translation.y + y_max * pixel_scale).floor().max(0.0) as usize;
+        for x in 0..(right + 2).min(width) {
+            for y in 0..(bottom + 48).min(height) {
+                let pixel = output.get_pixel(x, y);
+                let index = (y * width + x) * 4;
+                pixels[index..index + 4].copy_from_slice(&[
+                    (pixel.r * 255.0) as u8,
+                    (pixel.g * 255.0) as u8,
+                    (pixel.b * 255.0) as u8,
+                    255,
+                ]);
+            }
+        }
+    }
+
+    let glyph = Glyph {
+        unicode: c,
+        advance,
+        atlas_bounds: Some(Rect {
+            left: 0.0,
+            bottom: 0.0,
+            right: width as f32,
+            top: height as f32,
+        }),
+        plane_bounds: Some(plane),
+    };
+
+    Some((glyph, pixels))
+}
+
+/// Feeds the outline curves of a glyph into the shape builder, splitting them into contours.
+fn build_shape(builder: &mut ShapeBuilder, curves: &[OutlineCurve]) {
+    use ttf_parser::OutlineBuilder;
+
+    let mut end: Option<Point> = None;
+    for curve in curves {
+        let (start, last) = match curve {
+            OutlineCurve::Line(p0, p1) => (*p0, *p1),
+            OutlineCurve::Quad(p0, _, p1) => (*p0, *p1),
+            OutlineCurve::Cubic(p0, _, _, p1) => (*p0, *p1),
+        };
+
+        // Closing a contour adds a line back to its start, even if it's already there
+        if start == last && matches!(curve, OutlineCurve::Line(..)) {
+            continue;
+        }
+
+        if end != Some(start) {
+            if end.is_some() {
+                builder.close();
+            }
+            builder.move_to(start.x, start.y);
+        }
+
+        match curve {
+            OutlineCurve::Line(_, p1) => builder.line_to(p1.x, p1.y),
+            OutlineCurve::Quad(_, c, p1) => builder.quad_to(c.x, c.y, p1.x, p1.y),
+            OutlineCurve::Cubic(_, c1, c2, p1) => {
+                builder.curve_to(c1.x, c1.y, c2.x, c2.y, p1.x, p1.y)
+            }
+        }
+        end = Some(last);
+    }
+
+    if end.is_some() {
+        builder.close();
+    }
+}
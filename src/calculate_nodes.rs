@@ -1,26 +1,34 @@
+use std::borrow::Cow;
+
 use bevy::{
-    prelude::{Assets, Commands, Entity, In, Query, Res, With},
+    prelude::{Assets, Commands, Entity, Image, In, Query, Res, ResMut, Vec2, With},
     utils::HashMap,
 };
-use kayak_font::{KayakFont, TextProperties};
+use kayak_font::{Alignment, FontRun, KayakFont, TextProperties};
 use morphorm::Hierarchy;
 
 use crate::{
-    layout::{DataCache, Rect},
+    layout::{DataCache, LayoutCache, LayoutHierarchy, Rect, WidgetLayout},
     node::{DirtyNode, Node, NodeBuilder, WrappedIndex},
+    on_measure::OnMeasure,
     prelude::{KStyle, KayakRootContext, Tree},
     render::font::FontMapping,
-    styles::{ComputedStyles, RenderCommand, StyleProp, Units},
+    styles::{
+        ComputedStyles, KDisplay, LayoutDirection, LayoutType, RenderCommand, StyleProp,
+        TextOverflow, Units,
+    },
 };
 
 pub fn calculate_nodes(
     In(mut context): In<KayakRootContext>,
     mut commands: Commands,
-    fonts: Res<Assets<KayakFont>>,
+    mut fonts: ResMut<Assets<KayakFont>>,
+    mut images: ResMut<Assets<Image>>,
     font_mapping: Res<FontMapping>,
     query: Query<Entity, With<DirtyNode>>,
     all_styles_query: Query<&ComputedStyles>,
     node_query: Query<(Entity, &Node)>,
+    measure_query: Query<&OnMeasure>,
     // widget_names: Query<&WidgetName>,
 ) -> KayakRootContext {
     let mut new_nodes = HashMap::<Entity, (Node, bool)>::default();
@@ -91,38 +99,15 @@ pub fn calculate_nodes(
             styles.apply(&initial_styles);
             // Fill in all `inherited` values for any `inherit` property
             styles.inherit(&parent_styles);
+            if matches!(
+                styles.layout_direction,
+                StyleProp::Value(LayoutDirection::Rtl)
+            ) {
+                mirror_horizontal_edges(&mut styles);
+            }
 
-            // Lock opacity so the max opacity for a child is the opacity of the parent.
-            // if let StyleProp::Value(opacity) = &mut styles.opacity {
-            //     if let StyleProp::Value(parent_opacity) = &parent_styles.opacity {
-            //         *opacity = opacity.min(*parent_opacity);
-            //     }
-            // } else {
-            //     styles.opacity = parent_styles.opacity.clone();
-            // }
-
-            // if let StyleProp::Value(opacity) = &mut styles.opacity {
-            //     if let StyleProp::Value(background_color) = &mut styles.background_color {
-            //         // Apply opacity to background_color
-            //         background_color.set_a(opacity.min(background_color.a()));
-            //     } else {
-            //         styles.background_color = Color::rgba(1.0, 1.0, 1.0, *opacity).into();
-            //     }
-
-            //     if let StyleProp::Value(color) = &mut styles.color {
-            //         // Apply opacity to color
-            //         color.set_a(opacity.min(color.a()));
-            //     } else {
-            //         styles.color = Color::rgba(1.0, 1.0, 1.0, *opacity).into();
-            //     }
-
-            //     if let StyleProp::Value(border_color) = &mut styles.border_color {
-            //         // Apply opacity to border_color
-            //         border_color.set_a(opacity.min(border_color.a()));
-            //     } else {
-            //         styles.border_color = Color::rgba(1.0, 1.0, 1.0, *opacity).into();
-            //     }
-            // }
+            // Opacity isn't resolved here. A node is faded along with its ancestors when its
+            // primitives are built, which keeps nested opacity layers from fading it twice.
 
             // let mut current_z = {
             //     if parent_z > -1.0 {
@@ -140,10 +125,11 @@ pub fn calculate_nodes(
                 -1.0
             };
 
-            let needs_layout = create_primitive(
+            let mut needs_layout = create_primitive(
                 &mut commands,
                 &context,
-                &fonts,
+                &mut fonts,
+                &mut images,
                 &font_mapping,
                 &query,
                 // &node_query,
@@ -154,7 +140,9 @@ pub fn calculate_nodes(
                     .map(|(_, node)| node.raw_styles.clone().unwrap_or_default())
                     .unwrap_or_default(),
                 &all_styles_query,
+                &measure_query,
             );
+            needs_layout |= apply_aspect_ratio(&context, dirty_entity, &mut styles);
 
             let children = tree
                 .children
@@ -171,12 +159,14 @@ pub fn calculate_nodes(
             let height = styles.height.resolve().value_or(0.0, 0.0);
 
             let opacity = styles.opacity.resolve_or(1.0);
+            let tint = styles.tint.resolve_as_option();
 
             let mut node: Node = NodeBuilder::empty()
                 .with_id(dirty_entity)
                 .with_styles(styles, Some(raw_styles))
                 .with_children(children)
                 .with_opacity(opacity)
+                .with_tint(tint)
                 .build();
 
             if dirty_entity == tree.root_node.unwrap() {
@@ -216,19 +206,58 @@ pub fn calculate_nodes(
 }
 
 pub fn calculate_layout(
-    In(context): In<KayakRootContext>,
+    In((context, dirty_nodes)): In<(KayakRootContext, Vec<WrappedIndex>)>,
     mut commands: Commands,
     nodes_no_entity_query: Query<&'static Node>,
+    layouts: Query<&WidgetLayout>,
 ) -> KayakRootContext {
     if let Ok(tree) = context.tree.try_read() {
         // tree.dump();
         let node_tree = &*tree;
         if let Ok(mut cache) = context.layout_cache.try_write() {
+            let root = find_layout_root(node_tree, &dirty_nodes, &nodes_no_entity_query, &cache);
+            if root.is_some() {
+                // Nodes outside of the subtree keep their layout
+                cache.geometry_changed.clear();
+            }
+            // Nodes that aren't displayed are skipped by the layout, along with their descendants
+            for index in node_tree.down_iter() {
+                let displayed = nodes_no_entity_query.get(index.0).map_or(true, |node| {
+                    !matches!(
+                        node.resolved_styles.display,
+                        StyleProp::Value(KDisplay::None)
+                    )
+                });
+                let parent_visible = node_tree
+                    .get_parent(index)
+                    .and_then(|parent| cache.visible.get(&parent).copied())
+                    .unwrap_or(true);
+                cache.visible.insert(index, displayed && parent_visible);
+            }
             let mut data_cache = DataCache {
                 cache: &mut cache,
                 query: &nodes_no_entity_query,
             };
-            morphorm::layout(&mut data_cache, node_tree, &nodes_no_entity_query);
+            // Right-to-left rows place their children from right to left
+            let reversed = nodes_no_entity_query
+                .iter()
+                .filter(|node| {
+                    matches!(
+                        node.resolved_styles.layout_direction,
+                        StyleProp::Value(LayoutDirection::Rtl)
+                    ) && matches!(
+                        node.resolved_styles.layout_type,
+                        StyleProp::Value(LayoutType::Row)
+                    )
+                })
+                .map(|node| node.id)
+                .collect();
+            let hierarchy = LayoutHierarchy {
+                tree: node_tree,
+                reversed: &reversed,
+                root,
+            };
+            morphorm::layout(&mut data_cache, &hierarchy, &nodes_no_entity_query);
 
             for (entity, change) in cache.geometry_changed.iter() {
                 if !change.is_empty() {
@@ -240,6 +269,14 @@ pub fn calculate_layout(
                     }
                 }
             }
+
+            for (id, rect) in cache.rect.iter() {
+                if layouts.get(id.0).map_or(true, |layout| layout.0 != *rect) {
+                    if let Some(mut entity_commands) = commands.get_entity(id.0) {
+                        entity_commands.insert(WidgetLayout(*rect));
+                    }
+                }
+            }
         }
     }
 
@@ -249,7 +286,8 @@ pub fn calculate_layout(
 fn create_primitive(
     commands: &mut Commands,
     context: &KayakRootContext,
-    fonts: &Assets<KayakFont>,
+    fonts: &mut Assets<KayakFont>,
+    images: &mut Assets<Image>,
     font_mapping: &FontMapping,
     // query: &Query<(Entity, &Node)>,
     dirty: &Query<Entity, With<DirtyNode>>,
@@ -257,6 +295,7 @@ fn create_primitive(
     styles: &mut KStyle,
     _prev_styles: KStyle,
     all_styles_query: &Query<&ComputedStyles>,
+    measure_query: &Query<&OnMeasure>,
 ) -> bool {
     let mut needs_layout = true;
     if let StyleProp::Value(render_command) = &mut styles.render_command {
@@ -273,89 +312,175 @@ fn create_primitive(
                     .font
                     .resolve_or_else(|| String::from(crate::DEFAULT_FONT));
                 // --- Bind to Font Asset --- //
-                let font_handle = font_mapping.get_handle(font).unwrap();
-                if let Some(font) = fonts.get(&font_handle) {
-                    if let Ok(node_tree) = context.tree.try_read() {
-                        if let Some(parent_id) =
-                            find_not_empty_parent(&node_tree, all_styles_query, &id)
+                let font_handles = font_mapping.get_fallback_handles(font);
+                let Some(font_handle) = font_handles.first() else {
+                    return needs_layout;
+                };
+                // --- Rasterize Missing Glyphs --- //
+                // Fonts loaded from a TTF/OTF at runtime only contain the glyphs measured so far.
+                // Fallback fonts only need the glyphs that the fonts before them don't have.
+                let mut missing = Cow::Borrowed(content.as_str());
+                for (index, handle) in font_handles.iter().enumerate() {
+                    let needs_glyphs = fonts
+                        .get(handle)
+                        .map(|font| font.needs_glyphs(&missing))
+                        .unwrap_or_default();
+                    if needs_glyphs {
+                        if let Some(font) = fonts.get_mut(handle) {
+                            font.rasterize_glyphs(&missing, images);
+                        }
+                    }
+                    if index + 1 < font_handles.len() {
+                        if let Some(font) = fonts.get(handle) {
+                            missing = Cow::Owned(
+                                missing
+                                    .chars()
+                                    .filter(|c| font.get_char_id(*c).is_none())
+                                    .collect(),
+                            );
+                        }
+                    }
+                }
+                if let Some(font) = fonts.get(font_handle) {
+                    let font_size = styles.font_size.resolve_or(14.0);
+                    *properties = TextProperties {
+                        font_size,
+                        line_height: styles.line_height.resolve_or(font_size * 1.2),
+                        alignment: *alignment,
+                        ..*properties
+                    };
+
+                    if let Some((text_needs_layout, available_width)) = fit_text_to_parent(
+                        context,
+                        dirty,
+                        id,
+                        all_styles_query,
+                        *word_wrap,
+                        properties,
+                    ) {
+                        needs_layout = text_needs_layout;
+
+                        // --- Calculate Text Layout --- //
+                        let content = if !*word_wrap
+                            && styles.text_overflow.resolve() == TextOverflow::Ellipsis
                         {
-                            if let Some(parent_layout) = context.get_layout(&parent_id) {
-                                let border_x = if let Ok(style) = all_styles_query.get(parent_id.0)
-                                {
-                                    let border = style.0.border.resolve();
-                                    border.left + border.right
-                                } else {
-                                    0.0
-                                };
-                                let border_y = if let Ok(style) = all_styles_query.get(parent_id.0)
-                                {
-                                    let border = style.0.border.resolve();
-                                    border.top + border.bottom
-                                } else {
-                                    0.0
-                                };
-
-                                let font_size = styles.font_size.resolve_or(14.0);
-                                *properties = TextProperties {
-                                    font_size,
-                                    line_height: styles.line_height.resolve_or(font_size * 1.2),
-                                    alignment: *alignment,
-                                    ..*properties
+                            font.ellipsize(content, available_width, *properties)
+                        } else {
+                            Cow::Borrowed(content.as_str())
+                        };
+                        let content = if let StyleProp::Value(max_lines) = styles.max_lines {
+                            let clamped =
+                                match font.clamp_lines(&content, max_lines as usize, *properties) {
+                                    Cow::Owned(clamped) => Some(clamped),
+                                    Cow::Borrowed(_) => None,
                                 };
-
-                                properties.max_size = (
-                                    parent_layout.width - border_x,
-                                    parent_layout.height - border_y,
-                                );
-
-                                // TODO: Fix this hack.
-                                if !*word_wrap {
-                                    properties.max_size.0 = 100000.0;
-                                }
-
-                                needs_layout = false;
-
-                                if properties.max_size.0 == 0.0 || properties.max_size.1 == 0.0 {
-                                    needs_layout = true;
-                                }
-
-                                if context.get_geometry_changed(&parent_id) {
-                                    needs_layout = true;
-                                }
-
-                                if dirty.contains(parent_id.0) {
-                                    needs_layout = true;
-                                }
-
-                                // --- Calculate Text Layout --- //
-                                *text_layout = font.measure(content, *properties);
-                                let measurement = text_layout.size();
-
-                                log::trace!(
-                                    "Text Node: {}, has a measurement of: {:?}, it's parent takes up: {:?}",
-                                    &content,
-                                    measurement,
-                                    properties.max_size
-                                );
-
-                                // --- Apply Layout --- //
-                                if matches!(styles.width, StyleProp::Default) {
-                                    styles.width = StyleProp::Value(Units::Pixels(measurement.0));
-                                }
-                                if matches!(styles.height, StyleProp::Default) {
-                                    styles.height = StyleProp::Value(Units::Pixels(measurement.1));
-                                }
-                            } else {
-                                log::trace!("no layout for: {:?}", parent_id.0.index());
-                            }
+                            clamped.map(Cow::Owned).unwrap_or(content)
                         } else {
-                            log::trace!("No parent found for: {:?}", id.0.index());
+                            content
+                        };
+                        let fallback_fonts = font_handles
+                            .iter()
+                            .filter_map(|handle| fonts.get(handle))
+                            .collect::<Vec<_>>();
+                        *text_layout = KayakFont::measure_with_fallbacks(
+                            &content,
+                            &fallback_fonts,
+                            *properties,
+                        );
+                        let measurement = text_layout.size();
+
+                        log::trace!(
+                            "Text Node: {}, has a measurement of: {:?}, it's parent takes up: {:?}",
+                            &content,
+                            measurement,
+                            properties.max_size
+                        );
+
+                        // --- Apply Layout --- //
+                        if matches!(styles.width, StyleProp::Default) {
+                            styles.width = StyleProp::Value(Units::Pixels(measurement.0));
+                        }
+                        if matches!(styles.height, StyleProp::Default) {
+                            styles.height = StyleProp::Value(Units::Pixels(measurement.1));
+                        }
+                    }
+                }
+            }
+            RenderCommand::RichText {
+                runs,
+                alignment,
+                word_wrap,
+                text_layout,
+                properties,
+                ..
+            } => {
+                let default_font = styles
+                    .font
+                    .resolve_or_else(|| String::from(crate::DEFAULT_FONT));
+                let default_font_size = styles.font_size.resolve_or(14.0);
+                // --- Bind to Font Assets --- //
+                // Every run's font needs to be loaded before the text can be measured.
+                let run_fonts = runs
+                    .iter()
+                    .map(|run| {
+                        let font = run.font.clone().unwrap_or_else(|| default_font.clone());
+                        font_mapping
+                            .get_handle(font)
+                            .and_then(|font_handle| fonts.get(&font_handle))
+                    })
+                    .collect::<Option<Vec<_>>>();
+                if let Some(run_fonts) = run_fonts {
+                    // The baseline and line height fit the largest run.
+                    let font_size = runs
+                        .iter()
+                        .filter_map(|run| run.font_size)
+                        .fold(default_font_size, f32::max);
+                    *properties = TextProperties {
+                        font_size,
+                        line_height: styles.line_height.resolve_or(font_size * 1.2),
+                        alignment: *alignment,
+                        ..*properties
+                    };
+
+                    if let Some((text_needs_layout, _)) = fit_text_to_parent(
+                        context,
+                        dirty,
+                        id,
+                        all_styles_query,
+                        *word_wrap,
+                        properties,
+                    ) {
+                        needs_layout = text_needs_layout;
+
+                        // --- Calculate Text Layout --- //
+                        let font_runs = runs
+                            .iter()
+                            .zip(run_fonts)
+                            .map(|(run, font)| FontRun {
+                                content: &run.content,
+                                font,
+                                font_size: run.font_size.unwrap_or(default_font_size),
+                            })
+                            .collect::<Vec<_>>();
+                        *text_layout = KayakFont::measure_runs(&font_runs, *properties);
+                        let measurement = text_layout.size();
+
+                        // --- Apply Layout --- //
+                        if matches!(styles.width, StyleProp::Default) {
+                            styles.width = StyleProp::Value(Units::Pixels(measurement.0));
+                        }
+                        if matches!(styles.height, StyleProp::Default) {
+                            styles.height = StyleProp::Value(Units::Pixels(measurement.1));
                         }
                     }
                 }
             }
             _ => {
                 needs_layout = false;
+                if let Ok(on_measure) = measure_query.get(id.0) {
+                    needs_layout =
+                        measure_node(context, dirty, id, styles, all_styles_query, on_measure);
+                }
             }
         }
     }
@@ -374,26 +499,249 @@ fn create_primitive(
     needs_layout
 }
 
-pub fn find_not_empty_parent(
+/// Finds the smallest subtree that contains every dirty node and can be laid out on its own
+///
+/// This is the closest common ancestor of the dirty nodes whose size is fixed in pixels, since
+/// nothing inside of it can change its rect. Returns `None` if the whole tree needs to be laid out.
+fn find_layout_root(
     tree: &Tree,
-    all_styles_query: &Query<&ComputedStyles>,
-    node: &WrappedIndex,
+    dirty_nodes: &[WrappedIndex],
+    nodes: &Query<&Node>,
+    cache: &LayoutCache,
 ) -> Option<WrappedIndex> {
-    if let Some(parent) = tree.parent(*node) {
-        if let Ok(styles) = all_styles_query.get(parent.0) {
-            if matches!(styles.0.render_command.resolve(), RenderCommand::Empty)
-                || matches!(styles.0.render_command.resolve(), RenderCommand::Layout)
-            {
-                find_not_empty_parent(tree, all_styles_query, &parent)
+    let has_fixed_size = |node: WrappedIndex| {
+        nodes.get(node.0).map_or(false, |node| {
+            let styles = &node.resolved_styles;
+            matches!(styles.width, StyleProp::Value(Units::Pixels(..)))
+                && matches!(styles.height, StyleProp::Value(Units::Pixels(..)))
+                && !matches!(styles.dock, StyleProp::Value(..))
+        })
+    };
+
+    // The ancestors of the first dirty node, from its parent up to the root of the tree
+    let mut ancestors = Vec::new();
+    let mut node = *dirty_nodes.first()?;
+    while let Some(parent) = tree.get_parent(node) {
+        ancestors.push(parent);
+        node = parent;
+    }
+    let ancestor_depths = ancestors
+        .iter()
+        .enumerate()
+        .map(|(depth, ancestor)| (*ancestor, depth))
+        .collect::<HashMap<_, _>>();
+
+    // Every other dirty node raises the closest common ancestor to the first of its own
+    // ancestors that is shared with the first node
+    let mut common_depth = 0;
+    for node in dirty_nodes.iter().skip(1) {
+        let mut node = *node;
+        let depth = loop {
+            let parent = tree.get_parent(node)?;
+            if let Some(depth) = ancestor_depths.get(&parent) {
+                break *depth;
+            }
+            node = parent;
+        };
+        common_depth = common_depth.max(depth);
+    }
+
+    ancestors[common_depth..]
+        .iter()
+        .copied()
+        .find(|root| has_fixed_size(*root) && cache.rect.contains_key(root))
+        .filter(|root| Some(*root) != tree.root_node)
+}
+
+/// Swaps the left and right edges of a right-to-left node, so that they refer to its start and end
+///
+/// Text aligned to the start or end is flipped as well.
+fn mirror_horizontal_edges(styles: &mut KStyle) {
+    std::mem::swap(&mut styles.left, &mut styles.right);
+    std::mem::swap(&mut styles.padding_left, &mut styles.padding_right);
+    for edge in [&mut styles.offset, &mut styles.padding] {
+        if let StyleProp::Value(edge) = edge {
+            std::mem::swap(&mut edge.left, &mut edge.right);
+        }
+    }
+    if let StyleProp::Value(
+        RenderCommand::Text { alignment, .. } | RenderCommand::RichText { alignment, .. },
+    ) = &mut styles.render_command
+    {
+        *alignment = match *alignment {
+            Alignment::Start => Alignment::End,
+            Alignment::End => Alignment::Start,
+            alignment => alignment,
+        };
+    }
+}
+
+/// Derives the unset axis of a node from the other one to keep its aspect ratio
+///
+/// Returns true if the node needs to be laid out again to honor the ratio, which is the case
+/// when the set axis depends on the layout and the last layout doesn't match the ratio yet.
+fn apply_aspect_ratio(context: &KayakRootContext, id: WrappedIndex, styles: &mut KStyle) -> bool {
+    let aspect_ratio = match styles.aspect_ratio {
+        StyleProp::Value(aspect_ratio) if aspect_ratio > 0.0 => aspect_ratio,
+        _ => return false,
+    };
+    let width_set = matches!(styles.width, StyleProp::Value(..));
+    let height_set = matches!(styles.height, StyleProp::Value(..));
+    if width_set && height_set {
+        return false;
+    }
+
+    let layout = context.get_layout(&id);
+    if height_set {
+        let height = match styles.height.resolve() {
+            Units::Pixels(height) => Some(height),
+            _ => layout.map(|layout| layout.height),
+        };
+        if let Some(height) = height {
+            let width = height * aspect_ratio;
+            styles.width = StyleProp::Value(Units::Pixels(width));
+            return layout.map_or(true, |layout| (layout.width - width).abs() > 0.5);
+        }
+    } else {
+        let width = match styles.width.resolve() {
+            Units::Pixels(width) => Some(width),
+            _ => layout.map(|layout| layout.width),
+        };
+        if let Some(width) = width {
+            let height = width / aspect_ratio;
+            styles.height = StyleProp::Value(Units::Pixels(height));
+            return layout.map_or(true, |layout| (layout.height - height).abs() > 0.5);
+        }
+    }
+
+    true
+}
+
+/// Sets the max size of the text properties to the space available in the text's parent
+///
+/// Returns `None` if the parent hasn't been laid out yet. Otherwise returns whether the text
+/// needs to be measured again once its parent has been laid out, along with the width available
+/// to the text (which is kept even when word wrapping is disabled).
+fn fit_text_to_parent(
+    context: &KayakRootContext,
+    dirty: &Query<Entity, With<DirtyNode>>,
+    id: WrappedIndex,
+    all_styles_query: &Query<&ComputedStyles>,
+    word_wrap: bool,
+    properties: &mut TextProperties,
+) -> Option<(bool, f32)> {
+    if let Ok(node_tree) = context.tree.try_read() {
+        if let Some(parent_id) = find_not_empty_parent(&node_tree, all_styles_query, &id) {
+            if let Some(parent_layout) = context.get_layout(&parent_id) {
+                let border = all_styles_query
+                    .get(parent_id.0)
+                    .map(|style| style.0.border.resolve())
+                    .unwrap_or_default();
+                properties.max_size = (
+                    parent_layout.width - border.left - border.right,
+                    parent_layout.height - border.top - border.bottom,
+                );
+                let available_width = properties.max_size.0;
+
+                // TODO: Fix this hack.
+                if !word_wrap {
+                    properties.max_size.0 = 100000.0;
+                }
+
+                return Some((
+                    properties.max_size.0 == 0.0
+                        || properties.max_size.1 == 0.0
+                        || context.get_geometry_changed(&parent_id)
+                        || dirty.contains(parent_id.0),
+                    available_width,
+                ));
             } else {
-                Some(parent)
+                log::trace!("no layout for: {:?}", parent_id.0.index());
             }
         } else {
-            find_not_empty_parent(tree, all_styles_query, &parent)
+            log::trace!("No parent found for: {:?}", id.0.index());
         }
-    } else {
-        None
     }
+
+    None
+}
+
+/// Measures a node using its [`OnMeasure`] handler, filling in any unset width or height
+///
+/// Returns true if the node needs to be measured again once its parent has been laid out.
+fn measure_node(
+    context: &KayakRootContext,
+    dirty: &Query<Entity, With<DirtyNode>>,
+    id: WrappedIndex,
+    styles: &mut KStyle,
+    all_styles_query: &Query<&ComputedStyles>,
+    on_measure: &OnMeasure,
+) -> bool {
+    if let Ok(node_tree) = context.tree.try_read() {
+        if let Some(parent_id) = find_not_empty_parent(&node_tree, all_styles_query, &id) {
+            if let Some(parent_layout) = context.get_layout(&parent_id) {
+                let border = all_styles_query
+                    .get(parent_id.0)
+                    .map(|style| style.0.border.resolve())
+                    .unwrap_or_default();
+                let available = Vec2::new(
+                    parent_layout.width - border.left - border.right,
+                    parent_layout.height - border.top - border.bottom,
+                );
+
+                let measurement = on_measure.measure(available);
+
+                // --- Apply Layout --- //
+                if matches!(styles.width, StyleProp::Default) {
+                    styles.width = StyleProp::Value(Units::Pixels(measurement.x));
+                }
+                if matches!(styles.height, StyleProp::Default) {
+                    styles.height = StyleProp::Value(Units::Pixels(measurement.y));
+                }
+
+                return available.x == 0.0
+                    || available.y == 0.0
+                    || context.get_geometry_changed(&parent_id)
+                    || dirty.contains(parent_id.0);
+            }
+        }
+    }
+
+    true
+}
+
+/// Finds the closest ancestor of a node that renders something, which text is measured against
+///
+/// If every ancestor is [`RenderCommand::Empty`] or [`RenderCommand::Layout`], or the tree's
+/// parents loop back on themselves, falls back to the root node so the node still gets a box to
+/// measure against. Returns `None` only for the root node itself.
+pub fn find_not_empty_parent(
+    tree: &Tree,
+    all_styles_query: &Query<&ComputedStyles>,
+    node: &WrappedIndex,
+) -> Option<WrappedIndex> {
+    let mut current = *node;
+    let mut topmost = None;
+    // A well formed tree can't have more ancestors than nodes
+    for _ in 0..=tree.len() {
+        let Some(parent) = tree.parent(current) else {
+            return topmost.or(tree.root_node).filter(|root| root != node);
+        };
+        let is_empty = all_styles_query.get(parent.0).map_or(true, |styles| {
+            matches!(
+                styles.0.render_command.resolve(),
+                RenderCommand::Empty | RenderCommand::Layout
+            )
+        });
+        if !is_empty {
+            return Some(parent);
+        }
+        topmost = Some(parent);
+        current = parent;
+    }
+
+    log::warn!("Found a cycle in the parents of {:?}!", node.0);
+    tree.root_node.filter(|root| root != node)
 }
 
 // pub fn build_nodes_tree(context: &mut Context, tree: &Tree, node_query: &Query<(Entity, &Node)>) {
@@ -481,3 +829,157 @@ pub fn find_not_empty_parent(
 
 //     None
 // }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+    use bevy::prelude::{Entity, Query, With, World};
+    use kayak_font::TextProperties;
+
+    use super::{find_layout_root, find_not_empty_parent, fit_text_to_parent};
+    use crate::layout::{LayoutCache, Rect};
+    use crate::node::{DirtyNode, Node, WrappedIndex};
+    use crate::prelude::KayakRootContext;
+    use crate::styles::{ComputedStyles, KStyle, RenderCommand, StyleProp, Units};
+    use crate::tree::Tree;
+
+    fn add_node(
+        world: &mut World,
+        tree: &mut Tree,
+        render_command: RenderCommand,
+        parent: Option<WrappedIndex>,
+    ) -> WrappedIndex {
+        let node = WrappedIndex(
+            world
+                .spawn(ComputedStyles(KStyle {
+                    render_command: StyleProp::Value(render_command),
+                    ..Default::default()
+                }))
+                .id(),
+        );
+        tree.add(node, parent);
+        node
+    }
+
+    #[test]
+    fn finds_closest_rendered_ancestor() {
+        let mut world = World::new();
+        let mut tree = Tree::default();
+        let root = add_node(&mut world, &mut tree, RenderCommand::Layout, None);
+        let quad = add_node(&mut world, &mut tree, RenderCommand::Quad, Some(root));
+        let empty = add_node(&mut world, &mut tree, RenderCommand::Empty, Some(quad));
+        let text = add_node(&mut world, &mut tree, RenderCommand::Empty, Some(empty));
+
+        let mut state: SystemState<Query<&ComputedStyles>> = SystemState::new(&mut world);
+        let query = state.get(&world);
+        assert_eq!(find_not_empty_parent(&tree, &query, &text), Some(quad));
+        assert_eq!(find_not_empty_parent(&tree, &query, &root), None);
+    }
+
+    #[test]
+    fn falls_back_to_root() {
+        let mut world = World::new();
+        let mut tree = Tree::default();
+        let root = add_node(&mut world, &mut tree, RenderCommand::Layout, None);
+        let text = add_node(&mut world, &mut tree, RenderCommand::Empty, Some(root));
+        let empty = add_node(&mut world, &mut tree, RenderCommand::Empty, Some(root));
+        let nested_text = add_node(&mut world, &mut tree, RenderCommand::Empty, Some(empty));
+
+        let mut state: SystemState<Query<&ComputedStyles>> = SystemState::new(&mut world);
+        let query = state.get(&world);
+        assert_eq!(find_not_empty_parent(&tree, &query, &text), Some(root));
+        assert_eq!(
+            find_not_empty_parent(&tree, &query, &nested_text),
+            Some(root)
+        );
+    }
+
+    #[test]
+    fn stops_on_cycles() {
+        let mut world = World::new();
+        let mut tree = Tree::default();
+        let root = add_node(&mut world, &mut tree, RenderCommand::Layout, None);
+        let a = add_node(&mut world, &mut tree, RenderCommand::Empty, Some(root));
+        let b = add_node(&mut world, &mut tree, RenderCommand::Empty, Some(a));
+        tree.parents.insert(a, b);
+
+        let mut state: SystemState<Query<&ComputedStyles>> = SystemState::new(&mut world);
+        let query = state.get(&world);
+        assert_eq!(find_not_empty_parent(&tree, &query, &b), Some(root));
+    }
+
+    #[test]
+    fn text_under_root_gets_measured() {
+        let mut world = World::new();
+        let context = KayakRootContext::new(Entity::PLACEHOLDER);
+        let text = {
+            let mut tree = context.tree.write().unwrap();
+            let root = add_node(&mut world, &mut tree, RenderCommand::Layout, None);
+            context.layout_cache.write().unwrap().rect.insert(
+                root,
+                Rect {
+                    width: 800.0,
+                    height: 600.0,
+                    ..Default::default()
+                },
+            );
+            add_node(&mut world, &mut tree, RenderCommand::Empty, Some(root))
+        };
+
+        let mut state: SystemState<(Query<Entity, With<DirtyNode>>, Query<&ComputedStyles>)> =
+            SystemState::new(&mut world);
+        let (dirty, query) = state.get(&world);
+        let mut properties = TextProperties::default();
+        let measured = fit_text_to_parent(&context, &dirty, text, &query, true, &mut properties);
+        assert_eq!(measured, Some((false, 800.0)));
+        assert_eq!(properties.max_size, (800.0, 600.0));
+    }
+
+    #[test]
+    fn layout_root_is_closest_fixed_size_common_ancestor() {
+        let mut world = World::new();
+        let mut tree = Tree::default();
+        let mut cache = LayoutCache::default();
+        let mut add = |parent: Option<WrappedIndex>, fixed_size: bool| {
+            let size = if fixed_size {
+                StyleProp::Value(Units::Pixels(100.0))
+            } else {
+                StyleProp::Default
+            };
+            let node = WrappedIndex(
+                world
+                    .spawn(Node {
+                        resolved_styles: KStyle {
+                            width: size.clone(),
+                            height: size,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .id(),
+            );
+            tree.add(node, parent);
+            cache.rect.insert(node, Rect::default());
+            node
+        };
+        let root = add(None, true);
+        let panel = add(Some(root), true);
+        let row = add(Some(panel), false);
+        let a = add(Some(row), false);
+        let b = add(Some(row), false);
+        let other = add(Some(root), false);
+
+        let mut state: SystemState<Query<&Node>> = SystemState::new(&mut world);
+        let query = state.get(&world);
+        assert_eq!(
+            find_layout_root(&tree, &[a, b], &query, &cache),
+            Some(panel)
+        );
+        assert_eq!(
+            find_layout_root(&tree, &[a, row], &query, &cache),
+            Some(panel)
+        );
+        assert_eq!(find_layout_root(&tree, &[a, other], &query, &cache), None);
+        assert_eq!(find_layout_root(&tree, &[root], &query, &cache), None);
+    }
+}
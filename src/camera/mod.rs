@@ -2,8 +2,13 @@ use bevy::{
     ecs::query::QueryItem,
     prelude::*,
     render::extract_component::{ExtractComponent, ExtractComponentPlugin},
+    transform::TransformSystem,
 };
 
+mod world_panel;
+
+pub use world_panel::{Billboard, WorldPanelBundle};
+
 #[derive(Component, Default, Debug, Clone, Copy)]
 pub struct CameraUIKayak;
 impl ExtractComponent for CameraUIKayak {
@@ -19,6 +24,10 @@ impl ExtractComponent for CameraUIKayak {
 pub struct KayakUICameraPlugin;
 impl Plugin for KayakUICameraPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_plugins(ExtractComponentPlugin::<CameraUIKayak>::default());
+        app.add_plugins(ExtractComponentPlugin::<CameraUIKayak>::default())
+            .add_systems(
+                PostUpdate,
+                world_panel::face_billboards.before(TransformSystem::TransformPropagate),
+            );
     }
 }
@@ -0,0 +1,104 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+    },
+};
+
+use super::CameraUIKayak;
+
+/// A UI camera that draws into an image instead of the window
+///
+/// Use the image as the texture of a mesh in the world to show the UI on it, like an in-world
+/// menu or a name tag. Create a [`KayakRootContext`](crate::prelude::KayakRootContext) for the
+/// camera entity like for any other UI camera. The UI is laid out at the size of the image.
+///
+/// The panel doesn't receive input yet, since pointer events would have to be projected onto the
+/// mesh first.
+#[derive(Bundle)]
+pub struct WorldPanelBundle {
+    pub camera: Camera2dBundle,
+    pub ui_camera: CameraUIKayak,
+}
+
+impl WorldPanelBundle {
+    /// Creates a transparent image of the given size in pixels, along with a camera drawing into it
+    pub fn new(images: &mut Assets<Image>, size: UVec2) -> (Self, Handle<Image>) {
+        let size = Extent3d {
+            width: size.x.max(1),
+            height: size.y.max(1),
+            ..default()
+        };
+        let mut image = Image {
+            texture_descriptor: TextureDescriptor {
+                label: Some("kayak_world_panel"),
+                size,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Bgra8UnormSrgb,
+                mip_level_count: 1,
+                sample_count: 1,
+                usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[TextureFormat::Bgra8UnormSrgb],
+            },
+            ..default()
+        };
+        image.resize(size);
+        let image = images.add(image);
+
+        let bundle = Self {
+            camera: Camera2dBundle {
+                camera: Camera {
+                    // Draw the panel before the cameras that show it
+                    order: -1,
+                    target: RenderTarget::Image(image.clone()),
+                    ..default()
+                },
+                camera_2d: Camera2d {
+                    clear_color: bevy::core_pipeline::clear_color::ClearColorConfig::Custom(
+                        Color::NONE,
+                    ),
+                },
+                ..default()
+            },
+            ui_camera: CameraUIKayak,
+        };
+        (bundle, image)
+    }
+}
+
+/// Turns an entity so that its front (the positive z-axis) faces a 3D camera
+///
+/// Useful for panels that should always be readable, like name tags above characters. The entity
+/// shouldn't have a rotated parent, since its rotation is set in world space.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct Billboard {
+    /// The camera to face, or the first 3D camera if `None`
+    pub camera: Option<Entity>,
+}
+
+pub(crate) fn face_billboards(
+    mut billboards: Query<(&Billboard, &mut Transform, &GlobalTransform)>,
+    cameras: Query<(Entity, &GlobalTransform), With<Camera3d>>,
+) {
+    for (billboard, mut transform, global_transform) in billboards.iter_mut() {
+        let camera = match billboard.camera {
+            Some(camera) => cameras.get(camera).ok(),
+            None => cameras.iter().next(),
+        };
+        if let Some((_, camera_transform)) = camera {
+            let position = global_transform.translation();
+            let away = 2.0 * position - camera_transform.translation();
+            if away != position {
+                let rotation = Transform::from_translation(position)
+                    .looking_at(away, Vec3::Y)
+                    .rotation;
+                transform.rotation = rotation;
+            }
+        }
+    }
+}
@@ -1,14 +1,28 @@
-use bevy::prelude::*;
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
 
 use crate::prelude::KayakWidgetContext;
 
 /// Defers widgets being added to the widget tree.
 #[derive(Component, Reflect, Debug, Default, Clone, PartialEq, Eq)]
-#[reflect(Component)]
+#[reflect(Component, MapEntities)]
 pub struct KChildren {
     inner: Vec<Entity>,
 }
 
+impl MapEntities for KChildren {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for entity in self.inner.iter_mut() {
+            *entity = entity_mapper.get_or_reserve(*entity);
+        }
+    }
+}
+
 impl KChildren {
     pub fn new() -> Self {
         Self { inner: Vec::new() }
@@ -0,0 +1,44 @@
+use bevy::prelude::Resource;
+
+/// Reads and writes the text of the OS clipboard
+///
+/// Talking to the OS clipboard requires the `clipboard` feature. Without it, or on platforms
+/// where the clipboard can't be reached, text is only shared within the app.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct Clipboard {
+    /// The text copied within the app, used when the OS clipboard isn't available
+    fallback: String,
+}
+
+impl Clipboard {
+    /// Returns the text currently on the clipboard
+    pub fn get_text(&mut self) -> Option<String> {
+        #[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+        {
+            match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+                Ok(text) => return Some(text),
+                Err(error) => log::warn!("Failed to read the clipboard: {}", error),
+            }
+        }
+
+        if self.fallback.is_empty() {
+            None
+        } else {
+            Some(self.fallback.clone())
+        }
+    }
+
+    /// Puts the given text on the clipboard
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.fallback = text.into();
+
+        #[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+        {
+            if let Err(error) = arboard::Clipboard::new()
+                .and_then(|mut clipboard| clipboard.set_text(self.fallback.clone()))
+            {
+                log::warn!("Failed to write to the clipboard: {}", error);
+            }
+        }
+    }
+}
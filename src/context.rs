@@ -1,4 +1,7 @@
-use std::sync::{Arc, RwLock};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, RwLock,
+};
 
 use bevy::{
     ecs::{event::ManualEventReader, system::CommandQueue},
@@ -25,17 +28,22 @@ use crate::{
     prelude::KayakWidgetContext,
     render::{
         font::FontMapping,
+        material::MaterialHandle,
         unified::pipeline::{ExtractedQuad, ExtractedQuads, UIQuadType},
-        MAX_OPACITY_LAYERS,
     },
     render_primitive::RenderPrimitive,
+    state_styles::StateStyles,
     styles::{
-        ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, LayoutType,
-        RenderCommand, StyleProp, Units,
+        AlignItems, Anchor, Animation, AnimationMode, AnimationProgress, BoxShadow, ColorStop,
+        ComputedStyles, Corner, Dock, Edge, Gradient, GradientKind, ImageRepeat, JustifyContent,
+        KCursorIcon, KDisplay, KPositionType, KStyle, Keyframe, LayoutDirection, LayoutType,
+        RenderCommand, StyleProp, StyleTransition, TextOverflow, TextRun, TransitionProperty,
+        Units,
     },
     tree::{Change, Tree},
+    widget_events::WidgetEventQueues,
     widget_state::WidgetState,
-    Focusable, KayakUIPlugin, WindowSize,
+    Focusable, KayakUIPlugin, KayakUiScale, WindowSize,
 };
 
 /// A tag component representing when a widget has been mounted(added to the tree).
@@ -85,6 +93,11 @@ type WidgetSystems = HashMap<
 ///     .add_systems(Startup, setup);
 /// }
 /// ```
+///
+/// By default the UI is only drawn by the context's own camera. Adding a
+/// [`RenderLayers`](bevy::render::view::RenderLayers) component to the context's entity instead
+/// draws it with every [`CameraUIKayak`](crate::CameraUIKayak) camera sharing one of its layers
+/// (cameras without `RenderLayers` are on layer 0). Layout is still based on the context's own camera.
 #[derive(Component)]
 pub struct KayakRootContext {
     pub tree: Arc<RwLock<Tree>>,
@@ -105,8 +118,31 @@ pub struct KayakRootContext {
     pub(crate) unique_ids: Arc<DashMap<Entity, DashMap<String, Entity>>>,
     /// Maps keyed entities to spawn parents. We can't use the tree in this case.
     pub(crate) unique_ids_parents: Arc<DashMap<Entity, Entity>>,
+    /// Events queued for individual widgets, by event type.
+    pub(crate) widget_events: WidgetEventQueues,
     pub(crate) uninitilized_systems: HashSet<String>,
+    /// Widget types that were found without systems, so the error is only logged once.
+    pub(crate) missing_widget_systems: HashSet<String>,
     pub camera_entity: Entity,
+    /// Set by the renderer whenever the widget tree was drawn with some content.
+    pub(crate) painted: Arc<AtomicBool>,
+    /// Whether a [`KayakFirstPaint`] event was sent for the current paint.
+    pub(crate) first_paint_sent: bool,
+    /// Skips drawing widgets that are entirely outside of the window or their clip.
+    ///
+    /// Enabled by default. Disabling it can be useful for debugging.
+    pub cull_offscreen: bool,
+}
+
+/// An event sent once a root context's UI has been drawn for the first time
+///
+/// This is useful for hiding a splash or loading screen only once the UI is actually on
+/// screen. If the root widget is removed and the UI is built again, the event is sent again
+/// once the new UI has been drawn.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KayakFirstPaint {
+    /// The entity holding the [`KayakRootContext`] that was drawn
+    pub context_entity: Entity,
 }
 
 impl Default for KayakRootContext {
@@ -133,8 +169,13 @@ impl KayakRootContext {
             order_tree: Default::default(),
             unique_ids: Default::default(),
             unique_ids_parents: Default::default(),
+            widget_events: Default::default(),
             uninitilized_systems: Default::default(),
+            missing_widget_systems: Default::default(),
             camera_entity,
+            painted: Default::default(),
+            first_paint_sent: false,
+            cull_offscreen: true,
         }
     }
 
@@ -143,6 +184,133 @@ impl KayakRootContext {
         plugin.build(self)
     }
 
+    /// Spawns a UI camera along with a root context and its [`EventDispatcher`].
+    ///
+    /// The camera is spawned from the given bundle with [`CameraUIKayak`](crate::CameraUIKayak)
+    /// added, and the default widgets are registered with the context. The `build` closure
+    /// is where the widget tree is created, usually with `rsx!`.
+    ///
+    /// Returns the camera entity and the context entity.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use kayak_ui::prelude::{widgets::*, *};
+    /// fn setup(mut commands: Commands) {
+    ///     KayakRootContext::spawn_with_camera(
+    ///         &mut commands,
+    ///         Camera2dBundle::default(),
+    ///         |widget_context, commands| {
+    ///             let parent_id = None;
+    ///             rsx! {
+    ///                 <KayakAppBundle />
+    ///             };
+    ///         },
+    ///     );
+    /// }
+    /// ```
+    pub fn spawn_with_camera(
+        commands: &mut Commands,
+        camera: impl Bundle,
+        build: impl FnOnce(&mut KayakRootContext, &mut Commands),
+    ) -> (Entity, Entity) {
+        let camera_entity = commands.spawn((camera, crate::CameraUIKayak)).id();
+
+        let mut widget_context = KayakRootContext::new(camera_entity);
+        widget_context.add_plugin(crate::widgets::KayakWidgetsContextPlugin);
+        build(&mut widget_context, commands);
+
+        let context_entity = commands
+            .spawn((widget_context, EventDispatcher::default()))
+            .id();
+
+        (camera_entity, context_entity)
+    }
+
+    /// Returns true once the widget tree has been drawn with some content.
+    ///
+    /// See [`KayakFirstPaint`] to be notified when this happens.
+    pub fn is_painted(&self) -> bool {
+        self.painted.load(Ordering::Relaxed)
+    }
+
+    /// Returns the final layout of the given widget
+    ///
+    /// The rect is in logical pixels, before the window's scale factor is applied, and is
+    /// relative to the top-left corner of the UI camera's viewport. It's also available as the
+    /// [`WidgetLayout`](crate::layout::WidgetLayout) component on the widget.
+    pub fn get_widget_layout(&self, widget: Entity) -> Option<Rect> {
+        self.get_layout(&WrappedIndex(widget))
+    }
+
+    /// Returns the entity holding the state of the given widget, if it has any
+    pub fn get_state_entity(&self, widget: Entity) -> Option<Entity> {
+        self.widget_state.get(widget)
+    }
+
+    /// Returns a copy of the given widget's state
+    ///
+    /// This lets systems outside of the widget's own render system read its state, for example:
+    ///
+    /// ```rust,ignore
+    /// fn read_slider(context: Query<&KayakRootContext>, sliders: Query<&SliderState>) {
+    ///     let context = context.single();
+    ///     if let Some(state) = context.get_state::<SliderState>(slider_entity, &sliders) {
+    ///         info!("The slider is at {}", state.value);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Widgets are rendered by [`update_widgets_sys`] in `PostUpdate`, so during `Update` this
+    /// returns the state as of the end of the previous frame, and nothing before the widget's
+    /// first render.
+    pub fn get_state<State: Component + Clone>(
+        &self,
+        widget: Entity,
+        query: &Query<&State>,
+    ) -> Option<State> {
+        let state_entity = self.widget_state.get(widget)?;
+        query.get(state_entity).ok().cloned()
+    }
+
+    /// Replaces the given widget's state, returning false if the widget has no such state
+    ///
+    /// The widget is marked dirty if the state changed, so it's re-rendered by the next
+    /// [`update_widgets_sys`]. Run the system setting the state before `PostUpdate` to see the
+    /// change in the same frame. The widget's own event handlers and render system may still
+    /// change the state again afterwards.
+    pub fn set_state<State: Component + PartialEq>(
+        &self,
+        commands: &mut Commands,
+        widget: Entity,
+        state: State,
+        query: &mut Query<&mut State>,
+    ) -> bool {
+        let Some(mut current) = self
+            .widget_state
+            .get(widget)
+            .and_then(|state_entity| query.get_mut(state_entity).ok())
+        else {
+            return false;
+        };
+        if *current != state {
+            *current = state;
+            commands.entity(widget).insert(DirtyNode);
+        }
+        true
+    }
+
+    /// Queues an event for the given widget
+    ///
+    /// This lets systems outside of the UI talk to a single widget, which reads the events with
+    /// [`KayakWidgetContext::read_events`]. Events sent before `PostUpdate` are seen in the same
+    /// frame, and are dropped if the widget hasn't read them by the end of the next frame's
+    /// [`update_widgets_sys`].
+    pub fn emit_event<T: Send + 'static>(&self, widget: Entity, event: T) {
+        self.widget_events.get::<T>().send(widget, event);
+    }
+
     /// Get's the layout for th given widget index.
     pub(crate) fn get_layout(&self, id: &WrappedIndex) -> Option<Rect> {
         if let Ok(cache) = self.layout_cache.try_read() {
@@ -168,6 +336,9 @@ impl KayakRootContext {
     /// Update systems are ran every frame and return true or false depending on if the widget has "changed".
     /// Render systems are ran only if the widget has changed and are meant to re-render children and handle
     /// tree changes.
+    ///
+    /// If systems were already added for the widget type they are replaced, and the new systems are
+    /// initialized before the next update.
     pub fn add_widget_system<Params, Params2>(
         &mut self,
         type_name: impl Into<String>,
@@ -180,6 +351,26 @@ impl KayakRootContext {
         self.systems
             .insert(type_name.clone(), (update_system, render_system));
         self.uninitilized_systems.insert(type_name);
+        self.missing_widget_systems.clear();
+    }
+
+    /// Returns true if systems have been added for the given widget type.
+    pub fn has_widget_system(&self, type_name: &str) -> bool {
+        self.systems.contains_key(type_name)
+    }
+
+    /// Returns the names of all widget types that have systems added.
+    pub fn widget_system_names(&self) -> impl Iterator<Item = &String> {
+        self.systems.keys()
+    }
+
+    /// Removes the update and render systems of a widget type.
+    ///
+    /// Widgets of this type will no longer be updated or rendered until new systems are added.
+    /// Returns true if the widget type had systems.
+    pub fn remove_widget_system(&mut self, type_name: &str) -> bool {
+        self.uninitilized_systems.remove(type_name);
+        self.systems.remove(type_name).is_some()
     }
 
     /// Let's the widget context know what data types are used for a given widget.
@@ -356,6 +547,9 @@ impl KayakRootContext {
 
     /// Generates a flat list of widget render commands sorted by tree order.
     /// There is no need to call this unless you are implementing your own custom renderer.
+    ///
+    /// At most `available_opacity_layers` opacity layers are used. Returns the number of opacity
+    /// layers the UI needed, which may be more than were available.
     pub fn build_render_primitives(
         &self,
         commands: &mut Commands,
@@ -367,19 +561,35 @@ impl KayakRootContext {
         font_mapping: &FontMapping,
         images: &Assets<Image>,
         extracted_quads: &mut ExtractedQuads,
-    ) {
+        available_opacity_layers: u32,
+    ) -> u32 {
         let node_tree = self.tree.try_read();
         if node_tree.is_err() {
-            return;
+            return 0;
         }
 
         let node_tree = node_tree.unwrap();
 
         if node_tree.root_node.is_none() {
-            return;
+            self.painted.store(false, Ordering::Relaxed);
+            return 0;
         }
 
+        let mut requested_opacity_layers = 0;
+        let quad_count = extracted_quads.quads.len();
         if let Ok(mut layout_cache) = self.layout_cache.try_write() {
+            // The root node covers the whole window
+            let viewport = if self.cull_offscreen {
+                layout_cache
+                    .rect
+                    .get(&node_tree.root_node.unwrap())
+                    .map(|layout| bevy::prelude::Rect {
+                        min: Vec2::new(layout.posx, layout.posy),
+                        max: Vec2::new(layout.posx + layout.width, layout.posy + layout.height),
+                    })
+            } else {
+                None
+            };
             recurse_node_tree_to_build_primitives(
                 commands,
                 camera_entity,
@@ -396,15 +606,101 @@ impl KayakRootContext {
                 0.0,
                 0.0,
                 None,
+                viewport,
                 0,
                 0,
+                available_opacity_layers,
+                &mut requested_opacity_layers,
+                1.0,
+                1.0,
             );
         }
+
+        if extracted_quads.quads.len() > quad_count {
+            self.painted.store(true, Ordering::Relaxed);
+        }
+
+        requested_opacity_layers
     }
 }
 
 pub const UI_Z_STEP: f32 = 0.001;
 
+/// Checks if the primitives of a single node can be faded by multiplying their alpha
+/// rather than by drawing them into an opacity layer.
+///
+/// This is only the case when none of the primitives overlap, so either a single quad or
+/// a run of text glyphs.
+fn can_flatten_opacity(quads: &[ExtractedQuad]) -> bool {
+    let mut drawn = quads
+        .iter()
+        .filter(|quad| quad.quad_type != UIQuadType::Clip)
+        .peekable();
+
+    if drawn.peek().is_none() {
+        return true;
+    }
+
+    let mut count = 0;
+    let mut all_text = true;
+    for quad in drawn {
+        if quad.svg_handle.0.is_some() && quad.svg_handle.1.is_none() {
+            return false;
+        }
+        all_text &= matches!(quad.quad_type, UIQuadType::Text | UIQuadType::TextSubpixel);
+        count += 1;
+    }
+
+    all_text || count == 1
+}
+
+/// Checks if a node is entirely outside of the viewport or the clip it's drawn in.
+///
+/// The viewport is in logical pixels, while the clip is in physical pixels. Box shadows are
+/// included since they can be visible even when the node itself isn't.
+fn is_offscreen(
+    node: &crate::node::Node,
+    layout: &Rect,
+    viewport: bevy::prelude::Rect,
+    clip: Option<&ExtractedQuad>,
+    dpi: f32,
+) -> bool {
+    let visible = if let Some(clip) = clip {
+        viewport.intersect(bevy::prelude::Rect {
+            min: clip.rect.min / dpi,
+            max: clip.rect.max / dpi,
+        })
+    } else {
+        viewport
+    };
+
+    let shadow_extent = node
+        .resolved_styles
+        .box_shadow
+        .resolve_as_option()
+        .map(|box_shadows| {
+            box_shadows
+                .iter()
+                .map(|box_shadow| {
+                    box_shadow.radius
+                        + box_shadow.spread.max_element()
+                        + box_shadow.offset.abs().max_element()
+                })
+                .fold(0.0, f32::max)
+        })
+        .unwrap_or(0.0);
+
+    layout.posx + layout.width + shadow_extent < visible.min.x
+        || layout.posy + layout.height + shadow_extent < visible.min.y
+        || layout.posx - shadow_extent > visible.max.x
+        || layout.posy - shadow_extent > visible.max.y
+}
+
+/// Multiplies two colors component-wise
+fn multiply_color(a: Color, b: Color) -> Color {
+    Color::rgba(a.r() * b.r(), a.g() * b.g(), a.b() * b.b(), a.a() * b.a())
+}
+
 fn recurse_node_tree_to_build_primitives(
     commands: &mut Commands,
     camera_entity: Entity,
@@ -421,14 +717,26 @@ fn recurse_node_tree_to_build_primitives(
     _parent_global_z: f32,
     mut current_global_z: f32,
     mut prev_clip: Option<ExtractedQuad>,
+    viewport: Option<bevy::prelude::Rect>,
     mut current_opacity_layer: u32,
     mut total_opacity_layers: u32,
+    available_opacity_layers: u32,
+    requested_opacity_layers: &mut u32,
+    parent_opacity: f32,
+    mut layer_opacity: f32,
 ) -> (usize, f32, u32) {
     let mut opacity = None;
+    let mut effective_opacity = parent_opacity;
     let mut child_count = 0;
+    let mut render_layer = 0;
     if let Ok(node) = nodes.get(current_node.0) {
-        // Skip rendering completely transparent objects.
-        if node.opacity < 0.001 {
+        // A node is faded by the opacity of its ancestors as well as its own.
+        effective_opacity *= node.opacity;
+
+        // Skip rendering completely transparent or hidden objects.
+        if effective_opacity < 0.001
+            || !matches!(node.resolved_styles.display.resolve(), KDisplay::Shown)
+        {
             return (0, current_global_z, total_opacity_layers);
         }
         current_global_z += UI_Z_STEP + if node.z <= 0.0 { 0.0 } else { node.z };
@@ -459,37 +767,126 @@ fn recurse_node_tree_to_build_primitives(
             Rect::default()
         };
 
-        let new_clip = node.resolved_styles.extract(
-            commands,
-            &layout,
-            current_opacity_layer,
-            extracted_quads,
-            camera_entity,
-            fonts,
-            font_mapping,
-            images,
-            dpi,
-            prev_clip.clone(),
-        );
+        // Quads on another render layer are sorted away from their parent's clip, so restore it
+        // (or clear it) at the start of the layer.
+        render_layer = node.resolved_styles.render_layer.resolve_or(0);
+        let parent_render_layer = node_tree
+            .get_parent(current_node)
+            .and_then(|parent| nodes.get(parent.0).ok())
+            .map(|parent| parent.resolved_styles.render_layer.resolve_or(0))
+            .unwrap_or(0);
+        if render_layer != parent_render_layer {
+            let clip = if let Some(prev_clip) = &prev_clip {
+                prev_clip.clone()
+            } else {
+                let root_node_layout = layout_cache
+                    .rect
+                    .get(&node_tree.root_node.unwrap())
+                    .copied()
+                    .unwrap_or_default();
+                ExtractedQuad {
+                    camera_entity,
+                    rect: bevy::prelude::Rect {
+                        min: Vec2::new(root_node_layout.posx, root_node_layout.posy) * dpi,
+                        max: Vec2::new(
+                            root_node_layout.posx + root_node_layout.width,
+                            root_node_layout.posy + root_node_layout.height,
+                        ) * dpi,
+                    },
+                    quad_type: UIQuadType::Clip,
+                    opacity_layer: current_opacity_layer,
+                    ..Default::default()
+                }
+            };
+            extracted_quads.quads.push(ExtractedQuad {
+                z_index: layout.z_index - UI_Z_STEP * 0.5,
+                render_layer,
+                ..clip
+            });
+        }
 
-        // Only spawn an opacity layer if we have an opacity greater than zero or less than one.
-        if node.opacity < 1.0 {
-            // If we've hit max opacity layer capacity skip rendering.
-            if total_opacity_layers + 1 >= MAX_OPACITY_LAYERS {
-                return (0, current_global_z, total_opacity_layers);
-            }
+        let is_culled = viewport
+            .map(|viewport| is_offscreen(node, &layout, viewport, prev_clip.as_ref(), dpi))
+            .unwrap_or_default();
+        if is_culled
+            && matches!(
+                node.resolved_styles.render_command,
+                StyleProp::Value(RenderCommand::Clip)
+            )
+        {
+            // Everything within an offscreen clip is clipped away, so skip the children as well.
+            return (0, current_global_z, total_opacity_layers);
+        }
 
-            // Add in an opacity layer
-            total_opacity_layers += 1;
-            extracted_quads.quads.push(ExtractedQuad {
+        let first_extracted_quad = extracted_quads.quads.len();
+        let new_clip = if is_culled {
+            // Children aren't bound to their parent's layout, so they still need to be visited.
+            None
+        } else {
+            node.resolved_styles.extract(
+                commands,
+                &layout,
+                current_opacity_layer,
+                extracted_quads,
                 camera_entity,
-                z_index: layout.z_index,
-                quad_type: UIQuadType::OpacityLayer,
-                opacity_layer: total_opacity_layers,
-                ..Default::default()
-            });
-            opacity = Some((node.opacity, total_opacity_layers));
-            current_opacity_layer = total_opacity_layers;
+                fonts,
+                font_mapping,
+                images,
+                dpi,
+                prev_clip.clone(),
+            )
+        };
+
+        let is_leaf = node_tree
+            .children
+            .get(&current_node)
+            .map(|children| children.is_empty())
+            .unwrap_or(true);
+
+        // The opacity layer this node is drawn into has already been faded by the opacity of the
+        // node that added it, so only the remaining opacity is applied here.
+        let relative_opacity = effective_opacity / layer_opacity;
+
+        // Only spawn an opacity layer if we have an opacity greater than zero or less than one,
+        // or if the node is tinted.
+        // Leaf nodes whose primitives don't overlap can simply color their own quads instead.
+        let needs_layer = relative_opacity < 1.0 || node.tint.is_some();
+        if needs_layer
+            && is_leaf
+            && node.resolved_styles.material.resolve_as_option().is_none()
+            && can_flatten_opacity(&extracted_quads.quads[first_extracted_quad..])
+        {
+            let mut layer_color = node.tint.unwrap_or(Color::WHITE);
+            layer_color.set_a(layer_color.a() * relative_opacity);
+            for quad in extracted_quads.quads[first_extracted_quad..].iter_mut() {
+                quad.color = multiply_color(quad.color, layer_color);
+                if let Some(svg_color) = quad.svg_handle.1.as_mut() {
+                    *svg_color = multiply_color(*svg_color, layer_color);
+                }
+            }
+        } else if needs_layer {
+            *requested_opacity_layers += 1;
+            // Without a free opacity layer the node is drawn without its opacity rather than
+            // not at all. The layer is created for the next frame if the limit allows it.
+            if total_opacity_layers < available_opacity_layers {
+                // Add in an opacity layer
+                total_opacity_layers += 1;
+                extracted_quads.quads.push(ExtractedQuad {
+                    camera_entity,
+                    z_index: layout.z_index,
+                    quad_type: UIQuadType::OpacityLayer,
+                    opacity_layer: total_opacity_layers,
+                    render_layer,
+                    ..Default::default()
+                });
+                // Opacity layers are always drawn straight to the screen, rather than into the
+                // layer of an ancestor, so they're faded by the full opacity.
+                let mut layer_color = node.tint.unwrap_or(Color::WHITE);
+                layer_color.set_a(layer_color.a() * effective_opacity);
+                opacity = Some((layer_color, total_opacity_layers));
+                current_opacity_layer = total_opacity_layers;
+                layer_opacity = effective_opacity;
+            }
         }
 
         // let _indent = "  ".repeat(depth);
@@ -505,6 +902,7 @@ fn recurse_node_tree_to_build_primitives(
             let current_parent_global_z = current_global_z;
             let children = node_tree.children.get(&current_node).unwrap();
             for child in children {
+                let first_child_quad = extracted_quads.quads.len();
                 let (new_child_count, new_global_z, new_total_opacity_layers) =
                     recurse_node_tree_to_build_primitives(
                         commands,
@@ -522,18 +920,30 @@ fn recurse_node_tree_to_build_primitives(
                         current_parent_global_z,
                         current_global_z,
                         prev_clip.clone(),
+                        viewport,
                         current_opacity_layer,
                         total_opacity_layers,
+                        available_opacity_layers,
+                        requested_opacity_layers,
+                        effective_opacity,
+                        layer_opacity,
                     );
                 current_global_z = new_global_z;
                 child_count += new_child_count;
                 total_opacity_layers = new_total_opacity_layers;
 
-                // Between each child node we need to reset the clip.
-                if let Some(prev_clip) = &prev_clip {
+                // Between each child node we need to reset the clip, unless the child left it as is.
+                // Backdrops split the render pass, which drops the clip as well.
+                let child_clipped = extracted_quads.quads[first_child_quad..]
+                    .iter()
+                    .any(|quad| {
+                        matches!(quad.quad_type, UIQuadType::Clip | UIQuadType::BackdropBlur)
+                    });
+                if let Some(prev_clip) = prev_clip.as_ref().filter(|_| child_clipped) {
                     current_global_z += UI_Z_STEP * 2.0; // * child_count as f32;
                     extracted_quads.quads.push(ExtractedQuad {
                         z_index: current_global_z,
+                        render_layer,
                         ..prev_clip.clone()
                     });
                     // println!("{}   [previous_clip, z: {}, x: {}, y: {}, width: {}, height: {}", _indent, current_global_z, layout.posx, layout.posy, layout.width, layout.height);
@@ -572,7 +982,7 @@ fn recurse_node_tree_to_build_primitives(
 
     // When an opacity layer has been added all of its children are drawn to the same render target.
     // After we need to draw the render target for that opacity layer to the screen.
-    if let Some((opacity, opacity_layer)) = opacity {
+    if let Some((layer_color, opacity_layer)) = opacity {
         let root_node_layout = layout_cache
             .rect
             .get(&node_tree.root_node.unwrap())
@@ -581,8 +991,9 @@ fn recurse_node_tree_to_build_primitives(
         extracted_quads.quads.push(ExtractedQuad {
             camera_entity,
             z_index: current_global_z,
-            color: Color::rgba(1.0, 1.0, 1.0, opacity),
+            color: layer_color,
             opacity_layer,
+            render_layer,
             quad_type: UIQuadType::DrawOpacityLayer,
             rect: bevy::prelude::Rect {
                 min: Vec2::new(root_node_layout.posx, root_node_layout.posy),
@@ -639,6 +1050,7 @@ pub fn update_widgets_sys(world: &mut World) {
         // };
 
         let mut new_ticks = HashMap::new();
+        let mut skipped_widgets = HashSet::default();
 
         update_widgets(
             context.camera_entity,
@@ -657,6 +1069,9 @@ pub fn update_widgets_sys(world: &mut World) {
             &context.index,
             &context.unique_ids,
             &context.unique_ids_parents,
+            &context.widget_events,
+            &mut context.missing_widget_systems,
+            &mut skipped_widgets,
         );
 
         // if let Some(old_focus) = old_focus {
@@ -680,6 +1095,9 @@ pub fn update_widgets_sys(world: &mut World) {
         // Clear out indices
         context.index.clear();
 
+        // Age widget events, dropping the ones that went unread since the last update
+        context.widget_events.update();
+
         world.entity_mut(entity).insert(context);
     }
 }
@@ -701,8 +1119,15 @@ fn update_widgets(
     index: &Arc<DashMap<Entity, usize>>,
     unique_ids: &Arc<DashMap<Entity, DashMap<String, Entity>>>,
     unique_ids_parents: &Arc<DashMap<Entity, Entity>>,
+    widget_events: &WidgetEventQueues,
+    missing_widget_systems: &mut HashSet<String>,
+    skipped_widgets: &mut HashSet<Entity>,
 ) {
     for entity in widgets.iter() {
+        // Descendants of a widget without systems are left as they are.
+        if skipped_widgets.contains(&entity.0) {
+            continue;
+        }
         // if let (Some(entity_ref), Some(_)) = (
         //     world.get_entity(entity.0),
         //     tree.try_write()
@@ -721,11 +1146,12 @@ fn update_widgets(
                     Some(camera_entity),
                     unique_ids.clone(),
                     unique_ids_parents.clone(),
+                    widget_events.clone(),
                 );
                 widget_context.copy_from_point(tree, *entity);
                 let children_before = widget_context.get_children(entity.0);
                 // let widget_name = widget_type.0.clone();
-                let (widget_context, should_update_children) = update_widget(
+                let Some((widget_context, should_update_children)) = update_widget(
                     systems,
                     tree,
                     focus_tree,
@@ -738,7 +1164,13 @@ fn update_widgets(
                     cloned_widget_entities,
                     widget_state,
                     new_ticks,
-                );
+                    missing_widget_systems,
+                ) else {
+                    if let Ok(tree) = tree.read() {
+                        skipped_widgets.extend(tree.down_iter_at(*entity, true).map(|node| node.0));
+                    }
+                    continue;
+                };
 
                 if should_update_children {
                     if let Ok(mut tree) = tree.write() {
@@ -1041,6 +1473,9 @@ fn update_widgets(
                     index,
                     unique_ids,
                     unique_ids_parents,
+                    widget_events,
+                    missing_widget_systems,
+                    skipped_widgets,
                 );
                 // }
             }
@@ -1117,7 +1552,21 @@ fn update_widget(
     cloned_widget_entities: &DashMap<Entity, Entity>,
     widget_state: &WidgetState,
     new_ticks: &mut HashMap<String, bevy::ecs::component::Tick>,
-) -> (Tree, bool) {
+    missing_widget_systems: &mut HashSet<String>,
+) -> Option<(Tree, bool)> {
+    // A widget without registered systems can't be updated or rendered. Returning `None` skips
+    // it and its children rather than bringing down the whole app.
+    if !systems.contains_key(&widget_type) {
+        if missing_widget_systems.insert(widget_type.clone()) {
+            log::error!(
+                "Wasn't able to find render/update systems for widget: {} ({:?})! Did you forget to call `add_widget_system`? Skipping it and its children.",
+                widget_type,
+                entity.0
+            );
+        }
+        return None;
+    }
+
     // Check if we should update this widget
     let should_rerender = {
         // TODO: Move the spawning to when we create the widget.
@@ -1157,15 +1606,7 @@ fn update_widget(
                 target
             }
         };
-        let widget_update_system = &mut systems
-            .get_mut(&widget_type)
-            .unwrap_or_else(|| {
-                panic!(
-                    "Wasn't able to find render/update systems for widget: {}!",
-                    widget_type
-                )
-            })
-            .0;
+        let widget_update_system = &mut systems.get_mut(&widget_type).unwrap().0;
         let old_tick = widget_update_system.get_last_run();
 
         // Insert context as a bevy resource.
@@ -1219,7 +1660,7 @@ fn update_widget(
     };
 
     if !should_rerender {
-        return (widget_context.take(), false);
+        return Some((widget_context.take(), false));
     }
 
     let should_update_children;
@@ -1262,6 +1703,17 @@ fn update_widget(
 
         widget_context.index.insert(entity.0, 0);
     }
+
+    // Apply the styles for the widget's current state over the ones it rendered with.
+    if let Some(state_styles) = world.get::<StateStyles>(entity.0).cloned() {
+        if let Some(state_entity) = widget_state.get(entity.0) {
+            if let Some(styles) = state_styles.resolve(world, state_entity) {
+                if let Some(mut computed_styles) = world.get_mut::<ComputedStyles>(entity.0) {
+                    computed_styles.0 = styles.with_style(&computed_styles.0);
+                }
+            }
+        }
+    }
     let widget_context = widget_context.take();
     let mut command_queue = CommandQueue::default();
     let mut commands = Commands::new(&mut command_queue, world);
@@ -1274,7 +1726,55 @@ fn update_widget(
 
     command_queue.apply(world);
 
-    (widget_context, should_update_children)
+    Some((widget_context, should_update_children))
+}
+
+/// Marks every node dirty when the primary window's scale factor or the [`KayakUiScale`] changes.
+///
+/// This happens when a window is moved between monitors with different DPI. Marking the
+/// nodes dirty makes sure pixel sizes reflow and text is re-measured at the new DPI.
+fn mark_dirty_on_scale_factor_change(
+    mut commands: Commands,
+    mut last_scale_factor: Local<Option<f64>>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<KayakUiScale>,
+    context_query: Query<&KayakRootContext>,
+) {
+    let scale_factor = if let Ok(window) = primary_window.get_single() {
+        window.scale_factor() * ui_scale.get() as f64
+    } else {
+        return;
+    };
+    let previous_scale_factor = last_scale_factor.replace(scale_factor);
+    if previous_scale_factor.is_none() || previous_scale_factor == Some(scale_factor) {
+        return;
+    }
+
+    for context in context_query.iter() {
+        if let Ok(tree) = context.tree.try_read() {
+            for node in tree.down_iter() {
+                if let Some(mut entity_commands) = commands.get_entity(node.0) {
+                    entity_commands.insert(DirtyNode);
+                }
+            }
+        }
+    }
+}
+
+/// Sends a [`KayakFirstPaint`] event for every root context that was drawn since the last check.
+fn send_first_paint_events(
+    mut context_query: Query<(Entity, &mut KayakRootContext)>,
+    mut first_paint_events: EventWriter<KayakFirstPaint>,
+) {
+    for (context_entity, mut context) in context_query.iter_mut() {
+        let is_painted = context.is_painted();
+        if is_painted && !context.first_paint_sent {
+            first_paint_events.send(KayakFirstPaint { context_entity });
+        }
+        if context.first_paint_sent != is_painted {
+            context.first_paint_sent = is_painted;
+        }
+    }
 }
 
 /// The default Kayak Context plugin
@@ -1287,6 +1787,15 @@ pub struct CustomEventReader<T: bevy::ecs::event::Event>(pub ManualEventReader<T
 impl Plugin for KayakContextPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(WindowSize::default())
+            .init_resource::<KayakUiScale>()
+            .init_resource::<crate::input::KeyRepeat>()
+            .init_resource::<crate::event_dispatcher::MultiClick>()
+            .init_resource::<crate::clipboard::Clipboard>()
+            .init_resource::<crate::input::ImeComposition>()
+            .init_resource::<crate::input::PrimaryTouch>()
+            .init_resource::<crate::styles::StyleTransitions>()
+            .init_resource::<crate::input::ConsumedInput>()
+            .add_event::<KayakFirstPaint>()
             .insert_resource(CustomEventReader(ManualEventReader::<
                 bevy::window::CursorMoved,
             >::default()))
@@ -1302,6 +1811,12 @@ impl Plugin for KayakContextPlugin {
             .insert_resource(CustomEventReader(ManualEventReader::<
                 bevy::input::keyboard::KeyboardInput,
             >::default()))
+            .insert_resource(CustomEventReader(
+                ManualEventReader::<bevy::window::Ime>::default(),
+            ))
+            .insert_resource(CustomEventReader(ManualEventReader::<
+                bevy::input::touch::TouchInput,
+            >::default()))
             .add_plugins((
                 crate::camera::KayakUICameraPlugin,
                 crate::render::BevyKayakUIRenderPlugin,
@@ -1309,11 +1824,22 @@ impl Plugin for KayakContextPlugin {
             .add_systems(
                 Update,
                 (
-                    crate::input::process_events,
+                    crate::input::process_events.in_set(crate::input::KayakInputSet),
                     crate::window_size::update_window_size,
+                    mark_dirty_on_scale_factor_change,
+                    send_first_paint_events,
                 ),
             )
-            .add_systems(PostUpdate, (update_widgets_sys, calculate_ui).chain());
+            .add_systems(
+                PostUpdate,
+                (
+                    update_widgets_sys,
+                    crate::styles::update_animations,
+                    crate::styles::update_style_transitions,
+                    calculate_ui,
+                )
+                    .chain(),
+            );
 
         // Register reflection types.
         // A bit annoying..
@@ -1322,9 +1848,48 @@ impl Plugin for KayakContextPlugin {
             .register_type::<KStyle>()
             .register_type::<KChildren>()
             .register_type::<crate::layout::Rect>()
+            .register_type::<crate::layout::WidgetLayout>()
             .register_type::<crate::node::Node>()
             .register_type::<WidgetName>()
+            // Style values
+            .register_type::<AlignItems>()
+            .register_type::<Animation>()
+            .register_type::<AnimationMode>()
+            .register_type::<AnimationProgress>()
+            .register_type::<Anchor>()
+            .register_type::<BoxShadow>()
+            .register_type::<Vec<BoxShadow>>()
+            .register_type::<Corner<f32>>()
+            .register_type::<Dock>()
+            .register_type::<Edge<f32>>()
+            .register_type::<Edge<Units>>()
+            .register_type::<ColorStop>()
+            .register_type::<Vec<ColorStop>>()
+            .register_type::<Gradient>()
+            .register_type::<GradientKind>()
+            .register_type::<JustifyContent>()
+            .register_type::<Keyframe>()
+            .register_type::<Vec<Keyframe>>()
+            .register_type::<KCursorIcon>()
+            .register_type::<KDisplay>()
+            .register_type::<KPositionType>()
+            .register_type::<LayoutType>()
+            .register_type::<LayoutDirection>()
+            .register_type::<MaterialHandle>()
+            .register_type::<PointerEvents>()
+            .register_type::<RenderCommand>()
+            .register_type::<StyleTransition>()
+            .register_type::<Vec<StyleTransition>>()
+            .register_type::<TextOverflow>()
+            .register_type::<ImageRepeat>()
+            .register_type::<TransitionProperty>()
+            .register_type::<TextRun>()
+            .register_type::<Vec<TextRun>>()
+            .register_type::<Units>()
+            .register_type::<Vec<Units>>()
+            // Style props
             .register_type::<StyleProp<Color>>()
+            .register_type::<StyleProp<(f32, Color)>>()
             .register_type::<StyleProp<Corner<f32>>>()
             .register_type::<StyleProp<Edge<f32>>>()
             .register_type::<StyleProp<Units>>()
@@ -1332,11 +1897,27 @@ impl Plugin for KayakContextPlugin {
             .register_type::<StyleProp<String>>()
             .register_type::<StyleProp<f32>>()
             .register_type::<StyleProp<LayoutType>>()
+            .register_type::<StyleProp<LayoutDirection>>()
+            .register_type::<StyleProp<JustifyContent>>()
+            .register_type::<StyleProp<AlignItems>>()
+            .register_type::<StyleProp<Dock>>()
+            .register_type::<StyleProp<TextOverflow>>()
+            .register_type::<StyleProp<ImageRepeat>>()
+            .register_type::<StyleProp<Anchor>>()
             .register_type::<StyleProp<Edge<Units>>>()
             .register_type::<StyleProp<PointerEvents>>()
+            .register_type::<StyleProp<KDisplay>>()
             .register_type::<StyleProp<KPositionType>>()
             .register_type::<StyleProp<RenderCommand>>()
-            .register_type::<StyleProp<i32>>();
+            .register_type::<StyleProp<i32>>()
+            .register_type::<StyleProp<u32>>()
+            .register_type::<StyleProp<usize>>()
+            .register_type::<StyleProp<bool>>()
+            .register_type::<StyleProp<Vec<Units>>>()
+            .register_type::<StyleProp<Vec<BoxShadow>>>()
+            .register_type::<StyleProp<Gradient>>()
+            .register_type::<StyleProp<Vec<StyleTransition>>>()
+            .register_type::<StyleProp<MaterialHandle>>();
     }
 }
 
@@ -1362,11 +1943,36 @@ fn calculate_ui(world: &mut World) {
         let mut layout_system = IntoSystem::into_system(calculate_layout);
         layout_system.initialize(world);
 
+        let mut dirty_query = world.query_filtered::<Entity, With<DirtyNode>>();
+        let mut collect_dirty_nodes = |context: &KayakRootContext, world: &World| {
+            if let Ok(tree) = context.tree.try_read() {
+                dirty_query
+                    .iter(world)
+                    .map(WrappedIndex)
+                    .filter(|node| tree.contains(*node))
+                    .collect::<HashSet<_>>()
+            } else {
+                HashSet::default()
+            }
+        };
         for _ in 0..2 {
+            // Skip the layout entirely while nothing in this tree changed
+            let mut dirty_nodes = collect_dirty_nodes(&context, world);
+            if dirty_nodes.is_empty() {
+                if let Ok(mut cache) = context.layout_cache.try_write() {
+                    cache.geometry_changed.clear();
+                }
+                break;
+            }
+
             context = node_system.run(context, world);
             node_system.apply_deferred(world);
 
-            context = layout_system.run(context, world);
+            // Building the nodes can mark more of them dirty, e.g. text that has to be fit to
+            // its parent, and the layout has to cover those as well
+            dirty_nodes.extend(collect_dirty_nodes(&context, world));
+
+            context = layout_system.run((context, dirty_nodes.into_iter().collect()), world);
             layout_system.apply_deferred(world);
             LayoutEventDispatcher::dispatch(&mut context, world);
         }
@@ -1418,3 +2024,255 @@ impl From<WidgetName> for String {
         val.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{asset::AssetPlugin, ecs::system::SystemState, prelude::*};
+    use kayak_font::KayakFont;
+
+    use super::{
+        recurse_node_tree_to_build_primitives, update_widgets_sys, KayakRootContext, WidgetName,
+    };
+    use crate::{
+        layout::{LayoutCache, Rect},
+        node::{Node, WrappedIndex},
+        render::{
+            font::FontMapping,
+            unified::pipeline::{ExtractedQuads, UIQuadType},
+        },
+        styles::{KStyle, RenderCommand, StyleProp},
+        tree::Tree,
+    };
+
+    struct TestUi {
+        app: App,
+        tree: Tree,
+        layout_cache: LayoutCache,
+    }
+
+    impl TestUi {
+        fn new() -> Self {
+            let mut app = App::new();
+            app.add_plugins(AssetPlugin::default())
+                .add_asset::<KayakFont>()
+                .add_asset::<Image>()
+                .init_resource::<FontMapping>();
+            Self {
+                app,
+                tree: Tree::default(),
+                layout_cache: LayoutCache::default(),
+            }
+        }
+
+        fn add(
+            &mut self,
+            parent: Option<WrappedIndex>,
+            styles: KStyle,
+            (posx, posy, width, height): (f32, f32, f32, f32),
+        ) -> WrappedIndex {
+            let node = WrappedIndex(
+                self.app
+                    .world
+                    .spawn((
+                        Node {
+                            resolved_styles: styles,
+                            ..Default::default()
+                        },
+                        WidgetName("Test".to_string()),
+                    ))
+                    .id(),
+            );
+            self.tree.add(node, parent);
+            self.layout_cache.rect.insert(
+                node,
+                Rect {
+                    posx,
+                    posy,
+                    width,
+                    height,
+                    z_index: 0.0,
+                },
+            );
+            node
+        }
+
+        /// Builds the quads of the tree, returning the type and rect of each one
+        fn build(&mut self) -> Vec<(UIQuadType, bevy::prelude::Rect)> {
+            let mut state: SystemState<(
+                Commands,
+                Query<&Node>,
+                Query<&WidgetName>,
+                Res<Assets<KayakFont>>,
+                Res<FontMapping>,
+                Res<Assets<Image>>,
+            )> = SystemState::new(&mut self.app.world);
+            let (mut commands, nodes, widget_names, fonts, font_mapping, images) =
+                state.get_mut(&mut self.app.world);
+
+            let mut extracted_quads = ExtractedQuads::default();
+            recurse_node_tree_to_build_primitives(
+                &mut commands,
+                Entity::PLACEHOLDER,
+                1.0,
+                &self.tree,
+                &mut self.layout_cache,
+                &nodes,
+                &widget_names,
+                &fonts,
+                &font_mapping,
+                &images,
+                &mut extracted_quads,
+                self.tree.root_node.unwrap(),
+                0.0,
+                0.0,
+                None,
+                None,
+                0,
+                0,
+                0,
+                &mut 0,
+                1.0,
+                1.0,
+            );
+
+            extracted_quads
+                .quads
+                .into_iter()
+                .map(|quad| (quad.quad_type, quad.rect))
+                .collect()
+        }
+    }
+
+    fn styles(render_command: RenderCommand) -> KStyle {
+        KStyle {
+            render_command: StyleProp::Value(render_command),
+            background_color: StyleProp::Value(Color::RED),
+            ..Default::default()
+        }
+    }
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> bevy::prelude::Rect {
+        bevy::prelude::Rect::new(x, y, x + width, y + height)
+    }
+
+    #[test]
+    fn nested_clip_restores_parent_clip() {
+        let mut ui = TestUi::new();
+        let root = ui.add(None, styles(RenderCommand::Clip), (0.0, 0.0, 100.0, 100.0));
+        let inner = ui.add(
+            Some(root),
+            styles(RenderCommand::Clip),
+            (10.0, 10.0, 50.0, 50.0),
+        );
+        ui.add(
+            Some(inner),
+            styles(RenderCommand::Quad),
+            (10.0, 10.0, 80.0, 80.0),
+        );
+        ui.add(
+            Some(root),
+            styles(RenderCommand::Quad),
+            (0.0, 70.0, 20.0, 20.0),
+        );
+
+        assert_eq!(
+            ui.build(),
+            vec![
+                (UIQuadType::Clip, rect(0.0, 0.0, 100.0, 100.0)),
+                (UIQuadType::Clip, rect(10.0, 10.0, 50.0, 50.0)),
+                (UIQuadType::Quad, rect(10.0, 10.0, 80.0, 80.0)),
+                (UIQuadType::Clip, rect(0.0, 0.0, 100.0, 100.0)),
+                (UIQuadType::Quad, rect(0.0, 70.0, 20.0, 20.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn backdrop_restores_parent_clip() {
+        let mut ui = TestUi::new();
+        let root = ui.add(None, styles(RenderCommand::Clip), (0.0, 0.0, 100.0, 100.0));
+        ui.add(
+            Some(root),
+            KStyle {
+                backdrop_blur: StyleProp::Value(4.0),
+                ..styles(RenderCommand::Layout)
+            },
+            (10.0, 10.0, 50.0, 50.0),
+        );
+        ui.add(
+            Some(root),
+            styles(RenderCommand::Quad),
+            (0.0, 70.0, 20.0, 20.0),
+        );
+
+        assert_eq!(
+            ui.build(),
+            vec![
+                (UIQuadType::Clip, rect(0.0, 0.0, 100.0, 100.0)),
+                (UIQuadType::BackdropBlur, rect(10.0, 10.0, 50.0, 50.0)),
+                (UIQuadType::Clip, rect(0.0, 0.0, 100.0, 100.0)),
+                (UIQuadType::Quad, rect(0.0, 70.0, 20.0, 20.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn unclipped_children_keep_parent_clip() {
+        let mut ui = TestUi::new();
+        let root = ui.add(None, styles(RenderCommand::Clip), (0.0, 0.0, 100.0, 100.0));
+        ui.add(
+            Some(root),
+            styles(RenderCommand::Quad),
+            (0.0, 0.0, 20.0, 20.0),
+        );
+        ui.add(
+            Some(root),
+            styles(RenderCommand::Quad),
+            (0.0, 70.0, 20.0, 20.0),
+        );
+
+        assert_eq!(
+            ui.build(),
+            vec![
+                (UIQuadType::Clip, rect(0.0, 0.0, 100.0, 100.0)),
+                (UIQuadType::Quad, rect(0.0, 0.0, 20.0, 20.0)),
+                (UIQuadType::Quad, rect(0.0, 70.0, 20.0, 20.0)),
+            ]
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct UpdatedWidgets(Vec<Entity>);
+
+    fn record_update(
+        In((entity, _previous_entity)): In<(Entity, Entity)>,
+        mut updated: ResMut<UpdatedWidgets>,
+    ) -> bool {
+        updated.0.push(entity);
+        false
+    }
+
+    #[test]
+    fn widget_without_systems_skips_its_subtree() {
+        let mut world = World::new();
+        world.init_resource::<UpdatedWidgets>();
+
+        let mut context = KayakRootContext::new(Entity::PLACEHOLDER);
+        context.add_widget_system("Recorded", record_update, |In(_entity): In<Entity>| false);
+        let root = world.spawn(WidgetName("Recorded".to_string())).id();
+        let missing = world.spawn(WidgetName("Missing".to_string())).id();
+        let child = world.spawn(WidgetName("Recorded".to_string())).id();
+        context.add_widget(None, root);
+        context.add_widget(Some(root), missing);
+        context.add_widget(Some(missing), child);
+        let context_entity = world.spawn(context).id();
+
+        update_widgets_sys(&mut world);
+
+        let updated = &world.resource::<UpdatedWidgets>().0;
+        assert!(updated.contains(&root));
+        assert!(!updated.contains(&child));
+        let context = world.get::<KayakRootContext>(context_entity).unwrap();
+        assert!(context.missing_widget_systems.contains("Missing"));
+    }
+}
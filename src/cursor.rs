@@ -1,4 +1,9 @@
-use bevy::reflect::Reflect;
+use bevy::{
+    prelude::{Component, Entity, ReflectComponent},
+    reflect::Reflect,
+};
+
+use crate::keyboard_event::KeyboardModifiers;
 
 /// Controls how the cursor interacts on a given node
 #[derive(Debug, Reflect, Copy, Clone, PartialEq, Eq)]
@@ -25,6 +30,35 @@ pub struct CursorEvent {
     pub just_pressed: bool,
     pub just_released: bool,
     pub position: (f32, f32),
+    /// The number of clicks in a row this event is part of
+    ///
+    /// Only set for [`EventType::Click`](crate::event::EventType::Click) events, where it's `1`
+    /// for a single click, `2` for a double click and so on. See
+    /// [`MultiClick`](crate::event_dispatcher::MultiClick) for when clicks count as a series.
+    pub click_count: u32,
+}
+
+/// Marks a widget that can be dragged
+///
+/// Pressing the cursor on a draggable widget and moving it a few pixels sends it an
+/// [`EventType::DragStart`](crate::event::EventType::DragStart) event, followed by
+/// [`EventType::DragMove`](crate::event::EventType::DragMove) events as the cursor moves and an
+/// [`EventType::DragEnd`](crate::event::EventType::DragEnd) event once it's released. The widget
+/// under the cursor at that point receives an [`EventType::Drop`](crate::event::EventType::Drop)
+/// event.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct Draggable;
+
+/// An event created while dragging a [`Draggable`] widget
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DragEvent {
+    /// The widget being dragged
+    pub source: Entity,
+    /// The current cursor position
+    pub position: (f32, f32),
+    /// How far the cursor has moved since it was pressed
+    pub delta: (f32, f32),
 }
 
 /// An event created on scroll
@@ -32,6 +66,8 @@ pub struct CursorEvent {
 pub struct ScrollEvent {
     /// The amount scrolled
     pub delta: ScrollUnit,
+    /// The keyboard modifiers held while scrolling
+    pub modifiers: KeyboardModifiers,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -47,3 +83,16 @@ impl Default for ScrollUnit {
         ScrollUnit::Pixel { x: 0.0, y: 0.0 }
     }
 }
+
+impl ScrollUnit {
+    /// The amount scrolled in pixels, where a line is `line_size` pixels
+    ///
+    /// Mouse wheels usually scroll by lines while touchpads scroll by pixels, so widgets can use
+    /// this to treat both the same.
+    pub fn to_pixels(self, line_size: f32) -> (f32, f32) {
+        match self {
+            ScrollUnit::Line { x, y } => (x * line_size, y * line_size),
+            ScrollUnit::Pixel { x, y } => (x, y),
+        }
+    }
+}
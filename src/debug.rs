@@ -0,0 +1,449 @@
+//! An inspector overlay for debugging widget layouts
+//!
+//! Add the [`DebugOverlayPlugin`] and press the [`DebugOverlay::toggle_key`] (F12 by default)
+//! to outline every widget's layout rect and label it with its [`WidgetName`]. The widget under
+//! the cursor is highlighted and its resolved styles are listed in a panel on the right side of
+//! the camera's viewport. This module requires the `debug_overlay` feature.
+//!
+//! Usage:
+//! ```rust,ignore
+//! App::new()
+//!     .add_plugins((DefaultPlugins, KayakContextPlugin, KayakWidgets))
+//!     .add_plugins(kayak_ui::debug::DebugOverlayPlugin)
+//!     .run();
+//! ```
+
+use bevy::{
+    prelude::*,
+    reflect::{ReflectRef, Struct},
+    render::{Extract, ExtractSchedule, Render, RenderApp, RenderSet},
+    window::PrimaryWindow,
+};
+use kayak_font::{KayakFont, TextProperties};
+use morphorm::Hierarchy;
+
+use crate::{
+    context::{KayakRootContext, WidgetName},
+    layout,
+    render::{
+        debug_grid::{DEBUG_GRID_Z, DEBUG_RENDER_LAYER},
+        extract::UIExtractedView,
+        font::{extract_texts, FontMapping},
+        unified::pipeline::{queue_quads, ExtractedQuad, ExtractedQuads, UIQuadType},
+    },
+    styles::{ComputedStyles, KStyle},
+    KayakUiScale, DEFAULT_FONT,
+};
+
+/// The z-index used for the inspector overlay. This keeps it above the other debug overlays.
+const DEBUG_OVERLAY_Z: f32 = DEBUG_GRID_Z + 100.0;
+const LABEL_PADDING: f32 = 2.0;
+const PANEL_PADDING: f32 = 8.0;
+
+/// Configures the inspector overlay drawn by the [`DebugOverlayPlugin`]
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DebugOverlay {
+    /// Whether or not the overlay is drawn
+    pub enabled: bool,
+    /// The key that toggles the overlay, or `None` to only toggle it with [`Self::enabled`]
+    pub toggle_key: Option<KeyCode>,
+    /// If true, widgets are labeled with their name
+    pub labels: bool,
+    /// The color of the widget outlines
+    pub outline_color: Color,
+    /// The color of the hovered widget's outline
+    pub hover_color: Color,
+    /// The width of the outlines in logical pixels
+    pub width: f32,
+    /// The size of the label and panel text
+    pub font_size: f32,
+    /// The width of the style panel in logical pixels
+    pub panel_width: f32,
+    /// The widget under the cursor
+    ///
+    /// This is set by the overlay while it's enabled.
+    pub hovered: Option<Entity>,
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            toggle_key: Some(KeyCode::F12),
+            labels: true,
+            outline_color: Color::rgba(0.0, 0.8, 1.0, 0.6),
+            hover_color: Color::rgba(1.0, 0.9, 0.0, 1.0),
+            width: 1.0,
+            font_size: 12.0,
+            panel_width: 360.0,
+            hovered: None,
+        }
+    }
+}
+
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>().add_systems(
+            Update,
+            (toggle_debug_overlay, update_hovered_widget).chain(),
+        );
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .init_resource::<ExtractedDebugOverlay>()
+            .add_systems(ExtractSchedule, extract_debug_overlay)
+            .add_systems(
+                Render,
+                queue_debug_overlay
+                    .in_set(RenderSet::Queue)
+                    .before(queue_quads),
+            );
+    }
+}
+
+fn toggle_debug_overlay(keys: Res<Input<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if let Some(toggle_key) = overlay.toggle_key {
+        if keys.just_pressed(toggle_key) {
+            overlay.enabled = !overlay.enabled;
+        }
+    }
+}
+
+/// Finds the innermost widget under the cursor
+fn update_hovered_widget(
+    mut overlay: ResMut<DebugOverlay>,
+    contexts: Query<&KayakRootContext>,
+    cameras: Query<&Camera>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<KayakUiScale>,
+) {
+    if !overlay.enabled {
+        if overlay.hovered.is_some() {
+            overlay.hovered = None;
+        }
+        return;
+    }
+
+    let cursor = primary_window
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    let mut hovered = None;
+    if let Some(cursor) = cursor {
+        for context in contexts.iter() {
+            let viewport_min = cameras
+                .get(context.camera_entity)
+                .ok()
+                .and_then(|camera| camera.logical_viewport_rect())
+                .map(|rect| rect.min)
+                .unwrap_or_default();
+            let point = (cursor - viewport_min) / ui_scale.get();
+            if let Ok(tree) = context.tree.read() {
+                // Later widgets are drawn on top of earlier ones
+                for index in tree.down_iter() {
+                    if let Some(rect) = context.get_widget_layout(index.0) {
+                        if rect.contains(&(point.x, point.y)) {
+                            hovered = Some(index.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if overlay.hovered != hovered {
+        overlay.hovered = hovered;
+    }
+}
+
+#[derive(Resource, Default)]
+struct ExtractedDebugOverlay {
+    quads: Vec<ExtractedQuad>,
+}
+
+/// Lists the fields of a style that are set, one per line
+fn style_lines(style: &KStyle) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (index, field) in style.iter_fields().enumerate() {
+        if let ReflectRef::Enum(prop) = field.reflect_ref() {
+            if prop.variant_name() == "Unset" {
+                continue;
+            }
+        }
+        if let Some(name) = style.name_at(index) {
+            lines.push(format!("{}: {:?}", name, field));
+        }
+    }
+    lines
+}
+
+struct TextPainter<'a> {
+    camera_entity: Entity,
+    font: &'a KayakFont,
+    fonts: &'a Assets<KayakFont>,
+    font_mapping: &'a FontMapping,
+    font_size: f32,
+}
+
+impl<'a> TextPainter<'a> {
+    /// Adds the quads of a single line of text, returning its size
+    fn paint(
+        &self,
+        quads: &mut Vec<ExtractedQuad>,
+        content: &str,
+        position: Vec2,
+        color: Color,
+        z_index: f32,
+    ) -> Vec2 {
+        let properties = TextProperties {
+            font_size: self.font_size,
+            line_height: self.font_size * 1.2,
+            max_size: (100000.0, self.font_size * 1.2),
+            ..Default::default()
+        };
+        let text_layout = self.font.measure(content, properties);
+        let size = Vec2::from(text_layout.size());
+        quads.extend(
+            extract_texts(
+                self.camera_entity,
+                color,
+                text_layout,
+                layout::Rect {
+                    posx: position.x,
+                    posy: position.y,
+                    width: size.x,
+                    height: size.y,
+                    z_index,
+                },
+                DEFAULT_FONT.into(),
+                properties,
+                false,
+                0,
+                self.fonts,
+                self.font_mapping,
+                1.0,
+            )
+            .into_iter()
+            .map(|mut quad| {
+                quad.render_layer = DEBUG_RENDER_LAYER;
+                quad
+            }),
+        );
+        size
+    }
+}
+
+fn push_outline(
+    quads: &mut Vec<ExtractedQuad>,
+    camera_entity: Entity,
+    min: Vec2,
+    max: Vec2,
+    width: f32,
+    color: Color,
+    z_index: f32,
+) {
+    let edges = [
+        // Top
+        (min, Vec2::new(max.x, min.y + width)),
+        // Bottom
+        (Vec2::new(min.x, max.y - width), max),
+        // Left
+        (min, Vec2::new(min.x + width, max.y)),
+        // Right
+        (Vec2::new(max.x - width, min.y), max),
+    ];
+    for (min, max) in edges {
+        quads.push(ExtractedQuad {
+            camera_entity,
+            rect: Rect { min, max },
+            color,
+            z_index,
+            render_layer: DEBUG_RENDER_LAYER,
+            quad_type: UIQuadType::Quad,
+            ..Default::default()
+        });
+    }
+}
+
+fn push_rect(
+    quads: &mut Vec<ExtractedQuad>,
+    camera_entity: Entity,
+    min: Vec2,
+    max: Vec2,
+    color: Color,
+    z_index: f32,
+) {
+    quads.push(ExtractedQuad {
+        camera_entity,
+        rect: Rect { min, max },
+        color,
+        z_index,
+        render_layer: DEBUG_RENDER_LAYER,
+        quad_type: UIQuadType::Quad,
+        ..Default::default()
+    });
+}
+
+fn extract_debug_overlay(
+    overlay: Extract<Res<DebugOverlay>>,
+    contexts: Extract<Query<&KayakRootContext>>,
+    widgets: Extract<Query<(&WidgetName, Option<&ComputedStyles>)>>,
+    cameras: Extract<Query<&Camera>>,
+    fonts: Extract<Res<Assets<KayakFont>>>,
+    font_mapping: Extract<Res<FontMapping>>,
+    ui_scale: Extract<Res<KayakUiScale>>,
+    mut extracted: ResMut<ExtractedDebugOverlay>,
+) {
+    extracted.quads.clear();
+    if !overlay.enabled {
+        return;
+    }
+    let Some(font) = font_mapping
+        .get_handle(DEFAULT_FONT.into())
+        .and_then(|handle| fonts.get(&handle))
+    else {
+        return;
+    };
+
+    let quads = &mut extracted.quads;
+    for context in contexts.iter() {
+        let camera_entity = context.camera_entity;
+        let painter = TextPainter {
+            camera_entity,
+            font,
+            fonts: &fonts,
+            font_mapping: &font_mapping,
+            font_size: overlay.font_size,
+        };
+        let Ok(tree) = context.tree.read() else {
+            continue;
+        };
+
+        let mut z_index = DEBUG_OVERLAY_Z;
+        for index in tree.down_iter() {
+            let Some(rect) = context.get_widget_layout(index.0) else {
+                continue;
+            };
+            let min = Vec2::new(rect.posx, rect.posy);
+            let max = min + Vec2::new(rect.width, rect.height);
+            push_outline(
+                quads,
+                camera_entity,
+                min,
+                max,
+                overlay.width,
+                overlay.outline_color,
+                z_index,
+            );
+            if overlay.labels {
+                if let Ok((name, _)) = widgets.get(index.0) {
+                    let text_position = min + Vec2::splat(LABEL_PADDING);
+                    let size =
+                        painter.paint(quads, &name.0, text_position, Color::WHITE, z_index + 2.0);
+                    push_rect(
+                        quads,
+                        camera_entity,
+                        min,
+                        text_position + size + Vec2::splat(LABEL_PADDING),
+                        overlay.outline_color,
+                        z_index + 1.0,
+                    );
+                }
+            }
+            z_index += 3.0;
+        }
+
+        let Some(hovered) = overlay.hovered else {
+            continue;
+        };
+        let Some(rect) = context.get_widget_layout(hovered) else {
+            continue;
+        };
+        let min = Vec2::new(rect.posx, rect.posy);
+        let max = min + Vec2::new(rect.width, rect.height);
+        push_rect(
+            quads,
+            camera_entity,
+            min,
+            max,
+            overlay.hover_color.with_a(overlay.hover_color.a() * 0.2),
+            z_index,
+        );
+        push_outline(
+            quads,
+            camera_entity,
+            min,
+            max,
+            overlay.width * 2.0,
+            overlay.hover_color,
+            z_index + 1.0,
+        );
+        z_index += 2.0;
+
+        // Dump the hovered widget's resolved styles to a panel on the right
+        let (name, styles) = widgets
+            .get(hovered)
+            .map(|(name, styles)| (name.0.as_str(), styles))
+            .unwrap_or(("", None));
+        let mut lines = vec![
+            format!("{} ({:?})", name, hovered),
+            format!(
+                "x: {}, y: {}, width: {}, height: {}",
+                rect.posx, rect.posy, rect.width, rect.height
+            ),
+        ];
+        if let Some(styles) = styles {
+            lines.extend(style_lines(&styles.0));
+        }
+        let viewport_size = cameras
+            .get(camera_entity)
+            .ok()
+            .and_then(|camera| camera.logical_viewport_size())
+            .unwrap_or_default()
+            / ui_scale.get();
+        let panel_min = Vec2::new((viewport_size.x - overlay.panel_width).max(0.0), 0.0);
+        let line_height = overlay.font_size * 1.2;
+        let mut position = panel_min + Vec2::splat(PANEL_PADDING);
+        for line in lines.iter() {
+            painter.paint(quads, line, position, Color::WHITE, z_index + 1.0);
+            position.y += line_height;
+        }
+        push_rect(
+            quads,
+            camera_entity,
+            panel_min,
+            Vec2::new(viewport_size.x, position.y + PANEL_PADDING),
+            Color::rgba(0.0, 0.0, 0.0, 0.8),
+            z_index,
+        );
+    }
+}
+
+fn queue_debug_overlay(
+    overlay: Res<ExtractedDebugOverlay>,
+    views: Query<(Entity, &UIExtractedView)>,
+    mut extracted_quads: ResMut<ExtractedQuads>,
+) {
+    if overlay.quads.is_empty() {
+        return;
+    }
+
+    // Reset the clip so the overlay is never scissored itself.
+    for (camera_entity, view) in views.iter() {
+        extracted_quads.quads.push(ExtractedQuad {
+            camera_entity,
+            rect: Rect {
+                min: Vec2::ZERO,
+                max: Vec2::new(view.viewport.z as f32, view.viewport.w as f32),
+            },
+            z_index: DEBUG_OVERLAY_Z - 1.0,
+            render_layer: DEBUG_RENDER_LAYER,
+            quad_type: UIQuadType::Clip,
+            ..Default::default()
+        });
+    }
+
+    extracted_quads.quads.extend(overlay.quads.iter().cloned());
+}
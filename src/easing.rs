@@ -0,0 +1,222 @@
+//! Easing functions and a small tween helper for animating widgets
+//!
+//! Every easing function maps a normalized time `t` in `0.0..=1.0` to an eased factor,
+//! where `0.0` is the start and `1.0` is the end of the animation. Some functions (such
+//! as the elastic ones) overshoot in between.
+
+use bevy::prelude::Time;
+use interpolation::Ease;
+
+/// No easing, `t` is returned as-is
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+/// Quadratic easing that starts slow
+pub fn quad_in(t: f32) -> f32 {
+    t.quadratic_in()
+}
+
+/// Quadratic easing that ends slow
+pub fn quad_out(t: f32) -> f32 {
+    t.quadratic_out()
+}
+
+/// Quadratic easing that starts and ends slow
+pub fn quad_in_out(t: f32) -> f32 {
+    t.quadratic_in_out()
+}
+
+/// Cubic easing that starts slow
+pub fn cubic_in(t: f32) -> f32 {
+    t.cubic_in()
+}
+
+/// Cubic easing that ends slow
+pub fn cubic_out(t: f32) -> f32 {
+    t.cubic_out()
+}
+
+/// Cubic easing that starts and ends slow
+pub fn cubic_in_out(t: f32) -> f32 {
+    t.cubic_in_out()
+}
+
+/// Quartic easing that starts slow
+pub fn quart_in(t: f32) -> f32 {
+    t.quartic_in()
+}
+
+/// Quartic easing that ends slow
+pub fn quart_out(t: f32) -> f32 {
+    t.quartic_out()
+}
+
+/// Quartic easing that starts and ends slow
+pub fn quart_in_out(t: f32) -> f32 {
+    t.quartic_in_out()
+}
+
+/// Springy easing that winds up before moving to the end
+pub fn elastic_in(t: f32) -> f32 {
+    t.elastic_in()
+}
+
+/// Springy easing that overshoots the end and settles on it
+pub fn elastic_out(t: f32) -> f32 {
+    t.elastic_out()
+}
+
+/// Springy easing on both the start and the end
+pub fn elastic_in_out(t: f32) -> f32 {
+    t.elastic_in_out()
+}
+
+/// Easing that bounces off the start
+pub fn bounce_in(t: f32) -> f32 {
+    t.bounce_in()
+}
+
+/// Easing that bounces off the end
+pub fn bounce_out(t: f32) -> f32 {
+    t.bounce_out()
+}
+
+/// Easing that bounces off both the start and the end
+pub fn bounce_in_out(t: f32) -> f32 {
+    t.bounce_in_out()
+}
+
+/// Advances a normalized time over a duration and eases it
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use kayak_ui::{easing, prelude::*};
+/// #[derive(Component)]
+/// struct FadeIn(Tween);
+///
+/// fn fade_in(time: Res<Time>, mut query: Query<(&mut FadeIn, &mut KStyle)>) {
+///     for (mut fade_in, mut styles) in query.iter_mut() {
+///         if !fade_in.0.is_finished() {
+///             styles.opacity = fade_in.0.tick(&time).into();
+///         }
+///     }
+/// }
+///
+/// let fade_in = FadeIn(Tween::new(0.25, easing::cubic_out));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Tween {
+    /// The length of the tween in seconds
+    pub duration: f32,
+    /// The easing function applied to the normalized time
+    pub easing: fn(f32) -> f32,
+    elapsed: f32,
+}
+
+impl Default for Tween {
+    fn default() -> Self {
+        Self::new(0.0, linear)
+    }
+}
+
+impl Tween {
+    /// Creates a new tween lasting `duration` seconds
+    pub fn new(duration: f32, easing: fn(f32) -> f32) -> Self {
+        Self {
+            duration,
+            easing,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advances the tween by the time since the last frame and returns the eased value
+    pub fn tick(&mut self, time: &Time) -> f32 {
+        self.advance(time.delta_seconds())
+    }
+
+    /// Advances the tween by `seconds` and returns the eased value
+    pub fn advance(&mut self, seconds: f32) -> f32 {
+        self.elapsed = (self.elapsed + seconds).min(self.duration.max(0.0));
+        self.value()
+    }
+
+    /// The normalized time of the tween, from `0.0` to `1.0`
+    pub fn t(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+
+    /// The eased value at the current time
+    pub fn value(&self) -> f32 {
+        (self.easing)(self.t())
+    }
+
+    /// Returns true once the full duration has elapsed
+    pub fn is_finished(&self) -> bool {
+        self.t() >= 1.0
+    }
+
+    /// Rewinds the tween to the start
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn easing_functions_should_start_and_end_on_the_bounds() {
+        let functions: [fn(f32) -> f32; 16] = [
+            linear,
+            quad_in,
+            quad_out,
+            quad_in_out,
+            cubic_in,
+            cubic_out,
+            cubic_in_out,
+            quart_in,
+            quart_out,
+            quart_in_out,
+            elastic_in,
+            elastic_out,
+            elastic_in_out,
+            bounce_in,
+            bounce_out,
+            bounce_in_out,
+        ];
+        for function in functions {
+            assert!(function(0.0).abs() < 0.001);
+            assert!((function(1.0) - 1.0).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn tween_should_advance_and_finish() {
+        let mut tween = Tween::new(2.0, linear);
+        assert_eq!(0.0, tween.value());
+
+        assert_eq!(0.25, tween.advance(0.5));
+        assert!(!tween.is_finished());
+
+        assert_eq!(1.0, tween.advance(10.0));
+        assert!(tween.is_finished());
+
+        tween.reset();
+        assert_eq!(0.0, tween.t());
+    }
+
+    #[test]
+    fn zero_length_tween_should_be_finished() {
+        let tween = Tween::new(0.0, quad_in);
+        assert!(tween.is_finished());
+        assert_eq!(1.0, tween.value());
+    }
+}
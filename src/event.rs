@@ -1,12 +1,18 @@
 use bevy::prelude::{Entity, Resource, World};
 
 use crate::{
-    cursor::{CursorEvent, ScrollEvent},
+    cursor::{CursorEvent, DragEvent, ScrollEvent},
     keyboard_event::KeyboardEvent,
     prelude::{KayakWidgetContext, OnChange},
 };
 
 /// An event type sent to widgets
+///
+/// Events are first sent to their [`target`](Self::target) and then bubble up through its
+/// ancestors, one parent at a time, until one of them calls
+/// [`stop_propagation`](Self::stop_propagation). There is no capture phase, so ancestors always
+/// see an event after their descendants. Some events never bubble, see
+/// [`EventType::propagates`].
 #[derive(Resource, Clone)]
 pub struct KEvent {
     /// The node targeted by this event
@@ -68,18 +74,11 @@ impl KEvent {
     }
 
     /// If called, prevents this event from propagating up the hierarchy
+    ///
+    /// The remaining handlers of the current widget aren't affected, only its ancestors stop
+    /// receiving the event.
     pub fn stop_propagation(&mut self) {
-        if matches!(
-            self.event_type,
-            EventType::Click(..)
-                | EventType::MouseIn(..)
-                | EventType::MouseDown(..)
-                | EventType::Scroll(..)
-                | EventType::Focus
-                | EventType::Hover(..)
-        ) {
-            self.should_propagate = false;
-        }
+        self.should_propagate = false;
     }
 
     /// Returns whether this event's default action has been prevented or not
@@ -118,12 +117,36 @@ pub enum EventType {
     MouseIn(CursorEvent),
     /// An event that occurs when the user moves the cursor out of a widget
     MouseOut(CursorEvent),
+    /// An event that occurs when a widget becomes the hovered widget or one of its ancestors
+    ///
+    /// Unlike [`EventType::MouseIn`], this follows the topmost widget under the cursor and also
+    /// fires when a widget moves under a stationary cursor. Moving from a widget onto one of its
+    /// children doesn't leave the parent. Outer widgets are entered before inner ones.
+    PointerEnter(CursorEvent),
+    /// An event that occurs when a widget stops being the hovered widget or one of its ancestors
+    ///
+    /// This is always paired with a preceding [`EventType::PointerEnter`]. Inner widgets are left
+    /// before outer ones.
+    PointerLeave(CursorEvent),
     /// An event that occurs when the user presses down on the cursor over a widget
     MouseDown(CursorEvent),
     /// An event that occurs when the user releases the cursor over a widget
     MouseUp(CursorEvent),
+    /// An event that occurs when the user presses the right mouse button over a widget
+    ///
+    /// Widgets showing a context menu should open it here and stop the event's propagation, so
+    /// their ancestors don't open one as well.
+    ContextMenu(CursorEvent),
     /// An event that occurs when the user scrolls over a widget
     Scroll(ScrollEvent),
+    /// An event that occurs when the user starts dragging a [`Draggable`](crate::cursor::Draggable) widget
+    DragStart(DragEvent),
+    /// An event that occurs when the user moves the cursor while dragging a widget
+    DragMove(DragEvent),
+    /// An event that occurs when the user releases a dragged widget
+    DragEnd(DragEvent),
+    /// An event that occurs when the user releases a dragged widget over this widget
+    Drop(DragEvent),
     /// An event that occurs when a widget receives focus
     Focus,
     /// An event that occurs when a widget loses focus
@@ -134,6 +157,20 @@ pub enum EventType {
     KeyUp(KeyboardEvent),
     /// An event that occurs when the user presses a key down within a _focused_ widget
     KeyDown(KeyboardEvent),
+    /// An event that occurs when the user asks a _focused_ widget to copy its selection
+    ///
+    /// Widgets should put the copied text on the [`Clipboard`](crate::clipboard::Clipboard).
+    /// Pasting is sent as [`EventType::CharInput`] events instead.
+    Copy,
+    /// An event that occurs when the user asks a _focused_ widget to cut its selection
+    Cut,
+    /// An event that occurs when the text being composed with an input method changes within a
+    /// _focused_ widget
+    ///
+    /// The text is stored in the [`ImeComposition`](crate::input::ImeComposition) resource. It
+    /// should be displayed but not added to the widget's value until the composition is
+    /// finalized, at which point it's sent as [`EventType::CharInput`] events.
+    ImePreedit,
 }
 
 impl Eq for EventType {}
@@ -175,15 +212,25 @@ impl EventType {
             Self::Click(..) => true,
             Self::MouseDown(..) => true,
             Self::MouseUp(..) => true,
+            Self::ContextMenu(..) => true,
             Self::Scroll(..) => true,
+            Self::Drop(..) => true,
             Self::CharInput { .. } => true,
             Self::KeyUp(..) => true,
             Self::KeyDown(..) => true,
+            Self::Copy => true,
+            Self::Cut => true,
             // Doesn't Propagate
             Self::MouseIn(..) => false,
             Self::MouseOut(..) => false,
+            Self::PointerEnter(..) => false,
+            Self::PointerLeave(..) => false,
+            Self::DragStart(..) => false,
+            Self::DragMove(..) => false,
+            Self::DragEnd(..) => false,
             Self::Focus => false,
             Self::Blur => false,
+            Self::ImePreedit => false,
         }
     }
 
@@ -195,13 +242,23 @@ impl EventType {
             Self::Click(..) => EventCategory::Mouse,
             Self::MouseDown(..) => EventCategory::Mouse,
             Self::MouseUp(..) => EventCategory::Mouse,
+            Self::ContextMenu(..) => EventCategory::Mouse,
             Self::MouseIn(..) => EventCategory::Mouse,
             Self::MouseOut(..) => EventCategory::Mouse,
+            Self::PointerEnter(..) => EventCategory::Mouse,
+            Self::PointerLeave(..) => EventCategory::Mouse,
             Self::Scroll(..) => EventCategory::Mouse,
+            Self::DragStart(..) => EventCategory::Mouse,
+            Self::DragMove(..) => EventCategory::Mouse,
+            Self::DragEnd(..) => EventCategory::Mouse,
+            Self::Drop(..) => EventCategory::Mouse,
             // Keyboard
             Self::CharInput { .. } => EventCategory::Keyboard,
             Self::KeyUp(..) => EventCategory::Keyboard,
             Self::KeyDown(..) => EventCategory::Keyboard,
+            Self::Copy => EventCategory::Keyboard,
+            Self::Cut => EventCategory::Keyboard,
+            Self::ImePreedit => EventCategory::Keyboard,
             // Focus
             Self::Focus => EventCategory::Focus,
             Self::Blur => EventCategory::Focus,
@@ -1,23 +1,30 @@
 use bevy::{
-    prelude::{Component, Entity, KeyCode, Resource, World},
+    prelude::{Camera, Component, Entity, KeyCode, Resource, With, World},
+    render::camera::RenderTarget,
     utils::{HashMap, HashSet},
+    window::{PrimaryWindow, Window, WindowRef},
 };
+use instant::Instant;
 
 use crate::{
     context::KayakRootContext,
-    cursor::{CursorEvent, PointerEvents, ScrollEvent, ScrollUnit},
+    cursor::{CursorEvent, DragEvent, Draggable, PointerEvents, ScrollEvent, ScrollUnit},
     event::{EventType, KEvent},
-    focus_tree::FocusTree,
+    focus_tree::{FocusTrap, FocusTree},
     input_event::{InputEvent, InputEventCategory},
     keyboard_event::{KeyboardEvent, KeyboardModifiers},
     layout::Rect,
     node::{Node, WrappedIndex},
     on_event::OnEvent,
     prelude::KayakWidgetContext,
-    styles::{ComputedStyles, KStyle, RenderCommand},
+    styles::{ComputedStyles, KDisplay, KStyle, RenderCommand, StyleProp},
+    tree::Tree,
     Focusable,
 };
 
+/// How far (in pixels) the cursor has to move while pressed before a drag starts
+const DRAG_THRESHOLD: f32 = 3.0;
+
 type EventMap = HashMap<WrappedIndex, HashSet<EventType>>;
 type TreeNode = (
     // The node ID
@@ -28,6 +35,7 @@ type TreeNode = (
 
 #[derive(Debug, Clone)]
 struct EventState {
+    best_render_layer: i32,
     best_z_index: f32,
     best_match: Option<WrappedIndex>,
     best_depth: isize,
@@ -36,6 +44,7 @@ struct EventState {
 impl Default for EventState {
     fn default() -> Self {
         Self {
+            best_render_layer: i32::MIN,
             best_z_index: f32::NEG_INFINITY,
             best_match: None,
             best_depth: -1,
@@ -43,12 +52,53 @@ impl Default for EventState {
     }
 }
 
+/// Configures when consecutive clicks are counted as a double (or triple) click
+///
+/// A click continues the current series if it lands on the same widget within
+/// [`interval`](Self::interval) seconds of the previous one and has not moved further than
+/// [`distance`](Self::distance) pixels from it. See [`CursorEvent::click_count`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct MultiClick {
+    /// The maximum time (in seconds) between two clicks of the same series
+    pub interval: f32,
+    /// The maximum distance (in pixels) between two clicks of the same series
+    pub distance: f32,
+}
+
+impl Default for MultiClick {
+    fn default() -> Self {
+        Self {
+            interval: 0.4,
+            distance: 4.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LastClick {
+    node: WrappedIndex,
+    time: Instant,
+    position: (f32, f32),
+    count: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DragState {
+    source: WrappedIndex,
+    /// The cursor position when the source was pressed
+    start: (f32, f32),
+    /// Whether the cursor has moved far enough to start dragging
+    dragging: bool,
+}
+
 #[derive(Component, Debug, Clone, Default)]
 pub struct EventDispatcher {
     is_mouse_pressed: bool,
     next_mouse_pressed: bool,
     current_mouse_position: (f32, f32),
     next_mouse_position: (f32, f32),
+    /// Whether the cursor has moved since startup, i.e. whether its position is known
+    has_mouse_position: bool,
     previous_events: EventMap,
     keyboard_modifiers: KeyboardModifiers,
     // pub last_clicked: Binding<WrappedIndex>,
@@ -56,7 +106,14 @@ pub struct EventDispatcher {
     wants_cursor: Option<bool>,
     has_cursor: Option<WrappedIndex>,
     pub(crate) cursor_capture: Option<WrappedIndex>,
+    /// Whether the cursor was captured by a press, rather than by a widget, and is released
+    /// along with the mouse button
+    implicit_capture: bool,
     pub(crate) hovered: Option<WrappedIndex>,
+    /// The node under the cursor followed by its ancestors
+    pointer_over: Vec<WrappedIndex>,
+    last_click: Option<LastClick>,
+    drag: Option<DragState>,
 }
 
 impl EventDispatcher {
@@ -67,13 +124,18 @@ impl EventDispatcher {
             next_mouse_pressed: Default::default(),
             current_mouse_position: Default::default(),
             next_mouse_position: Default::default(),
+            has_mouse_position: false,
             previous_events: Default::default(),
             keyboard_modifiers: Default::default(),
             contains_cursor: None,
             wants_cursor: None,
             has_cursor: None,
             cursor_capture: None,
+            implicit_capture: false,
             hovered: None,
+            pointer_over: Vec::new(),
+            last_click: None,
+            drag: None,
         }
     }
 
@@ -90,12 +152,23 @@ impl EventDispatcher {
     }
 
     /// Captures all cursor events and instead makes the given index the target
+    ///
+    /// Pressing the mouse over a [`Draggable`] widget already captures the cursor until the
+    /// button is released, so drags keep going when the cursor leaves the widget. Other widgets
+    /// that track the cursor while pressed (such as sliders) should capture it themselves, and
+    /// keep it until they release it.
     pub fn capture_cursor(&mut self, index: Entity) -> Option<WrappedIndex> {
         let old = self.cursor_capture;
         self.cursor_capture = Some(WrappedIndex(index));
+        self.implicit_capture = false;
         old
     }
 
+    /// Returns the widget that currently captures the cursor, if any
+    pub fn captured_cursor(&self) -> Option<WrappedIndex> {
+        self.cursor_capture
+    }
+
     /// Releases the captured cursor
     ///
     /// Returns true if successful.
@@ -123,6 +196,7 @@ impl EventDispatcher {
     pub fn force_release_cursor(&mut self) -> Option<WrappedIndex> {
         let old = self.cursor_capture;
         self.cursor_capture = None;
+        self.implicit_capture = false;
         old
     }
 
@@ -160,7 +234,14 @@ impl EventDispatcher {
         self.hovered
     }
 
-    /// Process and dispatch an [InputEvent](crate::InputEvent)
+    /// The node currently under the cursor
+    ///
+    /// Unlike [`hovered`](Self::hovered), this becomes `None` once the cursor leaves every node.
+    pub fn pointer_over(&self) -> Option<WrappedIndex> {
+        self.pointer_over.first().copied()
+    }
+
+    // /// Process and dispatch an [InputEvent](crate::InputEvent)
     // #[allow(dead_code)]
     // pub fn process_event(
     //     &mut self,
@@ -181,10 +262,56 @@ impl EventDispatcher {
         context: &mut KayakRootContext,
         world: &mut World,
     ) {
-        let events = { self.build_event_stream(input_events, context, world) };
+        let mut events = self.trap_focus(context, world);
+        events.extend(self.build_event_stream(input_events, context, world));
         self.dispatch_events(events, context, world);
     }
 
+    /// Moves focus into the active [`FocusTrap`] if it's currently outside of it
+    ///
+    /// Focus goes to the first widget in the trap that can be tabbed to, or the trap itself if
+    /// there's none. This is how a modal takes focus when it opens.
+    fn trap_focus(&mut self, context: &mut KayakRootContext, world: &mut World) -> Vec<KEvent> {
+        if world
+            .query_filtered::<(), With<FocusTrap>>()
+            .iter(world)
+            .next()
+            .is_none()
+        {
+            return Vec::new();
+        }
+
+        let current_focus = context.focus_tree.current().map(WrappedIndex);
+        let index = if let Ok(node_tree) = context.tree.try_read() {
+            let trap = if let Some(trap) = Self::active_focus_trap(&node_tree, world) {
+                trap
+            } else {
+                return Vec::new();
+            };
+            if current_focus.map_or(false, |current| Self::is_within(current, trap, &node_tree)) {
+                return Vec::new();
+            }
+            context
+                .focus_tree
+                .peek_tab(None, false, |index| {
+                    if !Self::is_within(index, trap, &node_tree) {
+                        return None;
+                    }
+                    Self::resolve_tab_index(index, &node_tree, world, context)
+                })
+                .unwrap_or(trap)
+        } else {
+            return Vec::new();
+        };
+
+        let mut events = vec![KEvent::new(index.0, EventType::Focus)];
+        if let Some(current_focus) = current_focus {
+            events.push(KEvent::new(current_focus.0, EventType::Blur));
+        }
+        context.focus_tree.focus(index.0);
+        events
+    }
+
     /// Dispatch an [KEvent](crate::KEvent)
     #[allow(dead_code)]
     pub fn dispatch_event(
@@ -249,6 +376,7 @@ impl EventDispatcher {
                             None,
                             context.unique_ids.clone(),
                             context.unique_ids_parents.clone(),
+                            context.widget_events.clone(),
                         );
                         node_event.run_on_change(world, widget_context);
                     }
@@ -356,10 +484,26 @@ impl EventDispatcher {
                 }
             }
 
+            // If the cursor hasn't moved we still need to hit-test it against the tree, otherwise
+            // widgets moving under a stationary cursor would never receive enter/leave events.
+            // That's only meaningful while the cursor is actually somewhere over the window.
+            let has_mouse_moved = input_events
+                .iter()
+                .any(|input_event| matches!(input_event, InputEvent::MouseMoved(..)));
+            self.has_mouse_position |= has_mouse_moved;
+            let stationary_mouse_move = InputEvent::MouseMoved(self.current_mouse_position);
+            let mut mouse_inputs = input_events.iter().collect::<Vec<_>>();
+            if !has_mouse_moved
+                && self.has_mouse_position
+                && Self::is_cursor_in_window(context, world)
+            {
+                mouse_inputs.push(&stationary_mouse_move);
+            }
+
             // === Mouse Events === //
             if let Some(captor) = self.cursor_capture {
                 // A widget has been set to capture pointer events -> it should be the only one receiving events
-                for input_event in input_events {
+                for input_event in mouse_inputs.iter().copied() {
                     // --- Process Event --- //
                     if matches!(input_event.category(), InputEventCategory::Mouse) {
                         // A widget's PointerEvents style will determine how it and its children are processed
@@ -370,6 +514,7 @@ impl EventDispatcher {
                                 let events = self.process_pointer_events(
                                     input_event,
                                     (captor, 0),
+                                    None,
                                     &mut states,
                                     world,
                                     context,
@@ -381,15 +526,33 @@ impl EventDispatcher {
                         }
                     }
                 }
+
+                // The captor gets the cursor's events, but hovering still follows whatever is
+                // actually under the cursor
+                states.remove(&EventType::Hover(Default::default()));
+                self.update_hover_state(
+                    &mouse_inputs,
+                    root,
+                    &node_tree,
+                    &mut states,
+                    world,
+                    context,
+                );
             } else {
                 // No capturing widget -> process cursor events as normal
-                let mut stack: Vec<TreeNode> = vec![(root, 0)];
-                while let Some((current, depth)) = stack.pop() {
+                // Each node is paired with the clip rect of its nearest clipping ancestors, so
+                // clipped-away parts of a widget can't be hit
+                let mut stack: Vec<(TreeNode, Option<Rect>)> = vec![((root, 0), None)];
+                while let Some(((current, depth), clip)) = stack.pop() {
+                    // Widgets that aren't drawn can't be hit, and neither can their children
+                    if !Self::is_displayed(current, world) {
+                        continue;
+                    }
                     let mut enter_children = true;
 
                     if let Some(entity_ref) = world.get_entity(current.0) {
                         if entity_ref.contains::<OnEvent>() {
-                            for input_event in input_events {
+                            for input_event in mouse_inputs.iter().copied() {
                                 // --- Process Event --- //
                                 if matches!(input_event.category(), InputEventCategory::Mouse) {
                                     // A widget's PointerEvents style will determine how it and its children are processed
@@ -401,6 +564,7 @@ impl EventDispatcher {
                                             let events = self.process_pointer_events(
                                                 input_event,
                                                 (current, depth),
+                                                clip,
                                                 &mut states,
                                                 world,
                                                 context,
@@ -421,18 +585,14 @@ impl EventDispatcher {
                     }
                     // --- Push Children to Stack --- //
                     if enter_children {
-                        if let Some(children) = node_tree.children.get(&current) {
-                            let mut stack_children = Vec::new();
-                            for child in children {
-                                let child_z = world
-                                    .get_entity(child.0)
-                                    .map(|e| e.get::<Node>().map(|node| node.z).unwrap_or(0.0))
-                                    .unwrap_or(0.0);
-                                stack_children.push((child_z, (*child, depth + 1)));
-                            }
-                            stack_children.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-                            stack.extend(stack_children.iter().map(|c| c.1));
-                        }
+                        Self::push_children(
+                            &mut stack,
+                            (current, depth),
+                            clip,
+                            &node_tree,
+                            world,
+                            context,
+                        );
                     }
                 }
             }
@@ -447,13 +607,50 @@ impl EventDispatcher {
 
             // === Additional Events === //
             let mut had_focus_event = false;
+            let mut pointer_over = None;
 
             // These events are ones that require a specific target and need the tree to be evaluated before selecting the best match
             for (event_type, state) in states {
                 if let Some(node) = state.best_match {
+                    if let EventType::DragStart(drag_event) = event_type {
+                        // The drag only starts once the cursor has moved far enough
+                        self.drag = Some(DragState {
+                            source: node,
+                            start: drag_event.position,
+                            dragging: false,
+                        });
+                        continue;
+                    }
+
+                    if let EventType::Hover(..) = event_type {
+                        pointer_over = Some(node);
+                        if !has_mouse_moved {
+                            // Only used for hit-testing, the cursor didn't actually move
+                            self.hovered = Some(node);
+                            continue;
+                        }
+                    }
+
+                    let event_type = if let EventType::Click(cursor_event) = event_type {
+                        EventType::Click(CursorEvent {
+                            click_count: self.count_click(node, cursor_event.position, world),
+                            ..cursor_event
+                        })
+                    } else {
+                        event_type
+                    };
+
                     event_stream.push(KEvent::new(node.0, event_type));
 
                     match event_type {
+                        EventType::MouseDown(..)
+                            if self.cursor_capture.is_none()
+                                && world.get::<Draggable>(node.0).is_some() =>
+                        {
+                            // A pressed draggable widget keeps receiving the cursor until it's released
+                            self.cursor_capture = Some(node);
+                            self.implicit_capture = true;
+                        }
                         EventType::Focus => {
                             had_focus_event = true;
                             if let Some(current_focus) =
@@ -483,6 +680,79 @@ impl EventDispatcher {
                 }
             }
 
+            // --- Drag Events --- //
+            if let Some(mut drag) = self.drag {
+                let position = self.next_mouse_position;
+                let delta = (position.0 - drag.start.0, position.1 - drag.start.1);
+                let drag_event = DragEvent {
+                    source: drag.source.0,
+                    position,
+                    delta,
+                };
+                if !self.next_mouse_pressed {
+                    if drag.dragging {
+                        if let Some(target) = Self::find_drop_target(
+                            root,
+                            drag.source,
+                            position,
+                            &node_tree,
+                            world,
+                            context,
+                        ) {
+                            event_stream.push(KEvent::new(target.0, EventType::Drop(drag_event)));
+                        }
+                        event_stream
+                            .push(KEvent::new(drag.source.0, EventType::DragEnd(drag_event)));
+                    }
+                    self.drag = None;
+                } else if has_mouse_moved {
+                    if !drag.dragging && delta.0.hypot(delta.1) >= DRAG_THRESHOLD {
+                        drag.dragging = true;
+                        event_stream
+                            .push(KEvent::new(drag.source.0, EventType::DragStart(drag_event)));
+                    }
+                    if drag.dragging {
+                        event_stream
+                            .push(KEvent::new(drag.source.0, EventType::DragMove(drag_event)));
+                    }
+                    self.drag = Some(drag);
+                }
+            }
+
+            // --- Pointer Enter/Leave Events --- //
+            if pointer_over != self.pointer_over() {
+                let mut chain = Vec::new();
+                let mut current = pointer_over;
+                while let Some(node) = current {
+                    chain.push(node);
+                    current = node_tree.get_parent(node);
+                }
+
+                // Leave the innermost widgets first and enter the outermost ones first
+                let cursor_event = self.get_cursor_event(self.next_mouse_position);
+                for previous in self.pointer_over.iter() {
+                    if !chain.contains(previous) {
+                        event_stream.push(KEvent::new(
+                            previous.0,
+                            EventType::PointerLeave(cursor_event),
+                        ));
+                    }
+                }
+                for current in chain.iter().rev() {
+                    if !self.pointer_over.contains(current) {
+                        event_stream.push(KEvent::new(
+                            current.0,
+                            EventType::PointerEnter(cursor_event),
+                        ));
+                    }
+                }
+                self.pointer_over = chain;
+            }
+
+            if !self.next_mouse_pressed && self.implicit_capture {
+                self.force_release_cursor();
+            }
+
             // === Process Cursor States === //
             self.current_mouse_position = self.next_mouse_position;
             self.is_mouse_pressed = self.next_mouse_pressed;
@@ -510,6 +780,7 @@ impl EventDispatcher {
     ///
     /// * `input_event`: The input event
     /// * `tree_node`: The current node to process
+    /// * `clip`: The clip rect of the current node's clipping ancestors, if any
     /// * `states`: The map of events to their current state (for selecting best fit)
     /// * `widget_manager`: The widget manager
     /// * `ignore_layout`: Whether to ignore layout (useful for handling captured events)
@@ -519,6 +790,7 @@ impl EventDispatcher {
         &mut self,
         input_event: &InputEvent,
         tree_node: TreeNode,
+        clip: Option<Rect>,
         states: &mut HashMap<EventType, EventState>,
         world: &mut World,
         context: &KayakRootContext,
@@ -532,11 +804,13 @@ impl EventDispatcher {
 
         match input_event {
             InputEvent::MouseMoved(point) => {
-                if let Some(layout) = context.get_layout(&node) {
+                if let Some(layout) = Self::get_hit_rect(node, world, context) {
                     let cursor_event = self.get_cursor_event(*point);
-                    let was_contained = layout.contains(&self.current_mouse_position);
-                    let is_contained = layout.contains(point);
-                    if !ignore_layout && was_contained != is_contained {
+                    let was_contained = Self::hit_test(&layout, clip, &self.current_mouse_position);
+                    let is_contained = Self::hit_test(&layout, clip, point);
+                    // A captor is still told when the cursor leaves it, so hover styles don't get
+                    // stuck once it's released
+                    if was_contained != is_contained {
                         if was_contained {
                             // Mouse out should fire even when
                             event_stream
@@ -553,6 +827,7 @@ impl EventDispatcher {
                                 states,
                                 (node, depth),
                                 &layout,
+                                world,
                                 EventType::MouseIn(cursor_event),
                             );
                         }
@@ -580,25 +855,50 @@ impl EventDispatcher {
                             states,
                             (node, depth),
                             &layout,
+                            world,
                             EventType::Hover(cursor_event),
                         );
                     }
                 }
             }
             InputEvent::MouseLeftPress => {
-                if let Some(layout) = context.get_layout(&node) {
-                    if ignore_layout || layout.contains(&self.current_mouse_position) {
-                        let cursor_event = self.get_cursor_event(self.current_mouse_position);
+                // A touch moves the cursor in the same batch as its press, so hit-test at the
+                // position the cursor had when it was pressed
+                let position = self.next_mouse_position;
+                if let Some(layout) = Self::get_hit_rect(node, world, context) {
+                    if ignore_layout || Self::hit_test(&layout, clip, &position) {
+                        let cursor_event = self.get_cursor_event(position);
                         // event_stream.push(Event::new(node.0, EventType::MouseDown(cursor_event)));
                         Self::update_state(
                             states,
                             (node, depth),
                             &layout,
+                            world,
                             EventType::MouseDown(cursor_event),
                         );
 
                         if world.get::<Focusable>(node.0).is_some() {
-                            Self::update_state(states, (node, depth), &layout, EventType::Focus);
+                            Self::update_state(
+                                states,
+                                (node, depth),
+                                &layout,
+                                world,
+                                EventType::Focus,
+                            );
+                        }
+
+                        if world.get::<Draggable>(node.0).is_some() {
+                            Self::update_state(
+                                states,
+                                (node, depth),
+                                &layout,
+                                world,
+                                EventType::DragStart(DragEvent {
+                                    source: node.0,
+                                    position,
+                                    delta: (0.0, 0.0),
+                                }),
+                            );
                         }
 
                         if self.has_cursor.is_none() {
@@ -613,47 +913,71 @@ impl EventDispatcher {
                 }
             }
             InputEvent::MouseLeftRelease => {
-                if let Some(layout) = context.get_layout(&node) {
-                    if ignore_layout || layout.contains(&self.current_mouse_position) {
+                if let Some(layout) = Self::get_hit_rect(node, world, context) {
+                    let is_contained = Self::hit_test(&layout, clip, &self.current_mouse_position);
+                    if ignore_layout || is_contained {
                         let cursor_event = self.get_cursor_event(self.current_mouse_position);
                         // event_stream.push(Event::new(node.0, EventType::MouseUp(cursor_event)));
                         Self::update_state(
                             states,
                             (node, depth),
                             &layout,
+                            world,
                             EventType::MouseUp(cursor_event),
                         );
                         // self.last_clicked.set(node);
 
-                        if Self::contains_event(
-                            &self.previous_events,
-                            &node,
-                            &EventType::MouseDown(cursor_event),
-                        ) {
+                        // A captor released outside of its layout isn't clicked
+                        if is_contained
+                            && Self::contains_event(
+                                &self.previous_events,
+                                &node,
+                                &EventType::MouseDown(cursor_event),
+                            )
+                        {
                             Self::update_state(
                                 states,
                                 (node, depth),
                                 &layout,
+                                world,
                                 EventType::Click(cursor_event),
                             );
                         }
                     }
                 }
             }
+            InputEvent::MouseRightPress => {
+                if let Some(layout) = Self::get_hit_rect(node, world, context) {
+                    if ignore_layout || Self::hit_test(&layout, clip, &self.current_mouse_position)
+                    {
+                        let cursor_event = self.get_cursor_event(self.current_mouse_position);
+                        Self::update_state(
+                            states,
+                            (node, depth),
+                            &layout,
+                            world,
+                            EventType::ContextMenu(cursor_event),
+                        );
+                    }
+                }
+            }
             InputEvent::Scroll { dx, dy, is_line } => {
-                if let Some(layout) = context.get_layout(&node) {
+                if let Some(layout) = Self::get_hit_rect(node, world, context) {
                     // Check for scroll eligibility
-                    if ignore_layout || layout.contains(&self.current_mouse_position) {
+                    if ignore_layout || Self::hit_test(&layout, clip, &self.current_mouse_position)
+                    {
                         Self::update_state(
                             states,
                             (node, depth),
                             &layout,
+                            world,
                             EventType::Scroll(ScrollEvent {
                                 delta: if *is_line {
                                     ScrollUnit::Line { x: *dx, y: *dy }
                                 } else {
                                     ScrollUnit::Pixel { x: *dx, y: *dy }
                                 },
+                                modifiers: self.keyboard_modifiers,
                             }),
                         );
                     }
@@ -665,6 +989,218 @@ impl EventDispatcher {
         event_stream
     }
 
+    /// Returns the area of a node that responds to pointer events
+    ///
+    /// This is the node's layout grown by its [`hit_padding`](crate::styles::KStyle::hit_padding).
+    fn get_hit_rect(node: WrappedIndex, world: &World, context: &KayakRootContext) -> Option<Rect> {
+        let layout = context.get_layout(&node)?;
+        let padding = world
+            .get::<Node>(node.0)
+            .map(|node| node.resolved_styles.hit_padding.resolve())
+            .unwrap_or_default();
+        Some(Rect {
+            posx: layout.posx - padding.left,
+            posy: layout.posy - padding.top,
+            width: layout.width + padding.left + padding.right,
+            height: layout.height + padding.top + padding.bottom,
+            z_index: layout.z_index,
+        })
+    }
+
+    /// Checks if a point is within the given layout and not clipped away
+    fn hit_test(layout: &Rect, clip: Option<Rect>, point: &(f32, f32)) -> bool {
+        layout.contains(point) && clip.map(|clip| clip.contains(point)).unwrap_or(true)
+    }
+
+    /// Returns the clip rect that applies to the children of a node
+    ///
+    /// Only widgets marked [`RenderCommand::Clip`] clip their children, so the clip of any
+    /// other widget is passed down as-is.
+    fn get_child_clip(
+        node: WrappedIndex,
+        clip: Option<Rect>,
+        world: &World,
+        context: &KayakRootContext,
+    ) -> Option<Rect> {
+        let is_clip = world
+            .get::<Node>(node.0)
+            .map(|node| {
+                matches!(
+                    node.resolved_styles.render_command,
+                    StyleProp::Value(RenderCommand::Clip)
+                )
+            })
+            .unwrap_or_default();
+        if !is_clip {
+            return clip;
+        }
+        let layout = if let Some(layout) = context.get_layout(&node) {
+            layout
+        } else {
+            return clip;
+        };
+        if let Some(clip) = clip {
+            let posx = layout.posx.max(clip.posx);
+            let posy = layout.posy.max(clip.posy);
+            let right = (layout.posx + layout.width).min(clip.posx + clip.width);
+            let bottom = (layout.posy + layout.height).min(clip.posy + clip.height);
+            Some(Rect {
+                posx,
+                posy,
+                width: (right - posx).max(0.0),
+                height: (bottom - posy).max(0.0),
+                z_index: layout.z_index,
+            })
+        } else {
+            Some(layout)
+        }
+    }
+
+    /// Pushes the children of a node onto a traversal stack, topmost last
+    fn push_children(
+        stack: &mut Vec<(TreeNode, Option<Rect>)>,
+        tree_node: TreeNode,
+        clip: Option<Rect>,
+        node_tree: &Tree,
+        world: &World,
+        context: &KayakRootContext,
+    ) {
+        let (node, depth) = tree_node;
+        if let Some(children) = node_tree.children.get(&node) {
+            let child_clip = Self::get_child_clip(node, clip, world, context);
+            let mut stack_children = Vec::new();
+            for child in children {
+                let child_z = world
+                    .get_entity(child.0)
+                    .map(|e| e.get::<Node>().map(|node| node.z).unwrap_or(0.0))
+                    .unwrap_or(0.0);
+                stack_children.push((child_z, (*child, depth + 1)));
+            }
+            stack_children.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            stack.extend(stack_children.iter().map(|c| (c.1, child_clip)));
+        }
+    }
+
+    /// Finds the topmost widget under the given position that isn't the dragged widget or
+    /// one of its descendants
+    fn find_drop_target(
+        root: WrappedIndex,
+        source: WrappedIndex,
+        position: (f32, f32),
+        node_tree: &Tree,
+        world: &mut World,
+        context: &KayakRootContext,
+    ) -> Option<WrappedIndex> {
+        let mut states: HashMap<EventType, EventState> = HashMap::new();
+        let mut stack: Vec<(TreeNode, Option<Rect>)> = vec![((root, 0), None)];
+        while let Some(((current, depth), clip)) = stack.pop() {
+            if current == source {
+                continue;
+            }
+
+            let mut enter_children = true;
+            if world.get::<OnEvent>(current.0).is_some() {
+                let pointer_events = Self::resolve_pointer_events(current, world);
+                if matches!(pointer_events, PointerEvents::All | PointerEvents::SelfOnly) {
+                    if let Some(layout) = Self::get_hit_rect(current, world, context) {
+                        if Self::hit_test(&layout, clip, &position) {
+                            Self::update_state(
+                                &mut states,
+                                (current, depth),
+                                &layout,
+                                world,
+                                EventType::Drop(DragEvent {
+                                    source: source.0,
+                                    position,
+                                    delta: (0.0, 0.0),
+                                }),
+                            );
+                        }
+                    }
+                }
+                enter_children = matches!(
+                    pointer_events,
+                    PointerEvents::All | PointerEvents::ChildrenOnly
+                );
+            }
+
+            if enter_children {
+                Self::push_children(
+                    &mut stack,
+                    (current, depth),
+                    clip,
+                    node_tree,
+                    world,
+                    context,
+                );
+            }
+        }
+
+        states
+            .into_values()
+            .next()
+            .and_then(|state| state.best_match)
+    }
+
+    /// Returns true if the given node is the ancestor or one of its descendants
+    fn is_within(node: WrappedIndex, ancestor: WrappedIndex, node_tree: &Tree) -> bool {
+        let mut current = Some(node);
+        while let Some(index) = current {
+            if index == ancestor {
+                return true;
+            }
+            current = node_tree.get_parent(index);
+        }
+        false
+    }
+
+    /// The [`FocusTrap`] that keeps focus within it, i.e. the last one in tree order
+    fn active_focus_trap(node_tree: &Tree, world: &World) -> Option<WrappedIndex> {
+        node_tree
+            .flatten()
+            .into_iter()
+            .rev()
+            .find(|index| world.get::<FocusTrap>(index.0).is_some())
+    }
+
+    /// Returns the tab index of a focusable widget, or `None` if it can't currently be focused
+    fn resolve_tab_index(
+        index: WrappedIndex,
+        node_tree: &Tree,
+        world: &World,
+        context: &KayakRootContext,
+    ) -> Option<i32> {
+        // Widgets that are hidden, or inside a hidden widget, can't be tabbed to
+        let mut current = Some(index);
+        while let Some(node) = current {
+            if !Self::is_displayed(node, world) {
+                return None;
+            }
+            current = node_tree.get_parent(node);
+        }
+
+        let styles = &world.get::<Node>(index.0)?.resolved_styles;
+        if styles.disabled.resolve_or(false)
+            || matches!(styles.pointer_events.resolve(), PointerEvents::None)
+        {
+            return None;
+        }
+
+        // Widgets that aren't laid out (or have no size) aren't visible
+        let layout = context.get_layout(&index)?;
+        if layout.width <= 0.0 || layout.height <= 0.0 {
+            return None;
+        }
+
+        Some(styles.tab_index.resolve_or(0))
+    }
+
+    fn is_displayed(index: WrappedIndex, world: &World) -> bool {
+        world.get::<ComputedStyles>(index.0).map_or(true, |styles| {
+            matches!(styles.0.display.resolve(), KDisplay::Shown)
+        })
+    }
+
     fn resolve_pointer_events(index: WrappedIndex, world: &mut World) -> PointerEvents {
         let mut pointer_events = PointerEvents::default();
         if let Some(styles) = world.get::<ComputedStyles>(index.0) {
@@ -673,6 +1209,104 @@ impl EventDispatcher {
         pointer_events
     }
 
+    /// Hit-tests the last cursor position against the tree to find the hovered widget
+    ///
+    /// This ignores any cursor capture, so the hover state matches what's under the cursor.
+    fn update_hover_state(
+        &self,
+        mouse_inputs: &[&InputEvent],
+        root: WrappedIndex,
+        node_tree: &Tree,
+        states: &mut HashMap<EventType, EventState>,
+        world: &mut World,
+        context: &KayakRootContext,
+    ) {
+        let point = mouse_inputs.iter().rev().find_map(|input_event| {
+            if let InputEvent::MouseMoved(point) = input_event {
+                Some(*point)
+            } else {
+                None
+            }
+        });
+        let point = if let Some(point) = point {
+            point
+        } else {
+            return;
+        };
+
+        let mut stack: Vec<(TreeNode, Option<Rect>)> = vec![((root, 0), None)];
+        while let Some(((current, depth), clip)) = stack.pop() {
+            if !Self::is_displayed(current, world) {
+                continue;
+            }
+
+            let mut enter_children = true;
+            if world.get::<OnEvent>(current.0).is_some() {
+                let pointer_events = Self::resolve_pointer_events(current, world);
+                match pointer_events {
+                    PointerEvents::All | PointerEvents::SelfOnly => {
+                        if let Some(layout) = Self::get_hit_rect(current, world, context) {
+                            if Self::hit_test(&layout, clip, &point) {
+                                Self::update_state(
+                                    states,
+                                    (current, depth),
+                                    &layout,
+                                    world,
+                                    EventType::Hover(self.get_cursor_event(point)),
+                                );
+                            }
+                        }
+                        enter_children = matches!(pointer_events, PointerEvents::All);
+                    }
+                    PointerEvents::None => enter_children = false,
+                    PointerEvents::ChildrenOnly => {}
+                }
+            }
+
+            if enter_children {
+                Self::push_children(
+                    &mut stack,
+                    (current, depth),
+                    clip,
+                    node_tree,
+                    world,
+                    context,
+                );
+            }
+        }
+    }
+
+    /// Checks whether the cursor is currently over the viewport of the context's camera
+    ///
+    /// Cameras that don't draw to a window, like world panels, never have the cursor over them.
+    fn is_cursor_in_window(context: &KayakRootContext, world: &mut World) -> bool {
+        let Some(camera) = world.get::<Camera>(context.camera_entity) else {
+            return false;
+        };
+        let viewport = camera.logical_viewport_rect();
+        let window_entity = match camera.target.clone() {
+            RenderTarget::Window(WindowRef::Entity(entity)) => entity,
+            RenderTarget::Window(WindowRef::Primary) => {
+                match world
+                    .query_filtered::<Entity, With<PrimaryWindow>>()
+                    .get_single(world)
+                {
+                    Ok(entity) => entity,
+                    Err(_) => return false,
+                }
+            }
+            _ => return false,
+        };
+        let Some(cursor) = world
+            .get::<Window>(window_entity)
+            .and_then(|window| window.cursor_position())
+        else {
+            return false;
+        };
+
+        viewport.map_or(true, |viewport| viewport.contains(cursor))
+    }
+
     fn get_cursor_event(&self, position: (f32, f32)) -> CursorEvent {
         let change = self.next_mouse_pressed != self.is_mouse_pressed;
         let pressed = self.next_mouse_pressed;
@@ -681,9 +1315,37 @@ impl EventDispatcher {
             pressed,
             just_pressed: change && pressed,
             just_released: change && !pressed,
+            click_count: 0,
         }
     }
 
+    /// Records a click on the given node and returns how many clicks in a row it makes
+    fn count_click(&mut self, node: WrappedIndex, position: (f32, f32), world: &World) -> u32 {
+        let settings = world
+            .get_resource::<MultiClick>()
+            .copied()
+            .unwrap_or_default();
+        let now = Instant::now();
+        let count = match self.last_click {
+            Some(last)
+                if last.node == node
+                    && now.duration_since(last.time).as_secs_f32() <= settings.interval
+                    && (last.position.0 - position.0).hypot(last.position.1 - position.1)
+                        <= settings.distance =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+        self.last_click = Some(LastClick {
+            node,
+            time: now,
+            position,
+            count,
+        });
+        count
+    }
+
     fn process_keyboard_events(
         &mut self,
         input_event: &InputEvent,
@@ -696,6 +1358,19 @@ impl EventDispatcher {
                 InputEvent::CharEvent { c } => {
                     event_stream.push(KEvent::new(current_focus, EventType::CharInput { c: *c }))
                 }
+                InputEvent::Copy => event_stream.push(KEvent::new(current_focus, EventType::Copy)),
+                InputEvent::Cut => event_stream.push(KEvent::new(current_focus, EventType::Cut)),
+                InputEvent::ImePreedit => {
+                    event_stream.push(KEvent::new(current_focus, EventType::ImePreedit))
+                }
+                InputEvent::Paste(text) | InputEvent::ImeCommit(text) => {
+                    // Pasted and composed text is typed in as if it was entered character by character
+                    event_stream.extend(
+                        text.chars()
+                            .filter(|c| !c.is_control())
+                            .map(|c| KEvent::new(current_focus, EventType::CharInput { c })),
+                    );
+                }
                 InputEvent::Keyboard { key, is_pressed } => {
                     // === Modifers === //
                     match key {
@@ -735,24 +1410,31 @@ impl EventDispatcher {
     }
 
     /// Updates the state data for the given event
+    ///
+    /// Like the renderer, this compares the node's render layer before its z-index, so the
+    /// widget drawn on top is the one that receives the event.
     fn update_state(
         states: &mut HashMap<EventType, EventState>,
         tree_node: TreeNode,
         layout: &Rect,
+        world: &World,
         event_type: EventType,
     ) {
         let state = states.entry(event_type).or_default();
 
         let (node, depth) = tree_node;
-        // Node is at or above best depth and is at or above best z-level
-
-        let mut should_update = depth >= state.best_depth && layout.z_index >= state.best_z_index;
-        // OR node is above best z-level
-        should_update |= layout.z_index >= state.best_z_index;
+        let render_layer = world
+            .get::<Node>(node.0)
+            .map(|node| node.resolved_styles.render_layer.resolve_or(0))
+            .unwrap_or(0);
+        // Node is on a higher render layer, or on the same one and at or above best z-level
+        let should_update =
+            (render_layer, layout.z_index) >= (state.best_render_layer, state.best_z_index);
 
         if should_update {
             // dbg!(node.0.id(), layout.z_index);
             state.best_match = Some(node);
+            state.best_render_layer = render_layer;
             state.best_z_index = layout.z_index;
             state.best_depth = depth;
         }
@@ -798,15 +1480,26 @@ impl EventDispatcher {
     ) {
         if let EventType::KeyDown(evt) = event.event_type {
             if let KeyCode::Tab = evt.key() {
-                let (index, current_focus) = {
-                    let current_focus = context.focus_tree.current();
-
-                    let index = if evt.is_shift_pressed() {
-                        context.focus_tree.prev()
-                    } else {
-                        context.focus_tree.next()
-                    };
-                    (index, current_focus)
+                let current_focus = context.focus_tree.current();
+                let index = if let Ok(node_tree) = context.tree.try_read() {
+                    let trap = Self::active_focus_trap(&node_tree, world);
+                    context
+                        .focus_tree
+                        .peek_tab(
+                            current_focus.map(WrappedIndex),
+                            evt.is_shift_pressed(),
+                            |index| {
+                                if let Some(trap) = trap {
+                                    if !Self::is_within(index, trap, &node_tree) {
+                                        return None;
+                                    }
+                                }
+                                Self::resolve_tab_index(index, &node_tree, world, context)
+                            },
+                        )
+                        .map(|index| index.0)
+                } else {
+                    None
                 };
 
                 if let Some(index) = index {
@@ -844,15 +1537,20 @@ impl EventDispatcher {
         self.next_mouse_pressed = from.next_mouse_pressed;
         self.current_mouse_position = from.current_mouse_position;
         self.next_mouse_position = from.next_mouse_position;
+        self.has_mouse_position = from.has_mouse_position;
         self.previous_events = from.previous_events;
         self.keyboard_modifiers = from.keyboard_modifiers;
         self.contains_cursor = from.contains_cursor;
         self.wants_cursor = from.wants_cursor;
         self.has_cursor = from.has_cursor;
         self.hovered = from.hovered;
+        self.pointer_over = from.pointer_over;
+        self.last_click = from.last_click;
+        self.drag = from.drag;
 
         // Do not include:
         // self.cursor_capture = from.cursor_capture;
+        // self.implicit_capture = from.implicit_capture;
     }
 }
 
@@ -899,7 +1597,105 @@ impl EventDispatcherContext {
         old
     }
 
+    /// Returns the widget that currently captures the cursor, if any
+    pub fn captured_cursor(&self) -> Option<WrappedIndex> {
+        self.cursor_capture
+    }
+
     pub(crate) fn merge(self, event_dispatcher: &mut EventDispatcher) {
-        event_dispatcher.cursor_capture = self.cursor_capture;
+        if event_dispatcher.cursor_capture != self.cursor_capture {
+            event_dispatcher.cursor_capture = self.cursor_capture;
+            event_dispatcher.implicit_capture = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{prelude::*, utils::HashMap};
+
+    use super::{EventDispatcher, EventState};
+    use crate::{
+        context::KayakRootContext,
+        event::EventType,
+        input_event::InputEvent,
+        layout::Rect,
+        node::{Node, WrappedIndex},
+        on_event::OnEvent,
+        styles::{ComputedStyles, KStyle, RenderCommand, StyleProp},
+    };
+
+    fn add_node(
+        world: &mut World,
+        context: &KayakRootContext,
+        parent: Option<WrappedIndex>,
+        render_layer: StyleProp<i32>,
+        z_index: f32,
+    ) -> WrappedIndex {
+        let styles = KStyle {
+            render_command: StyleProp::Value(RenderCommand::Quad),
+            render_layer,
+            ..Default::default()
+        };
+        let node = WrappedIndex(
+            world
+                .spawn((
+                    Node {
+                        resolved_styles: styles.clone(),
+                        ..Default::default()
+                    },
+                    ComputedStyles(styles),
+                    OnEvent::default(),
+                ))
+                .id(),
+        );
+        context.tree.write().unwrap().add(node, parent);
+        context.layout_cache.write().unwrap().rect.insert(
+            node,
+            Rect {
+                posx: 0.0,
+                posy: 0.0,
+                width: 100.0,
+                height: 100.0,
+                z_index,
+            },
+        );
+        node
+    }
+
+    #[test]
+    fn later_sibling_does_not_take_events_from_modal() {
+        let mut world = World::new();
+        let context = KayakRootContext::new(Entity::PLACEHOLDER);
+
+        // The modal is drawn on a higher render layer, above the sibling declared after it
+        // even though the sibling has the higher z-index
+        let root = add_node(&mut world, &context, None, StyleProp::Value(0), 0.0);
+        let modal = add_node(&mut world, &context, Some(root), StyleProp::Value(1), 1.0);
+        let sibling = add_node(&mut world, &context, Some(root), StyleProp::Value(0), 2.0);
+
+        let tree = context.tree.read().unwrap().clone();
+        let mut dispatcher = EventDispatcher::new();
+        let mut states: HashMap<EventType, EventState> = HashMap::new();
+        let moved = InputEvent::MouseMoved((50.0, 50.0));
+        dispatcher.update_hover_state(&[&moved], root, &tree, &mut states, &mut world, &context);
+        let hover = EventType::Hover(dispatcher.get_cursor_event((50.0, 50.0)));
+        assert_eq!(states[&hover].best_match, Some(modal));
+
+        dispatcher.next_mouse_position = (50.0, 50.0);
+        let mut states: HashMap<EventType, EventState> = HashMap::new();
+        for node in [root, modal, sibling] {
+            dispatcher.process_pointer_events(
+                &InputEvent::MouseLeftPress,
+                (node, if node == root { 0 } else { 1 }),
+                None,
+                &mut states,
+                &mut world,
+                &context,
+                false,
+            );
+        }
+        let press = EventType::MouseDown(dispatcher.get_cursor_event((50.0, 50.0)));
+        assert_eq!(states[&press].best_match, Some(modal));
     }
 }
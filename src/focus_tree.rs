@@ -11,6 +11,14 @@ use crate::{node::WrappedIndex, prelude::Tree};
 #[reflect(Component)]
 pub struct Focusable;
 
+/// Keeps Tab navigation within a widget and its descendants
+///
+/// When several traps are in the tree, the last one in tree order (the one drawn on top) is
+/// used. This is used by modals so the UI behind them can't be focused with the keyboard.
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct FocusTrap;
+
 #[derive(Resource, Debug, Clone, Default)]
 pub struct FocusTree {
     tree: Arc<RwLock<Tree>>,
@@ -136,6 +144,45 @@ impl FocusTree {
         }
     }
 
+    /// Peek the index Tab (or Shift-Tab when `reverse` is true) moves focus to
+    ///
+    /// The `tab_index` function returns the tab index of a focusable index, or `None` if it
+    /// can't currently be focused. Indices with a positive tab index come first, in ascending
+    /// order, followed by the rest in tree order. Negative tab indices are skipped. Wraps around
+    /// at both ends.
+    pub fn peek_tab(
+        &self,
+        current_focus: Option<WrappedIndex>,
+        reverse: bool,
+        tab_index: impl Fn(WrappedIndex) -> Option<i32>,
+    ) -> Option<WrappedIndex> {
+        let mut order = if let Ok(tree) = self.tree.try_read() {
+            tree.flatten()
+                .into_iter()
+                .filter_map(|index| tab_index(index).map(|tab_index| (tab_index, index)))
+                .filter(|(tab_index, _)| *tab_index >= 0)
+                .collect::<Vec<_>>()
+        } else {
+            return None;
+        };
+        // A stable sort keeps the tree order within the same tab index
+        order.sort_by_key(|(tab_index, _)| if *tab_index > 0 { *tab_index } else { i32::MAX });
+
+        let len = order.len();
+        if len == 0 {
+            return None;
+        }
+        let position =
+            current_focus.and_then(|current| order.iter().position(|(_, index)| *index == current));
+        let next = match (position, reverse) {
+            (Some(position), false) => (position + 1) % len,
+            (Some(position), true) => (position + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        };
+        Some(order[next].1)
+    }
+
     /// Peek the next focusable index without actually changing focus
     pub fn peek_next(&self, current_focus: Option<WrappedIndex>) -> Option<WrappedIndex> {
         if let Ok(tree) = self.tree.try_read() {
@@ -346,4 +393,44 @@ mod tests {
 
         // etc.
     }
+
+    #[test]
+    fn tab_should_follow_tab_index() {
+        let focus_tree = FocusTree::default();
+        let mut tree = Tree::default();
+
+        let a = WrappedIndex(Entity::from_raw(0));
+        tree.add(a, None);
+        let a_a = WrappedIndex(Entity::from_raw(1));
+        tree.add(a_a, Some(a));
+        let a_b = WrappedIndex(Entity::from_raw(2));
+        tree.add(a_b, Some(a));
+        let a_c = WrappedIndex(Entity::from_raw(3));
+        tree.add(a_c, Some(a));
+        let a_d = WrappedIndex(Entity::from_raw(4));
+        tree.add(a_d, Some(a));
+
+        focus_tree.add(a, &tree);
+        focus_tree.add(a_a, &tree);
+        focus_tree.add(a_b, &tree);
+        focus_tree.add(a_c, &tree);
+        focus_tree.add(a_d, &tree);
+
+        // `a` is disabled, `a_c` comes first and `a_d` is skipped
+        let tab_index = |index: WrappedIndex| match index {
+            index if index == a => None,
+            index if index == a_c => Some(1),
+            index if index == a_d => Some(-1),
+            _ => Some(0),
+        };
+
+        assert_eq!(Some(a_c), focus_tree.peek_tab(None, false, tab_index));
+        assert_eq!(Some(a_a), focus_tree.peek_tab(Some(a_c), false, tab_index));
+        assert_eq!(Some(a_b), focus_tree.peek_tab(Some(a_a), false, tab_index));
+        assert_eq!(Some(a_c), focus_tree.peek_tab(Some(a_b), false, tab_index));
+
+        assert_eq!(Some(a_b), focus_tree.peek_tab(Some(a_c), true, tab_index));
+        assert_eq!(Some(a_c), focus_tree.peek_tab(Some(a_a), true, tab_index));
+        assert_eq!(Some(a_b), focus_tree.peek_tab(Some(a_d), true, tab_index));
+    }
 }
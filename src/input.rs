@@ -1,20 +1,244 @@
+use std::borrow::Cow;
+
 use bevy::{
     input::{
         keyboard::KeyboardInput,
         mouse::{MouseButtonInput, MouseScrollUnit, MouseWheel},
+        touch::{TouchInput, TouchPhase},
         ButtonState,
     },
     prelude::*,
+    window::Ime,
 };
 
 use crate::{
+    clipboard::Clipboard,
     context::{CustomEventReader, KayakRootContext},
     event_dispatcher::EventDispatcher,
     input_event::InputEvent,
+    KayakUiScale,
 };
 
+/// Configures how held keys are repeated for the focused widget
+///
+/// While one of the [`keys`](Self::keys) is held, a new key press is sent every
+/// [`interval`](Self::interval) seconds once it has been held for [`delay`](Self::delay) seconds.
+/// Releasing the key stops the repeat. For the Backspace and Delete keys, the matching character
+/// input is repeated as well.
+#[derive(Resource, Debug, Clone)]
+pub struct KeyRepeat {
+    /// The time (in seconds) a key needs to be held before it starts repeating
+    pub delay: f32,
+    /// The time (in seconds) between each repeat
+    ///
+    /// Intervals shorter than 10 milliseconds are treated as 10 milliseconds.
+    pub interval: f32,
+    /// The keys that repeat while held
+    pub keys: Vec<KeyCode>,
+    held: Option<HeldKey>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HeldKey {
+    key: KeyCode,
+    /// Time (in seconds) until the next repeat
+    next_repeat: f32,
+}
+
+impl Default for KeyRepeat {
+    fn default() -> Self {
+        Self {
+            delay: 0.5,
+            interval: 0.035,
+            keys: vec![
+                KeyCode::Left,
+                KeyCode::Right,
+                KeyCode::Up,
+                KeyCode::Down,
+                KeyCode::Home,
+                KeyCode::End,
+                KeyCode::Back,
+                KeyCode::Delete,
+            ],
+            held: None,
+        }
+    }
+}
+
+impl KeyRepeat {
+    /// The maximum number of repeats sent in a single frame
+    const MAX_REPEATS_PER_FRAME: usize = 8;
+    /// The shortest time (in seconds) between each repeat
+    const MIN_INTERVAL: f32 = 0.01;
+
+    fn is_held(&self, key: KeyCode) -> bool {
+        matches!(self.held, Some(held) if held.key == key)
+    }
+
+    /// Returns true if the given character is produced by a key that's currently being repeated
+    fn is_repeated_char(&self, c: char) -> bool {
+        self.held
+            .and_then(|held| Self::repeated_char(held.key))
+            .map(|repeated| repeated == c)
+            .unwrap_or_default()
+    }
+
+    fn repeated_char(key: KeyCode) -> Option<char> {
+        match key {
+            KeyCode::Back => Some('\u{8}'),
+            KeyCode::Delete => Some('\u{7f}'),
+            _ => None,
+        }
+    }
+
+    fn on_key(&mut self, key: KeyCode, is_pressed: bool) {
+        if is_pressed {
+            if self.keys.contains(&key) {
+                self.held = Some(HeldKey {
+                    key,
+                    next_repeat: self.delay,
+                });
+            }
+        } else if self.is_held(key) {
+            self.held = None;
+        }
+    }
+
+    fn tick(&mut self, delta_seconds: f32, input_events: &mut Vec<InputEvent>) {
+        let interval = self.interval.max(Self::MIN_INTERVAL);
+        if let Some(held) = &mut self.held {
+            held.next_repeat -= delta_seconds;
+
+            let mut repeats = 0;
+            while held.next_repeat <= 0.0 {
+                if repeats >= Self::MAX_REPEATS_PER_FRAME {
+                    // Skip the repeats that didn't fit in this frame
+                    held.next_repeat = held.next_repeat.rem_euclid(interval);
+                    break;
+                }
+                held.next_repeat += interval;
+                repeats += 1;
+
+                input_events.push(InputEvent::Keyboard {
+                    key: held.key,
+                    is_pressed: true,
+                });
+                if let Some(c) = Self::repeated_char(held.key) {
+                    input_events.push(InputEvent::CharEvent { c });
+                }
+            }
+        }
+    }
+}
+
+/// The text being composed with an input method editor (IME), if any
+///
+/// Input methods are used to type characters that aren't on the keyboard, such as Chinese,
+/// Japanese or Korean characters or accented letters. While the user composes, the focused widget
+/// receives [`EventType::ImePreedit`](crate::event::EventType::ImePreedit) events and can read the
+/// in-progress text here. Once the composition is finalized, the resulting characters are sent as
+/// [`EventType::CharInput`](crate::event::EventType::CharInput) events.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ImeComposition {
+    /// The text being composed
+    pub value: String,
+    /// The byte range of the cursor within the [`value`](Self::value), if any
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl ImeComposition {
+    /// Returns true if the user is in the middle of composing text
+    pub fn is_composing(&self) -> bool {
+        !self.value.is_empty()
+    }
+}
+
+/// The touch that acts as the mouse cursor
+///
+/// The first finger to touch the screen presses, moves and releases the cursor. Other fingers
+/// are ignored until it's lifted.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PrimaryTouch(pub Option<u64>);
+
+/// The system set that processes input for the UI
+///
+/// Systems that read [`ConsumedInput`] should run after this set.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KayakInputSet;
+
+/// The raw input events that were consumed by the UI this frame
+///
+/// Mouse and touch events are consumed when the cursor is over a widget that displays something of its
+/// own (see [`EventDispatcher::contains_cursor`]) or that wants the cursor (see
+/// [`EventDispatcher::wants_cursor`]). Widgets with [`PointerEvents::None`](crate::cursor::PointerEvents::None)
+/// never consume them, so clicks pass through them. Keyboard and character events are consumed
+/// while a widget has focus.
+///
+/// This is useful for keeping game input from reacting to input meant for the UI:
+/// ```
+/// # use bevy::{input::mouse::MouseButtonInput, prelude::*};
+/// # use kayak_ui::prelude::*;
+/// fn shoot(mut events: EventReader<MouseButtonInput>, consumed: Res<ConsumedInput>) {
+///     for event in events.iter() {
+///         if consumed.is_mouse_button_consumed(event) {
+///             continue;
+///         }
+///         // ...
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_systems(Update, shoot.after(KayakInputSet));
+/// ```
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ConsumedInput {
+    mouse_buttons: Vec<MouseButtonInput>,
+    mouse_wheel: Vec<MouseWheel>,
+    touches: Vec<TouchInput>,
+    keyboard: Vec<KeyboardInput>,
+    characters: Vec<ReceivedCharacter>,
+}
+
+impl ConsumedInput {
+    /// Returns true if the given mouse button event was consumed by the UI
+    pub fn is_mouse_button_consumed(&self, event: &MouseButtonInput) -> bool {
+        self.mouse_buttons.contains(event)
+    }
+
+    /// Returns true if the given mouse wheel event was consumed by the UI
+    pub fn is_mouse_wheel_consumed(&self, event: &MouseWheel) -> bool {
+        self.mouse_wheel.contains(event)
+    }
+
+    /// Returns true if the given touch event was consumed by the UI
+    pub fn is_touch_consumed(&self, event: &TouchInput) -> bool {
+        self.touches.contains(event)
+    }
+
+    /// Returns true if the given keyboard event was consumed by the UI
+    pub fn is_keyboard_consumed(&self, event: &KeyboardInput) -> bool {
+        self.keyboard.contains(event)
+    }
+
+    /// Returns true if the given character event was consumed by the UI
+    pub fn is_char_consumed(&self, event: &ReceivedCharacter) -> bool {
+        self.characters.contains(event)
+    }
+
+    /// Returns true if no input was consumed by the UI this frame
+    pub fn is_empty(&self) -> bool {
+        self.mouse_buttons.is_empty()
+            && self.mouse_wheel.is_empty()
+            && self.touches.is_empty()
+            && self.keyboard.is_empty()
+            && self.characters.is_empty()
+    }
+}
+
 pub(crate) fn process_events(world: &mut World) {
     let mut input_events = Vec::new();
+    // The raw events read this frame, in case the UI consumes them
+    let mut raw_events = ConsumedInput::default();
 
     query_world::<
         (
@@ -28,6 +252,20 @@ pub(crate) fn process_events(world: &mut World) {
             ResMut<CustomEventReader<MouseWheel>>,
             ResMut<CustomEventReader<ReceivedCharacter>>,
             ResMut<CustomEventReader<KeyboardInput>>,
+            Res<Time>,
+            ResMut<KeyRepeat>,
+            Res<Input<KeyCode>>,
+            ResMut<Clipboard>,
+            (
+                Res<Events<Ime>>,
+                ResMut<CustomEventReader<Ime>>,
+                ResMut<ImeComposition>,
+            ),
+            (
+                Res<Events<TouchInput>>,
+                ResMut<CustomEventReader<TouchInput>>,
+                ResMut<PrimaryTouch>,
+            ),
         ),
         _,
         _,
@@ -43,6 +281,12 @@ pub(crate) fn process_events(world: &mut World) {
             mut custom_event_mouse_wheel,
             mut custom_event_char_input,
             mut custom_event_keyboard,
+            time,
+            mut key_repeat,
+            keys,
+            mut clipboard,
+            (ime_events, mut custom_event_ime, mut ime_composition),
+            (touch_events, mut custom_event_touch, mut primary_touch),
         )| {
             if let Some(event) = custom_event_reader_cursor
                 .0
@@ -54,41 +298,121 @@ pub(crate) fn process_events(world: &mut World) {
             }
 
             for event in custom_event_mouse_button.0.iter(&mouse_button_input_events) {
-                if let MouseButton::Left = event.button {
-                    if event.state == ButtonState::Pressed {
+                raw_events.mouse_buttons.push(*event);
+                match (event.button, event.state) {
+                    (MouseButton::Left, ButtonState::Pressed) => {
                         input_events.push(InputEvent::MouseLeftPress);
-                    } else if event.state == ButtonState::Released {
+                    }
+                    (MouseButton::Left, ButtonState::Released) => {
                         input_events.push(InputEvent::MouseLeftRelease);
                     }
+                    (MouseButton::Right, ButtonState::Pressed) => {
+                        input_events.push(InputEvent::MouseRightPress);
+                    }
+                    _ => {}
                 }
             }
 
-            for MouseWheel {
-                x,
-                y,
-                unit,
-                window: _,
-            } in custom_event_mouse_wheel.0.iter(&mouse_wheel_events)
-            {
+            for event in custom_event_touch.0.iter(&touch_events) {
+                if primary_touch.0.is_none() && event.phase == TouchPhase::Started {
+                    primary_touch.0 = Some(event.id);
+                }
+                if primary_touch.0 != Some(event.id) {
+                    continue;
+                }
+
+                raw_events.touches.push(*event);
+                input_events.push(InputEvent::MouseMoved(event.position.into()));
+                match event.phase {
+                    TouchPhase::Started => input_events.push(InputEvent::MouseLeftPress),
+                    TouchPhase::Moved => {}
+                    TouchPhase::Ended | TouchPhase::Canceled => {
+                        primary_touch.0 = None;
+                        input_events.push(InputEvent::MouseLeftRelease);
+                    }
+                }
+            }
+
+            for event in custom_event_mouse_wheel.0.iter(&mouse_wheel_events) {
+                raw_events.mouse_wheel.push(*event);
                 input_events.push(InputEvent::Scroll {
-                    dx: *x,
-                    dy: *y,
-                    is_line: matches!(unit, MouseScrollUnit::Line),
+                    dx: event.x,
+                    dy: event.y,
+                    is_line: matches!(event.unit, MouseScrollUnit::Line),
                 })
             }
 
             for event in custom_event_char_input.0.iter(&char_input_events) {
+                raw_events.characters.push(event.clone());
+                // Characters of held keys are repeated by `KeyRepeat` instead.
+                if key_repeat.is_repeated_char(event.char) {
+                    continue;
+                }
                 input_events.push(InputEvent::CharEvent { c: event.char });
             }
 
             for event in custom_event_keyboard.0.iter(&keyboard_input_events) {
+                raw_events.keyboard.push(*event);
                 if let Some(key_code) = event.key_code {
+                    let is_pressed = matches!(event.state, ButtonState::Pressed);
+                    // Ignore presses sent by the OS while a key is held, these are
+                    // repeated by `KeyRepeat` instead.
+                    if is_pressed && key_repeat.is_held(key_code) {
+                        continue;
+                    }
+                    key_repeat.on_key(key_code, is_pressed);
                     input_events.push(InputEvent::Keyboard {
                         key: key_code,
-                        is_pressed: matches!(event.state, ButtonState::Pressed),
+                        is_pressed,
                     });
+
+                    // The Command key is used for shortcuts on Mac
+                    let is_shortcut = keys.any_pressed([
+                        KeyCode::ControlLeft,
+                        KeyCode::ControlRight,
+                        KeyCode::SuperLeft,
+                        KeyCode::SuperRight,
+                    ]);
+                    if is_pressed && is_shortcut {
+                        match key_code {
+                            KeyCode::C => input_events.push(InputEvent::Copy),
+                            KeyCode::X => input_events.push(InputEvent::Cut),
+                            KeyCode::V => {
+                                if let Some(text) = clipboard.get_text() {
+                                    input_events.push(InputEvent::Paste(text));
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            for event in custom_event_ime.0.iter(&ime_events) {
+                match event {
+                    Ime::Preedit { value, cursor, .. } => {
+                        *ime_composition = ImeComposition {
+                            value: value.clone(),
+                            cursor: *cursor,
+                        };
+                        input_events.push(InputEvent::ImePreedit);
+                    }
+                    Ime::Commit { value, .. } => {
+                        *ime_composition = ImeComposition::default();
+                        input_events.push(InputEvent::ImePreedit);
+                        input_events.push(InputEvent::ImeCommit(value.clone()));
+                    }
+                    Ime::Disabled { .. } => {
+                        if *ime_composition != ImeComposition::default() {
+                            *ime_composition = ImeComposition::default();
+                            input_events.push(InputEvent::ImePreedit);
+                        }
+                    }
+                    Ime::Enabled { .. } => {}
                 }
             }
+
+            key_repeat.tick(time.delta_seconds(), &mut input_events);
         },
         world,
     );
@@ -109,11 +433,62 @@ pub(crate) fn process_events(world: &mut World) {
         world,
     );
 
+    let mut is_pointer_consumed = false;
+    let mut is_keyboard_consumed = false;
     for (entity, mut event_dispatcher, mut context) in context_data.drain(..) {
-        event_dispatcher.process_events(&input_events, &mut context, world);
+        let viewport_origin = world
+            .get::<Camera>(context.camera_entity)
+            .and_then(|camera| camera.logical_viewport_rect())
+            .map(|rect| rect.min)
+            .unwrap_or_default();
+        let ui_scale = world.resource::<KayakUiScale>().get();
+        let context_events = to_viewport(&input_events, viewport_origin, ui_scale);
+        event_dispatcher.process_events(&context_events, &mut context, world);
+
+        is_pointer_consumed |=
+            event_dispatcher.contains_cursor() || event_dispatcher.wants_cursor();
+        is_keyboard_consumed |= context.focus_tree.current().is_some();
 
         world.entity_mut(entity).insert((event_dispatcher, context));
     }
+
+    let mut consumed_input = world.resource_mut::<ConsumedInput>();
+    *consumed_input = ConsumedInput::default();
+    if is_pointer_consumed {
+        consumed_input.mouse_buttons = raw_events.mouse_buttons;
+        consumed_input.mouse_wheel = raw_events.mouse_wheel;
+        consumed_input.touches = raw_events.touches;
+    }
+    if is_keyboard_consumed {
+        consumed_input.keyboard = raw_events.keyboard;
+        consumed_input.characters = raw_events.characters;
+    }
+}
+
+/// Moves the cursor positions into the space of a camera's viewport, whose origin is given in
+/// logical pixels, and divides them by the [`KayakUiScale`]
+///
+/// Each context lays out its widgets from the top left of its camera's viewport, so cameras
+/// rendering to different parts of a window see the cursor at different positions.
+fn to_viewport(
+    input_events: &[InputEvent],
+    viewport_origin: Vec2,
+    ui_scale: f32,
+) -> Cow<[InputEvent]> {
+    if viewport_origin == Vec2::ZERO && ui_scale == 1.0 {
+        return Cow::Borrowed(input_events);
+    }
+
+    input_events
+        .iter()
+        .map(|event| match event {
+            InputEvent::MouseMoved((x, y)) => InputEvent::MouseMoved((
+                (x - viewport_origin.x) / ui_scale,
+                (y - viewport_origin.y) / ui_scale,
+            )),
+            event => event.clone(),
+        })
+        .collect()
 }
 
 pub(crate) fn query_world<T: bevy::ecs::system::SystemParam + 'static, F, R>(
@@ -132,3 +507,75 @@ where
 
     r
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::KeyCode;
+
+    use super::KeyRepeat;
+    use crate::input_event::InputEvent;
+
+    fn key_repeat(interval: f32) -> KeyRepeat {
+        KeyRepeat {
+            delay: 0.5,
+            interval,
+            keys: vec![KeyCode::Left],
+            ..Default::default()
+        }
+    }
+
+    /// Advances the key repeat, returning how many key presses were sent
+    fn tick(key_repeat: &mut KeyRepeat, delta_seconds: f32) -> usize {
+        let mut input_events = Vec::new();
+        key_repeat.tick(delta_seconds, &mut input_events);
+        input_events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    InputEvent::Keyboard {
+                        key: KeyCode::Left,
+                        is_pressed: true
+                    }
+                )
+            })
+            .count()
+    }
+
+    #[test]
+    fn repeats_after_delay() {
+        let mut key_repeat = key_repeat(0.25);
+        key_repeat.on_key(KeyCode::Left, true);
+
+        assert_eq!(tick(&mut key_repeat, 0.25), 0);
+        assert_eq!(tick(&mut key_repeat, 0.25), 1);
+        assert_eq!(tick(&mut key_repeat, 0.125), 0);
+        assert_eq!(tick(&mut key_repeat, 0.125), 1);
+        assert_eq!(tick(&mut key_repeat, 0.5), 2);
+
+        key_repeat.on_key(KeyCode::Left, false);
+        assert_eq!(tick(&mut key_repeat, 1.0), 0);
+    }
+
+    #[test]
+    fn ignores_keys_that_dont_repeat() {
+        let mut key_repeat = key_repeat(0.25);
+        key_repeat.on_key(KeyCode::A, true);
+        assert_eq!(tick(&mut key_repeat, 1.0), 0);
+    }
+
+    #[test]
+    fn zero_interval_is_clamped() {
+        let mut key_repeat = key_repeat(0.0);
+        key_repeat.on_key(KeyCode::Left, true);
+
+        // A long frame only sends a limited number of repeats
+        assert_eq!(
+            tick(&mut key_repeat, 60.0),
+            KeyRepeat::MAX_REPEATS_PER_FRAME
+        );
+        assert!(tick(&mut key_repeat, KeyRepeat::MIN_INTERVAL) <= 1);
+        let repeats = tick(&mut key_repeat, KeyRepeat::MIN_INTERVAL * 4.5);
+        assert!((4..=5).contains(&repeats));
+    }
+}
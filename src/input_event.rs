@@ -1,7 +1,7 @@
 use bevy::prelude::KeyCode;
 
 /// Events sent to [`KayakContext`](crate::KayakContext) containing user input data
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum InputEvent {
     /// An event that occurs when the user moves the mouse
     MouseMoved((f32, f32)),
@@ -9,12 +9,28 @@ pub enum InputEvent {
     MouseLeftPress,
     /// An event that occurs when the user releases the left mouse button
     MouseLeftRelease,
+    /// An event that occurs when the user presses the right mouse button
+    MouseRightPress,
     /// An event that occurs when the user scrolls
     Scroll { dx: f32, dy: f32, is_line: bool },
     /// An event that occurs when the user types in a character
     CharEvent { c: char },
     /// An event that occurs when the user presses or releases a key
     Keyboard { key: KeyCode, is_pressed: bool },
+    /// An event that occurs when the user presses the copy shortcut (Ctrl+C)
+    Copy,
+    /// An event that occurs when the user presses the cut shortcut (Ctrl+X)
+    Cut,
+    /// An event that occurs when the user presses the paste shortcut (Ctrl+V)
+    ///
+    /// Contains the text on the clipboard.
+    Paste(String),
+    /// An event that occurs when the text being composed with an input method changes
+    ///
+    /// The text itself is stored in the [`ImeComposition`](crate::input::ImeComposition) resource.
+    ImePreedit,
+    /// An event that occurs when the user finalizes the text composed with an input method
+    ImeCommit(String),
 }
 
 /// The various categories an input event can belong to
@@ -34,10 +50,16 @@ impl InputEvent {
             Self::MouseMoved(..) => InputEventCategory::Mouse,
             Self::MouseLeftPress => InputEventCategory::Mouse,
             Self::MouseLeftRelease => InputEventCategory::Mouse,
+            Self::MouseRightPress => InputEventCategory::Mouse,
             Self::Scroll { .. } => InputEventCategory::Mouse,
             // Keyboard events
             Self::CharEvent { .. } => InputEventCategory::Keyboard,
             Self::Keyboard { .. } => InputEventCategory::Keyboard,
+            Self::Copy => InputEventCategory::Keyboard,
+            Self::Cut => InputEventCategory::Keyboard,
+            Self::Paste(..) => InputEventCategory::Keyboard,
+            Self::ImePreedit => InputEventCategory::Keyboard,
+            Self::ImeCommit(..) => InputEventCategory::Keyboard,
         }
     }
 }
@@ -1,14 +1,16 @@
 use std::collections::hash_map::Iter;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::iter::Rev;
 
 use bevy::{
-    prelude::{Entity, Query},
+    prelude::{Component, Entity, Query, ReflectComponent, Vec2},
     reflect::Reflect,
 };
-use morphorm::Cache;
 pub use morphorm::GeometryChanged;
+use morphorm::{Cache, Hierarchy};
 
 use crate::node::WrappedIndex;
+use crate::tree::{DownwardIterator, Tree};
 
 #[derive(Debug, Reflect, Default, Clone, Copy, PartialEq)]
 pub struct Rect {
@@ -24,8 +26,31 @@ impl Rect {
         (point.0 >= self.posx && point.0 <= self.posx + self.width)
             && (point.1 >= self.posy && point.1 <= self.posy + self.height)
     }
+
+    /// Returns the center of this rect in the world space of a default 2D camera
+    ///
+    /// Such a camera has its origin at the center of the viewport with the y-axis pointing up,
+    /// while layouts start at the top-left corner with the y-axis pointing down. The viewport size
+    /// is in logical pixels, like the rect.
+    pub fn world_center(&self, viewport_size: Vec2) -> Vec2 {
+        Vec2::new(
+            self.posx + self.width / 2.0 - viewport_size.x / 2.0,
+            viewport_size.y / 2.0 - (self.posy + self.height / 2.0),
+        )
+    }
 }
 
+/// The final layout of a widget
+///
+/// This is added to every widget once it has been laid out, and updated whenever its layout
+/// changes. The rect is in logical pixels, before the window's scale factor is applied, and is
+/// relative to the top-left corner of the UI camera's viewport with the y-axis pointing down.
+///
+/// Use [`Rect::world_center`] to place other entities, like sprites, over a widget.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct WidgetLayout(pub Rect);
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Space {
     pub left: f32,
@@ -117,6 +142,66 @@ impl LayoutCache {
     }
 }
 
+/// The widget tree as seen by morphorm
+///
+/// The children of the nodes in `reversed` are iterated back to front, which lays them out in
+/// the opposite direction. When a `root` is given, only that node's subtree is laid out, and the
+/// root is sized against the cached rect of its parent, which isn't laid out again.
+pub(crate) struct LayoutHierarchy<'a> {
+    pub tree: &'a Tree,
+    pub reversed: &'a HashSet<WrappedIndex>,
+    pub root: Option<WrappedIndex>,
+}
+
+impl<'a> Hierarchy<'a> for LayoutHierarchy<'a> {
+    type DownIter = DownwardIterator<'a>;
+    type UpIter = Rev<std::vec::IntoIter<WrappedIndex>>;
+    type Item = WrappedIndex;
+    type ChildIter = std::vec::IntoIter<WrappedIndex>;
+
+    fn up_iter(&'a self) -> Self::UpIter {
+        match self.root {
+            Some(root) => self.tree.flatten_node(root).into_iter().rev(),
+            None => self.tree.up_iter(),
+        }
+    }
+
+    fn down_iter(&'a self) -> Self::DownIter {
+        match self.root {
+            Some(root) => self.tree.down_iter_at(root, true),
+            None => self.tree.down_iter(),
+        }
+    }
+
+    fn child_iter(&'a self, node: WrappedIndex) -> Self::ChildIter {
+        let mut children = self.tree.children.get(&node).cloned().unwrap_or_default();
+        if self.reversed.contains(&node) {
+            children.reverse();
+        }
+        children.into_iter()
+    }
+
+    fn parent(&self, node: WrappedIndex) -> Option<WrappedIndex> {
+        // The root of a partial layout keeps its parent, so its percentages resolve against the
+        // parent's cached size rather than an empty one
+        self.tree.parent(node)
+    }
+
+    fn is_first_child(&self, node: WrappedIndex) -> bool {
+        match self.parent(node) {
+            Some(parent) if self.reversed.contains(&parent) => self.tree.is_last_child(node),
+            _ => self.tree.is_first_child(node),
+        }
+    }
+
+    fn is_last_child(&self, node: WrappedIndex) -> bool {
+        match self.parent(node) {
+            Some(parent) if self.reversed.contains(&parent) => self.tree.is_first_child(node),
+            _ => self.tree.is_last_child(node),
+        }
+    }
+}
+
 pub(crate) struct DataCache<'borrow, 'world, 'state> {
     pub query: &'borrow Query<'world, 'state, &'static crate::node::Node>,
     pub cache: &'borrow mut LayoutCache,
@@ -476,3 +561,179 @@ impl LayoutEvent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use bevy::ecs::system::SystemState;
+    use bevy::prelude::{Query, World};
+
+    use super::{DataCache, LayoutCache, LayoutHierarchy, Rect};
+    use crate::node::{Node, NodeBuilder, WrappedIndex};
+    use crate::styles::{KStyle, LayoutDirection, LayoutType, StyleProp, Units};
+    use crate::tree::Tree;
+
+    fn add_node(
+        world: &mut World,
+        tree: &mut Tree,
+        cache: &mut LayoutCache,
+        styles: KStyle,
+        parent: Option<WrappedIndex>,
+    ) -> WrappedIndex {
+        let node = WrappedIndex(world.spawn_empty().id());
+        world
+            .entity_mut(node.0)
+            .insert(NodeBuilder::new(node, styles).build());
+        tree.add(node, parent);
+        cache.add(node);
+        node
+    }
+
+    fn layout(
+        world: &mut World,
+        tree: &Tree,
+        cache: &mut LayoutCache,
+        reversed: &[WrappedIndex],
+        root: Option<WrappedIndex>,
+    ) {
+        let reversed = reversed.iter().copied().collect::<HashSet<_>>();
+        let hierarchy = LayoutHierarchy {
+            tree,
+            reversed: &reversed,
+            root,
+        };
+        let mut state: SystemState<Query<&Node>> = SystemState::new(world);
+        let query = state.get(world);
+        let mut data_cache = DataCache {
+            query: &query,
+            cache,
+        };
+        morphorm::layout(&mut data_cache, &hierarchy, &query);
+    }
+
+    fn assert_rect(cache: &LayoutCache, node: WrappedIndex, expected: (f32, f32, f32, f32)) {
+        let actual = cache.rect.get(&node).copied().unwrap_or_default();
+        assert_eq!(
+            expected,
+            (actual.posx, actual.posy, actual.width, actual.height)
+        );
+    }
+
+    #[test]
+    fn should_layout_grid() {
+        let mut world = World::new();
+        let mut tree = Tree::default();
+        let mut cache = LayoutCache::default();
+
+        let parent_styles = KStyle {
+            layout_type: LayoutType::Grid.into(),
+            width: Units::Pixels(200.0).into(),
+            height: Units::Pixels(100.0).into(),
+            grid_rows: vec![Units::Stretch(1.0), Units::Stretch(1.0)].into(),
+            grid_cols: vec![Units::Pixels(50.0), Units::Stretch(1.0)].into(),
+            ..Default::default()
+        };
+        let parent = add_node(&mut world, &mut tree, &mut cache, parent_styles, None);
+
+        let children = [(0, 0), (0, 1), (1, 0), (1, 1)].map(|(row, col)| {
+            let child_styles = KStyle {
+                row_index: StyleProp::Value(row),
+                col_index: StyleProp::Value(col),
+                ..Default::default()
+            };
+            add_node(
+                &mut world,
+                &mut tree,
+                &mut cache,
+                child_styles,
+                Some(parent),
+            )
+        });
+
+        layout(&mut world, &tree, &mut cache, &[], None);
+
+        assert_rect(&cache, children[0], (0.0, 0.0, 50.0, 50.0));
+        assert_rect(&cache, children[1], (50.0, 0.0, 150.0, 50.0));
+        assert_rect(&cache, children[2], (0.0, 50.0, 50.0, 50.0));
+        assert_rect(&cache, children[3], (50.0, 50.0, 150.0, 50.0));
+    }
+
+    #[test]
+    fn should_layout_rtl_row() {
+        let mut world = World::new();
+        let mut tree = Tree::default();
+        let mut cache = LayoutCache::default();
+
+        let parent_styles = KStyle {
+            layout_direction: LayoutDirection::Rtl.into(),
+            layout_type: LayoutType::Row.into(),
+            width: Units::Pixels(200.0).into(),
+            height: Units::Pixels(100.0).into(),
+            ..Default::default()
+        };
+        let parent = add_node(&mut world, &mut tree, &mut cache, parent_styles, None);
+
+        let children = [30.0, 50.0].map(|width| {
+            let child_styles = KStyle {
+                width: Units::Pixels(width).into(),
+                height: Units::Pixels(10.0).into(),
+                ..Default::default()
+            };
+            add_node(
+                &mut world,
+                &mut tree,
+                &mut cache,
+                child_styles,
+                Some(parent),
+            )
+        });
+
+        layout(&mut world, &tree, &mut cache, &[parent], None);
+
+        // The first child is placed against the right edge
+        assert_rect(&cache, children[0], (170.0, 0.0, 30.0, 10.0));
+        assert_rect(&cache, children[1], (120.0, 0.0, 50.0, 10.0));
+    }
+
+    #[test]
+    fn should_resolve_partial_layout_root_against_its_parent() {
+        let mut world = World::new();
+        let mut tree = Tree::default();
+        let mut cache = LayoutCache::default();
+
+        let window_styles = KStyle {
+            width: Units::Pixels(400.0).into(),
+            height: Units::Pixels(300.0).into(),
+            ..Default::default()
+        };
+        let window = add_node(&mut world, &mut tree, &mut cache, window_styles, None);
+        let panel_styles = KStyle {
+            width: Units::Pixels(100.0).into(),
+            height: Units::Pixels(100.0).into(),
+            min_width: Units::Percentage(60.0).into(),
+            ..Default::default()
+        };
+        let panel = add_node(
+            &mut world,
+            &mut tree,
+            &mut cache,
+            panel_styles,
+            Some(window),
+        );
+        let child_styles = KStyle {
+            width: Units::Percentage(50.0).into(),
+            height: Units::Pixels(10.0).into(),
+            ..Default::default()
+        };
+        let child = add_node(&mut world, &mut tree, &mut cache, child_styles, Some(panel));
+
+        layout(&mut world, &tree, &mut cache, &[], None);
+        assert_rect(&cache, child, (0.0, 0.0, 120.0, 10.0));
+
+        // Laying out only the panel's subtree still sizes the panel against the window
+        layout(&mut world, &tree, &mut cache, &[], Some(panel));
+        assert_rect(&cache, panel, (0.0, 0.0, 240.0, 100.0));
+        assert_rect(&cache, child, (0.0, 0.0, 120.0, 10.0));
+    }
+}
@@ -3,10 +3,14 @@
 mod calculate_nodes;
 mod camera;
 mod children;
+mod clipboard;
 mod clone_component;
 mod context;
 mod context_entities;
 mod cursor;
+#[cfg(feature = "debug_overlay")]
+pub mod debug;
+pub mod easing;
 mod event;
 mod event_dispatcher;
 mod focus_tree;
@@ -19,18 +23,23 @@ mod node;
 mod on_change;
 mod on_event;
 mod on_layout;
+mod on_measure;
+#[cfg(feature = "persistence")]
+pub mod persistence;
 pub(crate) mod render;
 mod render_primitive;
+mod state_styles;
 mod styles;
 mod tree;
 mod widget;
 mod widget_context;
+mod widget_events;
 mod widget_state;
 pub mod widgets;
 mod window_size;
 
 use context::KayakRootContext;
-pub use window_size::WindowSize;
+pub use window_size::{KayakUiScale, WindowSize};
 
 pub use camera::*;
 
@@ -40,25 +49,34 @@ pub const DEFAULT_FONT: &str = "Kayak-Default";
 pub mod prelude {
     pub use crate::camera::*;
     pub use crate::children::KChildren;
+    pub use crate::clipboard::Clipboard;
     pub use crate::clone_component::PreviousWidget;
     pub use crate::context::*;
     pub use crate::cursor::*;
+    pub use crate::easing::Tween;
     pub use crate::event::*;
-    pub use crate::event_dispatcher::{EventDispatcher, EventDispatcherContext};
-    pub use crate::focus_tree::{FocusTree, Focusable};
+    pub use crate::event_dispatcher::{EventDispatcher, EventDispatcherContext, MultiClick};
+    pub use crate::focus_tree::{FocusTrap, FocusTree, Focusable};
+    pub use crate::input::{ConsumedInput, ImeComposition, KayakInputSet, KeyRepeat, PrimaryTouch};
     pub use crate::input_event::*;
     pub use crate::keyboard_event::*;
     pub use crate::layout::*;
-    pub use crate::node::DirtyNode;
+    pub use crate::node::{DirtyNode, ResolvedStyles};
     pub use crate::on_change::OnChange;
     pub use crate::on_event::OnEvent;
     pub use crate::on_layout::OnLayout;
+    pub use crate::on_measure::OnMeasure;
+    pub use crate::render::debug_clip::DebugClips;
+    pub use crate::render::debug_grid::{DebugGrid, GridColumns, GuideLine};
     pub use crate::render::draw_ui_graph;
     pub use crate::render::font::FontMapping;
+    pub use crate::render::{OpacityLayerConfig, MAX_OPACITY_LAYERS};
+    pub use crate::state_styles::StateStyles;
     pub use crate::styles::*;
     pub use crate::tree::*;
     pub use crate::widget::*;
     pub use crate::widget_context::*;
+    pub use crate::widget_events::WidgetEvents;
     pub use crate::widget_state::*;
     pub use crate::widgets;
     pub mod kayak_font {
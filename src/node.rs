@@ -1,6 +1,12 @@
-use bevy::prelude::{Component, Entity, Query, Reflect, ReflectComponent};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{Color, Component, Entity, Query, Reflect, ReflectComponent},
+};
 
-use crate::styles::{KStyle, StyleProp};
+use crate::styles::{
+    AlignItems, Dock, JustifyContent, KPositionType, KStyle, LayoutDirection, LayoutType,
+    StyleProp, Units,
+};
 
 #[derive(Component, Debug, Clone, Copy)]
 pub struct DirtyNode;
@@ -21,6 +27,8 @@ pub struct Node {
     pub z: f32,
     pub old_z: f32,
     pub opacity: f32,
+    /// The color this node and its children are multiplied with, if any
+    pub tint: Option<Color>,
 }
 
 impl Default for Node {
@@ -33,6 +41,209 @@ impl Default for Node {
             z: Default::default(),
             old_z: Default::default(),
             opacity: 1.0,
+            tint: None,
+        }
+    }
+}
+
+/// A system param used to read the fully resolved styles of widgets
+///
+/// These are the styles after unset values were filled in and inherited values were taken from
+/// the parent. They're updated whenever nodes are calculated, so within a render system they
+/// reflect the previous layout pass. A widget that was just spawned won't have any yet.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// fn print_color(In(entity): In<Entity>, resolved_styles: ResolvedStyles) {
+///     if let Some(styles) = resolved_styles.get(entity) {
+///         println!("Text color: {:?}", styles.color.resolve());
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct ResolvedStyles<'w, 's> {
+    node_query: Query<'w, 's, &'static Node>,
+}
+
+impl<'w, 's> ResolvedStyles<'w, 's> {
+    /// Returns the resolved styles of the given widget, if it has been calculated
+    pub fn get(&self, entity: Entity) -> Option<KStyle> {
+        self.node_query
+            .get(entity)
+            .ok()
+            .map(|node| node.resolved_styles.clone())
+    }
+}
+
+impl Node {
+    /// Returns true if the given axis is the main axis of this node's layout
+    ///
+    /// Returns `None` for layouts that don't support alignment
+    fn is_main_axis(&self, horizontal: bool) -> Option<bool> {
+        match self.resolved_styles.layout_type {
+            StyleProp::Value(LayoutType::Row) => Some(horizontal),
+            StyleProp::Value(LayoutType::Grid) => None,
+            _ => Some(!horizontal),
+        }
+    }
+
+    /// The child space required on one edge of this node by its
+    /// [`justify_content`](KStyle::justify_content) or [`align_items`](KStyle::align_items) styles
+    fn aligned_child_space(
+        &self,
+        horizontal: bool,
+        start: bool,
+        store: &Query<&Node>,
+    ) -> Option<morphorm::Units> {
+        // Right-to-left widgets start on the right
+        let rtl = horizontal
+            && matches!(
+                self.resolved_styles.layout_direction,
+                StyleProp::Value(LayoutDirection::Rtl)
+            );
+        let start = start != rtl;
+        if self.is_main_axis(horizontal)? {
+            let justify_content = match self.resolved_styles.justify_content {
+                StyleProp::Value(justify_content) => Some(justify_content),
+                _ if rtl && !self.has_stretched_child(store) => Some(JustifyContent::Start),
+                _ => None,
+            };
+            match justify_content {
+                Some(JustifyContent::Start) if !start => Some(morphorm::Units::Stretch(1.0)),
+                Some(JustifyContent::End) if start => Some(morphorm::Units::Stretch(1.0)),
+                Some(JustifyContent::Center) | Some(JustifyContent::SpaceAround) => {
+                    Some(morphorm::Units::Stretch(1.0))
+                }
+                _ => None,
+            }
+        } else {
+            match self.resolved_styles.align_items {
+                StyleProp::Value(AlignItems::Start) if !start => {
+                    Some(morphorm::Units::Stretch(1.0))
+                }
+                StyleProp::Value(AlignItems::End) if start => Some(morphorm::Units::Stretch(1.0)),
+                StyleProp::Value(AlignItems::Center) => Some(morphorm::Units::Stretch(1.0)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Returns true if a child of this node stretches horizontally
+    ///
+    /// Right-to-left rows are only packed against their right edge when none of their children
+    /// take up the free space, since those children would share it with the packing space.
+    fn has_stretched_child(&self, store: &Query<&Node>) -> bool {
+        self.children.iter().any(|child| {
+            store.get(child.0).map_or(false, |child| {
+                let styles = &child.resolved_styles;
+                // Widgets stretch to fill their parent unless they are given a width
+                matches!(
+                    styles.width,
+                    StyleProp::Default | StyleProp::Value(Units::Stretch(..))
+                ) || matches!(styles.left, StyleProp::Value(Units::Stretch(..)))
+                    || matches!(styles.right, StyleProp::Value(Units::Stretch(..)))
+            })
+        })
+    }
+
+    /// The space required on one edge of this node by its [`anchor`](KStyle::anchor) style
+    fn anchored_space(&self, horizontal: bool, start: bool) -> Option<morphorm::Units> {
+        if !matches!(
+            self.resolved_styles.position_type,
+            StyleProp::Value(KPositionType::SelfDirected)
+        ) {
+            return None;
+        }
+        if let StyleProp::Value(anchor) = self.resolved_styles.anchor {
+            let stretches = if start {
+                anchor.stretches_start(horizontal)
+            } else {
+                anchor.stretches_end(horizontal)
+            };
+            if stretches {
+                return Some(morphorm::Units::Stretch(1.0));
+            }
+        }
+        None
+    }
+
+    /// The space on one edge of this node when it's [docked](KStyle::dock)
+    ///
+    /// The docked edge is flush with the parent and the opposite edge stretches. Along the
+    /// docked edge, top and bottom docks span the full parent while left and right docks fall
+    /// back to the parent's child space, which makes room for the top and bottom docks.
+    fn docked_space(&self, horizontal: bool, start: bool) -> Option<morphorm::Units> {
+        let dock = self.resolved_styles.dock.resolve_as_option()?;
+        let (docked_start, docked_end) = match (dock, horizontal) {
+            (Dock::Top, false) | (Dock::Left, true) => (true, false),
+            (Dock::Bottom, false) | (Dock::Right, true) => (false, true),
+            (Dock::Top | Dock::Bottom, true) => return Some(morphorm::Units::Pixels(0.0)),
+            (Dock::Left | Dock::Right, false) => return Some(morphorm::Units::Auto),
+        };
+        if (start && docked_start) || (!start && docked_end) {
+            Some(morphorm::Units::Pixels(0.0))
+        } else {
+            Some(morphorm::Units::Stretch(1.0))
+        }
+    }
+
+    /// The size of this node along the given axis when it's [docked](KStyle::dock)
+    fn docked_size(&self, horizontal: bool) -> Option<morphorm::Units> {
+        match (self.resolved_styles.dock.resolve_as_option()?, horizontal) {
+            (Dock::Top | Dock::Bottom, true) | (Dock::Left | Dock::Right, false) => {
+                Some(morphorm::Units::Stretch(1.0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Adds the size of the children docked to the given edge to this node's child space
+    fn with_dock_inset(
+        &self,
+        child_space: morphorm::Units,
+        dock: Dock,
+        store: &Query<&Node>,
+    ) -> morphorm::Units {
+        let inset = self
+            .children
+            .iter()
+            .filter_map(|child| store.get(child.0).ok())
+            .filter(|child| child.resolved_styles.dock == StyleProp::Value(dock))
+            .map(|child| {
+                let size = match dock {
+                    Dock::Top | Dock::Bottom => &child.resolved_styles.height,
+                    Dock::Left | Dock::Right => &child.resolved_styles.width,
+                };
+                match size {
+                    StyleProp::Value(Units::Pixels(size)) => *size,
+                    _ => 0.0,
+                }
+            })
+            .sum::<f32>();
+        if inset == 0.0 {
+            return child_space;
+        }
+        match child_space {
+            morphorm::Units::Pixels(space) => morphorm::Units::Pixels(space + inset),
+            morphorm::Units::Auto => morphorm::Units::Pixels(inset),
+            space => space,
+        }
+    }
+
+    /// The space required between children of this node by its
+    /// [`justify_content`](KStyle::justify_content) style
+    fn aligned_space_between(&self, horizontal: bool) -> Option<morphorm::Units> {
+        if !self.is_main_axis(horizontal)? {
+            return None;
+        }
+        match self.resolved_styles.justify_content {
+            StyleProp::Value(JustifyContent::SpaceBetween) => Some(morphorm::Units::Stretch(1.0)),
+            // Twice the space on the edges, so each child has an equal amount on either side
+            StyleProp::Value(JustifyContent::SpaceAround) => Some(morphorm::Units::Stretch(2.0)),
+            _ => None,
         }
     }
 }
@@ -54,6 +265,7 @@ impl NodeBuilder {
                 z: 0.0,
                 old_z: 0.0,
                 opacity: 1.0,
+                tint: None,
             },
         }
     }
@@ -69,6 +281,7 @@ impl NodeBuilder {
                 z: 0.0,
                 old_z: 0.0,
                 opacity: 1.0,
+                tint: None,
             },
         }
     }
@@ -97,6 +310,12 @@ impl NodeBuilder {
         self
     }
 
+    /// Sets the tint of the node being built
+    pub fn with_tint(mut self, tint: Option<Color>) -> Self {
+        self.node.tint = tint;
+        self
+    }
+
     /// Completes and builds the actual [`Node`]
     pub fn build(self) -> Node {
         self.node
@@ -122,6 +341,9 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn position_type(&self, store: &'_ Self::Data) -> Option<morphorm::PositionType> {
         if let Ok(node) = store.get(self.0) {
+            if node.resolved_styles.dock.resolve_as_option().is_some() {
+                return Some(morphorm::PositionType::SelfDirected);
+            }
             return match node.resolved_styles.position_type {
                 StyleProp::Default => Some(morphorm::PositionType::default()),
                 StyleProp::Value(prop) => Some(prop.into()),
@@ -133,6 +355,9 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn width(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
+            if let Some(size) = node.docked_size(true) {
+                return Some(size);
+            }
             return match node.resolved_styles.width {
                 StyleProp::Default => Some(morphorm::Units::Stretch(1.0)),
                 StyleProp::Value(prop) => Some(prop.into()),
@@ -144,6 +369,9 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn height(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
+            if let Some(size) = node.docked_size(false) {
+                return Some(size);
+            }
             return match node.resolved_styles.height {
                 StyleProp::Default => Some(morphorm::Units::Stretch(1.0)),
                 StyleProp::Value(prop) => Some(prop.into()),
@@ -199,6 +427,12 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn left(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
+            if let Some(space) = node.docked_space(true, true) {
+                return Some(space);
+            }
+            if let Some(space) = node.anchored_space(true, true) {
+                return Some(space);
+            }
             return match node.resolved_styles.left {
                 StyleProp::Default => match node.resolved_styles.offset {
                     StyleProp::Default => Some(morphorm::Units::Auto),
@@ -214,6 +448,12 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn right(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
+            if let Some(space) = node.docked_space(true, false) {
+                return Some(space);
+            }
+            if let Some(space) = node.anchored_space(true, false) {
+                return Some(space);
+            }
             return match node.resolved_styles.right {
                 StyleProp::Default => match node.resolved_styles.offset {
                     StyleProp::Default => Some(morphorm::Units::Auto),
@@ -229,6 +469,12 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn top(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
+            if let Some(space) = node.docked_space(false, true) {
+                return Some(space);
+            }
+            if let Some(space) = node.anchored_space(false, true) {
+                return Some(space);
+            }
             return match node.resolved_styles.top {
                 StyleProp::Default => match node.resolved_styles.offset {
                     StyleProp::Default => Some(morphorm::Units::Auto),
@@ -244,6 +490,12 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn bottom(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
+            if let Some(space) = node.docked_space(false, false) {
+                return Some(space);
+            }
+            if let Some(space) = node.anchored_space(false, false) {
+                return Some(space);
+            }
             return match node.resolved_styles.bottom {
                 StyleProp::Default => match node.resolved_styles.offset {
                     StyleProp::Default => Some(morphorm::Units::Auto),
@@ -291,7 +543,10 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn child_left(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
-            return match node.resolved_styles.padding_left {
+            if let Some(space) = node.aligned_child_space(true, true, store) {
+                return Some(space);
+            }
+            let child_space = match node.resolved_styles.padding_left {
                 StyleProp::Default => match node.resolved_styles.padding {
                     StyleProp::Default => Some(morphorm::Units::Auto),
                     StyleProp::Value(prop) => Some(prop.left.into()),
@@ -300,13 +555,17 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
                 StyleProp::Value(prop) => Some(prop.into()),
                 _ => Some(morphorm::Units::Auto),
             };
+            return child_space.map(|space| node.with_dock_inset(space, Dock::Left, store));
         }
         Some(morphorm::Units::Auto)
     }
 
     fn child_right(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
-            return match node.resolved_styles.padding_right {
+            if let Some(space) = node.aligned_child_space(true, false, store) {
+                return Some(space);
+            }
+            let child_space = match node.resolved_styles.padding_right {
                 StyleProp::Default => match node.resolved_styles.padding {
                     StyleProp::Default => Some(morphorm::Units::Auto),
                     StyleProp::Value(prop) => Some(prop.right.into()),
@@ -315,13 +574,17 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
                 StyleProp::Value(prop) => Some(prop.into()),
                 _ => Some(morphorm::Units::Auto),
             };
+            return child_space.map(|space| node.with_dock_inset(space, Dock::Right, store));
         }
         Some(morphorm::Units::Auto)
     }
 
     fn child_top(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
-            return match node.resolved_styles.padding_top {
+            if let Some(space) = node.aligned_child_space(false, true, store) {
+                return Some(space);
+            }
+            let child_space = match node.resolved_styles.padding_top {
                 StyleProp::Default => match node.resolved_styles.padding {
                     StyleProp::Default => Some(morphorm::Units::Auto),
                     StyleProp::Value(prop) => Some(prop.top.into()),
@@ -330,13 +593,17 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
                 StyleProp::Value(prop) => Some(prop.into()),
                 _ => Some(morphorm::Units::Auto),
             };
+            return child_space.map(|space| node.with_dock_inset(space, Dock::Top, store));
         }
         Some(morphorm::Units::Auto)
     }
 
     fn child_bottom(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
-            return match node.resolved_styles.padding_bottom {
+            if let Some(space) = node.aligned_child_space(false, false, store) {
+                return Some(space);
+            }
+            let child_space = match node.resolved_styles.padding_bottom {
                 StyleProp::Default => match node.resolved_styles.padding {
                     StyleProp::Default => Some(morphorm::Units::Auto),
                     StyleProp::Value(prop) => Some(prop.bottom.into()),
@@ -345,12 +612,16 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
                 StyleProp::Value(prop) => Some(prop.into()),
                 _ => Some(morphorm::Units::Auto),
             };
+            return child_space.map(|space| node.with_dock_inset(space, Dock::Bottom, store));
         }
         Some(morphorm::Units::Auto)
     }
 
     fn row_between(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
+            if let Some(space) = node.aligned_space_between(false) {
+                return Some(space);
+            }
             return match node.resolved_styles.row_between {
                 StyleProp::Default => Some(morphorm::Units::Auto),
                 StyleProp::Value(prop) => Some(prop.into()),
@@ -362,6 +633,9 @@ impl<'a> morphorm::Node<'a> for WrappedIndex {
 
     fn col_between(&self, store: &'_ Self::Data) -> Option<morphorm::Units> {
         if let Ok(node) = store.get(self.0) {
+            if let Some(space) = node.aligned_space_between(true) {
+                return Some(space);
+            }
             return match node.resolved_styles.col_between {
                 StyleProp::Default => Some(morphorm::Units::Auto),
                 StyleProp::Value(prop) => Some(prop.into()),
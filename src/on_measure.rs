@@ -0,0 +1,62 @@
+use bevy::prelude::{Component, Vec2};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// A container for a function that measures the intrinsic size of a widget
+///
+/// This is called while calculating nodes, the same way text is measured. The function is
+/// given the space available within the parent (in pixels) and should return the desired
+/// size of the widget. The returned size is only applied to a `width` or `height` that is
+/// not otherwise set.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// // A square canvas that fills as much of the available width as it can.
+/// let on_measure = OnMeasure::new(|available: Vec2| {
+///     let side = available.x.min(256.0);
+///     Vec2::splat(side)
+/// });
+/// ```
+#[derive(Component, Clone)]
+pub struct OnMeasure {
+    measure: Arc<dyn Fn(Vec2) -> Vec2 + Send + Sync>,
+}
+
+impl Default for OnMeasure {
+    fn default() -> Self {
+        Self::new(|_| Vec2::ZERO)
+    }
+}
+
+impl OnMeasure {
+    /// Create a new measure handler
+    ///
+    /// The handler should be a closure that takes the following arguments:
+    /// 1. The size available within the parent
+    pub fn new(measure: impl Fn(Vec2) -> Vec2 + Send + Sync + 'static) -> Self {
+        Self {
+            measure: Arc::new(measure),
+        }
+    }
+
+    /// Call the measure handler, returning the desired size
+    pub fn measure(&self, available: Vec2) -> Vec2 {
+        (self.measure)(available)
+    }
+}
+
+impl Debug for OnMeasure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OnMeasure").finish()
+    }
+}
+
+impl PartialEq for OnMeasure {
+    fn eq(&self, _: &Self) -> bool {
+        // Never prevent "==" for being true because of this struct
+        true
+    }
+}
@@ -0,0 +1,608 @@
+//! Saving and restoring a UI's widget tree along with its props and state
+//!
+//! A [`UiSnapshot`] stores every widget in a [`KayakRootContext`]'s tree with the components
+//! on the widget and its state entity that are registered for reflection with
+//! `#[reflect(Component)]`. Props and state that aren't registered, along with event handlers,
+//! are rebuilt by the widgets' render systems once the snapshot is restored.
+//!
+//! Snapshots can be written to and read from [RON](https://github.com/ron-rs/ron). This module
+//! requires the `persistence` feature.
+//!
+//! ```rust,ignore
+//! fn save_ui(world: &mut World) {
+//!     let context_entity = world.query_filtered::<Entity, With<KayakRootContext>>().single(world);
+//!     let registry = world.resource::<AppTypeRegistry>().clone();
+//!     if let Some(snapshot) = UiSnapshot::capture(world, context_entity) {
+//!         let text = snapshot.to_ron(&registry.read()).unwrap();
+//!         std::fs::write("ui.ron", text).unwrap();
+//!     }
+//! }
+//! ```
+
+use std::{any::TypeId, fmt};
+
+use bevy::{
+    ecs::{
+        entity::EntityMap,
+        reflect::{ReflectComponent, ReflectMapEntities},
+    },
+    hierarchy::{BuildWorldChildren, Children, Parent},
+    prelude::{AppTypeRegistry, Entity, World},
+    reflect::{
+        serde::{ReflectSerializer, UntypedReflectDeserializer},
+        Reflect, TypeRegistryInternal,
+    },
+    utils::HashSet,
+};
+use morphorm::Hierarchy;
+use serde::{
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeStruct},
+    Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    context::{KayakRootContext, Mounted},
+    layout::WidgetLayout,
+    node::Node,
+    styles::ComputedStyles,
+};
+
+/// A widget saved in a [`UiSnapshot`]
+#[derive(Debug)]
+pub struct SnapshotNode {
+    /// The widget entity when the snapshot was captured
+    ///
+    /// Entities referenced by the saved components are mapped from these to the respawned
+    /// widgets when the snapshot is restored.
+    pub entity: Entity,
+    /// The index of the widget's parent within [`UiSnapshot::nodes`]
+    pub parent: Option<usize>,
+    /// The reflected components of the widget entity
+    pub components: Vec<Box<dyn Reflect>>,
+    /// The reflected components of the widget's state entity
+    pub state: Vec<Box<dyn Reflect>>,
+}
+
+/// A saved copy of a UI's widget tree
+///
+/// Nodes are stored in tree order, so the root widget comes first and every widget comes
+/// after its parent.
+#[derive(Debug, Default)]
+pub struct UiSnapshot {
+    pub nodes: Vec<SnapshotNode>,
+}
+
+impl UiSnapshot {
+    /// Saves the widget tree of the [`KayakRootContext`] on the given entity
+    ///
+    /// Returns `None` if the entity has no context.
+    pub fn capture(world: &World, context_entity: Entity) -> Option<Self> {
+        let context = world.get::<KayakRootContext>(context_entity)?;
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let tree = context.tree.read().ok()?;
+
+        let mut nodes = Vec::new();
+        let mut indices = bevy::utils::HashMap::new();
+        for index in tree.down_iter() {
+            let Some(entity_ref) = world.get_entity(index.0) else {
+                continue;
+            };
+            let parent = tree
+                .get_parent(index)
+                .and_then(|parent| indices.get(&parent).copied());
+            let state = context
+                .widget_state
+                .get(index.0)
+                .map(|state_entity| reflect_components(world, &registry, state_entity))
+                .unwrap_or_default();
+            indices.insert(index, nodes.len());
+            nodes.push(SnapshotNode {
+                entity: entity_ref.id(),
+                parent,
+                components: reflect_components(world, &registry, index.0),
+                state,
+            });
+        }
+
+        Some(Self { nodes })
+    }
+
+    /// Rebuilds the saved widget tree in the [`KayakRootContext`] on the given entity
+    ///
+    /// Every widget is spawned again, with its state entity if it had one, and added to the
+    /// context's tree. The context must not have a root widget yet. Returns the new root widget
+    /// entity, or `None` if the snapshot is empty or the context couldn't be restored into.
+    pub fn restore(&self, world: &mut World, context_entity: Entity) -> Option<Entity> {
+        let root = self.nodes.first()?;
+        if root.parent.is_some() {
+            return None;
+        }
+
+        let mut context = {
+            let mut context = world.get_mut::<KayakRootContext>(context_entity)?;
+            if context
+                .tree
+                .read()
+                .map(|tree| tree.root_node.is_some())
+                .unwrap_or(true)
+            {
+                log::warn!("Can't restore a UI snapshot into a context that has a root widget!");
+                return None;
+            }
+            std::mem::take(&mut *context)
+        };
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let mut entity_map = EntityMap::default();
+        let mut spawned = Vec::with_capacity(self.nodes.len());
+        let mut mapped_types = HashSet::new();
+        for node in self.nodes.iter() {
+            let widget_entity = world.spawn_empty().id();
+            entity_map.insert(node.entity, widget_entity);
+            spawned.push(widget_entity);
+            insert_components(
+                world,
+                &registry,
+                widget_entity,
+                &node.components,
+                &mut mapped_types,
+            );
+
+            if !node.state.is_empty() {
+                let state_entity = world.spawn_empty().id();
+                world.entity_mut(widget_entity).add_child(state_entity);
+                insert_components(
+                    world,
+                    &registry,
+                    state_entity,
+                    &node.state,
+                    &mut mapped_types,
+                );
+                context.widget_state.insert(widget_entity, state_entity);
+                spawned.push(state_entity);
+            }
+
+            let parent = node.parent.and_then(|parent| {
+                self.nodes
+                    .get(parent)
+                    .and_then(|parent| entity_map.get(parent.entity))
+            });
+            if let Some(parent) = parent {
+                world.entity_mut(parent).add_child(widget_entity);
+            }
+            context.add_widget(parent, widget_entity);
+        }
+
+        // Point entities held by the saved components at the respawned widgets
+        for type_id in mapped_types {
+            if let Some(map_entities) = registry
+                .get(type_id)
+                .and_then(|registration| registration.data::<ReflectMapEntities>())
+            {
+                map_entities.map_entities(world, &mut entity_map, &spawned);
+            }
+        }
+
+        let root = entity_map.get(root.entity);
+        world.entity_mut(context_entity).insert(context);
+        root
+    }
+
+    /// Writes the snapshot to a RON document
+    pub fn to_ron(&self, registry: &TypeRegistryInternal) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(
+            &UiSnapshotSerializer {
+                snapshot: self,
+                registry,
+            },
+            ron::ser::PrettyConfig::default(),
+        )
+    }
+
+    /// Reads a snapshot from a RON document written by [`Self::to_ron`]
+    pub fn from_ron(
+        text: &str,
+        registry: &TypeRegistryInternal,
+    ) -> Result<Self, ron::error::SpannedError> {
+        let mut deserializer = ron::Deserializer::from_str(text)?;
+        UiSnapshotDeserializer { registry }
+            .deserialize(&mut deserializer)
+            .map_err(|error| deserializer.span_error(error))
+    }
+}
+
+/// Returns true for components that are rebuilt when the widget tree is, and so aren't saved
+fn is_rebuilt(type_id: TypeId) -> bool {
+    type_id == TypeId::of::<Parent>()
+        || type_id == TypeId::of::<Children>()
+        || type_id == TypeId::of::<Mounted>()
+        || type_id == TypeId::of::<Node>()
+        || type_id == TypeId::of::<WidgetLayout>()
+        || type_id == TypeId::of::<ComputedStyles>()
+}
+
+/// Copies the reflected components of an entity
+fn reflect_components(
+    world: &World,
+    registry: &TypeRegistryInternal,
+    entity: Entity,
+) -> Vec<Box<dyn Reflect>> {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+        .filter(|type_id| !is_rebuilt(*type_id))
+        .filter_map(|type_id| registry.get(type_id)?.data::<ReflectComponent>())
+        .filter_map(|reflect_component| reflect_component.reflect(entity_ref))
+        .map(|component| component.clone_value())
+        .collect()
+}
+
+/// Inserts reflected components on an entity, noting their types
+fn insert_components(
+    world: &mut World,
+    registry: &TypeRegistryInternal,
+    entity: Entity,
+    components: &[Box<dyn Reflect>],
+    inserted_types: &mut HashSet<TypeId>,
+) {
+    for component in components.iter() {
+        let Some(registration) = registry.get_with_name(component.type_name()) else {
+            log::warn!(
+                "Skipped restoring {} as it isn't registered!",
+                component.type_name()
+            );
+            continue;
+        };
+        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+            log::warn!(
+                "Skipped restoring {} as it doesn't reflect Component!",
+                component.type_name()
+            );
+            continue;
+        };
+        reflect_component.insert(&mut world.entity_mut(entity), &**component);
+        inserted_types.insert(registration.type_id());
+    }
+}
+
+struct UiSnapshotSerializer<'a> {
+    snapshot: &'a UiSnapshot,
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a> Serialize for UiSnapshotSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("UiSnapshot", 1)?;
+        state.serialize_field(
+            "nodes",
+            &NodesSerializer {
+                nodes: &self.snapshot.nodes,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct NodesSerializer<'a> {
+    nodes: &'a [SnapshotNode],
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a> Serialize for NodesSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.nodes.len()))?;
+        for node in self.nodes.iter() {
+            seq.serialize_element(&NodeSerializer {
+                node,
+                registry: self.registry,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct NodeSerializer<'a> {
+    node: &'a SnapshotNode,
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a> Serialize for NodeSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("SnapshotNode", 4)?;
+        state.serialize_field("entity", &self.node.entity.to_bits())?;
+        state.serialize_field("parent", &self.node.parent)?;
+        state.serialize_field(
+            "components",
+            &ComponentsSerializer {
+                components: &self.node.components,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            "state",
+            &ComponentsSerializer {
+                components: &self.node.state,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct ComponentsSerializer<'a> {
+    components: &'a [Box<dyn Reflect>],
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a> Serialize for ComponentsSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.components.len()))?;
+        for component in self.components.iter() {
+            seq.serialize_element(&ReflectSerializer::new(&**component, self.registry))?;
+        }
+        seq.end()
+    }
+}
+
+struct UiSnapshotDeserializer<'a> {
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for UiSnapshotDeserializer<'a> {
+    type Value = UiSnapshot;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("UiSnapshot", &["nodes"], self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for UiSnapshotDeserializer<'a> {
+    type Value = UiSnapshot;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a UI snapshot")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut nodes = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "nodes" => {
+                    nodes = Some(map.next_value_seed(NodesDeserializer {
+                        registry: self.registry,
+                    })?)
+                }
+                _ => return Err(de::Error::unknown_field(&key, &["nodes"])),
+            }
+        }
+        Ok(UiSnapshot {
+            nodes: nodes.ok_or_else(|| de::Error::missing_field("nodes"))?,
+        })
+    }
+}
+
+struct NodesDeserializer<'a> {
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for NodesDeserializer<'a> {
+    type Value = Vec<SnapshotNode>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for NodesDeserializer<'a> {
+    type Value = Vec<SnapshotNode>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of snapshot nodes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut nodes = Vec::new();
+        while let Some(node) = seq.next_element_seed(NodeDeserializer {
+            registry: self.registry,
+        })? {
+            nodes.push(node);
+        }
+        Ok(nodes)
+    }
+}
+
+const NODE_FIELDS: &[&str] = &["entity", "parent", "components", "state"];
+
+struct NodeDeserializer<'a> {
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for NodeDeserializer<'a> {
+    type Value = SnapshotNode;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("SnapshotNode", NODE_FIELDS, self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for NodeDeserializer<'a> {
+    type Value = SnapshotNode;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a snapshot node")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entity = None;
+        let mut parent = None;
+        let mut components = None;
+        let mut state = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "entity" => entity = Some(Entity::from_bits(map.next_value::<u64>()?)),
+                "parent" => parent = Some(map.next_value::<Option<usize>>()?),
+                "components" => {
+                    components = Some(map.next_value_seed(ComponentsDeserializer {
+                        registry: self.registry,
+                    })?)
+                }
+                "state" => {
+                    state = Some(map.next_value_seed(ComponentsDeserializer {
+                        registry: self.registry,
+                    })?)
+                }
+                _ => return Err(de::Error::unknown_field(&key, NODE_FIELDS)),
+            }
+        }
+        Ok(SnapshotNode {
+            entity: entity.ok_or_else(|| de::Error::missing_field("entity"))?,
+            parent: parent.unwrap_or_default(),
+            components: components.unwrap_or_default(),
+            state: state.unwrap_or_default(),
+        })
+    }
+}
+
+struct ComponentsDeserializer<'a> {
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ComponentsDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ComponentsDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of reflected components")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut components = Vec::new();
+        while let Some(component) =
+            seq.next_element_seed(UntypedReflectDeserializer::new(self.registry))?
+        {
+            components.push(component);
+        }
+        Ok(components)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::UiSnapshot;
+    use crate::{children::KChildren, context::KayakRootContext, node::WrappedIndex};
+
+    #[derive(Component, Reflect, Default, Debug, Clone, PartialEq)]
+    #[reflect(Component)]
+    struct TestProps {
+        label: String,
+    }
+
+    #[derive(Component, Reflect, Default, Debug, Clone, PartialEq)]
+    #[reflect(Component)]
+    struct TestState {
+        count: u32,
+    }
+
+    fn props(label: &str) -> TestProps {
+        TestProps {
+            label: label.to_string(),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_ron() {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Entity>();
+            registry.register::<Vec<Entity>>();
+            registry.register::<KChildren>();
+            registry.register::<TestProps>();
+            registry.register::<TestState>();
+        }
+        world.insert_resource(registry.clone());
+
+        // root
+        // ├── first (with state)
+        // └── second
+        let root = world.spawn(props("root")).id();
+        let first = world.spawn(props("first")).id();
+        let second = world.spawn(props("second")).id();
+        let mut children = KChildren::new();
+        children.add(first);
+        children.add(second);
+        world.entity_mut(root).insert(children);
+        let state = world.spawn(TestState { count: 3 }).id();
+
+        let mut context = KayakRootContext::default();
+        context.widget_state.insert(first, state);
+        context.add_widget(None, root);
+        context.add_widget(Some(root), first);
+        context.add_widget(Some(root), second);
+        let context_entity = world.spawn(context).id();
+
+        let snapshot = UiSnapshot::capture(&world, context_entity).unwrap();
+        let text = snapshot.to_ron(&registry.read()).unwrap();
+        let snapshot = UiSnapshot::from_ron(&text, &registry.read()).unwrap();
+
+        // Restoring into the same world gives every widget a new entity
+        let restored_entity = world.spawn(KayakRootContext::default()).id();
+        let new_root = snapshot.restore(&mut world, restored_entity).unwrap();
+        assert_ne!(new_root, root);
+
+        let context = world.get::<KayakRootContext>(restored_entity).unwrap();
+        let tree = context.tree.read().unwrap();
+        assert_eq!(tree.root_node, Some(WrappedIndex(new_root)));
+        let new_children = tree
+            .children
+            .get(&WrappedIndex(new_root))
+            .unwrap()
+            .iter()
+            .map(|child| child.0)
+            .collect::<Vec<_>>();
+        assert_eq!(new_children.len(), 2);
+        assert!(!new_children.contains(&first) && !new_children.contains(&second));
+        assert!(tree.children.get(&WrappedIndex(new_children[0])).is_none());
+        assert!(tree.children.get(&WrappedIndex(new_children[1])).is_none());
+
+        assert_eq!(world.get::<TestProps>(new_root), Some(&props("root")));
+        assert_eq!(
+            world.get::<TestProps>(new_children[0]),
+            Some(&props("first"))
+        );
+        assert_eq!(
+            world.get::<TestProps>(new_children[1]),
+            Some(&props("second"))
+        );
+
+        // KChildren points at the restored widgets rather than the captured ones
+        let kchildren = world.get::<KChildren>(new_root).unwrap();
+        assert_eq!(kchildren.iter().copied().collect::<Vec<_>>(), new_children);
+
+        let new_state = context.widget_state.get(new_children[0]).unwrap();
+        assert_ne!(new_state, state);
+        assert_eq!(
+            world.get::<TestState>(new_state),
+            Some(&TestState { count: 3 })
+        );
+        assert!(context.widget_state.get(new_children[1]).is_none());
+    }
+}
@@ -0,0 +1,141 @@
+use bevy::{
+    prelude::*,
+    render::{Extract, ExtractSchedule, Render, RenderApp, RenderSet},
+};
+
+use super::{
+    debug_grid::{DEBUG_GRID_Z, DEBUG_RENDER_LAYER},
+    extract::UIExtractedView,
+    unified::pipeline::{queue_quads, ExtractedQuad, ExtractedQuads, UIQuadType},
+};
+
+/// The z-index used for the clip outlines. This keeps them above every widget and the debug grid.
+const DEBUG_CLIP_Z: f32 = DEBUG_GRID_Z + 10.0;
+
+/// Configures an overlay that outlines every clip rect used while rendering the UI.
+///
+/// The overlay is disabled by default. Each distinct clip rect is outlined once, so nested
+/// clips (such as a scroll box inside a clipped panel) show up as nested outlines.
+///
+/// Usage:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// fn toggle_clip_outlines(mut debug_clips: ResMut<DebugClips>) {
+///     debug_clips.enabled = !debug_clips.enabled;
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DebugClips {
+    /// Whether or not the outlines are drawn
+    pub enabled: bool,
+    /// The color of the outlines
+    pub color: Color,
+    /// The width of the outlines in logical pixels
+    pub width: f32,
+}
+
+impl Default for DebugClips {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Color::rgba(1.0, 0.5, 0.0, 0.9),
+            width: 1.0,
+        }
+    }
+}
+
+pub struct DebugClipPlugin;
+
+impl Plugin for DebugClipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugClips>();
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .add_systems(ExtractSchedule, extract_debug_clips)
+            .add_systems(
+                Render,
+                queue_debug_clips
+                    .in_set(RenderSet::Queue)
+                    .before(queue_quads),
+            );
+    }
+}
+
+fn extract_debug_clips(mut commands: Commands, debug_clips: Extract<Res<DebugClips>>) {
+    commands.insert_resource(debug_clips.clone());
+}
+
+fn queue_debug_clips(
+    debug_clips: Option<Res<DebugClips>>,
+    views: Query<(Entity, &UIExtractedView)>,
+    mut extracted_quads: ResMut<ExtractedQuads>,
+) {
+    let Some(debug_clips) = debug_clips else {
+        return;
+    };
+    if !debug_clips.enabled {
+        return;
+    }
+    // Clip rects are in physical pixels. The same clip is pushed again between siblings,
+    // so only keep the distinct ones.
+    let mut clips: Vec<(Entity, Rect)> = Vec::new();
+    for quad in extracted_quads.quads.iter() {
+        if quad.quad_type != UIQuadType::Clip || quad.render_layer == DEBUG_RENDER_LAYER {
+            continue;
+        }
+        if !clips
+            .iter()
+            .any(|(camera, rect)| *camera == quad.camera_entity && *rect == quad.rect)
+        {
+            clips.push((quad.camera_entity, quad.rect));
+        }
+    }
+
+    // Reset the clip so the outlines are never scissored themselves.
+    for (camera_entity, view) in views.iter() {
+        extracted_quads.quads.push(ExtractedQuad {
+            camera_entity,
+            rect: Rect {
+                min: Vec2::ZERO,
+                max: Vec2::new(view.viewport.z as f32, view.viewport.w as f32),
+            },
+            z_index: DEBUG_CLIP_Z,
+            render_layer: DEBUG_RENDER_LAYER,
+            quad_type: UIQuadType::Clip,
+            ..Default::default()
+        });
+    }
+
+    for (camera_entity, rect) in clips {
+        let dpi = views
+            .get(camera_entity)
+            .map(|(_, view)| view.scale_factor)
+            .unwrap_or(1.0);
+        let width = debug_clips.width.max(1.0 / dpi);
+        let min = rect.min / dpi;
+        let max = rect.max / dpi;
+        let edges = [
+            // Top
+            (min, Vec2::new(max.x, min.y + width)),
+            // Bottom
+            (Vec2::new(min.x, max.y - width), max),
+            // Left
+            (min, Vec2::new(min.x + width, max.y)),
+            // Right
+            (Vec2::new(max.x - width, min.y), max),
+        ];
+        for (min, max) in edges {
+            extracted_quads.quads.push(ExtractedQuad {
+                camera_entity,
+                rect: Rect { min, max },
+                color: debug_clips.color,
+                z_index: DEBUG_CLIP_Z + 1.0,
+                render_layer: DEBUG_RENDER_LAYER,
+                quad_type: UIQuadType::Quad,
+                ..Default::default()
+            });
+        }
+    }
+}
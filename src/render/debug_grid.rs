@@ -0,0 +1,212 @@
+use bevy::{
+    prelude::*,
+    render::{Extract, ExtractSchedule, Render, RenderApp, RenderSet},
+};
+
+use super::{
+    extract::UIExtractedView,
+    unified::pipeline::{queue_quads, ExtractedQuad, ExtractedQuads, UIQuadType},
+};
+
+/// The z-index used for the debug grid overlay. This keeps it above every widget.
+pub(crate) const DEBUG_GRID_Z: f32 = 1_000_000.0;
+
+/// The render layer used for debug overlays. This keeps them above every render layer.
+pub(crate) const DEBUG_RENDER_LAYER: i32 = i32::MAX;
+
+/// Configures an overlay that draws a pixel grid and layout guides on top of the UI.
+///
+/// The overlay is disabled by default. Lines are snapped to device pixels so they line up
+/// exactly with what gets rendered on screen.
+///
+/// Usage:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// fn toggle_grid(mut grid: ResMut<DebugGrid>) {
+///     grid.enabled = !grid.enabled;
+///     grid.columns = Some(GridColumns {
+///         count: 12,
+///         gutter: 16.0,
+///         margin: 32.0,
+///     });
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DebugGrid {
+    /// Whether or not the overlay is drawn
+    pub enabled: bool,
+    /// The spacing between grid lines in logical pixels
+    ///
+    /// Set to `None` to only draw columns and guides.
+    pub cell_size: Option<f32>,
+    /// The color of the grid lines
+    pub grid_color: Color,
+    /// Layout columns drawn as translucent bands
+    pub columns: Option<GridColumns>,
+    /// The color of the layout columns
+    pub column_color: Color,
+    /// Custom guide lines
+    pub guides: Vec<GuideLine>,
+    /// The color of the custom guide lines
+    pub guide_color: Color,
+}
+
+impl Default for DebugGrid {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cell_size: Some(8.0),
+            grid_color: Color::rgba(1.0, 0.0, 1.0, 0.15),
+            columns: None,
+            column_color: Color::rgba(1.0, 0.0, 0.0, 0.1),
+            guides: Vec::new(),
+            guide_color: Color::rgba(0.0, 1.0, 1.0, 0.6),
+        }
+    }
+}
+
+/// Describes evenly sized layout columns separated by gutters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridColumns {
+    /// The number of columns
+    pub count: u32,
+    /// The space between two columns in logical pixels
+    pub gutter: f32,
+    /// The space between the outer columns and the edges of the window in logical pixels
+    pub margin: f32,
+}
+
+/// A single guide line, positioned in logical pixels from the top left of the window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuideLine {
+    Horizontal(f32),
+    Vertical(f32),
+}
+
+pub struct DebugGridPlugin;
+
+impl Plugin for DebugGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugGrid>();
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .add_systems(ExtractSchedule, extract_debug_grid)
+            .add_systems(
+                Render,
+                queue_debug_grid
+                    .in_set(RenderSet::Queue)
+                    .before(queue_quads),
+            );
+    }
+}
+
+fn extract_debug_grid(mut commands: Commands, debug_grid: Extract<Res<DebugGrid>>) {
+    commands.insert_resource(debug_grid.clone());
+}
+
+fn queue_debug_grid(
+    debug_grid: Option<Res<DebugGrid>>,
+    views: Query<(Entity, &UIExtractedView)>,
+    mut extracted_quads: ResMut<ExtractedQuads>,
+) {
+    let Some(debug_grid) = debug_grid else {
+        return;
+    };
+    if !debug_grid.enabled {
+        return;
+    }
+    for (camera_entity, view) in views.iter() {
+        let dpi = view.scale_factor;
+        // Snaps a logical position to the nearest device pixel.
+        let snap = |value: f32| (value * dpi).round() / dpi;
+        let line_width = 1.0 / dpi;
+        let size = Vec2::new(view.viewport.z as f32, view.viewport.w as f32) / dpi;
+
+        // Reset the clip so the overlay is never scissored by the last clipped widget.
+        extracted_quads.quads.push(ExtractedQuad {
+            camera_entity,
+            rect: Rect {
+                min: Vec2::ZERO,
+                max: size * dpi,
+            },
+            z_index: DEBUG_GRID_Z,
+            render_layer: DEBUG_RENDER_LAYER,
+            quad_type: UIQuadType::Clip,
+            ..Default::default()
+        });
+
+        let mut push_rect = |min: Vec2, max: Vec2, color: Color| {
+            extracted_quads.quads.push(ExtractedQuad {
+                camera_entity,
+                rect: Rect { min, max },
+                color,
+                z_index: DEBUG_GRID_Z + 1.0,
+                render_layer: DEBUG_RENDER_LAYER,
+                quad_type: UIQuadType::Quad,
+                ..Default::default()
+            });
+        };
+
+        if let Some(columns) = debug_grid.columns.filter(|columns| columns.count > 0) {
+            let count = columns.count as f32;
+            let column_width =
+                (size.x - columns.margin * 2.0 - columns.gutter * (count - 1.0)) / count;
+            if column_width > 0.0 {
+                for i in 0..columns.count {
+                    let x = columns.margin + (column_width + columns.gutter) * i as f32;
+                    push_rect(
+                        Vec2::new(snap(x), 0.0),
+                        Vec2::new(snap(x + column_width), size.y),
+                        debug_grid.column_color,
+                    );
+                }
+            }
+        }
+
+        if let Some(cell_size) = debug_grid.cell_size.filter(|cell_size| *cell_size >= 1.0) {
+            let mut x = 0.0;
+            while x < size.x {
+                let x_snapped = snap(x);
+                push_rect(
+                    Vec2::new(x_snapped, 0.0),
+                    Vec2::new(x_snapped + line_width, size.y),
+                    debug_grid.grid_color,
+                );
+                x += cell_size;
+            }
+            let mut y = 0.0;
+            while y < size.y {
+                let y_snapped = snap(y);
+                push_rect(
+                    Vec2::new(0.0, y_snapped),
+                    Vec2::new(size.x, y_snapped + line_width),
+                    debug_grid.grid_color,
+                );
+                y += cell_size;
+            }
+        }
+
+        for guide in debug_grid.guides.iter() {
+            match *guide {
+                GuideLine::Horizontal(y) => {
+                    let y = snap(y);
+                    push_rect(
+                        Vec2::new(0.0, y),
+                        Vec2::new(size.x, y + line_width),
+                        debug_grid.guide_color,
+                    );
+                }
+                GuideLine::Vertical(x) => {
+                    let x = snap(x);
+                    push_rect(
+                        Vec2::new(x, 0.0),
+                        Vec2::new(x + line_width, size.y),
+                        debug_grid.guide_color,
+                    );
+                }
+            }
+        }
+    }
+}
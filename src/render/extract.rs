@@ -1,22 +1,30 @@
 use crate::{
     context::{KayakRootContext, WidgetName},
+    layout::WidgetLayout,
     node::Node,
-    CameraUIKayak,
+    styles::StyleProp,
+    CameraUIKayak, KayakUiScale,
 };
+use bevy::utils::{HashMap, HashSet};
 use bevy::{
     prelude::*,
     render::{
         render_phase::RenderPhase,
         render_resource::{DynamicUniformBuffer, ShaderType},
         renderer::{RenderDevice, RenderQueue},
-        view::ColorGrading,
+        view::{ColorGrading, RenderLayers},
         Extract, ExtractSchedule, Render, RenderApp, RenderSet,
     },
     window::{PrimaryWindow, Window, WindowRef},
 };
 use kayak_font::KayakFont;
 
-use super::{font::FontMapping, ui_pass::TransparentUI, unified::pipeline::ExtractedQuads};
+use super::{
+    font::FontMapping,
+    opacity_layer::OpacityLayerManager,
+    ui_pass::TransparentUI,
+    unified::pipeline::{ExtractedQuad, ExtractedQuads, UIQuadType},
+};
 
 // mod nine_patch;
 // mod texture_atlas;
@@ -40,9 +48,24 @@ impl Plugin for BevyKayakUIExtractPlugin {
     }
 }
 
+/// The quads built for a context and camera, reused while nothing in the UI changes
+pub(crate) struct CachedPrimitives {
+    quads: Vec<ExtractedQuad>,
+    dpi: f32,
+    available_opacity_layers: u32,
+    requested_opacity_layers: u32,
+}
+
 pub fn extract(
     mut commands: Commands,
-    context_query: Extract<Query<(Entity, &KayakRootContext)>>,
+    mut cached_primitives: Local<HashMap<(Entity, Entity), CachedPrimitives>>,
+    mut material_nodes: Local<HashSet<Entity>>,
+    (changed_nodes, changed_styles, mut removed_nodes): (
+        Extract<Query<(), Or<(Changed<Node>, Changed<WidgetLayout>)>>>,
+        Extract<Query<(Entity, &Node), Changed<Node>>>,
+        Extract<RemovedComponents<Node>>,
+    ),
+    context_query: Extract<Query<(Entity, &KayakRootContext, Option<&RenderLayers>)>>,
     fonts: Extract<Res<Assets<KayakFont>>>,
     font_mapping: Extract<Res<FontMapping>>,
     node_query: Extract<Query<&Node>>,
@@ -50,37 +73,123 @@ pub fn extract(
     images: Extract<Res<Assets<Image>>>,
     primary_window: Extract<Query<&Window, With<PrimaryWindow>>>,
     cameras: Extract<Query<&Camera>>,
+    ui_cameras: Extract<Query<(Entity, Option<&RenderLayers>), With<CameraUIKayak>>>,
+    opacity_layers: Extract<Res<OpacityLayerManager>>,
+    ui_scale: Extract<Res<KayakUiScale>>,
     mut extracted_quads: ResMut<ExtractedQuads>,
 ) {
     extracted_quads.quads.clear();
 
-    for (_entity, context) in context_query.iter() {
-        let dpi = if let Ok(camera) = cameras.get(context.camera_entity) {
-            if let bevy::render::camera::RenderTarget::Window(WindowRef::Primary) = &camera.target {
-                if let Ok(window) = primary_window.get_single() {
-                    window.scale_factor() as f32
+    // Materials spawn their own entities while the quads are built, so the nodes using one are
+    // tracked to know whether the quads can be cached at all
+    for (entity, node) in changed_styles.iter() {
+        if matches!(node.resolved_styles.material, StyleProp::Value(..)) {
+            material_nodes.insert(entity);
+        } else {
+            material_nodes.remove(&entity);
+        }
+    }
+    let mut has_removed_nodes = false;
+    for entity in removed_nodes.iter() {
+        material_nodes.remove(&entity);
+        has_removed_nodes = true;
+    }
+    let uses_materials = !material_nodes.is_empty();
+
+    // Every change to a widget's styles, children or layout shows up as a changed node or
+    // layout, and despawned widgets as a removed node, so the quads only have to be rebuilt
+    // then. They aren't cached per subtree since the z-indices and opacity layers of a node
+    // depend on everything drawn before it.
+    if !changed_nodes.is_empty() || has_removed_nodes || fonts.is_changed() || images.is_changed() {
+        cached_primitives.clear();
+    }
+    let mut used_keys = HashSet::new();
+
+    for (entity, context, render_layers) in context_query.iter() {
+        // A context with render layers is drawn by every UI camera that shares a layer with it,
+        // otherwise it's only drawn by its own camera.
+        let camera_entities = if let Some(render_layers) = render_layers {
+            ui_cameras
+                .iter()
+                .filter(|(_, camera_layers)| {
+                    camera_layers
+                        .copied()
+                        .unwrap_or_default()
+                        .intersects(render_layers)
+                })
+                .map(|(camera_entity, _)| camera_entity)
+                .collect::<Vec<_>>()
+        } else {
+            vec![context.camera_entity]
+        };
+
+        for camera_entity in camera_entities {
+            let dpi = if let Ok(camera) = cameras.get(camera_entity) {
+                if let bevy::render::camera::RenderTarget::Window(WindowRef::Primary) =
+                    &camera.target
+                {
+                    if let Ok(window) = primary_window.get_single() {
+                        window.scale_factor() as f32
+                    } else {
+                        1.0
+                    }
                 } else {
                     1.0
                 }
             } else {
                 1.0
-            }
-        } else {
-            1.0
-        };
+            } * ui_scale.get();
 
-        context.build_render_primitives(
-            &mut commands,
-            context.camera_entity,
-            dpi,
-            &node_query,
-            &widget_names,
-            &fonts,
-            &font_mapping,
-            &images,
-            &mut extracted_quads,
-        );
+            let key = (entity, camera_entity);
+            used_keys.insert(key);
+            let available_opacity_layers = opacity_layers.layer_count(camera_entity);
+            let cached = cached_primitives.get(&key).filter(|cached| {
+                cached.dpi == dpi && cached.available_opacity_layers == available_opacity_layers
+            });
+            let requested_opacity_layers = if let Some(cached) = cached {
+                extracted_quads.quads.extend(cached.quads.iter().cloned());
+                cached.requested_opacity_layers
+            } else {
+                let first_quad = extracted_quads.quads.len();
+                let requested_opacity_layers = context.build_render_primitives(
+                    &mut commands,
+                    camera_entity,
+                    dpi,
+                    &node_query,
+                    &widget_names,
+                    &fonts,
+                    &font_mapping,
+                    &images,
+                    &mut extracted_quads,
+                    available_opacity_layers,
+                );
+                if uses_materials {
+                    cached_primitives.remove(&key);
+                } else {
+                    cached_primitives.insert(
+                        key,
+                        CachedPrimitives {
+                            quads: extracted_quads.quads[first_quad..].to_vec(),
+                            dpi,
+                            available_opacity_layers,
+                            requested_opacity_layers,
+                        },
+                    );
+                }
+                requested_opacity_layers
+            };
+            opacity_layers.request_layers(requested_opacity_layers);
+            if extracted_quads
+                .quads
+                .iter()
+                .any(|quad| quad.quad_type == UIQuadType::BackdropBlur)
+            {
+                opacity_layers.request_backdrop();
+            }
+        }
     }
+
+    cached_primitives.retain(|key, _| used_keys.contains(key));
 }
 
 const UI_CAMERA_TRANSFORM_OFFSET: f32 = -0.1;
@@ -96,12 +205,15 @@ pub struct UIExtractedView {
     pub hdr: bool,
     // uvec4(origin.x, origin.y, width, height)
     pub viewport: UVec4,
+    // The number of physical pixels per logical pixel of the camera's render target
+    pub scale_factor: f32,
     pub color_grading: ColorGrading,
 }
 
 pub fn extract_default_ui_camera_view<T: Component>(
     mut commands: Commands,
     query: Extract<Query<(Entity, &Camera, &CameraUIKayak), With<T>>>,
+    ui_scale: Extract<Res<KayakUiScale>>,
 ) {
     for (entity, camera, _camera_ui) in &query {
         if let (Some(logical_size), Some((physical_origin, _)), Some(physical_size)) = (
@@ -109,6 +221,8 @@ pub fn extract_default_ui_camera_view<T: Component>(
             camera.physical_viewport_rect(),
             camera.physical_viewport_size(),
         ) {
+            // The UI is laid out in logical pixels divided by the UI scale
+            let logical_size = logical_size / ui_scale.get();
             // use a projection matrix with the origin in the top left instead of the bottom left that comes with OrthographicProjection
             let projection_matrix =
                 Mat4::orthographic_rh(0.0, logical_size.x, logical_size.y, 0.0, 0.0, 1000.0);
@@ -127,6 +241,7 @@ pub fn extract_default_ui_camera_view<T: Component>(
                         physical_size.x,
                         physical_size.y,
                     ),
+                    scale_factor: physical_size.x as f32 / logical_size.x.max(1.0),
                     view_projection: None,
                     color_grading: ColorGrading::default(),
                 },
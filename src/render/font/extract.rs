@@ -6,7 +6,7 @@ use kayak_font::{KayakFont, TextLayout, TextProperties};
 
 use crate::{
     render::unified::pipeline::{ExtractedQuad, UIQuadType},
-    styles::Corner,
+    styles::{Corner, TextRun},
 };
 
 use super::font_mapping::FontMapping;
@@ -26,15 +26,27 @@ pub fn extract_texts(
 ) -> Vec<ExtractedQuad> {
     let mut extracted_texts = Vec::new();
 
-    let font_handle = font_mapping.get_handle(font).unwrap();
-    let font = match fonts.get(&font_handle) {
-        Some(font) => font,
-        None => {
-            return Vec::new();
-        }
-    };
-
-    let forced = font_mapping.get_subpixel_forced(&font_handle);
+    // Glyphs the font doesn't have are drawn with the first fallback font that has them, which
+    // is how the text was laid out as well.
+    let font_handles = font_mapping.get_fallback_handles(font);
+    if font_handles
+        .first()
+        .and_then(|font_handle| fonts.get(font_handle))
+        .is_none()
+    {
+        return Vec::new();
+    }
+    let fallback_fonts = font_handles
+        .iter()
+        .filter_map(|font_handle| {
+            let font = fonts.get(font_handle)?;
+            Some((
+                font_handle,
+                font,
+                font_mapping.get_subpixel_forced(font_handle),
+            ))
+        })
+        .collect::<Vec<_>>();
 
     let base_position = Vec2::new(layout.posx, layout.posy + properties.font_size);
 
@@ -44,17 +56,22 @@ pub fn extract_texts(
 
         let size = Vec2::from(glyph_rect.size);
 
+        let (font_handle, font, forced) = fallback_fonts
+            .iter()
+            .find(|(_, font, _)| font.get_char_id(glyph_rect.content).is_some())
+            .unwrap_or(&fallback_fonts[0]);
+
         extracted_texts.push(ExtractedQuad {
             camera_entity,
-            font_handle: Some(font_handle.clone()),
+            font_handle: Some((*font_handle).clone()),
             rect: Rect {
                 min: position,
                 max: position + size,
             },
             color,
-            char_id: font.get_char_id(glyph_rect.content).unwrap(),
+            char_id: font.get_char_id(glyph_rect.content).unwrap_or_default(),
             z_index: layout.z_index,
-            quad_type: if subpixel || forced {
+            quad_type: if subpixel || *forced {
                 UIQuadType::TextSubpixel
             } else {
                 UIQuadType::Text
@@ -71,3 +88,95 @@ pub fn extract_texts(
 
     extracted_texts
 }
+
+/// Creates the outline quads for the given glyph quads, drawn just behind them
+///
+/// The outline samples each glyph's own distance field at an expanded threshold, so `width` (in
+/// logical pixels) is limited by the distance range the font was generated with.
+pub fn extract_text_strokes(
+    glyphs: &[ExtractedQuad],
+    width: f32,
+    color: Color,
+    dpi: f32,
+) -> Vec<ExtractedQuad> {
+    if width <= 0.0 {
+        return Vec::new();
+    }
+
+    glyphs
+        .iter()
+        .map(|glyph| ExtractedQuad {
+            color,
+            // The stroke is always drawn in grayscale, even under subpixel text
+            quad_type: UIQuadType::Text,
+            // The text shader reads the stroke width in physical pixels from here
+            shader_param: width * dpi,
+            z_index: glyph.z_index - 0.0001,
+            ..glyph.clone()
+        })
+        .collect()
+}
+
+pub fn extract_rich_texts(
+    camera_entity: Entity,
+    color: Color,
+    runs: &[TextRun],
+    text_layout: TextLayout,
+    layout: crate::layout::Rect,
+    font: String,
+    properties: TextProperties,
+    subpixel: bool,
+    opacity_layer: u32,
+    fonts: &Assets<KayakFont>,
+    font_mapping: &FontMapping,
+    _dpi: f32,
+) -> Vec<ExtractedQuad> {
+    // Resolve the font and color of each run up front
+    let mut run_styles = Vec::with_capacity(runs.len());
+    for run in runs {
+        let font_handle =
+            match font_mapping.get_handle(run.font.clone().unwrap_or_else(|| font.clone())) {
+                Some(font_handle) => font_handle,
+                None => return Vec::new(),
+            };
+        let run_font = match fonts.get(&font_handle) {
+            Some(run_font) => run_font,
+            None => return Vec::new(),
+        };
+        let forced = font_mapping.get_subpixel_forced(&font_handle);
+        run_styles.push((font_handle, run_font, run.color.unwrap_or(color), forced));
+    }
+
+    let base_position = Vec2::new(layout.posx, layout.posy + properties.font_size);
+
+    let mut extracted_texts = Vec::new();
+    for glyph_rect in text_layout.glyphs() {
+        let (font_handle, font, color, forced) = match run_styles.get(glyph_rect.run) {
+            Some(run_style) => run_style,
+            None => continue,
+        };
+        let position = Vec2::from(glyph_rect.position) + base_position;
+        let size = Vec2::from(glyph_rect.size);
+
+        extracted_texts.push(ExtractedQuad {
+            camera_entity,
+            font_handle: Some(font_handle.clone()),
+            rect: Rect {
+                min: position,
+                max: position + size,
+            },
+            color: *color,
+            char_id: font.get_char_id(glyph_rect.content).unwrap_or_default(),
+            z_index: layout.z_index,
+            quad_type: if subpixel || *forced {
+                UIQuadType::TextSubpixel
+            } else {
+                UIQuadType::Text
+            },
+            opacity_layer,
+            ..Default::default()
+        });
+    }
+
+    extracted_texts
+}
@@ -1,5 +1,7 @@
 use bevy::{
-    prelude::{Handle, Resource},
+    asset::HandleId,
+    prelude::{Assets, Handle, Image, Resource},
+    text::Font,
     utils::{HashMap, HashSet},
 };
 use kayak_font::KayakFont;
@@ -41,6 +43,8 @@ pub struct FontMapping {
     font_handles: HashMap<String, Handle<KayakFont>>,
     new_fonts: Vec<String>,
     subpixel: HashSet<Handle<KayakFont>>,
+    /// TTF/OTF fonts waiting to be loaded before their `KayakFont` can be created
+    pending_ttf: Vec<(String, Handle<Font>)>,
 }
 
 impl FontMapping {
@@ -54,18 +58,120 @@ impl FontMapping {
         }
     }
 
+    /// Add a plain TTF/OTF font to be tracked
+    ///
+    /// Unlike a `.kayak_font` file, no atlas needs to be generated ahead of time: glyphs are
+    /// rasterized into the font's texture the first time they are measured and are cached after
+    /// that. Use [`DEFAULT_FONT`](crate::DEFAULT_FONT) as the name to make it the default font.
+    ///
+    /// ```ignore
+    /// font_mapping.add_ttf("Roboto", asset_server.load("roboto.ttf"));
+    /// ```
+    pub fn add_ttf(&mut self, name: &str, handle: Handle<Font>) {
+        // Reserve the handle now so text can refer to the font before it has loaded
+        let font_handle = Handle::weak(HandleId::random::<KayakFont>());
+        self.add(name, font_handle);
+        self.pending_ttf.push((name.to_string(), handle));
+    }
+
+    /// Creates the `KayakFont` for each TTF/OTF font added with [`FontMapping::add_ttf`] that has
+    /// finished loading
+    pub(crate) fn create_ttf_fonts(
+        &mut self,
+        ttf_fonts: &Assets<Font>,
+        fonts: &mut Assets<KayakFont>,
+        images: &mut Assets<Image>,
+    ) {
+        let pending = std::mem::take(&mut self.pending_ttf);
+        for (name, ttf_handle) in pending {
+            let Some(ttf_font) = ttf_fonts.get(&ttf_handle) else {
+                self.pending_ttf.push((name, ttf_handle));
+                continue;
+            };
+
+            if let Some(font_handle) = self.font_handles.get_mut(&name) {
+                let font = KayakFont::from_ttf(ttf_font.font.clone(), images);
+                // Swap in a strong handle so the font stays loaded
+                *font_handle = fonts.set(font_handle.id(), font);
+                self.new_fonts.push(name);
+            }
+        }
+    }
+
     /// Set a default `KayakFont`
+    ///
+    /// The handle can come from a pre-baked `.kayak_font` file, a `.kttf` config or a font file
+    /// named `*.kayak.ttf`/`*.kayak.otf`, whose glyphs are rasterized as they're needed. Plain
+    /// `.ttf`/`.otf` files are loaded by Bevy as a [`Font`], so use
+    /// [`FontMapping::set_default_ttf`] for those.
     pub fn set_default(&mut self, handle: Handle<KayakFont>) {
         self.add(crate::DEFAULT_FONT, handle);
     }
 
+    /// Set a plain TTF/OTF font as the default font
+    ///
+    /// This is the same as [`FontMapping::add_ttf`] with [`DEFAULT_FONT`](crate::DEFAULT_FONT)
+    /// as the name.
+    pub fn set_default_ttf(&mut self, handle: Handle<Font>) {
+        self.add_ttf(crate::DEFAULT_FONT, handle);
+    }
+
     pub(crate) fn mark_all_as_new(&mut self) {
         self.new_fonts.extend(self.font_handles.keys().cloned());
     }
 
     /// Get the handle for the given font name
+    ///
+    /// The name can also be a font stack: a comma-separated list of font names in order of
+    /// preference, such as `"Noto Sans, Roboto"`. In that case, the handle of the first
+    /// registered font is returned.
     pub fn get_handle(&self, id: String) -> Option<Handle<KayakFont>> {
-        self.font_handles.get(&id).cloned()
+        if let Some(handle) = self.font_handles.get(&id) {
+            return Some(handle.clone());
+        }
+
+        Self::parse_font_stack(&id).find_map(|name| self.font_handles.get(name).cloned())
+    }
+
+    /// Get the handles of all registered fonts in the given font stack, in order of preference
+    pub fn get_stack_handles(&self, font_stack: &str) -> Vec<Handle<KayakFont>> {
+        Self::parse_font_stack(font_stack)
+            .filter_map(|name| self.font_handles.get(name).cloned())
+            .collect()
+    }
+
+    /// Get the handle for the given font name, followed by the handles of the other registered
+    /// fonts in its stack
+    ///
+    /// Glyphs missing from the first font are drawn with the first of the later fonts that has
+    /// them. Fonts in the stack that aren't registered are skipped. If none of them are, this
+    /// warns and falls back to the default font, and returns nothing if that isn't registered
+    /// either.
+    pub fn get_fallback_handles(&self, id: String) -> Vec<Handle<KayakFont>> {
+        let mut handles = self.get_stack_handles(&id);
+        let Some(handle) = self.get_handle(id.clone()) else {
+            log::warn!(
+                "No font in \"{}\" is registered, falling back to the default font",
+                id
+            );
+            return self
+                .font_handles
+                .get(crate::DEFAULT_FONT)
+                .cloned()
+                .into_iter()
+                .collect();
+        };
+        handles.retain(|fallback| *fallback != handle);
+        handles.insert(0, handle);
+        handles
+    }
+
+    /// Splits a font stack into its font names, trimming whitespace and quotes
+    pub fn parse_font_stack(font_stack: &str) -> impl Iterator<Item = &str> {
+        font_stack
+            .split(',')
+            .map(|name| name.trim().trim_matches(|c| c == '"' || c == '\''))
+            .filter(|name| !name.is_empty())
     }
 
     /// Get the font name for the given handle
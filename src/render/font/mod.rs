@@ -1,9 +1,11 @@
-use bevy::prelude::{Added, Entity, Plugin, Query, ResMut, Update};
+use bevy::prelude::{Added, Assets, Entity, Image, Plugin, Query, Res, ResMut, Update};
+use bevy::text::Font;
+use kayak_font::KayakFont;
 
 mod extract;
 mod font_mapping;
 
-pub use extract::extract_texts;
+pub use extract::{extract_rich_texts, extract_text_strokes, extract_texts};
 pub use font_mapping::*;
 
 use crate::context::KayakRootContext;
@@ -14,7 +16,7 @@ pub struct TextRendererPlugin;
 impl Plugin for TextRendererPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_resource::<FontMapping>()
-            .add_systems(Update, process_loaded_fonts);
+            .add_systems(Update, (process_loaded_fonts, create_ttf_fonts));
     }
 }
 
@@ -26,3 +28,12 @@ fn process_loaded_fonts(
         font_mapping.mark_all_as_new();
     }
 }
+
+fn create_ttf_fonts(
+    mut font_mapping: ResMut<FontMapping>,
+    ttf_fonts: Res<Assets<Font>>,
+    mut fonts: ResMut<Assets<KayakFont>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    font_mapping.create_ttf_fonts(&ttf_fonts, &mut fonts, &mut images);
+}
@@ -1,6 +1,6 @@
 use crate::{
     render::unified::pipeline::{ExtractedQuad, UIQuadType},
-    styles::Corner,
+    styles::{Corner, ImageRepeat},
 };
 use bevy::{math::Vec2, prelude::*, render::color::Color};
 
@@ -9,9 +9,38 @@ pub fn extract_images(
     border_radius: Corner<f32>,
     layout: crate::layout::Rect,
     handle: Handle<Image>,
+    image_repeat: ImageRepeat,
     opacity_layer: u32,
+    images: &Assets<Image>,
     _dpi: f32,
 ) -> Vec<ExtractedQuad> {
+    let image_size = images.get(&handle).map(|image| {
+        Vec2::new(
+            image.texture_descriptor.size.width as f32,
+            image.texture_descriptor.size.height as f32,
+        )
+    });
+
+    // Tiles start at the top-left corner of the widget. Since the top of the quad has a v of 1,
+    // the repeats extend downwards from there.
+    let (uv_min, uv_max, repeat_image) = match (image_repeat, image_size) {
+        (ImageRepeat::Stretch, _) | (_, None) => (None, None, false),
+        (image_repeat, Some(image_size)) => {
+            let mut tiles = Vec2::new(layout.width, layout.height) / image_size.max(Vec2::ONE);
+            if image_repeat == ImageRepeat::TileX {
+                tiles.y = 1.0;
+            }
+            if image_repeat == ImageRepeat::TileY {
+                tiles.x = 1.0;
+            }
+            (
+                Some(Vec2::new(0.0, 1.0 - tiles.y)),
+                Some(Vec2::new(tiles.x, 1.0)),
+                true,
+            )
+        }
+    };
+
     vec![ExtractedQuad {
         camera_entity,
         rect: Rect {
@@ -26,8 +55,9 @@ pub fn extract_images(
         type_index: 0,
         border_radius,
         image: Some(handle.clone_weak()),
-        uv_max: None,
-        uv_min: None,
+        uv_max,
+        uv_min,
+        repeat_image,
         opacity_layer,
         ..Default::default()
     }]
@@ -30,6 +30,7 @@ use std::marker::PhantomData;
 
 use crate::render::{
     extract::UIExtractedView,
+    mesh::RenderMeshes,
     opacity_layer::OpacityLayerManager,
     svg::RenderSvgs,
     ui_pass::{TransparentOpacityUI, TransparentUI},
@@ -301,6 +302,7 @@ impl<P: PhaseItem, M: MaterialUI, const I: usize> RenderCommand<P> for SetMateri
 
 pub fn queue_material_ui_quads<M: MaterialUI>(
     render_svgs: Res<RenderSvgs>,
+    render_meshes: Res<RenderMeshes>,
     opacity_layers: Res<OpacityLayerManager>,
     mut commands: Commands,
     draw_functions: Res<DrawFunctions<TransparentUI>>,
@@ -340,11 +342,14 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
     let mut current_batch = QuadBatch {
         image_handle_id: None,
         font_handle_id: None,
+        repeat_image: false,
         quad_type: UIQuadType::None,
         type_id: quad_types_offsets.quad_type_offset,
         z_index: -999.0,
+        opacity_layer: 0,
     };
     let mut current_batch_entity = Entity::PLACEHOLDER;
+    let mut current_material = None;
 
     // Vertex buffer indices
     let mut index = prev_index.index;
@@ -366,8 +371,16 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
 
         for (mut quad, material_handle) in extracted_quads.iter_mut() {
             if let Some(materialui) = render_materials.get(material_handle) {
+                // Each batch entity holds a single material handle, so start a new batch
+                // whenever the material changes.
+                if current_material.as_ref() != Some(material_handle) {
+                    current_batch.quad_type = UIQuadType::None;
+                    current_material = Some(material_handle.clone_weak());
+                }
+
                 if quad.quad_type == UIQuadType::Clip {
                     prev_clip.rect = quad.rect;
+                    prev_clip.edge_fade = quad.edge_fade;
                 }
 
                 if prev_clip.rect.width() < 1.0 || prev_clip.rect.height() < 1.0 {
@@ -392,6 +405,7 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
                     &gpu_images,
                     &quad_pipeline,
                     &render_svgs,
+                    &render_meshes,
                     &mut transparent_phase,
                     &mut opacity_transparent_phase,
                     draw_opacity_quad,
@@ -399,6 +413,7 @@ pub fn queue_material_ui_quads<M: MaterialUI>(
                     pipeline_id,
                     &mut quad_meta,
                     &mut quad,
+                    &prev_clip,
                     camera_entity,
                     *quad_types_offsets,
                     &mut current_batch,
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::render::unified::pipeline::ExtractedQuad;
+
+pub fn extract_mesh(
+    camera_entity: Entity,
+    handle: Handle<Mesh>,
+    layout: crate::layout::Rect,
+    tint: Color,
+    opacity_layer: u32,
+    _dpi: f32,
+) -> Vec<ExtractedQuad> {
+    vec![ExtractedQuad {
+        camera_entity,
+        rect: Rect {
+            min: Vec2::new(layout.posx, layout.posy),
+            max: Vec2::new(layout.posx + layout.width, layout.posy + layout.height),
+        },
+        z_index: layout.z_index,
+        color: tint,
+        mesh_handle: Some(handle),
+        opacity_layer,
+        ..Default::default()
+    }]
+}
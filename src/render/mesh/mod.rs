@@ -0,0 +1,138 @@
+use bevy::{
+    prelude::*,
+    render::{mesh::VertexAttributeValues, render_resource::PrimitiveTopology, Extract},
+    utils::{HashMap, HashSet},
+};
+
+use crate::{
+    node::Node,
+    styles::{RenderCommand, StyleProp},
+};
+
+mod extract;
+pub use extract::extract_mesh;
+
+/// A 2D mesh, ready to be drawn by any number of widgets
+#[derive(Debug, Clone)]
+pub struct RenderMesh {
+    /// The top-left corner of the mesh's bounds
+    pub min: Vec2,
+    /// The size of the mesh's bounds
+    pub size: Vec2,
+    /// The triangle vertices of the mesh as (position, color) pairs
+    ///
+    /// Positions use the mesh's x and y coordinates, with the y-axis flipped to point down.
+    pub vertices: Vec<(Vec2, [f32; 4])>,
+}
+
+impl RenderMesh {
+    /// Flattens a triangle list mesh into a list of triangle vertices
+    ///
+    /// Vertex colors are optional and default to white. Returns `None` if the mesh isn't a
+    /// triangle list or is missing positions.
+    pub fn new(mesh: &Mesh) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+        let colors = match mesh.attribute(Mesh::ATTRIBUTE_COLOR) {
+            Some(VertexAttributeValues::Float32x4(colors)) => Some(colors),
+            _ => None,
+        };
+        let vertex = |index: usize| {
+            let position = positions[index];
+            let color = colors
+                .and_then(|colors| colors.get(index).copied())
+                .unwrap_or([1.0; 4]);
+            (Vec2::new(position[0], -position[1]), color)
+        };
+        let vertices: Vec<_> = if let Some(indices) = mesh.indices() {
+            indices
+                .iter()
+                .filter(|index| *index < positions.len())
+                .map(vertex)
+                .collect()
+        } else {
+            (0..positions.len()).map(vertex).collect()
+        };
+
+        let min = vertices
+            .iter()
+            .fold(Vec2::splat(f32::MAX), |min, (position, _)| {
+                min.min(*position)
+            });
+        let max = vertices
+            .iter()
+            .fold(Vec2::splat(f32::MIN), |max, (position, _)| {
+                max.max(*position)
+            });
+
+        Some(Self {
+            min,
+            size: (max - min).max(Vec2::splat(f32::EPSILON)),
+            vertices,
+        })
+    }
+}
+
+/// The meshes drawn by widgets with a [`RenderCommand::Mesh`]
+///
+/// Only meshes that are in use are kept, and a mesh is only converted again when its asset
+/// changes.
+#[derive(Resource, Default, Debug, Clone, Deref, DerefMut)]
+pub struct RenderMeshes(pub HashMap<Handle<Mesh>, RenderMesh>);
+
+pub fn extract_mesh_asset(
+    mut events: Extract<EventReader<AssetEvent<Mesh>>>,
+    mesh_assets: Extract<Res<Assets<Mesh>>>,
+    nodes: Extract<Query<&Node>>,
+    mut render_assets: ResMut<RenderMeshes>,
+    mut invalid_assets: Local<HashSet<Handle<Mesh>>>,
+) {
+    let mut changed_assets = HashSet::default();
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                changed_assets.insert(handle.clone_weak());
+                invalid_assets.remove(handle);
+            }
+            AssetEvent::Removed { handle } => {
+                render_assets.remove(handle);
+                invalid_assets.remove(handle);
+            }
+        }
+    }
+
+    let used_assets = nodes
+        .iter()
+        .filter_map(|node| {
+            if let StyleProp::Value(RenderCommand::Mesh { handle }) =
+                &node.resolved_styles.render_command
+            {
+                Some(handle.clone_weak())
+            } else {
+                None
+            }
+        })
+        .collect::<HashSet<_>>();
+    render_assets.retain(|handle, _| used_assets.contains(handle));
+
+    for handle in used_assets {
+        let is_current = render_assets.contains_key(&handle) || invalid_assets.contains(&handle);
+        if is_current && !changed_assets.contains(&handle) {
+            continue;
+        }
+        if let Some(mesh) = mesh_assets.get(&handle) {
+            if let Some(render_mesh) = RenderMesh::new(mesh) {
+                render_assets.insert(handle, render_mesh);
+            } else {
+                log::warn!(
+                    "Only meshes with a triangle list topology and positions can be drawn by the UI"
+                );
+                render_assets.remove(&handle);
+                invalid_assets.insert(handle);
+            }
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use bevy::{
+    core_pipeline::blit::{BlitPipeline, BlitPipelineKey},
     prelude::*,
     render::{
         camera::RenderTarget,
         render_asset::RenderAssets,
         render_graph::{RenderGraph, RunGraphOnViewNode},
         render_phase::{batch_phase_system, sort_phase_system, DrawFunctions, RenderPhase},
+        render_resource::{PipelineCache, SpecializedRenderPipelines, TextureFormat},
+        texture::BevyDefault,
+        view::{ExtractedView, ViewTarget},
         Extract, ExtractSchedule, Render, RenderApp, RenderSet,
     },
     window::{PrimaryWindow, Window, WindowRef},
@@ -18,13 +22,16 @@ use crate::{
 use self::{
     extract::BevyKayakUIExtractPlugin,
     opacity_layer::OpacityLayerManager,
-    ui_pass::{TransparentOpacityUI, TransparentUI},
+    ui_pass::{BackdropBlitPipeline, TransparentOpacityUI, TransparentUI},
 };
 
-mod extract;
+pub(crate) mod debug_clip;
+pub(crate) mod debug_grid;
+pub(crate) mod extract;
 pub(crate) mod font;
 pub(crate) mod image;
 pub mod material;
+pub(crate) mod mesh;
 pub(crate) mod nine_patch;
 mod opacity_layer;
 pub(crate) mod quad;
@@ -33,7 +40,7 @@ pub(crate) mod texture_atlas;
 mod ui_pass;
 pub mod unified;
 
-pub use opacity_layer::MAX_OPACITY_LAYERS;
+pub use opacity_layer::{OpacityLayerConfig, MAX_OPACITY_LAYERS};
 
 pub mod draw_ui_graph {
     pub const NAME: &str = "kayak_draw_ui";
@@ -50,6 +57,7 @@ pub struct BevyKayakUIRenderPlugin;
 impl Plugin for BevyKayakUIRenderPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_resource::<OpacityLayerManager>()
+            .init_resource::<OpacityLayerConfig>()
             .add_systems(Update, update_opacity_layer_cameras);
 
         let render_app = app.sub_app_mut(RenderApp);
@@ -63,6 +71,10 @@ impl Plugin for BevyKayakUIRenderPlugin {
                     .in_set(RenderSet::Queue)
                     .before(unified::pipeline::queue_quads),
             )
+            .add_systems(
+                Render,
+                prepare_backdrop_blit_pipelines.in_set(RenderSet::Prepare),
+            )
             .add_systems(
                 Render,
                 (
@@ -128,6 +140,8 @@ impl Plugin for BevyKayakUIRenderPlugin {
         }
 
         app.add_plugins((
+            debug_clip::DebugClipPlugin,
+            debug_grid::DebugGridPlugin,
             font::TextRendererPlugin,
             UnifiedRenderPlugin,
             BevyKayakUIExtractPlugin,
@@ -147,8 +161,11 @@ pub fn update_opacity_layer_cameras(
     cameras: Query<(Entity, &Camera), With<CameraUIKayak>>,
     primary_window: Query<Entity, With<PrimaryWindow>>,
     mut opacity_layers: ResMut<OpacityLayerManager>,
+    opacity_layer_config: Res<OpacityLayerConfig>,
     mut images: ResMut<Assets<Image>>,
 ) {
+    let layer_count = opacity_layers.allowed_layers(&opacity_layer_config);
+    let backdrop = opacity_layers.take_backdrop_request();
     for (camera_entity, camera) in cameras.iter() {
         if let RenderTarget::Window(window_ref) = &camera.target {
             let window_entity = match window_ref {
@@ -156,7 +173,14 @@ pub fn update_opacity_layer_cameras(
                 WindowRef::Primary => primary_window.get_single().unwrap(),
             };
             if let Ok(camera_window) = windows.get(window_entity) {
-                opacity_layers.add_or_update(&camera_entity, camera, camera_window, &mut images);
+                opacity_layers.add_or_update(
+                    &camera_entity,
+                    camera,
+                    camera_window,
+                    &mut images,
+                    layer_count,
+                    backdrop,
+                );
             }
         }
     }
@@ -188,3 +212,28 @@ fn prepare_opacity_layers(
         layer.set_texture_views(&gpu_images);
     }
 }
+
+fn prepare_backdrop_blit_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
+    blit_pipeline: Res<BlitPipeline>,
+    views: Query<(Entity, &ExtractedView), With<CameraUIKayak>>,
+) {
+    for (entity, view) in views.iter() {
+        // The backdrop texture has the same format as the opacity layers.
+        let key = BlitPipelineKey {
+            texture_format: if view.hdr {
+                ViewTarget::TEXTURE_FORMAT_HDR
+            } else {
+                TextureFormat::bevy_default()
+            },
+            blend_state: None,
+            samples: 1,
+        };
+        let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
+        commands
+            .entity(entity)
+            .insert(BackdropBlitPipeline(pipeline));
+    }
+}
@@ -12,11 +12,42 @@ use bevy::{
     utils::HashMap,
     window::Window,
 };
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+/// The default number of opacity layers per camera, including the camera's own render target.
+pub const MAX_OPACITY_LAYERS: u32 = 16;
+
+/// Configures how many opacity layers the UI may use
+///
+/// Opacity layers are render targets that faded or tinted widgets are drawn into. They are
+/// created when the UI first needs them and reused every frame after that, up to `max_layers`.
+/// Widgets that need a layer past the limit are drawn without their opacity.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct OpacityLayerConfig {
+    /// The maximum number of opacity layers per camera, including the camera's own render target
+    pub max_layers: u32,
+}
+
+impl Default for OpacityLayerConfig {
+    fn default() -> Self {
+        Self {
+            max_layers: MAX_OPACITY_LAYERS,
+        }
+    }
+}
 
 /// Keeps track of opacity layer textures.
 #[derive(Resource, Clone, Default)]
 pub struct OpacityLayerManager {
     pub camera_layers: HashMap<Entity, OpacityCamera>,
+    /// The most layers the UI asked for, shared with the copy in the render world
+    requested_layers: Arc<AtomicU32>,
+    /// Whether the UI has a backdrop blur, shared with the copy in the render world
+    requested_backdrop: Arc<AtomicBool>,
+    warned_limit: bool,
 }
 
 impl OpacityLayerManager {
@@ -26,65 +57,104 @@ impl OpacityLayerManager {
         camera: &Camera,
         window: &Window,
         images: &mut Assets<Image>,
+        layer_count: u32,
+        backdrop: bool,
     ) {
         if let Some(opacity_camera) = self.camera_layers.get_mut(camera_entity) {
             opacity_camera.update_images(window, camera, images);
+            opacity_camera.grow(layer_count, window, camera, images);
+            if backdrop {
+                opacity_camera.add_backdrop(window, camera, images);
+            } else {
+                opacity_camera.backdrop = None;
+            }
         } else {
-            self.camera_layers
-                .insert(*camera_entity, OpacityCamera::new(window, camera, images));
+            let mut opacity_camera = OpacityCamera::default();
+            opacity_camera.grow(layer_count, window, camera, images);
+            if backdrop {
+                opacity_camera.add_backdrop(window, camera, images);
+            }
+            self.camera_layers.insert(*camera_entity, opacity_camera);
+        }
+    }
+
+    /// The number of layers available to the given camera (not counting its own render target)
+    pub fn layer_count(&self, camera_entity: Entity) -> u32 {
+        self.camera_layers
+            .get(&camera_entity)
+            .map(|opacity_camera| opacity_camera.layers.len() as u32)
+            .unwrap_or(0)
+    }
+
+    /// Asks for enough layers to be created for the next frame
+    pub(crate) fn request_layers(&self, layer_count: u32) {
+        self.requested_layers
+            .fetch_max(layer_count, Ordering::Relaxed);
+    }
+
+    /// Asks for a backdrop texture to be created for the next frame
+    pub(crate) fn request_backdrop(&self) {
+        self.requested_backdrop.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether a backdrop was requested since the last call, clearing the request
+    ///
+    /// The UI asks again every frame it has a backdrop blur, so the texture is dropped once
+    /// the last one goes away.
+    pub(crate) fn take_backdrop_request(&self) -> bool {
+        self.requested_backdrop.swap(false, Ordering::Relaxed)
+    }
+
+    /// Returns how many layers should be allocated per camera, given the configured limit
+    ///
+    /// Logs a warning the first time the UI asks for more layers than allowed.
+    pub(crate) fn allowed_layers(&mut self, config: &OpacityLayerConfig) -> u32 {
+        let requested = self.requested_layers.load(Ordering::Relaxed);
+        let max_layers = config.max_layers.saturating_sub(1);
+        if requested > max_layers && !self.warned_limit {
+            log::warn!(
+                "The UI needs {} opacity layers but only {} are allowed, some widgets will be drawn without their opacity. Raise OpacityLayerConfig::max_layers to fix this.",
+                requested + 1,
+                config.max_layers,
+            );
+            self.warned_limit = true;
         }
+        requested.min(max_layers)
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct OpacityCamera {
     layers: HashMap<u32, (Extent3d, Handle<Image>)>,
     views: HashMap<u32, TextureView>,
+    /// The texture that backdrop blurs copy the current render target into
+    backdrop: Option<(Extent3d, Handle<Image>)>,
 }
 
-pub const MAX_OPACITY_LAYERS: u32 = 5;
-
 impl OpacityCamera {
-    /// Creates as new opacity layer render target manager
-    pub(crate) fn new(window: &Window, camera: &Camera, images: &mut Assets<Image>) -> Self {
-        let main_texture_format = if camera.hdr {
-            ViewTarget::TEXTURE_FORMAT_HDR
-        } else {
-            TextureFormat::bevy_default()
-        };
-
-        let mut layers = HashMap::default();
-        for layer in 1..MAX_OPACITY_LAYERS {
-            let size = Extent3d {
-                width: window.resolution.physical_width(),
-                height: window.resolution.physical_height(),
-                ..Default::default()
-            };
-            // This is the texture that will be rendered to.
-            let mut image = Image {
-                texture_descriptor: TextureDescriptor {
-                    label: None,
-                    size,
-                    dimension: TextureDimension::D2,
-                    format: main_texture_format,
-                    mip_level_count: 1,
-                    sample_count: 1,
-                    usage: TextureUsages::TEXTURE_BINDING
-                        | TextureUsages::COPY_DST
-                        | TextureUsages::RENDER_ATTACHMENT,
-                    view_formats: &[],
-                },
-                ..Default::default()
-            };
-            image.resize(size);
-            let image_handle = images.add(image);
-
-            layers.insert(layer, (size, image_handle));
+    /// Creates render targets until there are `layer_count` of them
+    pub(crate) fn grow(
+        &mut self,
+        layer_count: u32,
+        window: &Window,
+        camera: &Camera,
+        images: &mut Assets<Image>,
+    ) {
+        for layer in (self.layers.len() as u32 + 1)..=layer_count {
+            self.layers
+                .insert(layer, create_layer_image(window, camera, images));
         }
+    }
 
-        Self {
-            layers,
-            views: HashMap::default(),
+    /// Creates the backdrop texture if it doesn't exist yet
+    pub(crate) fn add_backdrop(
+        &mut self,
+        window: &Window,
+        camera: &Camera,
+        images: &mut Assets<Image>,
+    ) {
+        if self.backdrop.is_none() {
+            self.backdrop = Some(create_layer_image(window, camera, images));
         }
     }
 
@@ -105,7 +175,7 @@ impl OpacityCamera {
             height: window.resolution.physical_height(),
             ..Default::default()
         };
-        for (size, layer_handle) in self.layers.values_mut() {
+        for (size, layer_handle) in self.layers.values_mut().chain(self.backdrop.as_mut()) {
             if *size != new_size {
                 let layer_image = images.get_mut(layer_handle).unwrap();
                 layer_image.texture_descriptor.format = main_texture_format;
@@ -119,6 +189,12 @@ impl OpacityCamera {
         self.layers.get(&layer_id).unwrap().1.clone_weak()
     }
 
+    pub(crate) fn get_backdrop_handle(&self) -> Option<Handle<Image>> {
+        self.backdrop
+            .as_ref()
+            .map(|(_, image_handle)| image_handle.clone_weak())
+    }
+
     pub(crate) fn set_texture_views(&mut self, gpu_images: &RenderAssets<Image>) {
         for (layer, image) in self.layers.iter() {
             if let Some(gpu_image) = gpu_images.get(&image.1) {
@@ -127,3 +203,40 @@ impl OpacityCamera {
         }
     }
 }
+
+/// Creates a render target the size of the window
+fn create_layer_image(
+    window: &Window,
+    camera: &Camera,
+    images: &mut Assets<Image>,
+) -> (Extent3d, Handle<Image>) {
+    let main_texture_format = if camera.hdr {
+        ViewTarget::TEXTURE_FORMAT_HDR
+    } else {
+        TextureFormat::bevy_default()
+    };
+
+    let size = Extent3d {
+        width: window.resolution.physical_width(),
+        height: window.resolution.physical_height(),
+        ..Default::default()
+    };
+    // This is the texture that will be rendered to.
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: main_texture_format,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..Default::default()
+    };
+    image.resize(size);
+    (size, images.add(image))
+}
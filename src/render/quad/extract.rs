@@ -37,9 +37,26 @@ pub fn extract_quads(
         ..Default::default()
     }];
 
-    let count = box_shadow.len();
+    // Inset shadows are drawn over the background, with the first one on top.
+    let inset_count = box_shadow.iter().filter(|shadow| shadow.inset).count();
+    let background = extracted_quads[0].clone();
+    for (i, box_shadow) in box_shadow.iter().filter(|shadow| shadow.inset).enumerate() {
+        let z = ((inset_count - i) as f32 / (inset_count + 1) as f32) * 0.0001;
+        extracted_quads.push(ExtractedQuad {
+            color: box_shadow.color,
+            z_index: layout.z_index + z,
+            quad_type: UIQuadType::InsetBoxShadow,
+            // Same hack as the outer shadows to pass the blur radius to the shader.
+            uv_min: Some(Vec2::splat(box_shadow.radius)),
+            uv_max: Some(Vec2::splat(box_shadow.radius)),
+            box_shadow: Some(*box_shadow),
+            ..background.clone()
+        });
+    }
+
+    let count = box_shadow.iter().filter(|shadow| !shadow.inset).count();
     let mut z = 0.0;
-    for (i, box_shadow) in box_shadow.iter().enumerate() {
+    for (i, box_shadow) in box_shadow.iter().filter(|shadow| !shadow.inset).enumerate() {
         z = ((i + 1) as f32 / (count + 1) as f32) * 0.0001;
         let half_spread = box_shadow.spread;
         let radius = box_shadow.radius * 3.0;
@@ -8,6 +8,8 @@ pub fn extract_svg(
     handle: Handle<Svg>,
     layout: crate::layout::Rect,
     background_color: Option<Color>,
+    tint: Color,
+    rotation: f32,
     opacity_layer: u32,
     _dpi: f32,
 ) -> Vec<ExtractedQuad> {
@@ -18,7 +20,9 @@ pub fn extract_svg(
             max: Vec2::new(layout.posx + layout.width, layout.posy + layout.height),
         },
         z_index: layout.z_index,
+        color: tint,
         svg_handle: (Some(handle), background_color),
+        rotation,
         opacity_layer,
         ..Default::default()
     }]
@@ -0,0 +1,36 @@
+use bevy::asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use bevy_svg::prelude::Svg;
+
+/// Loads `.svg` files through the `AssetServer`
+///
+/// The svg is only parsed here. Its mesh is tessellated by [`tessellate_svgs`](super::tessellate_svgs)
+/// once the asset has been added.
+#[derive(Default)]
+pub struct SvgLoader;
+
+impl AssetLoader for SvgLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let svg = Svg::from_bytes(bytes, load_context.path(), None::<&std::path::Path>)
+                .map_err(|error| {
+                    anyhow::anyhow!(
+                        "Failed to load svg {}: {}",
+                        load_context.path().display(),
+                        error
+                    )
+                })?;
+            load_context.set_default_asset(LoadedAsset::new(svg));
+
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["svg"];
+        EXTENSIONS
+    }
+}
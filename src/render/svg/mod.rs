@@ -1,23 +1,67 @@
 use bevy::{
     prelude::*,
-    render::Extract,
+    render::{mesh::VertexAttributeValues, Extract},
     utils::{HashMap, HashSet},
 };
 use bevy_svg::prelude::Svg;
 
 mod extract;
+mod loader;
 pub use extract::extract_svg;
+pub use loader::SvgLoader;
 
+/// A tessellated svg, ready to be drawn by any number of widgets
+#[derive(Debug, Clone)]
+pub struct RenderSvg {
+    /// The size of the svg's view box
+    pub view_box_size: Vec2,
+    /// The triangle vertices of the svg as (position, color) pairs
+    ///
+    /// Positions are relative to the top-left of the view box with the y-axis pointing down.
+    pub vertices: Vec<(Vec2, [f32; 4])>,
+}
+
+impl RenderSvg {
+    /// Flattens the indexed mesh of an svg into a list of triangle vertices
+    ///
+    /// Returns `None` if the mesh is missing positions, colors, or indices.
+    pub fn new(svg: &Svg, mesh: &Mesh) -> Option<Self> {
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+        let colors = match mesh.attribute(Mesh::ATTRIBUTE_COLOR)? {
+            VertexAttributeValues::Float32x4(colors) => colors,
+            _ => return None,
+        };
+        let vertices = mesh
+            .indices()?
+            .iter()
+            .map(|index| {
+                let position = positions[index];
+                (Vec2::new(position[0], -position[1]), colors[index])
+            })
+            .collect();
+
+        Some(Self {
+            view_box_size: Vec2::new(svg.view_box.w as f32, svg.view_box.h as f32),
+            vertices,
+        })
+    }
+}
+
+/// The tessellated svgs, shared by every widget drawing the same svg
+///
+/// An svg is only tessellated again when its asset changes.
 #[derive(Resource, Default, Debug, Clone, Deref, DerefMut)]
-pub struct RenderSvgs(pub HashMap<Handle<Svg>, (Svg, Mesh)>);
+pub struct RenderSvgs(pub HashMap<Handle<Svg>, RenderSvg>);
 
 pub fn extract_svg_asset(
     mut events: Extract<EventReader<AssetEvent<Svg>>>,
     svg_assets: Extract<Res<Assets<Svg>>>,
     mesh_assets: Extract<Res<Assets<Mesh>>>,
     mut render_assets: ResMut<RenderSvgs>,
+    mut pending_assets: Local<HashSet<Handle<Svg>>>,
 ) {
-    let mut changed_assets = HashSet::default();
+    // Svgs whose mesh wasn't ready yet are retried until it is
+    let mut changed_assets = std::mem::take(&mut *pending_assets);
     for event in events.iter() {
         match event {
             AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
@@ -25,6 +69,7 @@ pub fn extract_svg_asset(
             }
             AssetEvent::Removed { handle } => {
                 changed_assets.remove(handle);
+                pending_assets.remove(handle);
                 render_assets.remove(handle);
             }
         }
@@ -33,7 +78,42 @@ pub fn extract_svg_asset(
     for handle in changed_assets.drain() {
         if let Some(asset) = svg_assets.get(&handle) {
             if let Some(mesh) = mesh_assets.get(&asset.mesh) {
-                render_assets.insert(handle, (asset.clone(), mesh.clone()));
+                if let Some(render_svg) = RenderSvg::new(asset, mesh) {
+                    render_assets.insert(handle, render_svg);
+                }
+            } else {
+                pending_assets.insert(handle);
+            }
+        }
+    }
+}
+
+/// Tessellates the mesh of every svg that was added without one
+///
+/// This is the case for svgs loaded by the [`SvgLoader`]. Until their mesh is ready, svgs are
+/// skipped when rendering.
+pub fn tessellate_svgs(
+    mut events: EventReader<AssetEvent<Svg>>,
+    mut svg_assets: ResMut<Assets<Svg>>,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+) {
+    let handles = events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                Some(handle.clone_weak())
+            }
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect::<HashSet<_>>();
+
+    for handle in handles {
+        let needs_mesh = svg_assets
+            .get(&handle)
+            .map_or(false, |svg| mesh_assets.get(&svg.mesh).is_none());
+        if needs_mesh {
+            if let Some(svg) = svg_assets.get_mut(&handle) {
+                svg.mesh = mesh_assets.add(svg.tessellate());
             }
         }
     }
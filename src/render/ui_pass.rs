@@ -1,13 +1,18 @@
 use std::ops::Range;
 
+use bevy::core_pipeline::blit::BlitPipeline;
 use bevy::ecs::prelude::*;
 use bevy::prelude::{Color, Image};
 use bevy::render::render_asset::RenderAssets;
 use bevy::render::render_phase::{
     BatchedPhaseItem, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, PhaseItem,
 };
-use bevy::render::render_resource::{CachedRenderPipelineId, RenderPassColorAttachment};
+use bevy::render::render_resource::{
+    BindGroupDescriptor, BindGroupEntry, BindingResource, CachedRenderPipelineId, PipelineCache,
+    RenderPassColorAttachment, TextureView,
+};
 use bevy::render::{
+    camera::ExtractedCamera,
     render_graph::{Node, NodeRunError, RenderGraphContext},
     render_phase::RenderPhase,
     render_resource::{LoadOp, Operations, RenderPassDescriptor},
@@ -18,7 +23,7 @@ use bevy::utils::FloatOrd;
 
 use crate::CameraUIKayak;
 
-use super::opacity_layer::{OpacityLayerManager, MAX_OPACITY_LAYERS};
+use super::opacity_layer::OpacityLayerManager;
 use super::unified::pipeline::UIQuadType;
 
 pub trait TransparentUIGeneric {
@@ -29,7 +34,8 @@ pub trait TransparentUIGeneric {
 }
 
 pub struct TransparentUI {
-    pub sort_key: FloatOrd,
+    /// The render layer and z-index of the item
+    pub sort_key: (i32, FloatOrd),
     pub entity: Entity,
     pub pipeline: CachedRenderPipelineId,
     pub draw_function: DrawFunctionId,
@@ -58,7 +64,7 @@ impl TransparentUIGeneric for TransparentUI {
 }
 
 impl PhaseItem for TransparentUI {
-    type SortKey = FloatOrd;
+    type SortKey = (i32, FloatOrd);
 
     #[inline]
     fn sort_key(&self) -> Self::SortKey {
@@ -93,7 +99,8 @@ impl CachedRenderPipelinePhaseItem for TransparentUI {
 }
 
 pub struct TransparentOpacityUI {
-    pub sort_key: FloatOrd,
+    /// The render layer and z-index of the item
+    pub sort_key: (i32, FloatOrd),
     pub entity: Entity,
     pub pipeline: CachedRenderPipelineId,
     pub draw_function: DrawFunctionId,
@@ -123,7 +130,7 @@ impl TransparentUIGeneric for TransparentOpacityUI {
 }
 
 impl PhaseItem for TransparentOpacityUI {
-    type SortKey = FloatOrd;
+    type SortKey = (i32, FloatOrd);
 
     #[inline]
     fn sort_key(&self) -> Self::SortKey {
@@ -157,6 +164,13 @@ impl CachedRenderPipelinePhaseItem for TransparentOpacityUI {
     }
 }
 
+/// The pipeline that copies a render target into the backdrop texture of its camera
+///
+/// Backdrop blurs can't sample the render target they're drawn into, so the UI pass is split
+/// before each of them to copy what was drawn so far.
+#[derive(Component)]
+pub struct BackdropBlitPipeline(pub CachedRenderPipelineId);
+
 pub struct MainPassUINode {
     query: QueryState<
         (
@@ -164,6 +178,8 @@ pub struct MainPassUINode {
             &'static RenderPhase<TransparentOpacityUI>,
             &'static ViewTarget,
             &'static CameraUIKayak,
+            Option<&'static ExtractedCamera>,
+            Option<&'static BackdropBlitPipeline>,
         ),
         With<ExtractedView>,
     >,
@@ -191,15 +207,32 @@ impl Node for MainPassUINode {
         let view_entity = graph.view_entity();
         // adapted from bevy itself;
         // see: <https://github.com/bevyengine/bevy/commit/09a3d8abe062984479bf0e99fcc1508bb722baf6>
-        let (transparent_phase, transparent_opacity_phase, target, _camera_ui) =
-            match self.query.get_manual(world, view_entity) {
-                Ok(it) => it,
-                _ => return Ok(()),
-            };
+        let (
+            transparent_phase,
+            transparent_opacity_phase,
+            target,
+            _camera_ui,
+            camera,
+            blit_pipeline,
+        ) = match self.query.get_manual(world, view_entity) {
+            Ok(it) => it,
+            _ => return Ok(()),
+        };
+        // Opacity layers are the size of the window, so they're drawn into the same viewport as
+        // the render target.
+        let viewport = camera.and_then(|camera| camera.viewport.as_ref());
+        let opacity_layer_manager = world.get_resource::<OpacityLayerManager>().unwrap();
+        let gpu_images = world.get_resource::<RenderAssets<Image>>().unwrap();
+        let backdrop = opacity_layer_manager
+            .camera_layers
+            .get(&view_entity)
+            .and_then(|opacity_camera| opacity_camera.get_backdrop_handle())
+            .and_then(|image_handle| gpu_images.get(&image_handle))
+            .map(|gpu_image| &gpu_image.texture_view);
 
         // Opacity passes first..
         {
-            let opacity_layer_manager = world.get_resource::<OpacityLayerManager>().unwrap();
+            let layer_count = opacity_layer_manager.layer_count(view_entity);
             if let Some(opacity_layer_manager) =
                 opacity_layer_manager.camera_layers.get(&view_entity)
             {
@@ -207,13 +240,19 @@ impl Node for MainPassUINode {
                 let mut draw_functions = draw_functions.write();
                 draw_functions.prepare(world);
 
-                for layer_id in 1..MAX_OPACITY_LAYERS {
-                    // Start new render pass.
-                    let gpu_images = world.get_resource::<RenderAssets<Image>>().unwrap();
+                for layer_id in 1..=layer_count {
                     let image_handle = opacity_layer_manager.get_image_handle(layer_id);
                     let gpu_image = gpu_images.get(&image_handle).unwrap();
-                    let pass_descriptor = RenderPassDescriptor {
-                        label: Some("opacity_ui_layer_pass"),
+                    let items = transparent_opacity_phase
+                        .items
+                        .iter()
+                        .filter(|i| i.opacity_layer == layer_id)
+                        .collect::<Vec<_>>();
+
+                    // Clear the layer, so that a backdrop blur at the start of it copies an
+                    // empty layer.
+                    render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                        label: Some("opacity_ui_layer_clear_pass"),
                         color_attachments: &[Some(RenderPassColorAttachment {
                             view: &gpu_image.texture_view,
                             resolve_target: None,
@@ -223,18 +262,52 @@ impl Node for MainPassUINode {
                             },
                         })],
                         depth_stencil_attachment: None,
-                    };
-
-                    let mut tracked_pass =
-                        render_context.begin_tracked_render_pass(pass_descriptor);
-
-                    for item in transparent_opacity_phase
-                        .items
-                        .iter()
-                        .filter(|i| i.opacity_layer == layer_id)
-                    {
-                        let draw_function = draw_functions.get_mut(item.draw_function()).unwrap();
-                        draw_function.draw(world, &mut tracked_pass, view_entity, item);
+                    });
+
+                    let mut clip: Option<&TransparentOpacityUI> = None;
+                    for items in split_at_backdrops(&items) {
+                        if starts_with_backdrop(items) {
+                            copy_backdrop(
+                                render_context,
+                                world,
+                                blit_pipeline,
+                                &gpu_image.texture_view,
+                                backdrop,
+                            );
+                        }
+
+                        // Start new render pass.
+                        let pass_descriptor = RenderPassDescriptor {
+                            label: Some("opacity_ui_layer_pass"),
+                            color_attachments: &[Some(RenderPassColorAttachment {
+                                view: &gpu_image.texture_view,
+                                resolve_target: None,
+                                ops: Operations {
+                                    load: LoadOp::Load,
+                                    store: true,
+                                },
+                            })],
+                            depth_stencil_attachment: None,
+                        };
+
+                        let mut tracked_pass =
+                            render_context.begin_tracked_render_pass(pass_descriptor);
+                        if let Some(viewport) = viewport {
+                            tracked_pass.set_camera_viewport(viewport);
+                        }
+                        // A new pass starts without a scissor rect, so restore the last clip.
+                        if let Some(clip) = clip {
+                            let draw_function =
+                                draw_functions.get_mut(clip.draw_function()).unwrap();
+                            draw_function.draw(world, &mut tracked_pass, view_entity, clip);
+                        }
+
+                        for item in items {
+                            let draw_function =
+                                draw_functions.get_mut(item.draw_function()).unwrap();
+                            draw_function.draw(world, &mut tracked_pass, view_entity, *item);
+                        }
+                        clip = last_clip(items).or(clip);
                     }
                 }
             }
@@ -242,18 +315,131 @@ impl Node for MainPassUINode {
 
         // Regular pass
         {
-            let pass_descriptor = RenderPassDescriptor {
-                label: Some("main_transparent_pass_UI"),
-                color_attachments: &[Some(target.get_unsampled_color_attachment(Operations {
-                    load: LoadOp::Load,
-                    store: true,
-                }))],
-                depth_stencil_attachment: None,
-            };
-            let mut tracked_pass = render_context.begin_tracked_render_pass(pass_descriptor);
-            transparent_phase.render(&mut tracked_pass, world, view_entity);
+            let draw_functions = world.resource::<DrawFunctions<TransparentUI>>();
+            let mut draw_functions = draw_functions.write();
+            draw_functions.prepare(world);
+
+            let items = transparent_phase.items.iter().collect::<Vec<_>>();
+            let mut clip: Option<&TransparentUI> = None;
+            for items in split_at_backdrops(&items) {
+                if starts_with_backdrop(items) {
+                    copy_backdrop(
+                        render_context,
+                        world,
+                        blit_pipeline,
+                        target.main_texture_view(),
+                        backdrop,
+                    );
+                }
+
+                let pass_descriptor = RenderPassDescriptor {
+                    label: Some("main_transparent_pass_UI"),
+                    color_attachments: &[Some(target.get_unsampled_color_attachment(Operations {
+                        load: LoadOp::Load,
+                        store: true,
+                    }))],
+                    depth_stencil_attachment: None,
+                };
+                let mut tracked_pass = render_context.begin_tracked_render_pass(pass_descriptor);
+                if let Some(viewport) = viewport {
+                    tracked_pass.set_camera_viewport(viewport);
+                }
+                // A new pass starts without a scissor rect, so restore the last clip.
+                if let Some(clip) = clip {
+                    let draw_function = draw_functions.get_mut(clip.draw_function()).unwrap();
+                    draw_function.draw(world, &mut tracked_pass, view_entity, clip);
+                }
+
+                for item in items {
+                    let draw_function = draw_functions.get_mut(item.draw_function()).unwrap();
+                    draw_function.draw(world, &mut tracked_pass, view_entity, *item);
+                }
+                clip = last_clip(items).or(clip);
+            }
         }
 
         Ok(())
     }
 }
+
+/// Splits phase items into runs that are drawn in separate render passes
+///
+/// A new run starts at every backdrop blur, so the render target can be copied before it.
+fn split_at_backdrops<'a, I: TransparentUIGeneric>(
+    items: &'a [&'a I],
+) -> impl Iterator<Item = &'a [&'a I]> {
+    let mut starts = items
+        .iter()
+        .enumerate()
+        .filter(|(i, item)| *i > 0 && item.get_quad_type() == UIQuadType::BackdropBlur)
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    starts.insert(0, 0);
+    starts.push(items.len());
+    (0..starts.len() - 1).map(move |i| &items[starts[i]..starts[i + 1]])
+}
+
+/// Returns the last clip in a run, whose scissor rect is still in effect at the end of it
+fn last_clip<'a, I: TransparentUIGeneric>(items: &[&'a I]) -> Option<&'a I> {
+    items
+        .iter()
+        .rev()
+        .find(|item| item.get_quad_type() == UIQuadType::Clip)
+        .copied()
+}
+
+fn starts_with_backdrop<I: TransparentUIGeneric>(items: &[&I]) -> bool {
+    items.first().map_or(false, |item| {
+        item.get_quad_type() == UIQuadType::BackdropBlur
+    })
+}
+
+/// Copies a render target into the backdrop texture sampled by backdrop blurs
+fn copy_backdrop(
+    render_context: &mut RenderContext,
+    world: &World,
+    blit_pipeline: Option<&BackdropBlitPipeline>,
+    source: &TextureView,
+    backdrop: Option<&TextureView>,
+) {
+    let pipeline = blit_pipeline.and_then(|blit_pipeline| {
+        world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(blit_pipeline.0)
+    });
+    if let (Some(pipeline), Some(backdrop)) = (pipeline, backdrop) {
+        let blit = world.resource::<BlitPipeline>();
+        let bind_group = render_context
+            .render_device()
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("ui_backdrop_bind_group"),
+                layout: &blit.texture_bind_group,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(source),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::Sampler(&blit.sampler),
+                    },
+                ],
+            });
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("ui_backdrop_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: backdrop,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::rgba(0.0, 0.0, 0.0, 0.0).into()),
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
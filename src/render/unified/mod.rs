@@ -1,7 +1,7 @@
 use bevy::{
     prelude::{
         AddAsset, Assets, Commands, HandleUntyped, IntoSystemConfigs, Plugin, Query, Res, ResMut,
-        Resource, With,
+        Resource, Update, With,
     },
     reflect::TypeUuid,
     render::{
@@ -19,7 +19,7 @@ use crate::{
         ui_pass::TransparentUI,
         unified::pipeline::{DrawUI, QuadMeta, UnifiedPipeline},
     },
-    WindowSize,
+    KayakUiScale, WindowSize,
 };
 
 use self::pipeline::{
@@ -27,7 +27,11 @@ use self::pipeline::{
     ImageBindGroups, PreviousClip, PreviousIndex, QuadTypeOffsets,
 };
 
-use super::{svg::RenderSvgs, ui_pass::TransparentOpacityUI};
+use super::{
+    mesh::{extract_mesh_asset, RenderMeshes},
+    svg::{tessellate_svgs, RenderSvgs, SvgLoader},
+    ui_pass::TransparentOpacityUI,
+};
 
 pub mod pipeline;
 pub mod text;
@@ -47,7 +51,10 @@ pub const VERTEX_OUTPUT_HANDLE: HandleUntyped =
 pub struct UnifiedRenderPlugin;
 impl Plugin for UnifiedRenderPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_asset::<Svg>().add_plugins(text::TextRendererPlugin);
+        app.add_asset::<Svg>()
+            .init_asset_loader::<SvgLoader>()
+            .add_systems(Update, tessellate_svgs)
+            .add_plugins(text::TextRendererPlugin);
 
         let mut shaders = app.world.get_resource_mut::<Assets<Shader>>().unwrap();
         let bindings_include = Shader::from_wgsl(
@@ -76,11 +83,16 @@ impl Plugin for UnifiedRenderPlugin {
             .init_resource::<ImageBindGroups>()
             .init_resource::<QuadMeta>()
             .init_resource::<RenderSvgs>()
+            .init_resource::<RenderMeshes>()
             .init_resource::<PreviousClip>()
             .init_resource::<PreviousIndex>()
             .add_systems(
                 ExtractSchedule,
-                (super::svg::extract_svg_asset, extract_baseline),
+                (
+                    super::svg::extract_svg_asset,
+                    extract_mesh_asset,
+                    extract_baseline,
+                ),
             )
             .add_systems(
                 Render,
@@ -104,19 +116,23 @@ impl Plugin for UnifiedRenderPlugin {
     }
 }
 
+/// The scale factor of the primary window, multiplied by the [`KayakUiScale`]
+///
+/// Cameras can render to other windows, each camera's view keeps its own scale factor.
 #[derive(Resource)]
-pub struct Dpi(f32);
+pub struct Dpi(pub f32);
 
 pub fn extract_baseline(
     mut commands: Commands,
     windows: Extract<Query<&Window, With<PrimaryWindow>>>,
     window_size: Extract<Res<WindowSize>>,
+    ui_scale: Extract<Res<KayakUiScale>>,
 ) {
     let dpi = if let Ok(window) = windows.get_single() {
         window.scale_factor() as f32
     } else {
         1.0
-    };
+    } * ui_scale.get();
 
     commands.insert_resource(**window_size);
     commands.insert_resource(Dpi(dpi));
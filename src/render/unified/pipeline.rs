@@ -3,9 +3,8 @@ use std::marker::PhantomData;
 use bevy::asset::HandleId;
 use bevy::ecs::query::ROQueryItem;
 use bevy::ecs::system::{SystemParam, SystemParamItem};
-use bevy::prelude::{Commands, Mesh, Rect, Resource, Vec3, With};
+use bevy::prelude::{Commands, Mesh, Rect, Resource, With};
 use bevy::render::globals::{GlobalsBuffer, GlobalsUniform};
-use bevy::render::mesh::VertexAttributeValues;
 use bevy::render::render_phase::{
     BatchedPhaseItem, DrawFunctionId, PhaseItem, RenderCommand, RenderCommandResult,
     SetItemPipeline,
@@ -18,20 +17,20 @@ use bevy::render::view::ViewTarget;
 use bevy::utils::FloatOrd;
 use bevy::{
     ecs::system::lifetimeless::{Read, SRes},
-    math::{Mat4, Quat, Vec2, Vec4},
+    math::{Mat4, Quat, Vec2, Vec3, Vec4},
     prelude::{Component, Entity, FromWorld, Handle, Query, Res, ResMut, World},
     render::{
         color::Color,
         render_asset::RenderAssets,
         render_phase::{DrawFunctions, RenderPhase, TrackedRenderPass},
         render_resource::{
-            BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+            AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
             BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
-            BlendState, BufferBindingType, BufferSize, BufferUsages, BufferVec, ColorTargetState,
-            ColorWrites, Extent3d, FragmentState, FrontFace, MultisampleState, PipelineCache,
-            PolygonMode, PrimitiveState, PrimitiveTopology, RenderPipelineDescriptor,
+            BlendState, BufferBindingType, BufferUsages, BufferVec, ColorTargetState, ColorWrites,
+            Extent3d, FilterMode, FragmentState, FrontFace, MultisampleState, PipelineCache,
+            PolygonMode, PrimitiveState, PrimitiveTopology, RenderPipelineDescriptor, Sampler,
             SamplerBindingType, SamplerDescriptor, Shader, ShaderStages, TextureDescriptor,
-            TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+            TextureDimension, TextureFormat, TextureId, TextureSampleType, TextureUsages,
             TextureViewDescriptor, TextureViewDimension, VertexAttribute, VertexBufferLayout,
             VertexFormat, VertexState, VertexStepMode,
         },
@@ -45,11 +44,13 @@ use bytemuck::{Pod, Zeroable};
 use kayak_font::{bevy::FontTextureCache, KayakFont};
 
 use super::UNIFIED_SHADER_HANDLE;
-use crate::prelude::Corner;
+use crate::prelude::{Corner, Edge};
 use crate::render::extract::{UIExtractedView, UIViewUniform, UIViewUniformOffset, UIViewUniforms};
+use crate::render::mesh::RenderMeshes;
 use crate::render::opacity_layer::OpacityLayerManager;
 use crate::render::svg::RenderSvgs;
 use crate::render::ui_pass::{TransparentOpacityUI, TransparentUI, TransparentUIGeneric};
+use crate::styles::{BoxShadow, Gradient, GradientKind};
 
 #[derive(Resource, Clone)]
 pub struct UnifiedPipeline {
@@ -58,6 +59,8 @@ pub struct UnifiedPipeline {
     pub image_layout: BindGroupLayout,
     empty_font_texture: GpuImage,
     default_image: (GpuImage, BindGroup),
+    /// Used instead of an image's own sampler when it is tiled
+    repeat_sampler: Sampler,
 }
 
 // const QUAD_VERTEX_POSITIONS: &[Vec3] = &[
@@ -122,9 +125,7 @@ impl FromWorld for UnifiedPipeline {
                 ty: BindingType::Buffer {
                     ty: BufferBindingType::Uniform,
                     has_dynamic_offset: true,
-                    // TODO: change this to ViewUniform::std140_size_static once crevice fixes this!
-                    // Context: https://github.com/LPGhatguy/crevice/issues/29
-                    min_binding_size: BufferSize::new(16),
+                    min_binding_size: Some(QuadType::min_size()),
                 },
                 count: None,
             }],
@@ -234,12 +235,22 @@ impl FromWorld for UnifiedPipeline {
             layout: &image_layout,
         });
 
+        let repeat_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("ui_repeat_sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
         UnifiedPipeline {
             view_layout,
             empty_font_texture,
             types_layout,
             image_layout,
             default_image: (image, binding),
+            repeat_sampler,
         }
     }
 }
@@ -249,7 +260,7 @@ impl SpecializedRenderPipeline for UnifiedPipeline {
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
         let vertex_buffer_layout = VertexBufferLayout {
-            array_stride: 60,
+            array_stride: 108,
             step_mode: VertexStepMode::Vertex,
             attributes: vec![
                 VertexAttribute {
@@ -263,15 +274,35 @@ impl SpecializedRenderPipeline for UnifiedPipeline {
                     shader_location: 1,
                 },
                 VertexAttribute {
-                    format: VertexFormat::Float32x4,
+                    format: VertexFormat::Float32x3,
                     offset: 28,
                     shader_location: 2,
                 },
                 VertexAttribute {
                     format: VertexFormat::Float32x4,
-                    offset: 44,
+                    offset: 40,
                     shader_location: 3,
                 },
+                VertexAttribute {
+                    format: VertexFormat::Float32,
+                    offset: 56,
+                    shader_location: 4,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 60,
+                    shader_location: 5,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 76,
+                    shader_location: 6,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x4,
+                    offset: 92,
+                    shader_location: 7,
+                },
             ],
         };
 
@@ -338,12 +369,17 @@ impl SpecializedRenderPipeline for UnifiedPipeline {
 pub enum UIQuadType {
     Quad,
     BoxShadow,
+    RadialFill,
+    Gradient,
+    InsetBoxShadow,
     Text,
     TextSubpixel,
     Image,
     Clip,
     OpacityLayer,
     DrawOpacityLayer,
+    /// Draws a blurred copy of what is behind the quad
+    BackdropBlur,
     None,
 }
 
@@ -361,8 +397,29 @@ pub struct ExtractedQuad {
     pub image: Option<Handle<Image>>,
     pub uv_min: Option<Vec2>,
     pub uv_max: Option<Vec2>,
+    /// Whether the image is sampled with a repeating sampler
+    ///
+    /// Used for tiled images, whose uvs go beyond `0..1`.
+    pub repeat_image: bool,
     pub svg_handle: (Option<Handle<Svg>>, Option<Color>),
+    /// The mesh drawn stretched over the quad's rect, tinted by its color
+    pub mesh_handle: Option<Handle<Mesh>>,
+    /// The clockwise rotation in radians around the center of the quad
+    ///
+    /// Only used by svg quads.
+    pub rotation: f32,
     pub opacity_layer: u32,
+    pub shader_param: f32,
+    /// The global band this quad is sorted into before its z-index
+    pub render_layer: i32,
+    /// The distance (in physical pixels) over which content fades out at each edge of a clip
+    ///
+    /// Only used by [`UIQuadType::Clip`] quads.
+    pub edge_fade: Edge<f32>,
+    /// Only used by [`UIQuadType::Gradient`] quads.
+    pub gradient: Option<Gradient>,
+    /// Only used by [`UIQuadType::InsetBoxShadow`] quads.
+    pub box_shadow: Option<BoxShadow>,
 }
 
 impl Default for ExtractedQuad {
@@ -380,8 +437,16 @@ impl Default for ExtractedQuad {
             image: Default::default(),
             uv_min: Default::default(),
             uv_max: Default::default(),
+            repeat_image: false,
             svg_handle: Default::default(),
+            mesh_handle: None,
+            rotation: 0.0,
             opacity_layer: 0,
+            shader_param: 0.0,
+            render_layer: 0,
+            edge_fade: Edge::default(),
+            gradient: None,
+            box_shadow: None,
         }
     }
 }
@@ -391,20 +456,74 @@ impl Default for ExtractedQuad {
 pub struct QuadVertex {
     pub position: [f32; 3],
     pub color: [f32; 4],
-    pub uv: [f32; 4],
+    pub uv: [f32; 3],
     pub pos_size: [f32; 4],
+    pub shader_param: f32,
+    /// The active clip rect in physical pixels as (min x, min y, max x, max y)
+    pub clip_rect: [f32; 4],
+    /// The fade distances of the active clip as (top, right, bottom, left)
+    pub edge_fade: [f32; 4],
+    /// The corner radii of the quad as (top left, top right, bottom right, bottom left)
+    pub border_radius: [f32; 4],
 }
 
 unsafe impl Zeroable for QuadVertex {}
 unsafe impl Pod for QuadVertex {}
 
 #[repr(C)]
-#[derive(Copy, Clone, ShaderType)]
+#[derive(Copy, Clone, Default, ShaderType)]
 struct QuadType {
     pub t: i32,
-    pub _padding_1: i32,
-    pub _padding_2: i32,
-    pub _padding_3: i32,
+    pub gradient_kind: i32,
+    pub gradient_stop_count: i32,
+    pub _padding: i32,
+    /// Per quad parameters
+    ///
+    /// Linear gradient: (angle in radians, 0, 0, 0). Radial gradient: (center x, center y, radius, 0).
+    /// Inset box shadow: (offset x, offset y, spread x, spread y).
+    pub params: Vec4,
+    /// Linear RGBA colors of the gradient stops
+    pub gradient_colors: [Vec4; Gradient::MAX_STOPS],
+    /// Gradient stop offsets, packed four to a vector
+    pub gradient_offsets: [Vec4; Gradient::MAX_STOPS / 4],
+}
+
+impl QuadType {
+    fn gradient(gradient: &Gradient) -> Self {
+        let mut quad_type = QuadType {
+            t: 6,
+            ..Default::default()
+        };
+        match gradient.kind {
+            GradientKind::Linear { angle } => {
+                quad_type.gradient_kind = 0;
+                quad_type.params = Vec4::new(angle.to_radians(), 0.0, 0.0, 0.0);
+            }
+            GradientKind::Radial { center, radius } => {
+                quad_type.gradient_kind = 1;
+                quad_type.params = Vec4::new(center.x, center.y, radius, 0.0);
+            }
+        }
+        for (i, stop) in gradient.stops.iter().take(Gradient::MAX_STOPS).enumerate() {
+            quad_type.gradient_colors[i] = Vec4::from(stop.color.as_linear_rgba_f32());
+            quad_type.gradient_offsets[i / 4][i % 4] = stop.offset;
+            quad_type.gradient_stop_count = i as i32 + 1;
+        }
+        quad_type
+    }
+
+    fn inset_box_shadow(box_shadow: &BoxShadow) -> Self {
+        QuadType {
+            t: 7,
+            params: Vec4::new(
+                box_shadow.offset.x,
+                box_shadow.offset.y,
+                box_shadow.spread.x,
+                box_shadow.spread.y,
+            ),
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Resource)]
@@ -433,15 +552,23 @@ pub struct ExtractedQuads {
 pub struct QuadBatch {
     pub image_handle_id: Option<HandleId>,
     pub font_handle_id: Option<HandleId>,
+    /// Tiled images are bound with a repeating sampler, so they can't share a batch with the
+    /// same image drawn stretched.
+    pub repeat_image: bool,
     pub quad_type: UIQuadType,
     pub type_id: u32,
     pub z_index: f32,
+    /// Batches never span opacity layers since each layer is drawn into its own render target.
+    pub opacity_layer: u32,
 }
 
 #[derive(Default, Resource)]
 pub struct ImageBindGroups {
     values: HashMap<Handle<Image>, BindGroup>,
+    repeat_values: HashMap<Handle<Image>, BindGroup>,
     font_values: HashMap<Handle<KayakFont>, BindGroup>,
+    /// The texture each font bind group was created for, since runtime fonts grow their texture
+    font_textures: HashMap<Handle<KayakFont>, TextureId>,
     previous_sizes: HashMap<Handle<Image>, Vec2>,
 }
 
@@ -491,6 +618,8 @@ pub struct QuadTypeOffsets {
     pub text_type_offset: u32,
     pub image_type_offset: u32,
     pub box_shadow_type_offset: u32,
+    pub radial_fill_type_offset: u32,
+    pub backdrop_blur_type_offset: u32,
 }
 
 pub fn queue_quad_types(
@@ -499,38 +628,37 @@ pub fn queue_quad_types(
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut quad_meta: ResMut<QuadMeta>,
+    mut extracted_quads: ResMut<ExtractedQuads>,
 ) {
     quad_meta.types_buffer.clear();
     // sprite_meta.types_buffer.reserve(2, &render_device);
     let quad_type_offset = quad_meta.types_buffer.push(QuadType {
         t: 0,
-        _padding_1: 0,
-        _padding_2: 0,
-        _padding_3: 0,
+        ..Default::default()
     });
     let text_sub_pixel_type_offset = quad_meta.types_buffer.push(QuadType {
         t: 1,
-        _padding_1: 0,
-        _padding_2: 0,
-        _padding_3: 0,
+        ..Default::default()
     });
     let text_type_offset = quad_meta.types_buffer.push(QuadType {
         t: 2,
-        _padding_1: 0,
-        _padding_2: 0,
-        _padding_3: 0,
+        ..Default::default()
     });
     let image_type_offset = quad_meta.types_buffer.push(QuadType {
         t: 3,
-        _padding_1: 0,
-        _padding_2: 0,
-        _padding_3: 0,
+        ..Default::default()
     });
     let box_shadow_type_offset = quad_meta.types_buffer.push(QuadType {
         t: 4,
-        _padding_1: 0,
-        _padding_2: 0,
-        _padding_3: 0,
+        ..Default::default()
+    });
+    let radial_fill_type_offset = quad_meta.types_buffer.push(QuadType {
+        t: 5,
+        ..Default::default()
+    });
+    let backdrop_blur_type_offset = quad_meta.types_buffer.push(QuadType {
+        t: 8,
+        ..Default::default()
     });
     let quad_type_offsets = QuadTypeOffsets {
         quad_type_offset,
@@ -538,9 +666,23 @@ pub fn queue_quad_types(
         text_type_offset,
         image_type_offset,
         box_shadow_type_offset,
+        radial_fill_type_offset,
+        backdrop_blur_type_offset,
     };
     commands.insert_resource(quad_type_offsets);
 
+    // Gradients and inset shadows carry their own parameters so each one gets its own entry.
+    for quad in extracted_quads.quads.iter_mut() {
+        let quad_type = match (quad.quad_type, &quad.gradient, &quad.box_shadow) {
+            (UIQuadType::Gradient, Some(gradient), _) => QuadType::gradient(gradient),
+            (UIQuadType::InsetBoxShadow, _, Some(box_shadow)) => {
+                QuadType::inset_box_shadow(box_shadow)
+            }
+            _ => continue,
+        };
+        quad.type_index = quad_meta.types_buffer.push(quad_type);
+    }
+
     quad_meta
         .types_buffer
         .write_buffer(&render_device, &render_queue);
@@ -560,6 +702,7 @@ pub fn queue_quad_types(
 #[derive(Resource, Default)]
 pub struct PreviousClip {
     pub rect: Rect,
+    pub edge_fade: Edge<f32>,
 }
 
 #[derive(Resource, Default)]
@@ -570,6 +713,7 @@ pub struct PreviousIndex {
 #[derive(SystemParam)]
 pub struct QueueQuads<'w, 's> {
     render_svgs: Res<'w, RenderSvgs>,
+    render_meshes: Res<'w, RenderMeshes>,
     opacity_layers: Res<'w, OpacityLayerManager>,
     commands: Commands<'w, 's>,
     draw_functions: Res<'w, DrawFunctions<TransparentUI>>,
@@ -602,6 +746,7 @@ pub struct QueueQuads<'w, 's> {
 pub fn queue_quads(queue_quads: QueueQuads) {
     let QueueQuads {
         render_svgs,
+        render_meshes,
         opacity_layers,
         mut commands,
         draw_functions,
@@ -634,17 +779,23 @@ pub fn queue_quads(queue_quads: QueueQuads) {
         &render_device,
     );
 
-    // Sort sprites by z for correct transparency and then by handle to improve batching
+    // Sort sprites by render layer and z for correct transparency and then by handle to improve batching
     // NOTE: This can be done independent of views by reasonably assuming that all 2D views look along the negative-z axis in world space
     let extracted_quads = &mut extracted_quads.quads;
-    extracted_quads.sort_unstable_by(|a, b| a.z_index.partial_cmp(&b.z_index).unwrap());
+    extracted_quads.sort_unstable_by(|a, b| {
+        a.render_layer
+            .cmp(&b.render_layer)
+            .then(a.z_index.partial_cmp(&b.z_index).unwrap())
+    });
 
     let mut current_batch = QuadBatch {
         image_handle_id: None,
         font_handle_id: None,
+        repeat_image: false,
         quad_type: UIQuadType::None,
         type_id: quad_type_offsets.quad_type_offset,
         z_index: -999.0,
+        opacity_layer: 0,
     };
     let mut current_batch_entity = Entity::PLACEHOLDER;
 
@@ -668,6 +819,23 @@ pub fn queue_quads(queue_quads: QueueQuads) {
         for quad in extracted_quads.iter_mut() {
             if quad.quad_type == UIQuadType::Clip {
                 prev_clip.rect = quad.rect;
+                prev_clip.edge_fade = quad.edge_fade;
+            }
+
+            // Opacity layers are the size of the window, only the camera's viewport is drawn.
+            if quad.quad_type == UIQuadType::DrawOpacityLayer && quad.camera_entity == camera_entity
+            {
+                if let Some(layer_size) = opacity_layers
+                    .camera_layers
+                    .get(&camera_entity)
+                    .and_then(|layer| gpu_images.get(&layer.get_image_handle(quad.opacity_layer)))
+                    .map(|gpu_image| gpu_image.size)
+                {
+                    let origin = Vec2::new(view.viewport.x as f32, view.viewport.y as f32);
+                    let size = Vec2::new(view.viewport.z as f32, view.viewport.w as f32);
+                    quad.uv_min = Some(origin / layer_size);
+                    quad.uv_max = Some((origin + size) / layer_size);
+                }
             }
 
             if prev_clip.rect.width() < 1.0 || prev_clip.rect.height() < 1.0 {
@@ -682,6 +850,7 @@ pub fn queue_quads(queue_quads: QueueQuads) {
                 &gpu_images,
                 &unified_pipeline,
                 &render_svgs,
+                &render_meshes,
                 &mut transparent_phase,
                 &mut opacity_transparent_phase,
                 draw_opacity_quad,
@@ -689,6 +858,7 @@ pub fn queue_quads(queue_quads: QueueQuads) {
                 spec_pipeline,
                 &mut quad_meta,
                 quad,
+                &prev_clip,
                 camera_entity,
                 *quad_type_offsets,
                 &mut current_batch,
@@ -701,6 +871,20 @@ pub fn queue_quads(queue_quads: QueueQuads) {
     prev_index.index = index;
 }
 
+/// Packs the corner radii of a quad in the order the shader reads them: top left, top right,
+/// bottom right, bottom left
+///
+/// Every vertex of the quad gets the same radii, so the shader can pick the nearest corner
+/// without the radii being interpolated into each other.
+fn vertex_border_radius(radius: &Corner<f32>) -> [f32; 4] {
+    [
+        radius.top_left,
+        radius.top_right,
+        radius.bottom_right,
+        radius.bottom_left,
+    ]
+}
+
 pub fn queue_quads_inner(
     commands: &mut Commands,
     render_device: &RenderDevice,
@@ -710,6 +894,7 @@ pub fn queue_quads_inner(
     gpu_images: &RenderAssets<Image>,
     unified_pipeline: &UnifiedPipeline,
     render_svgs: &RenderSvgs,
+    render_meshes: &RenderMeshes,
     transparent_phase: &mut RenderPhase<TransparentUI>,
     opacity_transparent_phase: &mut RenderPhase<TransparentOpacityUI>,
     draw_opacity_quad: DrawFunctionId,
@@ -717,6 +902,7 @@ pub fn queue_quads_inner(
     spec_pipeline: CachedRenderPipelineId,
     quad_meta: &mut QuadMeta,
     quad: &mut ExtractedQuad,
+    clip: &PreviousClip,
     camera_entity: Entity,
     quad_type_offsets: QuadTypeOffsets,
     current_batch: &mut QuadBatch,
@@ -733,10 +919,14 @@ pub fn queue_quads_inner(
         UIQuadType::TextSubpixel => quad.type_index = quad_type_offsets.text_sub_pixel_type_offset,
         UIQuadType::Image => quad.type_index = quad_type_offsets.image_type_offset,
         UIQuadType::BoxShadow => quad.type_index = quad_type_offsets.box_shadow_type_offset,
+        UIQuadType::RadialFill => quad.type_index = quad_type_offsets.radial_fill_type_offset,
+        // Assigned per quad in `queue_quad_types`.
+        UIQuadType::Gradient | UIQuadType::InsetBoxShadow => {}
         UIQuadType::Clip => quad.type_index = 100000,
         UIQuadType::None => quad.type_index = 100001,
         UIQuadType::OpacityLayer => quad.type_index = 100002,
         UIQuadType::DrawOpacityLayer => quad.type_index = quad_type_offsets.image_type_offset,
+        UIQuadType::BackdropBlur => quad.type_index = quad_type_offsets.backdrop_blur_type_offset,
     };
 
     // Ignore opacity layers
@@ -747,48 +937,56 @@ pub fn queue_quads_inner(
     let mut new_batch = QuadBatch {
         image_handle_id: quad.image.clone().map(HandleId::from),
         font_handle_id: quad.font_handle.clone().map(HandleId::from),
+        repeat_image: quad.repeat_image,
         quad_type: quad.quad_type,
         type_id: quad.type_index,
         z_index: 0.0, // z_index: quad.z_index,
+        opacity_layer: quad.opacity_layer,
     };
 
     if new_batch != *current_batch
         || matches!(quad.quad_type, UIQuadType::Clip)
         || matches!(quad.quad_type, UIQuadType::DrawOpacityLayer)
+        || matches!(quad.quad_type, UIQuadType::BackdropBlur)
     {
         if let Some(image_handle) = quad.image.as_ref() {
             if let Some(gpu_image) = gpu_images.get(image_handle) {
-                image_bind_groups
-                    .values
-                    .entry(image_handle.clone_weak())
-                    .or_insert_with(|| {
-                        render_device.create_bind_group(&BindGroupDescriptor {
-                            entries: &[
-                                BindGroupEntry {
-                                    binding: 0,
-                                    resource: BindingResource::TextureView(&gpu_image.texture_view),
-                                },
-                                BindGroupEntry {
-                                    binding: 1,
-                                    resource: BindingResource::Sampler(&gpu_image.sampler),
-                                },
-                                BindGroupEntry {
-                                    binding: 2,
-                                    resource: BindingResource::TextureView(
-                                        &unified_pipeline.empty_font_texture.texture_view,
-                                    ),
-                                },
-                                BindGroupEntry {
-                                    binding: 3,
-                                    resource: BindingResource::Sampler(
-                                        &unified_pipeline.empty_font_texture.sampler,
-                                    ),
-                                },
-                            ],
-                            label: Some("ui_image_bind_group"),
-                            layout: &unified_pipeline.image_layout,
-                        })
-                    });
+                let (values, sampler) = if quad.repeat_image {
+                    (
+                        &mut image_bind_groups.repeat_values,
+                        &unified_pipeline.repeat_sampler,
+                    )
+                } else {
+                    (&mut image_bind_groups.values, &gpu_image.sampler)
+                };
+                values.entry(image_handle.clone_weak()).or_insert_with(|| {
+                    render_device.create_bind_group(&BindGroupDescriptor {
+                        entries: &[
+                            BindGroupEntry {
+                                binding: 0,
+                                resource: BindingResource::TextureView(&gpu_image.texture_view),
+                            },
+                            BindGroupEntry {
+                                binding: 1,
+                                resource: BindingResource::Sampler(sampler),
+                            },
+                            BindGroupEntry {
+                                binding: 2,
+                                resource: BindingResource::TextureView(
+                                    &unified_pipeline.empty_font_texture.texture_view,
+                                ),
+                            },
+                            BindGroupEntry {
+                                binding: 3,
+                                resource: BindingResource::Sampler(
+                                    &unified_pipeline.empty_font_texture.sampler,
+                                ),
+                            },
+                        ],
+                        label: Some("ui_image_bind_group"),
+                        layout: &unified_pipeline.image_layout,
+                    })
+                });
             } else {
                 // Skip unloaded texture.
                 return;
@@ -798,6 +996,14 @@ pub fn queue_quads_inner(
         if let Some(font_handle) = quad.font_handle.as_ref() {
             if let Some(gpu_image) = font_texture_cache.get_gpu_image(font_handle, gpu_images) {
                 new_batch.image_handle_id = Some(font_handle.id());
+                let texture_id = gpu_image.texture.id();
+                if image_bind_groups
+                    .font_textures
+                    .insert(font_handle.clone_weak(), texture_id)
+                    != Some(texture_id)
+                {
+                    image_bind_groups.font_values.remove(font_handle);
+                }
                 image_bind_groups
                     .font_values
                     .entry(font_handle.clone_weak())
@@ -835,10 +1041,22 @@ pub fn queue_quads_inner(
         // Start new batch
         *current_batch = new_batch;
 
-        if quad.quad_type == UIQuadType::DrawOpacityLayer {
+        if matches!(
+            quad.quad_type,
+            UIQuadType::DrawOpacityLayer | UIQuadType::BackdropBlur
+        ) {
             if let Some(layer) = opacity_layers.camera_layers.get(&camera_entity) {
-                let image_handle = layer.get_image_handle(quad.opacity_layer);
-                if let Some(gpu_image) = gpu_images.get(&image_handle) {
+                // Backdrop blurs sample a copy of what was drawn before them, see `MainPassUINode`.
+                let image_handle = if quad.quad_type == UIQuadType::BackdropBlur {
+                    layer.get_backdrop_handle()
+                } else {
+                    Some(layer.get_image_handle(quad.opacity_layer))
+                };
+                if let Some((image_handle, gpu_image)) = image_handle.and_then(|image_handle| {
+                    gpu_images
+                        .get(&image_handle)
+                        .map(|gpu_image| (image_handle, gpu_image))
+                }) {
                     let new_image = if let Some(prev_size) =
                         image_bind_groups.previous_sizes.get(&image_handle)
                     {
@@ -896,8 +1114,12 @@ pub fn queue_quads_inner(
                 } else {
                     return;
                 }
+            } else if quad.quad_type == UIQuadType::BackdropBlur {
+                return;
+            }
+            if quad.quad_type == UIQuadType::DrawOpacityLayer {
+                quad.opacity_layer = 0;
             }
-            quad.opacity_layer = 0;
         }
 
         *current_batch_entity = commands.spawn(*current_batch).id();
@@ -906,54 +1128,82 @@ pub fn queue_quads_inner(
 
     let sprite_rect = quad.rect;
     let item_start = *index;
+    let clip_rect = [
+        clip.rect.min.x,
+        clip.rect.min.y,
+        clip.rect.max.x,
+        clip.rect.max.y,
+    ];
+    let edge_fade = [
+        clip.edge_fade.top,
+        clip.edge_fade.right,
+        clip.edge_fade.bottom,
+        clip.edge_fade.left,
+    ];
     let mut item_end = *index;
 
     if let (Some(svg_handle), color) = (quad.svg_handle.0.as_ref(), quad.svg_handle.1.as_ref()) {
-        if let Some((svg, mesh)) = render_svgs.get(svg_handle) {
-            let new_height = (svg.view_box.h as f32 / svg.view_box.w as f32) * sprite_rect.size().x;
-            let svg_scale_x = sprite_rect.size().x / svg.view_box.w as f32;
-            let svg_scale_y = new_height / svg.view_box.h as f32;
-            let positions = mesh
-                .attribute(Mesh::ATTRIBUTE_POSITION)
-                .unwrap()
-                .as_float3()
-                .unwrap();
-            let colors = match mesh.attribute(Mesh::ATTRIBUTE_COLOR).unwrap() {
-                VertexAttributeValues::Float32x4(d) => Some(d),
-                _ => None,
+        if let Some(render_svg) = render_svgs.get(svg_handle) {
+            // Fit the svg within the node, keeping its aspect ratio and centering it
+            let svg_scale = (sprite_rect.size() / render_svg.view_box_size).min_element();
+            let svg_offset = (sprite_rect.size() - render_svg.view_box_size * svg_scale) / 2.0;
+            let svg_origin = sprite_rect.min + svg_offset;
+            let color = color.map(|color| [color.r(), color.g(), color.b(), color.a()]);
+            let tint = [
+                quad.color.r(),
+                quad.color.g(),
+                quad.color.b(),
+                quad.color.a(),
+            ];
+            let rotation = Vec2::from_angle(quad.rotation);
+            let center = render_svg.view_box_size / 2.0;
+
+            for (position, vertex_color) in render_svg.vertices.iter() {
+                let position = rotation.rotate(*position - center) + center;
+                let final_position = (svg_origin + position * svg_scale).extend(0.0);
+
+                quad_meta.vertices.push(QuadVertex {
+                    position: final_position.into(),
+                    color: {
+                        let [r, g, b, a] = color.unwrap_or(*vertex_color);
+                        [r * tint[0], g * tint[1], b * tint[2], a * tint[3]]
+                    },
+                    uv: [0.0; 3],
+                    pos_size: [0.0, 0.0, sprite_rect.size().x, sprite_rect.size().y],
+                    shader_param: quad.shader_param,
+                    clip_rect,
+                    edge_fade,
+                    border_radius: [0.0; 4],
+                });
             }
-            .unwrap();
-            let indices = mesh.indices().unwrap();
+            *index += render_svg.vertices.len() as u32;
+            item_end = *index;
+        }
+    } else if let Some(mesh_handle) = quad.mesh_handle.as_ref() {
+        if let Some(render_mesh) = render_meshes.get(mesh_handle) {
+            // Stretch the bounds of the mesh over the node
+            let mesh_scale = sprite_rect.size() / render_mesh.size;
+            let tint = quad.color.as_linear_rgba_f32();
 
-            for index in indices.iter() {
-                let position = positions[index];
-                let color = if let Some(color) = color {
-                    [color.r(), color.g(), color.b(), color.a()]
-                } else {
-                    colors[index]
-                };
-                let world = Mat4::from_scale_rotation_translation(
-                    Vec3::new(svg_scale_x, svg_scale_y, 1.0), //sprite_rect.size().extend(1.0),
-                    Quat::default(),
-                    sprite_rect.min.extend(0.0),
-                );
-                let final_position = (world
-                    * Vec4::new(
-                        position[0],  // - 34.5,
-                        -position[1], // - 95.0,
-                        position[2],
-                        1.0,
-                    ))
-                .truncate();
+            for (position, vertex_color) in render_mesh.vertices.iter() {
+                let final_position =
+                    (sprite_rect.min + (*position - render_mesh.min) * mesh_scale).extend(0.0);
 
                 quad_meta.vertices.push(QuadVertex {
                     position: final_position.into(),
-                    color,
-                    uv: [0.0; 4],
+                    color: {
+                        let [r, g, b, a] = *vertex_color;
+                        [r * tint[0], g * tint[1], b * tint[2], a * tint[3]]
+                    },
+                    uv: [0.0; 3],
                     pos_size: [0.0, 0.0, sprite_rect.size().x, sprite_rect.size().y],
+                    shader_param: quad.shader_param,
+                    clip_rect,
+                    edge_fade,
+                    border_radius: [0.0; 4],
                 });
             }
-            *index += indices.len() as u32;
+            *index += render_mesh.vertices.len() as u32;
             item_end = *index;
         }
     } else {
@@ -962,32 +1212,13 @@ pub fn queue_quads_inner(
         let uv_min = quad.uv_min.unwrap_or(Vec2::ZERO);
         let uv_max = quad.uv_max.unwrap_or(Vec2::ONE);
 
-        let bottom_left = Vec4::new(
-            uv_min.x,
-            uv_min.y,
-            quad.char_id as f32,
-            quad.border_radius.bottom_left,
-        );
-        let top_left = Vec4::new(
-            uv_min.x,
-            uv_max.y,
-            quad.char_id as f32,
-            quad.border_radius.top_left,
-        );
-        let top_right = Vec4::new(
-            uv_max.x,
-            uv_max.y,
-            quad.char_id as f32,
-            quad.border_radius.top_right,
-        );
-        let bottom_right = Vec4::new(
-            uv_max.x,
-            uv_min.y,
-            quad.char_id as f32,
-            quad.border_radius.bottom_right,
-        );
+        let bottom_left = Vec3::new(uv_min.x, uv_min.y, quad.char_id as f32);
+        let top_left = Vec3::new(uv_min.x, uv_max.y, quad.char_id as f32);
+        let top_right = Vec3::new(uv_max.x, uv_max.y, quad.char_id as f32);
+        let bottom_right = Vec3::new(uv_max.x, uv_min.y, quad.char_id as f32);
+        let border_radius = vertex_border_radius(&quad.border_radius);
 
-        let uvs: [[f32; 4]; 6] = [
+        let uvs: [[f32; 3]; 6] = [
             top_left.into(),
             bottom_right.into(),
             bottom_left.into(),
@@ -1024,6 +1255,10 @@ pub fn queue_quads_inner(
                         sprite_rect.size().x,
                         sprite_rect.size().y,
                     ],
+                    shader_param: quad.shader_param,
+                    clip_rect,
+                    edge_fade,
+                    border_radius,
                 });
             }
 
@@ -1037,7 +1272,7 @@ pub fn queue_quads_inner(
             draw_function: draw_opacity_quad,
             pipeline: spec_pipeline,
             entity: *current_batch_entity,
-            sort_key: FloatOrd(quad.z_index),
+            sort_key: (quad.render_layer, FloatOrd(quad.z_index)),
             quad_type: quad.quad_type,
             type_index: quad.type_index,
             rect: sprite_rect,
@@ -1049,7 +1284,7 @@ pub fn queue_quads_inner(
             draw_function: draw_quad,
             pipeline: spec_pipeline,
             entity: *current_batch_entity,
-            sort_key: FloatOrd(quad.z_index),
+            sort_key: (quad.render_layer, FloatOrd(quad.z_index)),
             quad_type: quad.quad_type,
             type_index: quad.type_index,
             rect: sprite_rect,
@@ -1116,8 +1351,8 @@ impl<T: PhaseItem + TransparentUIGeneric + BatchedPhaseItem> RenderCommand<T> fo
         if item.get_quad_type() == UIQuadType::Clip {
             let window_size = (view.viewport.z as f32, view.viewport.w as f32);
             let rect = item.get_rect();
-            let x = rect.min.x as u32;
-            let y = rect.min.y as u32;
+            let mut x = rect.min.x as u32;
+            let mut y = rect.min.y as u32;
             let mut width = rect.width() as u32;
             let mut height = rect.height() as u32;
 
@@ -1132,6 +1367,9 @@ impl<T: PhaseItem + TransparentUIGeneric + BatchedPhaseItem> RenderCommand<T> fo
             if y + height >= window_size.1 as u32 {
                 height = window_size.1 as u32 - y;
             }
+            // Clip rects are relative to the viewport, scissor rects to the render target.
+            x += view.viewport.x;
+            y += view.viewport.y;
             pass.set_scissor_rect(x, y, width, height);
             return RenderCommandResult::Success;
         }
@@ -1160,7 +1398,12 @@ impl<T: PhaseItem + TransparentUIGeneric + BatchedPhaseItem> RenderCommand<T> fo
 
         if let Some(image_handle) = batch.image_handle_id.as_ref() {
             let image_bind_groups = image_bind_groups.into_inner();
-            if let Some(bind_group) = image_bind_groups.values.get(&Handle::weak(*image_handle)) {
+            let values = if batch.repeat_image {
+                &image_bind_groups.repeat_values
+            } else {
+                &image_bind_groups.values
+            };
+            if let Some(bind_group) = values.get(&Handle::weak(*image_handle)) {
                 pass.set_bind_group(1, bind_group, &[]);
             } else if let Some(bind_group) = image_bind_groups
                 .font_values
@@ -1179,3 +1422,28 @@ impl<T: PhaseItem + TransparentUIGeneric + BatchedPhaseItem> RenderCommand<T> fo
         RenderCommandResult::Success
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::vertex_border_radius;
+    use crate::prelude::Corner;
+
+    #[test]
+    fn only_top_left_is_rounded() {
+        let radius = Corner {
+            top_left: 8.0,
+            ..Default::default()
+        };
+        let [top_left, top_right, bottom_right, bottom_left] = vertex_border_radius(&radius);
+        assert_eq!(top_left, 8.0);
+        assert_eq!(top_right, 0.0);
+        assert_eq!(bottom_right, 0.0);
+        assert_eq!(bottom_left, 0.0);
+    }
+
+    #[test]
+    fn corners_keep_their_position() {
+        let radius = Corner::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(vertex_border_radius(&radius), [1.0, 2.0, 4.0, 3.0]);
+    }
+}
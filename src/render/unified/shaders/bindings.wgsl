@@ -18,9 +18,12 @@ var<uniform> globals: Globals;
 
 struct QuadType {
     t: i32,
-    _padding_a: i32,
-    _padding_b: i32,
-    _padding_c: i32,
+    gradient_kind: i32,
+    gradient_stop_count: i32,
+    _padding: i32,
+    params: vec4<f32>,
+    gradient_colors: array<vec4<f32>, 8>,
+    gradient_offsets: array<vec4<f32>, 2>,
 };
 
 @group(2) @binding(0)
@@ -1,6 +1,6 @@
 #define_import_path kayak_ui::sample_quad
 
-#import kayak_ui::bindings font_texture, font_sampler, image_texture, image_sampler, quad_type
+#import kayak_ui::bindings view, font_texture, font_sampler, image_texture, image_sampler, quad_type
 
 #import kayak_ui::vertex_output VertexOutput
 
@@ -10,6 +10,27 @@ fn sdRoundBox(p: vec2<f32>, b: vec2<f32>, r: f32) -> f32 {
     return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - r;
 }
 
+// Returns the radius of the corner nearest to P, where P is relative to the center of a box with
+// half size B and R holds the radii as (top left, top right, bottom right, bottom left).
+// Like HTML/CSS, the radii are scaled down together so that adjacent corners never overlap.
+fn corner_radius(p: vec2<f32>, b: vec2<f32>, r: vec4<f32>) -> f32 {
+    let top = r.x + r.y;
+    let right = r.y + r.z;
+    let bottom = r.z + r.w;
+    let left = r.w + r.x;
+    let largest = max(max(top, bottom) / (b.x * 2.0), max(left, right) / (b.y * 2.0));
+    let radii = r / max(largest, 1.0);
+    var radius = radii.w;
+    if p.x < 0.0 && p.y < 0.0 {
+        radius = radii.x;
+    } else if p.y < 0.0 {
+        radius = radii.y;
+    } else if p.x >= 0.0 {
+        radius = radii.z;
+    }
+    return min(radius, min(b.x, b.y));
+}
+
 fn median_three(v: vec3<f32>) -> f32 {
     return max(min(v.x, v.y), min(max(v.x, v.y), v.z));
 }
@@ -27,6 +48,44 @@ fn sigmoid(t: f32) -> f32 {
     return 1.0 / (1.0 + exp(-t));
 }
 
+// Fades content out towards the edges of the clip rect.
+// Both the clip rect and the fade distances are in physical pixels relative to the viewport.
+fn sample_edge_fade(in: VertexOutput) -> f32 {
+    let p = in.position.xy - view.viewport.xy;
+    var fade = 1.0;
+    // Top
+    if in.edge_fade.x > 0.0 {
+        fade *= clamp((p.y - in.clip_rect.y) / in.edge_fade.x, 0.0, 1.0);
+    }
+    // Right
+    if in.edge_fade.y > 0.0 {
+        fade *= clamp((in.clip_rect.z - p.x) / in.edge_fade.y, 0.0, 1.0);
+    }
+    // Bottom
+    if in.edge_fade.z > 0.0 {
+        fade *= clamp((in.clip_rect.w - p.y) / in.edge_fade.z, 0.0, 1.0);
+    }
+    // Left
+    if in.edge_fade.w > 0.0 {
+        fade *= clamp((p.x - in.clip_rect.x) / in.edge_fade.w, 0.0, 1.0);
+    }
+    return fade;
+}
+
+// Interpolates between the gradient stops at position t along the gradient.
+fn sample_gradient(t: f32) -> vec4<f32> {
+    var color = quad_type.gradient_colors[0];
+    for (var i = 1; i < quad_type.gradient_stop_count; i++) {
+        let start = quad_type.gradient_offsets[(i - 1) / 4][(i - 1) % 4];
+        let end = quad_type.gradient_offsets[i / 4][i % 4];
+        if t > start {
+            let f = clamp((t - start) / max(end - start, 0.00001), 0.0, 1.0);
+            color = mix(quad_type.gradient_colors[i - 1], quad_type.gradient_colors[i], f);
+        }
+    }
+    return color;
+}
+
 fn sample_quad(in: VertexOutput) -> vec4<f32> {
     var output_color = vec4<f32>(0.0);
     // Quad
@@ -34,7 +93,7 @@ fn sample_quad(in: VertexOutput) -> vec4<f32> {
         var size = in.size;
         var pos = in.pos.xy * 2.0;
         // Lock border to max size. This is similar to how HTML/CSS handles border radius.
-        var bs = min(in.border_radius * 2.0, min(size.x, size.y));
+        var bs = corner_radius(pos - size, size, in.border_radius * 2.0);
         var rect_dist = sdRoundBox(
             pos - size,
             size,
@@ -67,12 +126,15 @@ fn sample_quad(in: VertexOutput) -> vec4<f32> {
         let sd = sample_sdf(vec2(in.uv.x, 1.0 - in.uv.y), i32(in.uv.z), 0.0);
         let dxdy = fwidth(in.uv.xy) * vec2(f32(tex_dimensions.x), f32(tex_dimensions.y));
         let dist = sd + min(0.001, 0.5 - 1.0 / px_range) - 0.5;
-        let alpha = clamp(dist * px_range / length(dxdy) + 0.5, 0.0, 1.0);
+        // Text strokes move the edge outwards by their width in pixels. The width stays inside
+        // the range encoded in the glyph's distance field so it never fills the whole quad.
+        let stroke = clamp(in.shader_param, 0.0, 0.45 * px_range / length(dxdy));
+        let alpha = clamp(dist * px_range / length(dxdy) + stroke + 0.5, 0.0, 1.0);
         output_color = vec4(in.color.rgb, in.color.a * alpha);
     }
     // Image
     if quad_type.t == 3 {
-        var bs = min(in.border_radius, min(in.size.x, in.size.y));
+        var bs = corner_radius(in.pos.xy * 2.0 - in.size.xy, in.size.xy, in.border_radius);
         var mask = sdRoundBox(
             in.pos.xy * 2.0 - (in.size.xy),
             in.size.xy,
@@ -87,7 +149,7 @@ fn sample_quad(in: VertexOutput) -> vec4<f32> {
         var size = in.size - in.uv.x * 6.0;
         var pos = (in.pos.xy * 2.0) - (in.uv.x * 6.0); //(in.pos.xy - in.uv.x - 7.5) * 2.0;
         // Lock border to max size. This is similar to how HTML/CSS handles border radius.
-        var bs = min(in.border_radius * 2.0, min(size.x, size.y));
+        var bs = corner_radius(pos - size, size, in.border_radius * 2.0);
         var rect_dist = 1.0 - sigmoid(sdRoundBox(
             (pos - size),
             size,
@@ -96,6 +158,113 @@ fn sample_quad(in: VertexOutput) -> vec4<f32> {
         let color = in.color.rgb;// - vec3(rect_dist);
         output_color = vec4(color, in.color.a * rect_dist * 1.42);
     }
+    // Radial fill
+    if quad_type.t == 5 {
+        var size = in.size;
+        var pos = in.pos.xy * 2.0;
+        var bs = corner_radius(pos - size, size, in.border_radius * 2.0);
+        var rect_dist = sdRoundBox(
+            pos - size,
+            size,
+            bs,
+        );
+        rect_dist = 1.0 - smoothstep(0.0, fwidth(rect_dist), rect_dist);
+        // Angle from the top, going clockwise, normalized to 0..1.
+        let center_offset = in.pos.xy - size * 0.5;
+        let angle = fract(atan2(center_offset.x, -center_offset.y) / 6.28318530718 + 1.0);
+        let fill = clamp(in.shader_param, 0.0, 1.0);
+        // The angle wraps around at the top so keep the anti-aliased edge small.
+        let edge = min(fwidth(angle), 0.01);
+        var fill_mask = 1.0 - smoothstep(fill - edge, fill, angle);
+        if fill >= 1.0 {
+            fill_mask = 1.0;
+        }
+        output_color = vec4<f32>(in.color.rgb, rect_dist * fill_mask * in.color.a);
+    }
+    // Gradient
+    if quad_type.t == 6 {
+        var size = in.size;
+        var pos = in.pos.xy * 2.0;
+        var bs = corner_radius(pos - size, size, in.border_radius * 2.0);
+        var rect_dist = sdRoundBox(
+            pos - size,
+            size,
+            bs,
+        );
+        rect_dist = 1.0 - smoothstep(0.0, fwidth(rect_dist), rect_dist);
+        var t = 0.0;
+        // Linear
+        if quad_type.gradient_kind == 0 {
+            let angle = quad_type.params.x;
+            let direction = vec2<f32>(sin(angle), -cos(angle));
+            // Like CSS, the gradient line is long enough to reach the corners of the box.
+            let line_length = abs(size.x * direction.x) + abs(size.y * direction.y);
+            t = dot(in.pos.xy - size * 0.5, direction) / line_length + 0.5;
+        }
+        // Radial
+        if quad_type.gradient_kind == 1 {
+            let center = quad_type.params.xy * size;
+            let farthest_corner = length(max(center, size - center));
+            let radius = max(quad_type.params.z * farthest_corner, 0.0001);
+            t = length(in.pos.xy - center) / radius;
+        }
+        let color = sample_gradient(clamp(t, 0.0, 1.0));
+        output_color = vec4<f32>(color.rgb * in.color.rgb, rect_dist * color.a * in.color.a);
+    }
+    // Inset box shadow
+    if quad_type.t == 7 {
+        var size = in.size;
+        var pos = in.pos.xy * 2.0;
+        var bs = corner_radius(pos - size, size, in.border_radius * 2.0);
+        var rect_dist = sdRoundBox(
+            pos - size,
+            size,
+            bs,
+        );
+        rect_dist = 1.0 - smoothstep(0.0, fwidth(rect_dist), rect_dist);
+        // The shadow covers everything outside of the offset and shrunk "hole".
+        let offset = quad_type.params.xy;
+        let spread = quad_type.params.zw;
+        let hole_size = max(size * 0.5 - spread, vec2(0.0));
+        let radius = corner_radius(in.pos.xy - size * 0.5, size * 0.5, in.border_radius);
+        let hole_radius = clamp(radius - max(spread.x, spread.y), 0.0, min(hole_size.x, hole_size.y));
+        let hole_dist = sdRoundBox(
+            in.pos.xy - size * 0.5 - offset,
+            hole_size,
+            hole_radius,
+        );
+        let shadow = sigmoid(hole_dist / max(in.uv.x * 0.5, 0.0001));
+        output_color = vec4(in.color.rgb, in.color.a * shadow * rect_dist);
+    }
+    // Backdrop blur
+    if quad_type.t == 8 {
+        var bs = corner_radius(in.pos.xy * 2.0 - in.size.xy, in.size.xy, in.border_radius);
+        var mask = sdRoundBox(
+            in.pos.xy * 2.0 - (in.size.xy),
+            in.size.xy,
+            bs,
+        );
+        mask = 1.0 - smoothstep(0.0, fwidth(mask), mask);
+        // The image texture holds a copy of the render target, so it's sampled at the fragment's
+        // position. The blur radius is in physical pixels.
+        let texture_size = vec2<f32>(textureDimensions(image_texture));
+        let radius = max(in.shader_param, 0.0);
+        let sigma = max(radius * 0.5, 0.0001);
+        let spacing = radius / 4.0;
+        var sum = vec4<f32>(0.0);
+        var weight_sum = 0.0;
+        for (var x = -4; x <= 4; x++) {
+            for (var y = -4; y <= 4; y++) {
+                let offset = vec2<f32>(f32(x), f32(y)) * spacing;
+                let weight = exp(-dot(offset, offset) / (2.0 * sigma * sigma));
+                let uv = (in.position.xy + offset) / texture_size;
+                sum += textureSampleLevel(image_texture, image_sampler, uv, 0.0) * weight;
+                weight_sum += weight;
+            }
+        }
+        let color = sum / weight_sum;
+        output_color = vec4<f32>(color.rgb * in.color.rgb, mask * in.color.a);
+    }
 
     return output_color;
 }
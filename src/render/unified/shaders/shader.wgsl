@@ -6,23 +6,32 @@
 fn vertex(
     @location(0) vertex_position: vec3<f32>,
     @location(1) vertex_color: vec4<f32>,
-    @location(2) vertex_uv: vec4<f32>,
+    @location(2) vertex_uv: vec3<f32>,
     @location(3) vertex_pos_size: vec4<f32>,
+    @location(4) vertex_shader_param: f32,
+    @location(5) vertex_clip_rect: vec4<f32>,
+    @location(6) vertex_edge_fade: vec4<f32>,
+    @location(7) vertex_border_radius: vec4<f32>,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.color = vertex_color;
     out.pos = (vertex_position.xy - vertex_pos_size.xy);
     out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
     out.pixel_position = out.position.xy;
-    out.uv = vertex_uv.xyz;
+    out.uv = vertex_uv;
     out.size = vertex_pos_size.zw;
-    out.border_radius = vertex_uv.w;
+    out.border_radius = vertex_border_radius;
+    out.shader_param = vertex_shader_param;
+    out.clip_rect = vertex_clip_rect;
+    out.edge_fade = vertex_edge_fade;
     return out;
 }
 
-#import kayak_ui::sample_quad sample_quad
+#import kayak_ui::sample_quad sample_quad, sample_edge_fade
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-    return sample_quad(in);
+    var color = sample_quad(in);
+    color.a *= sample_edge_fade(in);
+    return color;
 }
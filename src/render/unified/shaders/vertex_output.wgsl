@@ -6,6 +6,10 @@ struct VertexOutput {
     @location(1) uv: vec3<f32>,
     @location(2) pos: vec2<f32>,
     @location(3) size: vec2<f32>,
-    @location(4) border_radius: f32,
+    // The corner radii as (top left, top right, bottom right, bottom left)
+    @location(4) @interpolate(flat) border_radius: vec4<f32>,
     @location(5) pixel_position: vec2<f32>,
+    @location(6) shader_param: f32,
+    @location(7) clip_rect: vec4<f32>,
+    @location(8) edge_fade: vec4<f32>,
 };
\ No newline at end of file
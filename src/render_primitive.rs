@@ -6,7 +6,7 @@ use crate::{
         font::FontMapping,
         unified::pipeline::{ExtractedQuad, ExtractedQuads, UIQuadType},
     },
-    styles::{Corner, KStyle, RenderCommand, StyleProp},
+    styles::{Corner, KStyle, RenderCommand, StyleProp, TextRun},
 };
 
 pub trait RenderPrimitive {
@@ -42,6 +42,27 @@ impl RenderPrimitive for KStyle {
         let background_color = self.background_color.resolve();
         let render_command = self.render_command.resolve();
         let material = self.material.resolve_as_option();
+        let render_layer = self.render_layer.resolve_or(0);
+        let first_quad = extracted_quads.quads.len();
+        let backdrop_blur = self.backdrop_blur.resolve_or(0.0);
+        if backdrop_blur > 0.0 && render_command != RenderCommand::Clip {
+            extracted_quads.quads.push(ExtractedQuad {
+                camera_entity,
+                rect: Rect {
+                    min: Vec2::new(layout.posx, layout.posy),
+                    max: Vec2::new(layout.posx + layout.width, layout.posy + layout.height),
+                },
+                color: Color::WHITE,
+                z_index: layout.z_index,
+                quad_type: UIQuadType::BackdropBlur,
+                border_radius: self.resolve_border_radius(layout),
+                opacity_layer,
+                render_layer,
+                // The backdrop is sampled in physical pixels.
+                shader_param: backdrop_blur * dpi,
+                ..Default::default()
+            });
+        }
         match render_command {
             RenderCommand::Clip => {
                 let mut rect = Rect {
@@ -71,6 +92,8 @@ impl RenderPrimitive for KStyle {
                     uv_min: None,
                     uv_max: None,
                     opacity_layer,
+                    render_layer,
+                    edge_fade: self.edge_fade.resolve() * dpi,
                     ..Default::default()
                 };
                 if let Some(material) = material {
@@ -82,12 +105,13 @@ impl RenderPrimitive for KStyle {
                     return Some(extracted);
                 }
             }
-            RenderCommand::Quad => {
+            RenderCommand::Quad | RenderCommand::RadialFill => {
                 let border_color = self.border_color.resolve();
-                let border_radius = self.border_radius.resolve();
+                let border_radius = self.resolve_border_radius(layout);
                 let border = self.border.resolve();
                 let box_shadow = self.box_shadow.resolve();
-                let quads = crate::render::quad::extract_quads(
+                let shader_param = self.shader_param.resolve();
+                let mut quads = crate::render::quad::extract_quads(
                     camera_entity,
                     background_color,
                     border_color,
@@ -98,9 +122,28 @@ impl RenderPrimitive for KStyle {
                     box_shadow,
                     1.0,
                 );
+                for (i, quad) in quads.iter_mut().enumerate() {
+                    quad.shader_param = shader_param;
+                    // The background is always the first quad.
+                    if i == 0 && render_command == RenderCommand::RadialFill {
+                        quad.quad_type = UIQuadType::RadialFill;
+                    } else if i == 0 {
+                        if let StyleProp::Value(gradient) = &self.background_gradient {
+                            quad.quad_type = UIQuadType::Gradient;
+                            quad.gradient = Some(gradient.clone());
+                            // The vertex color tints the gradient in the shader.
+                            quad.color = Color::WHITE;
+                        }
+                    }
+                }
                 if let Some(material) = material {
                     for extracted in quads {
-                        let id = commands.spawn(extracted).id();
+                        let id = commands
+                            .spawn(ExtractedQuad {
+                                render_layer,
+                                ..extracted
+                            })
+                            .id();
                         material.run(commands, id);
                     }
                     return None;
@@ -117,8 +160,8 @@ impl RenderPrimitive for KStyle {
                 let font = self
                     .font
                     .resolve_or_else(|| String::from(crate::DEFAULT_FONT));
-                let color = self.color.resolve_or(Color::WHITE);
-                let text = crate::render::font::extract_texts(
+                let color = self.resolve_disabled_color(self.color.resolve_or(Color::WHITE));
+                let mut text = crate::render::font::extract_texts(
                     camera_entity,
                     color,
                     text_layout,
@@ -131,9 +174,63 @@ impl RenderPrimitive for KStyle {
                     font_mapping,
                     dpi,
                 );
+                self.extract_text_stroke(&mut text, dpi);
                 if let Some(material) = material {
                     for extracted in text {
-                        let id = commands.spawn(extracted).id();
+                        let id = commands
+                            .spawn(ExtractedQuad {
+                                render_layer,
+                                ..extracted
+                            })
+                            .id();
+                        material.run(commands, id);
+                    }
+                    return None;
+                } else {
+                    extracted_quads.quads.extend(text);
+                }
+            }
+            RenderCommand::RichText {
+                runs,
+                subpixel,
+                text_layout,
+                properties,
+                ..
+            } => {
+                let font = self
+                    .font
+                    .resolve_or_else(|| String::from(crate::DEFAULT_FONT));
+                let color = self.resolve_disabled_color(self.color.resolve_or(Color::WHITE));
+                let runs = runs
+                    .into_iter()
+                    .map(|run| TextRun {
+                        color: run.color.map(|color| self.resolve_disabled_color(color)),
+                        ..run
+                    })
+                    .collect::<Vec<_>>();
+                let mut text = crate::render::font::extract_rich_texts(
+                    camera_entity,
+                    color,
+                    &runs,
+                    text_layout,
+                    *layout,
+                    font,
+                    properties,
+                    subpixel,
+                    opacity_layer,
+                    fonts,
+                    font_mapping,
+                    dpi,
+                );
+                self.extract_text_stroke(&mut text, dpi);
+                if let Some(material) = material {
+                    for extracted in text {
+                        let id = commands
+                            .spawn(ExtractedQuad {
+                                render_layer,
+                                ..extracted
+                            })
+                            .id();
                         material.run(commands, id);
                     }
                     return None;
@@ -142,18 +239,25 @@ impl RenderPrimitive for KStyle {
                 }
             }
             RenderCommand::Image { handle } => {
-                let border_radius = self.border_radius.resolve();
+                let border_radius = self.resolve_border_radius(layout);
                 let images = crate::render::image::extract_images(
                     camera_entity,
                     border_radius,
                     *layout,
                     handle,
+                    self.image_repeat.resolve(),
                     opacity_layer,
+                    images,
                     dpi,
                 );
                 if let Some(material) = material {
                     for extracted in images {
-                        let id = commands.spawn(extracted).id();
+                        let id = commands
+                            .spawn(ExtractedQuad {
+                                render_layer,
+                                ..extracted
+                            })
+                            .id();
                         material.run(commands, id);
                     }
                     return None;
@@ -178,7 +282,12 @@ impl RenderPrimitive for KStyle {
                 );
                 if let Some(material) = material {
                     for extracted in atlases {
-                        let id = commands.spawn(extracted).id();
+                        let id = commands
+                            .spawn(ExtractedQuad {
+                                render_layer,
+                                ..extracted
+                            })
+                            .id();
                         material.run(commands, id);
                     }
                     return None;
@@ -198,7 +307,12 @@ impl RenderPrimitive for KStyle {
                 );
                 if let Some(material) = material {
                     for extracted in nines {
-                        let id = commands.spawn(extracted).id();
+                        let id = commands
+                            .spawn(ExtractedQuad {
+                                render_layer,
+                                ..extracted
+                            })
+                            .id();
                         material.run(commands, id);
                     }
                     return None;
@@ -212,15 +326,27 @@ impl RenderPrimitive for KStyle {
                     handle,
                     *layout,
                     match self.background_color {
-                        StyleProp::Value(color) => Some(color),
+                        StyleProp::Value(color) => Some(self.resolve_disabled_color(color)),
                         _ => None,
                     },
+                    // The color multiplies the svg's own colors, unless they're replaced by
+                    // the background color.
+                    match self.background_color {
+                        StyleProp::Value(_) => Color::WHITE,
+                        _ => self.resolve_disabled_color(self.color.resolve_or(Color::WHITE)),
+                    },
+                    self.rotation.resolve_or(0.0),
                     opacity_layer,
                     dpi,
                 );
                 if let Some(material) = material {
                     for extracted in svgs {
-                        let id = commands.spawn(extracted).id();
+                        let id = commands
+                            .spawn(ExtractedQuad {
+                                render_layer,
+                                ..extracted
+                            })
+                            .id();
                         material.run(commands, id);
                     }
                     return None;
@@ -228,9 +354,79 @@ impl RenderPrimitive for KStyle {
                     extracted_quads.quads.extend(svgs);
                 }
             }
+            RenderCommand::Mesh { handle } => {
+                let meshes = crate::render::mesh::extract_mesh(
+                    camera_entity,
+                    handle,
+                    *layout,
+                    self.resolve_disabled_color(self.color.resolve_or(Color::WHITE)),
+                    opacity_layer,
+                    dpi,
+                );
+                if let Some(material) = material {
+                    for extracted in meshes {
+                        let id = commands
+                            .spawn(ExtractedQuad {
+                                render_layer,
+                                ..extracted
+                            })
+                            .id();
+                        material.run(commands, id);
+                    }
+                    return None;
+                } else {
+                    extracted_quads.quads.extend(meshes);
+                }
+            }
             _ => {}
         }
 
+        for quad in extracted_quads.quads[first_quad..].iter_mut() {
+            quad.render_layer = render_layer;
+        }
+
         None
     }
 }
+
+impl KStyle {
+    /// Returns the border radius in pixels, resolving
+    /// [`border_radius_percent`](KStyle::border_radius_percent) against the given layout.
+    fn resolve_border_radius(&self, layout: &crate::layout::Rect) -> Corner<f32> {
+        if let StyleProp::Value(percent) = self.border_radius_percent {
+            let size = layout.width.min(layout.height).max(0.0) / 100.0;
+            Corner::new(
+                percent.top_left * size,
+                percent.top_right * size,
+                percent.bottom_left * size,
+                percent.bottom_right * size,
+            )
+        } else {
+            self.border_radius.resolve()
+        }
+    }
+
+    /// Returns the color that should be rendered for text and icons, taking the
+    /// [`disabled`](KStyle::disabled) state into account.
+    fn resolve_disabled_color(&self, color: Color) -> Color {
+        if !self.disabled.resolve_or(false) {
+            return color;
+        }
+
+        self.disabled_color.resolve_or_else(|| {
+            // Desaturate and dim the color so disabled text stays legible but subdued.
+            let luma = color.r() * 0.299 + color.g() * 0.587 + color.b() * 0.114;
+            let grey = luma * 0.6;
+            Color::rgba(grey, grey, grey, color.a() * 0.6)
+        })
+    }
+
+    /// Adds the [`text_stroke`](KStyle::text_stroke) outline in front of the given glyph quads,
+    /// so it is drawn behind them.
+    fn extract_text_stroke(&self, text: &mut Vec<ExtractedQuad>, dpi: f32) {
+        if let StyleProp::Value((width, color)) = self.text_stroke {
+            let stroke = crate::render::font::extract_text_strokes(text, width, color, dpi);
+            text.splice(0..0, stroke);
+        }
+    }
+}
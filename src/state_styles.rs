@@ -0,0 +1,97 @@
+use bevy::prelude::{Component, Entity, World};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use crate::styles::KStyle;
+
+/// A set of styles keyed by a widget's state
+///
+/// After a widget with this component renders, the style matching its current state is
+/// applied over its [`ComputedStyles`](crate::styles::ComputedStyles). Fields set in the
+/// matching style take precedence, anything left unset keeps the value from the render system.
+///
+/// The state is read from the widget's state entity (see [`WidgetState`](crate::widget_state::WidgetState)),
+/// so it should be created with [`KayakWidgetContext::use_state`](crate::widget_context::KayakWidgetContext::use_state).
+/// Since changing the state re-renders the widget, the styles follow the state automatically.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// #[derive(Component, Default, Debug, Clone, Copy, PartialEq)]
+/// enum ButtonState {
+///     #[default]
+///     Default,
+///     Hover,
+///     Pressed,
+/// }
+///
+/// let state_styles = StateStyles::new([
+///     (
+///         ButtonState::Hover,
+///         KStyle {
+///             background_color: Color::rgb(0.3, 0.3, 0.4).into(),
+///             ..Default::default()
+///         },
+///     ),
+///     (
+///         ButtonState::Pressed,
+///         KStyle {
+///             background_color: Color::rgb(0.2, 0.2, 0.3).into(),
+///             ..Default::default()
+///         },
+///     ),
+/// ]);
+/// ```
+#[derive(Component, Clone)]
+pub struct StateStyles {
+    resolve: Arc<dyn Fn(&World, Entity) -> Option<KStyle> + Send + Sync>,
+}
+
+impl Default for StateStyles {
+    fn default() -> Self {
+        Self {
+            resolve: Arc::new(|_, _| None),
+        }
+    }
+}
+
+impl StateStyles {
+    /// Create a new set of state styles
+    ///
+    /// Each entry maps a state value to the style used while the widget is in that state.
+    /// If several entries match, the first one is used.
+    pub fn new<State: Component + PartialEq + Clone>(
+        variants: impl IntoIterator<Item = (State, KStyle)>,
+    ) -> Self {
+        let variants = variants.into_iter().collect::<Vec<_>>();
+        Self {
+            resolve: Arc::new(move |world, state_entity| {
+                let state = world.get::<State>(state_entity)?;
+                variants
+                    .iter()
+                    .find(|(variant, _)| variant == state)
+                    .map(|(_, styles)| styles.clone())
+            }),
+        }
+    }
+
+    /// Returns the style matching the state stored on the given state entity, if any
+    pub fn resolve(&self, world: &World, state_entity: Entity) -> Option<KStyle> {
+        (self.resolve)(world, state_entity)
+    }
+}
+
+impl Debug for StateStyles {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateStyles").finish()
+    }
+}
+
+impl PartialEq for StateStyles {
+    fn eq(&self, _: &Self) -> bool {
+        // Never prevent "==" for being true because of this struct
+        true
+    }
+}
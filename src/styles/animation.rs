@@ -0,0 +1,287 @@
+use bevy::{
+    prelude::{Commands, Component, Entity, Query, Res, Time, With, Without},
+    reflect::Reflect,
+};
+
+use crate::{
+    clone_component::PreviousWidget, context::KayakRootContext, node::DirtyNode,
+    widgets::TransitionEasing,
+};
+
+use super::{
+    transition::{TransitionProperty, TransitionValue},
+    ComputedStyles, KStyle,
+};
+
+/// The styles of an [`Animation`] at a point in time
+#[derive(Reflect, Debug, Default, Clone, PartialEq)]
+pub struct Keyframe {
+    /// The time of this keyframe in seconds from the start of the animation
+    pub time: f32,
+    /// The animated properties at this keyframe
+    ///
+    /// Only the properties listed in [`TransitionProperty`] are animated, anything else is
+    /// ignored. Properties left unset are interpolated between the surrounding keyframes that do
+    /// set them.
+    pub styles: KStyle,
+    /// The easing curve used from this keyframe to the next one
+    pub easing: TransitionEasing,
+}
+
+impl Keyframe {
+    pub fn new(time: f32, styles: KStyle) -> Self {
+        Self {
+            time,
+            styles,
+            easing: TransitionEasing::Linear,
+        }
+    }
+
+    /// Sets the easing curve used from this keyframe to the next one
+    pub fn with_easing(mut self, easing: TransitionEasing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// What an [`Animation`] does once it reaches its last keyframe
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationMode {
+    /// Stops on the last keyframe
+    #[default]
+    Once,
+    /// Starts over from the first keyframe
+    Loop,
+    /// Plays backwards to the first keyframe, then forwards again
+    PingPong,
+}
+
+/// Animates the styles of a widget along a timeline of keyframes
+///
+/// Every frame, the animated properties are sampled and applied over the widget's
+/// [`ComputedStyles`]. The progress is kept in a separate [`AnimationProgress`] component, so
+/// re-rendering the widget (or inserting an updated `Animation`) doesn't restart the animation.
+///
+/// # Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use kayak_ui::prelude::*;
+/// let pulse = Animation::new(vec![
+///     Keyframe::new(0.0, KStyle { opacity: 1.0.into(), ..Default::default() }),
+///     Keyframe::new(0.5, KStyle { opacity: 0.4.into(), ..Default::default() }),
+/// ])
+/// .with_mode(AnimationMode::PingPong);
+/// ```
+#[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
+pub struct Animation {
+    /// The keyframes of the animation, in increasing time order
+    pub keyframes: Vec<Keyframe>,
+    pub mode: AnimationMode,
+    /// Whether the animation is advancing
+    ///
+    /// Pausing keeps the widget on its current frame.
+    pub playing: bool,
+}
+
+impl Animation {
+    /// Creates an animation that starts playing right away
+    pub fn new(keyframes: Vec<Keyframe>) -> Self {
+        Self {
+            keyframes,
+            mode: AnimationMode::Once,
+            playing: true,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: AnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The length of the animation in seconds
+    pub fn duration(&self) -> f32 {
+        self.keyframes
+            .iter()
+            .map(|keyframe| keyframe.time)
+            .fold(0.0, f32::max)
+    }
+
+    /// Returns the animated styles at the given time
+    pub fn sample(&self, time: f32) -> KStyle {
+        let mut styles = KStyle::default();
+        for property in TransitionProperty::ALL {
+            if let Some(value) = self.sample_property(property, time) {
+                value.set(property, &mut styles);
+            }
+        }
+        styles
+    }
+
+    fn sample_property(&self, property: TransitionProperty, time: f32) -> Option<TransitionValue> {
+        let mut previous: Option<(&Keyframe, TransitionValue)> = None;
+        for keyframe in self.keyframes.iter() {
+            if let Some(value) = TransitionValue::get(property, &keyframe.styles) {
+                if keyframe.time >= time {
+                    if let Some((from, from_value)) = previous {
+                        let length = keyframe.time - from.time;
+                        let x = if length > 0.0 {
+                            from.easing.ease((time - from.time) / length)
+                        } else {
+                            1.0
+                        };
+                        return Some(from_value.lerp(&value, x).unwrap_or(if x < 1.0 {
+                            from_value
+                        } else {
+                            value
+                        }));
+                    }
+                    return Some(value);
+                }
+                previous = Some((keyframe, value));
+            }
+        }
+
+        previous.map(|(_, value)| value)
+    }
+}
+
+/// The playback position of an [`Animation`]
+///
+/// This is added to the widget automatically once its animation starts. Setting
+/// [`time`](Self::time) back to `0.0` restarts the animation.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub struct AnimationProgress {
+    /// The current position on the timeline in seconds
+    pub time: f32,
+    /// Whether a [`AnimationMode::PingPong`] animation is playing backwards
+    pub reversing: bool,
+}
+
+impl AnimationProgress {
+    fn advance(&mut self, animation: &Animation, seconds: f32) {
+        let duration = animation.duration();
+        if duration <= 0.0 {
+            self.time = 0.0;
+            return;
+        }
+
+        match animation.mode {
+            AnimationMode::Once => {
+                self.time = (self.time + seconds).min(duration);
+            }
+            AnimationMode::Loop => {
+                self.time = (self.time + seconds) % duration;
+            }
+            AnimationMode::PingPong => {
+                if self.reversing {
+                    self.time -= seconds;
+                } else {
+                    self.time += seconds;
+                }
+                if self.time >= duration {
+                    self.time = (2.0 * duration - self.time).max(0.0);
+                    self.reversing = true;
+                } else if self.time <= 0.0 {
+                    self.time = (-self.time).min(duration);
+                    self.reversing = false;
+                }
+            }
+        }
+    }
+}
+
+/// Samples every [`Animation`] and applies it over its widget's styles
+///
+/// The previous copy of the widget kept for diffing is updated as well, so that animating
+/// doesn't cause the widget to re-render every frame.
+pub(crate) fn update_animations(
+    mut commands: Commands,
+    time: Res<Time>,
+    roots: Query<&KayakRootContext>,
+    mut query: Query<
+        (
+            Entity,
+            &Animation,
+            Option<&mut AnimationProgress>,
+            &mut ComputedStyles,
+        ),
+        Without<PreviousWidget>,
+    >,
+    mut previous_query: Query<&mut ComputedStyles, With<PreviousWidget>>,
+) {
+    for (entity, animation, progress, mut computed_styles) in query.iter_mut() {
+        let progress = if let Some(mut progress) = progress {
+            if animation.playing {
+                progress.advance(animation, time.delta_seconds());
+            }
+            *progress
+        } else {
+            let progress = AnimationProgress::default();
+            commands.entity(entity).insert(progress);
+            progress
+        };
+
+        let styles = animation.sample(progress.time);
+        let animated = styles.clone().with_style(&computed_styles.0);
+        if animated == computed_styles.0 {
+            continue;
+        }
+        computed_styles.0 = animated;
+        commands.entity(entity).insert(DirtyNode);
+
+        for context in roots.iter() {
+            if let Some(previous_entity) = context.cloned_widget_entities.get(&entity) {
+                if let Ok(mut previous_styles) = previous_query.get_mut(*previous_entity) {
+                    previous_styles.0 = styles.clone().with_style(&previous_styles.0);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animation, AnimationMode, AnimationProgress, Keyframe};
+    use crate::styles::{KStyle, StyleProp, Units};
+
+    fn keyframe(time: f32, width: f32) -> Keyframe {
+        Keyframe::new(
+            time,
+            KStyle {
+                width: Units::Pixels(width).into(),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn sample_should_interpolate_between_keyframes() {
+        let animation = Animation::new(vec![keyframe(0.0, 0.0), keyframe(2.0, 100.0)]);
+
+        assert_eq!(
+            StyleProp::Value(Units::Pixels(25.0)),
+            animation.sample(0.5).width
+        );
+        assert_eq!(
+            StyleProp::Value(Units::Pixels(100.0)),
+            animation.sample(3.0).width
+        );
+        assert_eq!(StyleProp::Unset, animation.sample(1.0).height);
+    }
+
+    #[test]
+    fn ping_pong_should_reverse_at_the_ends() {
+        let animation = Animation::new(vec![keyframe(0.0, 0.0), keyframe(1.0, 100.0)])
+            .with_mode(AnimationMode::PingPong);
+        let mut progress = AnimationProgress::default();
+
+        progress.advance(&animation, 1.25);
+        assert!(progress.reversing);
+        assert_eq!(0.75, progress.time);
+
+        progress.advance(&animation, 1.0);
+        assert!(!progress.reversing);
+        assert_eq!(0.25, progress.time);
+    }
+}
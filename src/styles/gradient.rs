@@ -0,0 +1,80 @@
+use bevy::{
+    prelude::{Color, Vec2},
+    reflect::Reflect,
+};
+
+/// The shape of a [`Gradient`]
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    /// Colors change along a line through the center of the widget
+    ///
+    /// The angle is in degrees and follows CSS: `0.0` points to the top and
+    /// `90.0` points to the right.
+    Linear { angle: f32 },
+    /// Colors change with the distance from a center point
+    ///
+    /// The center is relative to the widget's size, so `(0.5, 0.5)` is the middle of the widget.
+    /// A radius of `1.0` reaches the corner farthest from the center.
+    Radial { center: Vec2, radius: f32 },
+}
+
+impl Default for GradientKind {
+    fn default() -> Self {
+        Self::Linear { angle: 180.0 }
+    }
+}
+
+/// A color at a given position along a [`Gradient`]
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    pub color: Color,
+    /// The position of this stop, from `0.0` (start) to `1.0` (end)
+    pub offset: f32,
+}
+
+impl ColorStop {
+    pub fn new(color: Color, offset: f32) -> Self {
+        Self { color, offset }
+    }
+}
+
+/// A linear or radial gradient used to fill a widget's background
+///
+/// Stops should be given in increasing offset order. Only the first [`Gradient::MAX_STOPS`]
+/// stops are drawn.
+#[derive(Reflect, Debug, Default, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    pub stops: Vec<ColorStop>,
+}
+
+impl Gradient {
+    /// The maximum number of color stops the renderer supports
+    pub const MAX_STOPS: usize = 8;
+
+    /// Create a linear gradient with the given colors spread evenly along it
+    pub fn linear(angle: f32, colors: impl IntoIterator<Item = Color>) -> Self {
+        Self {
+            kind: GradientKind::Linear { angle },
+            stops: Self::even_stops(colors),
+        }
+    }
+
+    /// Create a radial gradient with the given colors spread evenly from the center outwards
+    pub fn radial(center: Vec2, radius: f32, colors: impl IntoIterator<Item = Color>) -> Self {
+        Self {
+            kind: GradientKind::Radial { center, radius },
+            stops: Self::even_stops(colors),
+        }
+    }
+
+    fn even_stops(colors: impl IntoIterator<Item = Color>) -> Vec<ColorStop> {
+        let colors = colors.into_iter().collect::<Vec<_>>();
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, color)| ColorStop::new(color, i as f32 / last))
+            .collect()
+    }
+}
@@ -3,19 +3,31 @@ use bevy::{
     reflect::Reflect,
 };
 
+mod animation;
 mod corner;
 mod edge;
+mod gradient;
 mod options_ref;
 mod render_command;
 mod style;
+#[cfg(feature = "persistence")]
+mod theme;
+mod transition;
 mod units;
 
+pub(crate) use animation::update_animations;
+pub use animation::{Animation, AnimationMode, AnimationProgress, Keyframe};
 pub use corner::Corner;
 pub use edge::Edge;
 use fancy_regex::Matches;
+pub use gradient::{ColorStop, Gradient, GradientKind};
 pub use options_ref::AsRefOption;
-pub use render_command::RenderCommand;
+pub use render_command::{RenderCommand, TextRun};
 pub use style::*;
+#[cfg(feature = "persistence")]
+pub use theme::*;
+pub(crate) use transition::update_style_transitions;
+pub use transition::{StyleTransition, StyleTransitions, TransitionProperty};
 pub use units::*;
 
 #[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
@@ -33,6 +45,8 @@ pub struct BoxShadow {
     pub radius: f32,
     pub offset: Vec2,
     pub spread: Vec2,
+    /// Draws the shadow inside the widget, over its background, instead of behind it
+    pub inset: bool,
 }
 
 fn is_length(v: &str) -> bool {
@@ -64,7 +78,7 @@ impl BoxShadow {
                 last: 0,
             }
             .collect::<Vec<_>>();
-            let _inset = parts.contains(&"inset");
+            let inset = parts.contains(&"inset");
             let color = parts
                 .last()
                 .map(|last| {
@@ -100,6 +114,7 @@ impl BoxShadow {
                 radius: blur_radius,
                 offset: Vec2::new(offset_x, offset_y),
                 spread: Vec2::splat(spread),
+                inset,
             });
         }
 
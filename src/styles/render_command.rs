@@ -1,5 +1,5 @@
 use bevy::{
-    prelude::{Handle, Image, Vec2},
+    prelude::{Color, Handle, Image, Mesh, Vec2},
     reflect::Reflect,
 };
 use bevy_svg::prelude::Svg;
@@ -14,6 +14,10 @@ pub enum RenderCommand {
     Layout,
     Clip,
     Quad,
+    /// A quad that is only partially filled, clockwise from the top, like a radial progress indicator.
+    ///
+    /// The filled fraction (from `0.0` to `1.0`) is read from [`KStyle::shader_param`](crate::styles::KStyle::shader_param).
+    RadialFill,
     Text {
         content: String,
         alignment: Alignment,
@@ -22,6 +26,19 @@ pub enum RenderCommand {
         text_layout: TextLayout,
         properties: TextProperties,
     },
+    /// Text made of several runs that each have their own color, font and font size
+    ///
+    /// The runs are laid out together, so lines wrap across run boundaries. Anything a run
+    /// leaves unset comes from the widget's own styles. The line height defaults to fit the
+    /// largest font size.
+    RichText {
+        runs: Vec<TextRun>,
+        alignment: Alignment,
+        word_wrap: bool,
+        subpixel: bool,
+        text_layout: TextLayout,
+        properties: TextProperties,
+    },
     Image {
         handle: Handle<Image>,
     },
@@ -30,13 +47,63 @@ pub enum RenderCommand {
         size: Vec2,
         handle: Handle<Image>,
     },
+    /// An image sliced into nine regions, where the corners keep their size and the edges and
+    /// center stretch to fill the widget
     NinePatch {
+        /// The insets of the slices (in pixels of the source image)
         border: Edge<f32>,
         handle: Handle<Image>,
     },
     Svg {
         handle: Handle<Svg>,
     },
+    /// A 2D mesh stretched over the widget
+    ///
+    /// The x and y coordinates of the mesh's vertices are used, with the y-axis pointing up like
+    /// in the rest of bevy. The bounds of the mesh fill the widget's layout rect. Only meshes
+    /// with a [`TriangleList`](bevy::render::render_resource::PrimitiveTopology::TriangleList)
+    /// topology are drawn, and their vertex colors default to white when missing.
+    ///
+    /// The vertex colors are multiplied by the [`color`](crate::styles::KStyle::color) style.
+    Mesh {
+        handle: Handle<Mesh>,
+    },
+}
+
+/// A run of text within [`RenderCommand::RichText`]
+#[derive(Debug, Reflect, Clone, Default, PartialEq)]
+pub struct TextRun {
+    pub content: String,
+    /// The color of this run, or the widget's [`color`](crate::styles::KStyle::color) if `None`
+    pub color: Option<Color>,
+    /// The font of this run, or the widget's [`font`](crate::styles::KStyle::font) if `None`
+    pub font: Option<String>,
+    /// The font size of this run, or the widget's [`font_size`](crate::styles::KStyle::font_size) if `None`
+    pub font_size: Option<f32>,
+}
+
+impl TextRun {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_font(mut self, font: impl Into<String>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
 }
 
 impl Default for RenderCommand {
@@ -2,8 +2,11 @@
 
 use std::ops::Add;
 
-pub use super::units::{KPositionType, LayoutType, Units};
-use super::BoxShadow;
+pub use super::units::{
+    AlignItems, Anchor, Dock, ImageRepeat, JustifyContent, KDisplay, KPositionType,
+    LayoutDirection, LayoutType, TextOverflow, Units,
+};
+use super::{BoxShadow, Gradient, StyleTransition};
 use bevy::prelude::Color;
 use bevy::prelude::Component;
 use bevy::prelude::ReflectComponent;
@@ -24,7 +27,7 @@ use super::RenderCommand;
 
 /// Just a wrapper around bevy's CursorIcon so we can define a default.
 #[derive(Debug, Reflect, Clone, PartialEq, Eq)]
-pub struct KCursorIcon(#[reflect(ignore)] pub CursorIcon);
+pub struct KCursorIcon(pub CursorIcon);
 
 impl Default for KCursorIcon {
     fn default() -> Self {
@@ -262,10 +265,50 @@ define_styles! {
     #[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
     #[reflect(Component)]
     pub struct KStyle {
+        /// How children are aligned along the cross axis of this widget
+        ///
+        /// Only applies to widgets with a [`layout_type`](Self::layout_type) of
+        /// [`LayoutType::Row`] or [`LayoutType::Column`]. Aligning to the start, center, or end replaces
+        /// the padding on the edges that receive the free space.
+        ///
+        /// Children sized with [`Units::Stretch`] along the cross axis share the free space with the
+        /// alignment, so give them a fixed or [`Units::Auto`] size to align them.
+        pub align_items: StyleProp<AlignItems>,
+        /// The point of the parent this widget is pinned to
+        ///
+        /// Only applies to widgets with a [`position_type`](Self::position_type) of
+        /// [`KPositionType::SelfDirected`]. The edges the widget is pinned to keep their spacing
+        /// (e.g. [`right`](Self::right) and [`top`](Self::top) for [`Anchor::TopRight`]), while the
+        /// opposite edges stretch to push the widget into place. Centered axes stretch on both sides.
+        pub anchor: StyleProp<Anchor>,
+        /// The ratio of width to height this widget keeps
+        ///
+        /// When only one of [`width`](Self::width) and [`height`](Self::height) is set, the other
+        /// one is derived from it. If neither is set, the height is derived from the width. Once
+        /// both are set, the ratio is ignored.
+        ///
+        /// Sizes that depend on the layout, like [`Units::Stretch`], are honored once the widget
+        /// has been laid out, so the ratio may take effect a frame later.
+        pub aspect_ratio: StyleProp<f32>,
+        /// The radius (in pixels) of a blur applied to everything drawn behind this widget
+        ///
+        /// The blurred backdrop fills the widget's rect, rounded by its
+        /// [`border_radius`](Self::border_radius), and is drawn before the widget's own content.
+        /// Pair it with a translucent [`background_color`](Self::background_color) for a frosted
+        /// glass effect. Inside an [`opacity`](Self::opacity) layer only the content of that layer
+        /// is blurred.
+        pub backdrop_blur: StyleProp<f32>,
         /// The background color of this widget
         ///
         /// Only applies to widgets marked [`RenderCommand::Quad`]
         pub background_color : StyleProp<Color>,
+        /// A gradient drawn as the background of this widget
+        ///
+        /// Replaces [`background_color`](Self::background_color) when set. The gradient follows the
+        /// widget's [`border_radius`](Self::border_radius).
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Quad`]
+        pub background_gradient: StyleProp<Gradient>,
         /// The color of the border around this widget
         ///
         /// Currently, this controls all border sides.
@@ -274,10 +317,20 @@ define_styles! {
         pub border_color: StyleProp<Color>,
         /// The radius of the corners (in pixels)
         ///
-        /// The order is (Top, Right, Bottom, Left).
+        /// The order is (Top Left, Top Right, Bottom Left, Bottom Right).
         ///
         /// Only applies to widgets marked [`RenderCommand::Quad`] and [`RenderCommand::Image`]
         pub border_radius: StyleProp<Corner<f32>>,
+        /// The radius of the corners as a percentage of the widget's smaller dimension
+        ///
+        /// This takes precedence over [`border_radius`](Self::border_radius) and is resolved against
+        /// the widget's layout when it's rendered, so a value of `50.0` always results in a pill
+        /// (or a circle for square widgets) regardless of the widget's size.
+        ///
+        /// The order is (Top Left, Top Right, Bottom Left, Bottom Right).
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Quad`] and [`RenderCommand::Image`]
+        pub border_radius_percent: StyleProp<Corner<f32>>,
         /// The widths of the borders (in pixels)
         ///
         /// The order is (Top, Right, Bottom, Left).
@@ -286,20 +339,73 @@ define_styles! {
         pub border: StyleProp<Edge<f32>>,
         /// The distance between the bottom edge of this widget and the bottom edge of its containing widget
         pub bottom: StyleProp<Units>,
+        /// The color of the text caret
+        ///
+        /// Used by text input widgets such as [`TextBoxBundle`](crate::widgets::TextBoxBundle)
+        pub caret_color: StyleProp<Color>,
         /// The text color for this widget
         ///
+        /// For widgets marked [`RenderCommand::Svg`], the colors of the svg are multiplied by this
+        /// color, unless a [`background_color`](Self::background_color) replaces them. The svg
+        /// widget only inherits this color when it's set to [`StyleProp::Inherit`]. The vertex
+        /// colors of [`RenderCommand::Mesh`] are multiplied by it as well.
+        ///
         /// This property defaults to [`StyleProp::Inherit`] meaning that setting this field to some value will
         /// cause all descendents to receive that value, up to the next set value.
         ///
-        /// Only applies to widgets marked [`RenderCommand::Text`]
+        /// Only applies to widgets marked [`RenderCommand::Text`], [`RenderCommand::Svg`] and
+        /// [`RenderCommand::Mesh`]
         pub color: StyleProp<Color>,
         /// The spacing between child widgets along the horizontal axis
         pub col_between: StyleProp<Units>,
         /// The cursor icon to display when hovering this widget
-        #[reflect(ignore)]
         pub cursor: StyleProp<KCursorIcon>,
+        /// Marks this widget as disabled
+        ///
+        /// This property defaults to [`StyleProp::Inherit`] meaning that disabling a widget will
+        /// also disable all of its descendents, up to the next set value.
+        ///
+        /// Disabled widgets render their text and icons greyed out (see [`disabled_color`](Self::disabled_color)).
+        pub disabled: StyleProp<bool>,
+        /// The text and icon color to use when this widget is [`disabled`](Self::disabled)
+        ///
+        /// If not set, the regular color is desaturated and dimmed instead.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Text`] and [`RenderCommand::Svg`]
+        pub disabled_color: StyleProp<Color>,
+        /// Whether this widget is laid out and drawn
+        ///
+        /// A [`KDisplay::Hidden`] widget keeps its space, while a [`KDisplay::None`] widget is
+        /// removed from the layout as if it weren't there. Either way the widget and its
+        /// descendants aren't drawn and don't receive pointer events.
+        pub display: StyleProp<KDisplay>,
+        /// Docks this widget to an edge of its parent, like a border layout
+        ///
+        /// A docked widget is positioned on its own (see [`KPositionType::SelfDirected`]) and spans
+        /// the edge it's docked to. Its parent offsets the rest of its children by the docked
+        /// widget's size so that they fit between the docked widgets. This makes it easy to build
+        /// menu bars and status bars around the main content of the window.
+        ///
+        /// Widgets docked to the top or bottom need a [`Units::Pixels`] height, and widgets docked
+        /// to the left or right need a [`Units::Pixels`] width. The parent's padding on that edge
+        /// should also be in pixels (or unset).
+        pub dock: StyleProp<Dock>,
+        /// The distance (in pixels) over which content fades out towards each edge of this widget's clip
+        ///
+        /// Instead of being cut off abruptly, clipped content becomes more transparent the closer it
+        /// gets to an edge with a non-zero fade. This is useful for hinting that there's more content,
+        /// such as text that's wider than its container.
+        ///
+        /// The order is (Top, Right, Bottom, Left). Nested clips use their own fade.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Clip`]
+        pub edge_fade: StyleProp<Edge<f32>>,
         /// The font name for this widget
         ///
+        /// This can also be a comma-separated font stack, such as `"Noto Sans, Roboto"`, in
+        /// which case the first font registered in the [`FontMapping`](crate::prelude::FontMapping) is used.
+        /// Characters it has no glyph for are drawn with the first later font that has one.
+        ///
         /// Only applies to [`RenderCommand::Text`]
         pub font: StyleProp<String>,
         /// The font size for this widget, in pixels
@@ -308,6 +414,38 @@ define_styles! {
         pub font_size: StyleProp<f32>,
         /// The height of this widget
         pub height: StyleProp<Units>,
+        /// Extra space (in pixels) around this widget that still counts as hitting it
+        ///
+        /// This grows the area that responds to hover, click, and scroll events without
+        /// affecting layout or rendering. Useful for giving small controls, like icon buttons,
+        /// a larger target on touch screens.
+        ///
+        /// The order is (Top, Right, Bottom, Left).
+        pub hit_padding: StyleProp<Edge<f32>>,
+        /// How the image is drawn when the widget is a different size than the image
+        ///
+        /// Tiled images are repeated from the top-left corner of the widget at their native size
+        /// (in logical pixels).
+        ///
+        /// Only applies to [`RenderCommand::Image`]
+        pub image_repeat: StyleProp<ImageRepeat>,
+        /// How children are distributed along the main axis of this widget
+        ///
+        /// Only applies to widgets with a [`layout_type`](Self::layout_type) of
+        /// [`LayoutType::Row`] or [`LayoutType::Column`]. Any padding on the edges that receive the
+        /// free space is replaced, and [`JustifyContent::SpaceBetween`] and [`JustifyContent::SpaceAround`]
+        /// replace [`col_between`](Self::col_between) or [`row_between`](Self::row_between).
+        ///
+        /// Children sized with [`Units::Stretch`] along the main axis share the free space with the
+        /// spacing, so give them a fixed or [`Units::Auto`] size to justify them.
+        pub justify_content: StyleProp<JustifyContent>,
+        /// The reading direction of this widget
+        ///
+        /// See [`LayoutDirection::Rtl`] for how right-to-left widgets are laid out.
+        ///
+        /// This property defaults to [`StyleProp::Inherit`] meaning that setting this field to some value will
+        /// cause all descendents to receive that value, up to the next set value.
+        pub layout_direction: StyleProp<LayoutDirection>,
         /// The layout method for children of this widget
         pub layout_type: StyleProp<LayoutType>,
         /// The distance between the left edge of this widget and the left edge of its containing widget
@@ -318,6 +456,13 @@ define_styles! {
         pub line_height: StyleProp<f32>,
         /// The maximum height of this widget
         pub max_height: StyleProp<Units>,
+        /// The maximum number of lines of text to show
+        ///
+        /// Text that wraps into more lines is cut off after the last allowed line, which ends in an
+        /// ellipsis. The widget is only as tall as the lines that are shown.
+        ///
+        /// Only applies to [`RenderCommand::Text`]
+        pub max_lines: StyleProp<u32>,
         /// The maximum width of this widget
         pub max_width: StyleProp<Units>,
         /// The minimum height of this widget
@@ -371,16 +516,74 @@ define_styles! {
         pub pointer_events: StyleProp<PointerEvents>,
         /// The position type of the widget relative to its parent
         pub position_type: StyleProp<KPositionType>,
+        /// The global render layer of this widget and its descendants
+        ///
+        /// Unlike [`z_index`](Self::z_index), which only orders a widget relative to its siblings,
+        /// render layers sort the whole UI into global bands. Everything on a higher layer is drawn
+        /// above everything on a lower layer, regardless of where it is in the widget tree. Within a
+        /// layer the usual tree and z-index ordering applies.
+        ///
+        /// This is useful for overlays such as tooltips and drag previews. Widgets are on layer `0`
+        /// by default and negative layers are drawn below it.
+        ///
+        /// This property defaults to [`StyleProp::Inherit`] meaning that setting this field to some value will
+        /// cause all descendents to receive that value, up to the next set value.
+        pub render_layer: StyleProp<i32>,
         /// The render method for this widget
         ///
         /// This controls what actually gets rendered and how it's rendered.
         pub render_command: StyleProp<RenderCommand>,
         /// The distance between the right edge of this widget and the right edge of its containing widget
         pub right: StyleProp<Units>,
+        /// The clockwise rotation of this widget around its center, in radians
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Svg`]
+        pub rotation: StyleProp<f32>,
         /// The spacing between child widgets along the vertical axis
         pub row_between: StyleProp<Units>,
+        /// The highlight color drawn behind selected text
+        ///
+        /// This is drawn underneath the glyphs, so a semi-transparent color is recommended to keep
+        /// the selected text readable. Text inputs default to a half-transparent gray-blue.
+        ///
+        /// Used by text input widgets such as [`TextBoxBundle`](crate::widgets::TextBoxBundle)
+        pub selection_color: StyleProp<Color>,
+        /// A generic value passed to the shader of this widget's quads
+        ///
+        /// This is available to built-in effects as well as custom materials as `shader_param` on the
+        /// vertex output. For example, [`RenderCommand::RadialFill`] uses it as the filled fraction.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Quad`] and [`RenderCommand::RadialFill`]
+        pub shader_param: StyleProp<f32>,
+        /// The order in which Tab moves focus to this widget
+        ///
+        /// Widgets with a positive tab index are focused first, in ascending order, followed by
+        /// the rest in tree order. A negative tab index skips the widget entirely, although it can
+        /// still be focused by clicking it.
+        ///
+        /// Only applies to [`Focusable`](crate::Focusable) widgets.
+        pub tab_index: StyleProp<i32>,
+        /// How text that is wider than its parent is shown
+        ///
+        /// Only applies to [`RenderCommand::Text`] when word wrapping is disabled. Each line that
+        /// doesn't fit is shortened to end in an ellipsis.
+        pub text_overflow: StyleProp<TextOverflow>,
+        /// An outline drawn around text, as its width in logical pixels and its color
+        ///
+        /// The outline is drawn behind the text. Its width is limited by the distance range of
+        /// the font's atlas, so very wide outlines are clamped.
+        ///
+        /// This property defaults to [`StyleProp::Inherit`] meaning that setting this field to some value will
+        /// cause all descendents to receive that value, up to the next set value.
+        ///
+        /// Only applies to widgets marked [`RenderCommand::Text`] and [`RenderCommand::RichText`]
+        pub text_stroke: StyleProp<(f32, Color)>,
         /// The distance between the top edge of this widget and the top edge of its containing widget
         pub top: StyleProp<Units>,
+        /// The properties that are eased from their old to their new value when they change
+        ///
+        /// See [`StyleTransition`] for the properties that can be animated.
+        pub transitions: StyleProp<Vec<StyleTransition>>,
         /// The width of this widget
         pub width: StyleProp<Units>,
         /// The z-index relative to it's parent.
@@ -411,14 +614,19 @@ define_styles! {
         pub col_span: StyleProp<usize>,
         /// The opacity of the widget and it's children
         ///
-        /// This is also known as grouped opacity
+        /// This is also known as grouped opacity. The opacities of nested widgets multiply, so a
+        /// child can be more transparent than its parent, but never less.
         /// WARNING! This splits the widget and it's children into a new render pass. So use it sparingly!!!
         pub opacity: StyleProp<f32>,
+        /// A color multiplied with the widget and it's children
+        ///
+        /// Like [`opacity`](Self::opacity), this draws the widget and it's children into a new render pass
+        /// which is then drawn with this color. So use it sparingly!!!
+        pub tint: StyleProp<Color>,
         /// Box shadow
         /// Currently only applied to quads
         pub box_shadow: StyleProp<Vec<BoxShadow>>,
         /// Overrides the default renderer with a custom material
-        #[reflect(ignore)]
         pub material: StyleProp<MaterialHandle>,
     }
 }
@@ -430,21 +638,38 @@ impl KStyle {
     /// resolving the style.
     pub fn initial() -> Self {
         Self {
+            align_items: StyleProp::Default,
+            anchor: StyleProp::Default,
+            aspect_ratio: StyleProp::Default,
+            backdrop_blur: StyleProp::Default,
             background_color: StyleProp::Default,
+            background_gradient: StyleProp::Default,
             border: StyleProp::Default,
             border_color: StyleProp::Default,
             border_radius: StyleProp::Default,
+            border_radius_percent: StyleProp::Default,
             bottom: StyleProp::Default,
+            caret_color: StyleProp::Inherit,
             color: StyleProp::Inherit,
             cursor: StyleProp::Inherit,
+            disabled: StyleProp::Inherit,
+            disabled_color: StyleProp::Inherit,
+            display: StyleProp::Default,
+            dock: StyleProp::Default,
+            edge_fade: StyleProp::Default,
             col_between: StyleProp::Default,
             font: StyleProp::Inherit,
             font_size: StyleProp::Inherit,
             height: StyleProp::Default,
+            hit_padding: StyleProp::Default,
+            image_repeat: StyleProp::Default,
+            justify_content: StyleProp::Default,
+            layout_direction: StyleProp::Inherit,
             layout_type: StyleProp::Default,
             line_height: StyleProp::Inherit,
             left: StyleProp::Default,
             max_height: StyleProp::Default,
+            max_lines: StyleProp::Default,
             max_width: StyleProp::Default,
             min_height: StyleProp::Default,
             min_width: StyleProp::Default,
@@ -457,9 +682,17 @@ impl KStyle {
             pointer_events: StyleProp::Default,
             position_type: StyleProp::Default,
             render_command: StyleProp::Value(RenderCommand::Layout),
+            render_layer: StyleProp::Inherit,
             right: StyleProp::Default,
+            rotation: StyleProp::Default,
             row_between: StyleProp::Default,
+            selection_color: StyleProp::Inherit,
+            shader_param: StyleProp::Default,
+            tab_index: StyleProp::Default,
+            text_overflow: StyleProp::Default,
+            text_stroke: StyleProp::Inherit,
             top: StyleProp::Default,
+            transitions: StyleProp::Default,
             width: StyleProp::Default,
             z_index: StyleProp::Default,
             grid_rows: StyleProp::Default,
@@ -469,6 +702,7 @@ impl KStyle {
             row_span: StyleProp::Default,
             col_span: StyleProp::Default,
             opacity: StyleProp::Value(1.0),
+            tint: StyleProp::Default,
             box_shadow: StyleProp::Default,
             material: StyleProp::Default,
         }
@@ -527,6 +761,22 @@ impl KStyle {
             self.border_radius.clone()
         };
 
+        new_styles.border_radius_percent =
+            if let StyleProp::Value(border_a) = self.border_radius_percent {
+                if let StyleProp::Value(border_b) = b.border_radius_percent {
+                    StyleProp::Value(Corner::new(
+                        lerp(border_a.top_left, border_b.top_left, x),
+                        lerp(border_a.top_right, border_b.top_right, x),
+                        lerp(border_a.bottom_left, border_b.bottom_left, x),
+                        lerp(border_a.bottom_right, border_b.bottom_right, x),
+                    ))
+                } else {
+                    StyleProp::Value(border_a)
+                }
+            } else {
+                self.border_radius_percent.clone()
+            };
+
         new_styles.bottom = lerp_units(&self.bottom, &b.bottom, x);
 
         new_styles.color = if let StyleProp::Value(color_a) = self.color {
@@ -618,6 +868,16 @@ impl KStyle {
         new_styles.top = lerp_units(&self.top, &b.top, x);
         new_styles.width = lerp_units(&self.width, &b.width, x);
         new_styles.opacity = lerp_f32(&self.opacity, &b.opacity, x);
+        new_styles.tint = if let StyleProp::Value(color_a) = self.tint {
+            if let StyleProp::Value(color_b) = b.tint {
+                StyleProp::Value(hsv_lerp(&color_a, &color_b, x))
+            } else {
+                StyleProp::Value(color_a)
+            }
+        } else {
+            self.tint.clone()
+        };
+        new_styles.shader_param = lerp_f32(&self.shader_param, &b.shader_param, x);
 
         new_styles
     }
@@ -0,0 +1,268 @@
+//! Themes loaded from RON files
+//!
+//! A [`KayakTheme`] holds named colors, sizes, and font names that widgets look up while
+//! rendering. Themes can be written by hand as `.kayak_theme.ron` files:
+//!
+//! ```ron
+//! (
+//!     colors: {
+//!         "primary": "#5c6ec4",
+//!         "background": "#1f2128",
+//!     },
+//!     sizes: {
+//!         "font_size": 16.0,
+//!     },
+//!     fonts: {
+//!         "body": "roboto",
+//!     },
+//! )
+//! ```
+//!
+//! Point [`KayakThemeSource`] at the file and add [`KayakThemePlugin`]. Whatever is in
+//! [`KayakTheme`] when the file first loads is kept as the defaults. Tokens the file leaves out
+//! fall back to those defaults with a warning. With Bevy's asset watching turned on, saving the
+//! file restyles every widget that updates with [`widget_update_with_theme`]. This module
+//! requires the `persistence` feature.
+//!
+//! ```rust,no_run
+//! use bevy::prelude::*;
+//! use kayak_ui::prelude::{widgets::*, *};
+//!
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     let theme = asset_server.load("default.kayak_theme.ron");
+//!     commands.insert_resource(KayakThemeSource(theme));
+//! }
+//!
+//! fn main() {
+//!     App::new()
+//!         .add_plugins((DefaultPlugins, KayakContextPlugin, KayakWidgets, KayakThemePlugin))
+//!         .add_systems(Startup, setup)
+//!         .run()
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{
+        AddAsset, AssetEvent, AssetLoader, Assets, BoxedFuture, Handle, LoadContext, LoadedAsset,
+    },
+    prelude::{
+        App, Color, Component, Entity, EventReader, In, Local, Plugin, Res, ResMut, Resource,
+        Update,
+    },
+    reflect::{TypePath, TypeUuid},
+};
+use serde::Deserialize;
+
+use crate::{prelude::KayakWidgetContext, widget::WidgetParam};
+
+/// Named colors, sizes, and fonts shared by the widgets of an app
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct KayakTheme {
+    pub colors: HashMap<String, Color>,
+    pub sizes: HashMap<String, f32>,
+    /// Font names as registered with [`FontMapping`](crate::prelude::FontMapping)
+    pub fonts: HashMap<String, String>,
+}
+
+impl KayakTheme {
+    pub fn color(&self, token: &str) -> Option<Color> {
+        self.colors.get(token).copied()
+    }
+
+    pub fn size(&self, token: &str) -> Option<f32> {
+        self.sizes.get(token).copied()
+    }
+
+    pub fn font(&self, token: &str) -> Option<&str> {
+        self.fonts.get(token).map(String::as_str)
+    }
+
+    /// Builds a theme from a theme file, taking every token the file doesn't set from `defaults`
+    ///
+    /// A warning is logged for each default token the file leaves out and for each color that
+    /// isn't a valid hex string.
+    pub fn from_asset(asset: &KayakThemeAsset, defaults: &KayakTheme) -> Self {
+        let mut theme = defaults.clone();
+
+        for (token, hex) in asset.colors.iter() {
+            match parse_hex_color(hex) {
+                Some(color) => {
+                    theme.colors.insert(token.clone(), color);
+                }
+                None => log::warn!(
+                    "Theme color \"{}\" is not a valid hex color ({}), using its default",
+                    token,
+                    hex
+                ),
+            }
+        }
+        theme.sizes.extend(
+            asset
+                .sizes
+                .iter()
+                .map(|(token, size)| (token.clone(), *size)),
+        );
+        theme.fonts.extend(asset.fonts.clone());
+
+        warn_missing("color", defaults.colors.keys(), &asset.colors);
+        warn_missing("size", defaults.sizes.keys(), &asset.sizes);
+        warn_missing("font", defaults.fonts.keys(), &asset.fonts);
+
+        theme
+    }
+}
+
+fn warn_missing<'a, T>(
+    kind: &str,
+    tokens: impl Iterator<Item = &'a String>,
+    set: &HashMap<String, T>,
+) {
+    for token in tokens.filter(|token| !set.contains_key(*token)) {
+        log::warn!(
+            "Theme is missing the {} \"{}\", using its default",
+            kind,
+            token
+        );
+    }
+}
+
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    Color::hex(hex.trim().trim_start_matches('#')).ok()
+}
+
+/// A theme as written in a `.kayak_theme.ron` file
+///
+/// Colors are hex strings such as `"#5c6ec4"` or `"5c6ec480"`.
+#[derive(Deserialize, TypeUuid, TypePath, Debug, Default, Clone, PartialEq)]
+#[uuid = "7ce1b1f2-2ee4-49d9-a5f5-e36a9699a5c0"]
+pub struct KayakThemeAsset {
+    #[serde(default)]
+    pub colors: HashMap<String, String>,
+    #[serde(default)]
+    pub sizes: HashMap<String, f32>,
+    #[serde(default)]
+    pub fonts: HashMap<String, String>,
+}
+
+/// Loads `.kayak_theme.ron` files through the `AssetServer`
+#[derive(Default)]
+pub struct KayakThemeLoader;
+
+impl AssetLoader for KayakThemeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let theme: KayakThemeAsset = ron::de::from_bytes(bytes).map_err(|error| {
+                anyhow::anyhow!(
+                    "Failed to load theme {}: {}",
+                    load_context.path().display(),
+                    error
+                )
+            })?;
+            load_context.set_default_asset(LoadedAsset::new(theme));
+
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["kayak_theme.ron"];
+        EXTENSIONS
+    }
+}
+
+/// The theme file applied to [`KayakTheme`]
+#[derive(Resource, Debug, Clone)]
+pub struct KayakThemeSource(pub Handle<KayakThemeAsset>);
+
+/// Applies the [`KayakThemeSource`] file to [`KayakTheme`] whenever it loads or changes
+pub fn apply_theme_asset(
+    mut asset_events: EventReader<AssetEvent<KayakThemeAsset>>,
+    source: Option<Res<KayakThemeSource>>,
+    themes: Res<Assets<KayakThemeAsset>>,
+    mut theme: ResMut<KayakTheme>,
+    mut defaults: Local<Option<KayakTheme>>,
+) {
+    let Some(source) = source else {
+        asset_events.clear();
+        return;
+    };
+
+    let file_changed = asset_events.iter().any(|event| match event {
+        AssetEvent::Created { handle } | AssetEvent::Modified { handle } => *handle == source.0,
+        AssetEvent::Removed { .. } => false,
+    });
+    if !file_changed && !source.is_changed() {
+        return;
+    }
+
+    let Some(asset) = themes.get(&source.0) else {
+        return;
+    };
+    let defaults = defaults.get_or_insert_with(|| theme.clone());
+    let loaded = KayakTheme::from_asset(asset, defaults);
+    if *theme != loaded {
+        *theme = loaded;
+    }
+}
+
+/// Like [`widget_update`](crate::prelude::widget_update), but also updates the widget whenever
+/// [`KayakTheme`] changes
+pub fn widget_update_with_theme<
+    Props: PartialEq + Component + Clone,
+    State: PartialEq + Component + Clone,
+>(
+    In((entity, previous_entity)): In<(Entity, Entity)>,
+    widget_context: Res<KayakWidgetContext>,
+    widget_param: WidgetParam<Props, State>,
+    theme: Res<KayakTheme>,
+) -> bool {
+    theme.is_changed() || widget_param.has_changed(&widget_context, entity, previous_entity)
+}
+
+/// Adds [`KayakTheme`] and loads it from [`KayakThemeSource`]
+pub struct KayakThemePlugin;
+
+impl Plugin for KayakThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<KayakThemeAsset>()
+            .add_asset_loader(KayakThemeLoader)
+            .init_resource::<KayakTheme>()
+            .add_systems(Update, apply_theme_asset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+
+    use super::{KayakTheme, KayakThemeAsset};
+
+    #[test]
+    fn missing_and_invalid_tokens_fall_back_to_defaults() {
+        let mut defaults = KayakTheme::default();
+        defaults.colors.insert("primary".into(), Color::RED);
+        defaults.colors.insert("background".into(), Color::BLACK);
+        defaults.sizes.insert("font_size".into(), 14.0);
+
+        let asset: KayakThemeAsset = ron::from_str(
+            r##"(
+                colors: { "primary": "not a color", "background": "#ffffff", "accent": "00ff00" },
+                fonts: { "body": "roboto" },
+            )"##,
+        )
+        .unwrap();
+        let theme = KayakTheme::from_asset(&asset, &defaults);
+
+        assert_eq!(theme.color("primary"), Some(Color::RED));
+        assert_eq!(theme.color("background"), Some(Color::WHITE));
+        assert_eq!(theme.color("accent"), Some(Color::rgb(0.0, 1.0, 0.0)));
+        assert_eq!(theme.size("font_size"), Some(14.0));
+        assert_eq!(theme.font("body"), Some("roboto"));
+    }
+}
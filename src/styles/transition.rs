@@ -0,0 +1,336 @@
+use bevy::{
+    prelude::{Color, Commands, Entity, Query, Res, ResMut, Resource, Time},
+    reflect::Reflect,
+    utils::HashMap,
+};
+
+use crate::{node::DirtyNode, widgets::TransitionEasing};
+
+use super::{style::lerp, ComputedStyles, Corner, KStyle, StyleProp, Units};
+
+/// A style property that can be animated with a [`StyleTransition`]
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransitionProperty {
+    BackgroundColor,
+    BorderColor,
+    BorderRadius,
+    Bottom,
+    Color,
+    FontSize,
+    Height,
+    Left,
+    Opacity,
+    Right,
+    Top,
+    Width,
+}
+
+impl TransitionProperty {
+    pub(super) const ALL: [TransitionProperty; 12] = [
+        Self::BackgroundColor,
+        Self::BorderColor,
+        Self::BorderRadius,
+        Self::Bottom,
+        Self::Color,
+        Self::FontSize,
+        Self::Height,
+        Self::Left,
+        Self::Opacity,
+        Self::Right,
+        Self::Top,
+        Self::Width,
+    ];
+}
+
+/// Eases a style property from its old to its new value whenever it changes
+///
+/// Transitions are set with the [`transitions`](KStyle::transitions) style:
+///
+/// ```
+/// # use kayak_ui::prelude::*;
+/// # use kayak_ui::widgets::TransitionEasing;
+/// let styles = KStyle {
+///     background_color: Color::RED.into(),
+///     transitions: vec![StyleTransition::new(
+///         TransitionProperty::BackgroundColor,
+///         0.2,
+///         TransitionEasing::CubicOut,
+///     )]
+///     .into(),
+///     ..Default::default()
+/// };
+/// ```
+///
+/// Units can only be animated between values of the same kind, such as two pixel values.
+/// Other changes are applied right away.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct StyleTransition {
+    /// The animated property
+    pub property: TransitionProperty,
+    /// The length of the animation in seconds
+    pub duration: f32,
+    /// The easing curve of the animation
+    pub easing: TransitionEasing,
+}
+
+impl StyleTransition {
+    pub fn new(property: TransitionProperty, duration: f32, easing: TransitionEasing) -> Self {
+        Self {
+            property,
+            duration,
+            easing,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum TransitionValue {
+    Color(Color),
+    Corner(Corner<f32>),
+    Float(f32),
+    Units(Units),
+}
+
+impl TransitionValue {
+    pub(super) fn get(property: TransitionProperty, styles: &KStyle) -> Option<Self> {
+        match property {
+            TransitionProperty::BackgroundColor => value_of(&styles.background_color, Self::Color),
+            TransitionProperty::BorderColor => value_of(&styles.border_color, Self::Color),
+            TransitionProperty::BorderRadius => value_of(&styles.border_radius, Self::Corner),
+            TransitionProperty::Bottom => value_of(&styles.bottom, Self::Units),
+            TransitionProperty::Color => value_of(&styles.color, Self::Color),
+            TransitionProperty::FontSize => value_of(&styles.font_size, Self::Float),
+            TransitionProperty::Height => value_of(&styles.height, Self::Units),
+            TransitionProperty::Left => value_of(&styles.left, Self::Units),
+            TransitionProperty::Opacity => value_of(&styles.opacity, Self::Float),
+            TransitionProperty::Right => value_of(&styles.right, Self::Units),
+            TransitionProperty::Top => value_of(&styles.top, Self::Units),
+            TransitionProperty::Width => value_of(&styles.width, Self::Units),
+        }
+    }
+
+    pub(super) fn set(self, property: TransitionProperty, styles: &mut KStyle) {
+        match (property, self) {
+            (TransitionProperty::BackgroundColor, Self::Color(value)) => {
+                styles.background_color = value.into()
+            }
+            (TransitionProperty::BorderColor, Self::Color(value)) => {
+                styles.border_color = value.into()
+            }
+            (TransitionProperty::BorderRadius, Self::Corner(value)) => {
+                styles.border_radius = value.into()
+            }
+            (TransitionProperty::Bottom, Self::Units(value)) => styles.bottom = value.into(),
+            (TransitionProperty::Color, Self::Color(value)) => styles.color = value.into(),
+            (TransitionProperty::FontSize, Self::Float(value)) => styles.font_size = value.into(),
+            (TransitionProperty::Height, Self::Units(value)) => styles.height = value.into(),
+            (TransitionProperty::Left, Self::Units(value)) => styles.left = value.into(),
+            (TransitionProperty::Opacity, Self::Float(value)) => styles.opacity = value.into(),
+            (TransitionProperty::Right, Self::Units(value)) => styles.right = value.into(),
+            (TransitionProperty::Top, Self::Units(value)) => styles.top = value.into(),
+            (TransitionProperty::Width, Self::Units(value)) => styles.width = value.into(),
+            _ => {}
+        }
+    }
+
+    /// Interpolates between two values, or returns `None` if they can't be interpolated
+    pub(super) fn lerp(&self, to: &Self, x: f32) -> Option<Self> {
+        match (self, to) {
+            (Self::Color(a), Self::Color(b)) => {
+                let [a_r, a_g, a_b, a_a] = a.as_rgba_f32();
+                let [b_r, b_g, b_b, b_a] = b.as_rgba_f32();
+                Some(Self::Color(Color::rgba(
+                    lerp(a_r, b_r, x),
+                    lerp(a_g, b_g, x),
+                    lerp(a_b, b_b, x),
+                    lerp(a_a, b_a, x),
+                )))
+            }
+            (Self::Corner(a), Self::Corner(b)) => Some(Self::Corner(Corner::new(
+                lerp(a.top_left, b.top_left, x),
+                lerp(a.top_right, b.top_right, x),
+                lerp(a.bottom_left, b.bottom_left, x),
+                lerp(a.bottom_right, b.bottom_right, x),
+            ))),
+            (Self::Float(a), Self::Float(b)) => Some(Self::Float(lerp(*a, *b, x))),
+            (Self::Units(a), Self::Units(b)) => match (a, b) {
+                (Units::Pixels(a), Units::Pixels(b)) => {
+                    Some(Self::Units(Units::Pixels(lerp(*a, *b, x))))
+                }
+                (Units::Percentage(a), Units::Percentage(b)) => {
+                    Some(Self::Units(Units::Percentage(lerp(*a, *b, x))))
+                }
+                (Units::Stretch(a), Units::Stretch(b)) => {
+                    Some(Self::Units(Units::Stretch(lerp(*a, *b, x))))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+fn value_of<T, F>(prop: &StyleProp<T>, f: F) -> Option<TransitionValue>
+where
+    T: Default + Clone + Reflect + bevy::reflect::FromReflect,
+    F: FnOnce(T) -> TransitionValue,
+{
+    if let StyleProp::Value(value) = prop {
+        Some(f(value.clone()))
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PropertyState {
+    /// The value the animation starts from
+    from: TransitionValue,
+    /// The value set by the widget
+    target: TransitionValue,
+    /// The value last written to the node's styles
+    displayed: TransitionValue,
+    /// Time (in seconds) since the animation started
+    elapsed: f32,
+}
+
+/// The animation state of every node with [`transitions`](KStyle::transitions)
+#[derive(Resource, Debug, Default)]
+pub struct StyleTransitions {
+    nodes: HashMap<Entity, HashMap<TransitionProperty, PropertyState>>,
+}
+
+impl StyleTransitions {
+    /// Returns true if any property of the given node is currently animating
+    pub fn is_animating(&self, entity: Entity) -> bool {
+        self.nodes.get(&entity).map_or(false, |properties| {
+            properties
+                .values()
+                .any(|state| state.displayed != state.target)
+        })
+    }
+}
+
+/// Eases the transitioned properties of every node towards the values set by their widgets
+///
+/// This runs after widgets are rendered and before nodes are calculated, so that the eased
+/// values are used for layout and rendering.
+pub(crate) fn update_style_transitions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut transitions: ResMut<StyleTransitions>,
+    mut query: Query<(Entity, &mut ComputedStyles)>,
+) {
+    transitions
+        .nodes
+        .retain(|entity, _| query.contains(*entity));
+
+    for (entity, mut computed_styles) in query.iter_mut() {
+        let style_transitions =
+            if let StyleProp::Value(style_transitions) = &computed_styles.0.transitions {
+                style_transitions.clone()
+            } else {
+                transitions.nodes.remove(&entity);
+                continue;
+            };
+
+        let properties = transitions.nodes.entry(entity).or_default();
+        let mut is_dirty = false;
+        for transition in style_transitions {
+            let current = TransitionValue::get(transition.property, &computed_styles.0);
+            let current = if let Some(current) = current {
+                current
+            } else {
+                properties.remove(&transition.property);
+                continue;
+            };
+
+            if let Some(state) = properties.get_mut(&transition.property) {
+                if current != state.displayed && current != state.target {
+                    // The widget set a new value, ease towards it from what is shown now.
+                    state.from = state.displayed;
+                    state.target = current;
+                    state.elapsed = 0.0;
+                }
+
+                if state.displayed != state.target {
+                    state.elapsed += time.delta_seconds();
+                    let x = if transition.duration > 0.0 {
+                        transition.easing.ease(state.elapsed / transition.duration)
+                    } else {
+                        1.0
+                    };
+                    state.displayed = if state.elapsed >= transition.duration {
+                        state.target
+                    } else {
+                        state.from.lerp(&state.target, x).unwrap_or(state.target)
+                    };
+                }
+
+                if current != state.displayed {
+                    state
+                        .displayed
+                        .set(transition.property, &mut computed_styles.0);
+                    is_dirty = true;
+                }
+            } else {
+                // Values are only eased once they change, not when the node first appears.
+                properties.insert(
+                    transition.property,
+                    PropertyState {
+                        from: current,
+                        target: current,
+                        displayed: current,
+                        elapsed: 0.0,
+                    },
+                );
+            }
+        }
+
+        if is_dirty {
+            commands.entity(entity).insert(DirtyNode);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransitionProperty, TransitionValue};
+    use crate::styles::{KStyle, Units};
+
+    #[test]
+    fn units_should_only_lerp_between_matching_kinds() {
+        let a = TransitionValue::Units(Units::Pixels(10.0));
+        let b = TransitionValue::Units(Units::Pixels(20.0));
+        let c = TransitionValue::Units(Units::Percentage(50.0));
+
+        assert_eq!(
+            Some(TransitionValue::Units(Units::Pixels(15.0))),
+            a.lerp(&b, 0.5)
+        );
+        assert_eq!(None, a.lerp(&c, 0.5));
+    }
+
+    #[test]
+    fn values_should_round_trip_through_styles() {
+        let mut styles = KStyle {
+            opacity: 0.5.into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Some(TransitionValue::Float(0.5)),
+            TransitionValue::get(TransitionProperty::Opacity, &styles)
+        );
+        assert_eq!(
+            None,
+            TransitionValue::get(TransitionProperty::Width, &styles)
+        );
+
+        TransitionValue::Units(Units::Pixels(32.0)).set(TransitionProperty::Width, &mut styles);
+        assert_eq!(
+            Some(TransitionValue::Units(Units::Pixels(32.0))),
+            TransitionValue::get(TransitionProperty::Width, &styles)
+        );
+    }
+}
@@ -9,6 +9,12 @@ pub enum LayoutType {
     /// Stack child elements vertically
     Column,
     /// Position child elements into specified rows and columns
+    ///
+    /// The tracks are defined by the [`grid_rows`](crate::styles::KStyle::grid_rows) and
+    /// [`grid_cols`](crate::styles::KStyle::grid_cols) of this widget, while each child picks its
+    /// cell with [`row_index`](crate::styles::KStyle::row_index) and
+    /// [`col_index`](crate::styles::KStyle::col_index) and may cover several cells with
+    /// [`row_span`](crate::styles::KStyle::row_span) and [`col_span`](crate::styles::KStyle::col_span).
     Grid,
 }
 
@@ -22,6 +28,151 @@ impl From<LayoutType> for morphorm::LayoutType {
     }
 }
 
+/// The direction in which a widget reads, like the direction of its text
+#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutDirection {
+    #[default]
+    /// Read from left to right
+    Ltr,
+    /// Read from right to left, as in Arabic or Hebrew
+    ///
+    /// The children of a [`LayoutType::Row`] are placed from right to left, the left and right
+    /// edges of margins and padding are swapped, and text aligned to the start is aligned to the
+    /// right instead.
+    Rtl,
+}
+
+/// Distributes child elements along the main axis of a [`LayoutType::Row`] or [`LayoutType::Column`]
+///
+/// The main axis is horizontal for rows and vertical for columns.
+#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq)]
+pub enum JustifyContent {
+    #[default]
+    /// Pack child elements toward the start of the main axis
+    Start,
+    /// Pack child elements around the center of the main axis
+    Center,
+    /// Pack child elements toward the end of the main axis
+    End,
+    /// Place the first and last child elements on the edges and distribute the rest evenly
+    SpaceBetween,
+    /// Distribute child elements evenly with half-size spaces on the edges
+    SpaceAround,
+}
+
+/// Aligns child elements along the cross axis of a [`LayoutType::Row`] or [`LayoutType::Column`]
+///
+/// The cross axis is vertical for rows and horizontal for columns.
+#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq)]
+pub enum AlignItems {
+    /// Align child elements to the start of the cross axis
+    Start,
+    /// Align child elements to the center of the cross axis
+    Center,
+    /// Align child elements to the end of the cross axis
+    End,
+    #[default]
+    /// Leave the cross axis spacing as-is, allowing stretched children to fill it
+    Stretch,
+}
+
+/// The point of the parent a [`KPositionType::SelfDirected`] node is pinned to
+#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    /// Pin the node to the top-left corner of its parent
+    #[default]
+    TopLeft,
+    /// Pin the node to the center of the top edge of its parent
+    TopCenter,
+    /// Pin the node to the top-right corner of its parent
+    TopRight,
+    /// Pin the node to the center of the left edge of its parent
+    CenterLeft,
+    /// Pin the node to the center of its parent
+    Center,
+    /// Pin the node to the center of the right edge of its parent
+    CenterRight,
+    /// Pin the node to the bottom-left corner of its parent
+    BottomLeft,
+    /// Pin the node to the center of the bottom edge of its parent
+    BottomCenter,
+    /// Pin the node to the bottom-right corner of its parent
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns true if the space before the node along the given axis should stretch
+    pub(crate) fn stretches_start(&self, horizontal: bool) -> bool {
+        if horizontal {
+            !matches!(self, Self::TopLeft | Self::CenterLeft | Self::BottomLeft)
+        } else {
+            !matches!(self, Self::TopLeft | Self::TopCenter | Self::TopRight)
+        }
+    }
+
+    /// Returns true if the space after the node along the given axis should stretch
+    pub(crate) fn stretches_end(&self, horizontal: bool) -> bool {
+        if horizontal {
+            !matches!(self, Self::TopRight | Self::CenterRight | Self::BottomRight)
+        } else {
+            !matches!(
+                self,
+                Self::BottomLeft | Self::BottomCenter | Self::BottomRight
+            )
+        }
+    }
+}
+
+/// The edge of the parent a widget is docked to
+#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+pub enum Dock {
+    /// Dock to the top edge, spanning the full width of the parent
+    #[default]
+    Top,
+    /// Dock to the bottom edge, spanning the full width of the parent
+    Bottom,
+    /// Dock to the left edge, spanning the height between the top and bottom docks
+    Left,
+    /// Dock to the right edge, spanning the height between the top and bottom docks
+    Right,
+}
+
+/// How text that doesn't fit within its parent is shown
+#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// The text overflows and is cut off by the nearest clip
+    #[default]
+    Clip,
+    /// The text is cut short and ends with an ellipsis (`…`)
+    Ellipsis,
+}
+
+/// How an image is drawn across a widget that is a different size than the image
+#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+pub enum ImageRepeat {
+    /// The image is stretched to fill the widget
+    #[default]
+    Stretch,
+    /// The image is repeated at its native size in both directions
+    Tile,
+    /// The image is repeated at its native width and stretched vertically
+    TileX,
+    /// The image is repeated at its native height and stretched horizontally
+    TileY,
+}
+
+/// Whether a widget is laid out and drawn
+#[derive(Default, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+pub enum KDisplay {
+    #[default]
+    /// The widget is laid out and drawn as usual
+    Shown,
+    /// The widget keeps its space in the layout, but it and its descendants aren't drawn
+    Hidden,
+    /// The widget and its descendants are removed from the layout and aren't drawn
+    None,
+}
+
 /// The position type determines whether a node will be positioned in-line with its siblings or seperate
 #[derive(Default, Debug, Reflect, Clone, Copy, PartialEq)]
 pub enum KPositionType {
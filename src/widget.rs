@@ -53,6 +53,26 @@ pub fn widget_update_with_context<
     widget_param.has_changed(&widget_context, entity, previous_entity)
 }
 
+/// Like [`widget_update`], but also updates the widget whenever it has events of type `T` queued
+///
+/// Events are queued with [`KayakWidgetContext::emit_event`] and read in the render system with
+/// [`KayakWidgetContext::read_events`].
+pub fn widget_update_with_events<
+    Props: PartialEq + Component + Clone,
+    State: PartialEq + Component + Clone,
+    T: Send + 'static,
+>(
+    In((entity, previous_entity)): In<(Entity, Entity)>,
+    widget_context: Res<KayakWidgetContext>,
+    widget_param: WidgetParam<Props, State>,
+) -> bool {
+    if widget_context.has_events::<T>(entity) {
+        return true;
+    }
+
+    widget_param.has_changed(&widget_context, entity, previous_entity)
+}
+
 #[derive(SystemParam)]
 pub struct WidgetParam<'w, 's, Props: PartialEq + Component, State: PartialEq + Component> {
     pub props_query: Query<'w, 's, &'static Props>,
@@ -6,7 +6,7 @@ use morphorm::Hierarchy;
 
 use crate::{
     context_entities::ContextEntities, layout::LayoutCache, node::WrappedIndex, prelude::Tree,
-    widget_state::WidgetState,
+    widget_events::WidgetEventQueues, widget_state::WidgetState,
 };
 
 /// KayakWidgetContext manages tree, state, and context updates within a single widget.
@@ -30,6 +30,7 @@ pub struct KayakWidgetContext {
     // The 2nd hashmap is a list of keys and their entities.
     unique_ids: Arc<DashMap<Entity, DashMap<String, Entity>>>,
     unique_ids_parents: Arc<DashMap<Entity, Entity>>,
+    widget_events: WidgetEventQueues,
 }
 
 impl KayakWidgetContext {
@@ -43,6 +44,7 @@ impl KayakWidgetContext {
         camera_entity: Option<Entity>,
         unique_ids: Arc<DashMap<Entity, DashMap<String, Entity>>>,
         unique_ids_parents: Arc<DashMap<Entity, Entity>>,
+        widget_events: WidgetEventQueues,
     ) -> Self {
         Self {
             old_tree,
@@ -55,6 +57,7 @@ impl KayakWidgetContext {
             camera_entity,
             unique_ids,
             unique_ids_parents,
+            widget_events,
         }
     }
 
@@ -138,6 +141,33 @@ impl KayakWidgetContext {
         children
     }
 
+    /// Retrieves a list of all descendants in depth-first order.
+    ///
+    /// This reads the tree as of the last completed update. The returned list is a
+    /// snapshot, so no lock on the tree is held once this returns.
+    pub fn get_descendants(&self, entity: Entity) -> Vec<Entity> {
+        let mut descendants = vec![];
+        if let Ok(tree) = self.old_tree.read() {
+            descendants = tree
+                .down_iter_at(WrappedIndex(entity), false)
+                .map(|index| index.0)
+                .collect::<Vec<_>>();
+        }
+
+        descendants
+    }
+
+    /// Returns the number of descendants the given entity has.
+    ///
+    /// See [`get_descendants`](Self::get_descendants) for details.
+    pub fn descendant_count(&self, entity: Entity) -> usize {
+        if let Ok(tree) = self.old_tree.read() {
+            tree.down_iter_at(WrappedIndex(entity), false).count()
+        } else {
+            0
+        }
+    }
+
     fn get_children_ordered(&self, entity: Entity) -> Vec<Entity> {
         let mut children = vec![];
         if let Ok(tree) = self.order_tree.read() {
@@ -177,6 +207,27 @@ impl KayakWidgetContext {
         self.widget_state.get(widget_entity)
     }
 
+    /// Queues an event for the given widget, which it can read with [`Self::read_events`]
+    ///
+    /// Unlike bevy events, these are only seen by the widget they were sent to. Events that
+    /// aren't read by the end of the next widget update are dropped.
+    pub fn emit_event<T: Send + 'static>(&self, widget_entity: Entity, event: T) {
+        self.widget_events.get::<T>().send(widget_entity, event);
+    }
+
+    /// Removes and returns the events of the given type queued for a widget, oldest first
+    ///
+    /// Use [`widget_update_with_events`](crate::widget::widget_update_with_events) so the
+    /// widget is re-rendered when it has events to read.
+    pub fn read_events<T: Send + 'static>(&self, widget_entity: Entity) -> Vec<T> {
+        self.widget_events.get::<T>().drain(widget_entity)
+    }
+
+    /// Returns true if events of the given type are queued for a widget
+    pub fn has_events<T: Send + 'static>(&self, widget_entity: Entity) -> bool {
+        self.widget_events.get::<T>().has_events(widget_entity)
+    }
+
     /// Returns a new/existing widget entity.
     /// Because a re-render can potentially spawn new entities it's advised to use this
     /// to avoid creating a new entity.
@@ -0,0 +1,116 @@
+use std::{
+    any::{Any, TypeId},
+    sync::{Arc, Mutex},
+};
+
+use bevy::{prelude::Entity, utils::HashMap};
+use dashmap::DashMap;
+
+/// The events of a single type queued for widgets
+///
+/// Events are kept for one call of [`update_widgets_sys`](crate::context::update_widgets_sys)
+/// after the one they were sent before, so a widget rendered before the sender still gets them.
+/// Events that haven't been read by then are dropped.
+pub struct WidgetEvents<T> {
+    buffers: Mutex<WidgetEventBuffers<T>>,
+}
+
+struct WidgetEventBuffers<T> {
+    current: HashMap<Entity, Vec<T>>,
+    previous: HashMap<Entity, Vec<T>>,
+}
+
+impl<T> Default for WidgetEvents<T> {
+    fn default() -> Self {
+        Self {
+            buffers: Mutex::new(WidgetEventBuffers {
+                current: HashMap::default(),
+                previous: HashMap::default(),
+            }),
+        }
+    }
+}
+
+impl<T> WidgetEvents<T> {
+    /// Queues an event for the given widget
+    pub fn send(&self, widget: Entity, event: T) {
+        if let Ok(mut buffers) = self.buffers.lock() {
+            buffers.current.entry(widget).or_default().push(event);
+        }
+    }
+
+    /// Returns true if the given widget has unread events
+    pub fn has_events(&self, widget: Entity) -> bool {
+        self.buffers
+            .lock()
+            .map(|buffers| {
+                buffers.previous.contains_key(&widget) || buffers.current.contains_key(&widget)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Removes and returns the given widget's events, oldest first
+    pub fn drain(&self, widget: Entity) -> Vec<T> {
+        let Ok(mut buffers) = self.buffers.lock() else {
+            return Vec::new();
+        };
+        let mut events = buffers.previous.remove(&widget).unwrap_or_default();
+        events.extend(buffers.current.remove(&widget).unwrap_or_default());
+        events
+    }
+}
+
+/// Allows the queues of every event type to be updated together
+trait EventQueue: Send + Sync {
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
+    fn update(&self);
+}
+
+impl<T: Send + 'static> EventQueue for WidgetEvents<T> {
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+
+    fn update(&self) {
+        if let Ok(mut buffers) = self.buffers.lock() {
+            buffers.previous = std::mem::take(&mut buffers.current);
+        }
+    }
+}
+
+/// Stores the [`WidgetEvents`] of every event type sent to widgets
+#[derive(Default, Clone)]
+pub(crate) struct WidgetEventQueues {
+    queues: Arc<DashMap<TypeId, Arc<dyn EventQueue>>>,
+}
+
+impl WidgetEventQueues {
+    /// Returns the queue for the given event type, creating it if needed
+    pub fn get<T: Send + 'static>(&self) -> Arc<WidgetEvents<T>> {
+        let queue = self
+            .queues
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Arc::new(WidgetEvents::<T>::default()))
+            .clone();
+        // The queue for a type is always created with that type
+        queue
+            .into_any()
+            .downcast::<WidgetEvents<T>>()
+            .unwrap_or_else(|_| unreachable!())
+    }
+
+    /// Drops unread events from before the last update and ages the rest
+    pub fn update(&self) {
+        for queue in self.queues.iter() {
+            queue.update();
+        }
+    }
+}
+
+impl std::fmt::Debug for WidgetEventQueues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WidgetEventQueues")
+            .field("types", &self.queues.len())
+            .finish()
+    }
+}
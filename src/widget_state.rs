@@ -35,6 +35,12 @@ impl WidgetState {
         }
     }
 
+    /// Maps a widget entity to an already spawned state entity
+    pub(crate) fn insert(&self, widget_entity: Entity, state_entity: Entity) {
+        self.mapping.insert(widget_entity, state_entity);
+        self.reverse_mapping.insert(state_entity, widget_entity);
+    }
+
     /// Attempts to get a state entity
     pub fn get(&self, widget_entity: Entity) -> Option<Entity> {
         self.mapping.get(&widget_entity).map(|entry| *entry.value())
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use kayak_ui_macros::rsx;
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, KStyle, RenderCommand, Units},
+    widget::Widget,
+    widgets::{TextProps, TextWidgetBundle},
+};
+
+use super::{
+    AccordionContextBundle, AccordionContextProvider, AccordionDetailsBundle,
+    AccordionSummaryBundle,
+};
+
+/// Props used by the [`CollapsibleBundle`] widget
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct Collapsible {
+    /// The text shown in the header
+    pub title: String,
+    /// Whether the content starts out expanded
+    pub open: bool,
+}
+
+impl Widget for Collapsible {}
+
+/// A single accordion panel with a text header
+///
+/// Clicking the header expands or collapses the children, animating their height. Use an
+/// [`AccordionContextBundle`] directly to group several panels, optionally allowing only one
+/// of them to be open at a time.
+#[derive(Bundle, Debug, Clone, PartialEq)]
+pub struct CollapsibleBundle {
+    pub collapsible: Collapsible,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub children: KChildren,
+    pub widget_name: WidgetName,
+}
+
+impl Default for CollapsibleBundle {
+    fn default() -> Self {
+        Self {
+            collapsible: Default::default(),
+            styles: Default::default(),
+            computed_styles: Default::default(),
+            children: Default::default(),
+            widget_name: Collapsible::default().get_name(),
+        }
+    }
+}
+
+pub fn render(
+    In(widget_entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&Collapsible, &KChildren, &KStyle, &mut ComputedStyles)>,
+) -> bool {
+    if let Ok((collapsible, collapsible_children, styles, mut computed_styles)) =
+        query.get_mut(widget_entity)
+    {
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Auto.into(),
+                ..Default::default()
+            })
+            .with_style(styles)
+            .into();
+
+        let parent_id = Some(widget_entity);
+        rsx! {
+            <AccordionContextBundle
+                accordion={AccordionContextProvider {
+                    default_open: collapsible.open.then_some(0),
+                    ..Default::default()
+                }}
+            >
+                <AccordionSummaryBundle>
+                    <TextWidgetBundle
+                        text={TextProps {
+                            content: collapsible.title.clone(),
+                            size: 18.0,
+                            ..Default::default()
+                        }}
+                    />
+                </AccordionSummaryBundle>
+                <AccordionDetailsBundle children={collapsible_children.clone()} />
+            </AccordionContextBundle>
+        };
+    }
+
+    true
+}
@@ -102,6 +102,11 @@ pub fn render(
                             transition_state.transition.start();
                         }
                     }
+
+                    // Once fully collapsed the content is removed from the layout.
+                    if transition_props.reversing && !transition_state.transition.is_playing() {
+                        return true;
+                    }
                 }
 
                 let parent_id = Some(accordion_widget);
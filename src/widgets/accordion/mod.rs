@@ -3,11 +3,13 @@ use crate::{
     KayakUIPlugin,
 };
 
+mod collapsible;
 mod context;
 mod details;
 mod summary;
 
 pub use self::details::{AccordionDetails, AccordionDetailsBundle};
+pub use collapsible::{Collapsible, CollapsibleBundle};
 pub use context::{AccordionContext, AccordionContextBundle, AccordionContextProvider};
 pub use summary::{AccordionSummary, AccordionSummaryBundle};
 
@@ -23,6 +25,13 @@ impl KayakUIPlugin for AccordionPlugin {
             context::render,
         );
 
+        context.add_widget_data::<Collapsible, EmptyState>();
+        context.add_widget_system(
+            Collapsible::default().get_name(),
+            widget_update::<Collapsible, EmptyState>,
+            collapsible::render,
+        );
+
         context.add_widget_data::<AccordionSummary, EmptyState>();
         context.add_widget_system(
             AccordionSummary::default().get_name(),
@@ -7,7 +7,7 @@ use crate::{
     prelude::KayakWidgetContext,
     styles::{ComputedStyles, KStyle, RenderCommand, StyleProp, Units},
     widget::{EmptyState, Widget, WidgetParam},
-    CameraUIKayak,
+    CameraUIKayak, KayakUiScale,
 };
 
 use super::ClipBundle;
@@ -47,30 +47,22 @@ pub fn app_update(
     widget_param: WidgetParam<KayakApp, EmptyState>,
     camera: Query<&Camera, With<CameraUIKayak>>,
     windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<KayakUiScale>,
 ) -> bool {
     let mut window_change = false;
 
     if let Ok(app_style) = widget_param.computed_style_query.get(entity) {
         if let Some(camera_entity) = widget_context.camera_entity {
             if let Ok(camera) = camera.get(camera_entity) {
-                if let Some(size) = camera.logical_viewport_size() {
-                    if app_style.0.width != StyleProp::Value(Units::Pixels(size.x)) {
-                        window_change = true;
-                    }
-                    if app_style.0.height != StyleProp::Value(Units::Pixels(size.y)) {
-                        window_change = true;
-                    }
-                } else {
+                let size = camera.logical_viewport_size().unwrap_or_else(|| {
                     let primary_window = windows.single();
-                    if app_style.0.width != StyleProp::Value(Units::Pixels(primary_window.width()))
-                    {
-                        window_change = true;
-                    }
-                    if app_style.0.height
-                        != StyleProp::Value(Units::Pixels(primary_window.height()))
-                    {
-                        window_change = true;
-                    }
+                    Vec2::new(primary_window.width(), primary_window.height())
+                }) / ui_scale.get();
+                if app_style.0.width != StyleProp::Value(Units::Pixels(size.x)) {
+                    window_change = true;
+                }
+                if app_style.0.height != StyleProp::Value(Units::Pixels(size.y)) {
+                    window_change = true;
                 }
             }
         }
@@ -86,6 +78,7 @@ pub fn app_render(
     mut commands: Commands,
     mut query: Query<(&KStyle, &mut ComputedStyles, &KChildren)>,
     camera: Query<&Camera, With<CameraUIKayak>>,
+    ui_scale: Res<KayakUiScale>,
 ) -> bool {
     let (mut width, mut height) = (0.0, 0.0);
 
@@ -105,8 +98,8 @@ pub fn app_render(
         *computed_styles = KStyle::default()
             .with_style(KStyle {
                 render_command: RenderCommand::Layout.into(),
-                width: Units::Pixels(width).into(),
-                height: Units::Pixels(height).into(),
+                width: Units::Pixels(width / ui_scale.get()).into(),
+                height: Units::Pixels(height / ui_scale.get()).into(),
                 ..Default::default()
             })
             .with_style(app_style)
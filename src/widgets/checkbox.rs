@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use kayak_ui_macros::{constructor, rsx};
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    event::{EventType, KEvent},
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, Corner, Edge, KCursorIcon, KStyle, RenderCommand, Units},
+    widget::Widget,
+    widgets::{BackgroundBundle, KSvg, KSvgBundle, Svg, CHECK_HANDLE},
+    Focusable,
+};
+
+/// Props used by the [`CheckboxBundle`] widget
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct CheckboxProps {
+    /// If true, prevents the checkbox from being toggled
+    pub disabled: bool,
+    /// The initial checked state
+    ///
+    /// Changing this prop updates the checkbox to match.
+    pub checked: bool,
+}
+
+impl Widget for CheckboxProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct CheckboxState {
+    /// Whether the checkbox is currently checked
+    pub checked: bool,
+    /// The value of [`CheckboxProps::checked`] when it was last applied
+    pub prop_checked: bool,
+    pub focused: bool,
+}
+
+/// An event sent whenever the user toggles a checkbox
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckboxChanged {
+    /// The checkbox widget entity
+    pub entity: Entity,
+    /// Whether the checkbox is now checked
+    pub checked: bool,
+}
+
+/// A box that can be checked and unchecked
+///
+/// Clicking the checkbox, or pressing space while it's focused, toggles it and sends a
+/// [`CheckboxChanged`] event.
+#[derive(Bundle)]
+pub struct CheckboxBundle {
+    pub checkbox: CheckboxProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub on_event: OnEvent,
+    pub focusable: Focusable,
+    pub widget_name: WidgetName,
+}
+
+impl Default for CheckboxBundle {
+    fn default() -> Self {
+        Self {
+            checkbox: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            on_event: Default::default(),
+            focusable: Default::default(),
+            widget_name: CheckboxProps::default().get_name(),
+        }
+    }
+}
+
+pub fn checkbox_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&CheckboxProps, &KStyle, &mut ComputedStyles, &mut OnEvent)>,
+    mut state_query: ParamSet<(Query<&CheckboxState>, Query<&mut CheckboxState>)>,
+) -> bool {
+    if let Ok((checkbox, styles, mut computed_styles, mut on_event)) = query.get_mut(entity) {
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            CheckboxState {
+                checked: checkbox.checked,
+                prop_checked: checkbox.checked,
+                ..Default::default()
+            },
+        );
+
+        if let Ok(mut state) = state_query.p1().get_mut(state_entity) {
+            if state.prop_checked != checkbox.checked {
+                state.prop_checked = checkbox.checked;
+                state.checked = checkbox.checked;
+            }
+        }
+
+        if let Ok(state) = state_query.p0().get(state_entity) {
+            *computed_styles = KStyle::default()
+                .with_style(KStyle {
+                    render_command: RenderCommand::Layout.into(),
+                    ..Default::default()
+                })
+                .with_style(styles)
+                .with_style(KStyle {
+                    cursor: KCursorIcon(CursorIcon::Hand).into(),
+                    width: Units::Pixels(20.0).into(),
+                    height: Units::Pixels(20.0).into(),
+                    ..Default::default()
+                })
+                .into();
+
+            let box_styles = toggle_box_styles(state.focused, checkbox.disabled, 4.0);
+
+            let disabled = checkbox.disabled;
+            *on_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      event: Res<KEvent>,
+                      mut state_query: Query<&mut CheckboxState>,
+                      mut changed: EventWriter<CheckboxChanged>| {
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        let toggle = match event.event_type {
+                            EventType::Click(..) => true,
+                            EventType::KeyDown(key_event) => {
+                                state.focused && key_event.key() == KeyCode::Space
+                            }
+                            EventType::Focus => {
+                                state.focused = true;
+                                false
+                            }
+                            EventType::Blur => {
+                                state.focused = false;
+                                false
+                            }
+                            _ => false,
+                        };
+
+                        if toggle && !disabled {
+                            state.checked = !state.checked;
+                            changed.send(CheckboxChanged {
+                                entity,
+                                checked: state.checked,
+                            });
+                        }
+                    }
+                },
+            );
+
+            let parent_id = Some(entity);
+            rsx! {
+                <BackgroundBundle styles={box_styles}>
+                    {
+                        if state.checked {
+                            constructor! {
+                                <KSvgBundle
+                                    styles={toggle_icon_styles()}
+                                    svg={KSvg(CHECK_HANDLE.typed::<Svg>())}
+                                />
+                            }
+                        }
+                    }
+                </BackgroundBundle>
+            };
+        }
+    }
+
+    true
+}
+
+/// The styles of the box drawn by checkboxes and radio buttons
+pub(super) fn toggle_box_styles(focused: bool, disabled: bool, border_radius: f32) -> KStyle {
+    KStyle {
+        background_color: Color::rgba(0.160, 0.172, 0.235, 1.0).into(),
+        border: Edge::all(2.0).into(),
+        border_color: if focused {
+            Color::rgba(0.933, 0.745, 0.745, 1.0)
+        } else if disabled {
+            Color::rgba(0.254, 0.270, 0.349, 1.0)
+        } else {
+            Color::rgba(0.592, 0.627, 0.749, 1.0)
+        }
+        .into(),
+        border_radius: Corner::all(border_radius).into(),
+        disabled: disabled.into(),
+        width: Units::Stretch(1.0).into(),
+        height: Units::Stretch(1.0).into(),
+        padding: Edge::all(Units::Pixels(2.0)).into(),
+        ..Default::default()
+    }
+}
+
+/// The styles of the check mark or dot drawn inside checkboxes and radio buttons
+pub(super) fn toggle_icon_styles() -> KStyle {
+    KStyle {
+        background_color: Color::WHITE.into(),
+        width: Units::Stretch(1.0).into(),
+        height: Units::Stretch(1.0).into(),
+        ..Default::default()
+    }
+}
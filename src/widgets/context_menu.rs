@@ -0,0 +1,324 @@
+use bevy::prelude::*;
+use kayak_font::{KayakFont, TextProperties};
+use kayak_ui_macros::{constructor, rsx};
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    event::{EventType, KEvent},
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    render::font::FontMapping,
+    styles::{
+        ComputedStyles, Corner, Edge, KPositionType, KStyle, RenderCommand, StyleProp, Units,
+    },
+    widget::Widget,
+    widgets::{BackgroundBundle, ElementBundle, TextProps, TextWidgetBundle},
+    KayakUiScale, WindowSize, DEFAULT_FONT,
+};
+
+/// The z-index of an open context menu, keeping it above the rest of the UI but below tooltips
+const MENU_Z_INDEX: i32 = 150_000;
+const FONT_SIZE: f32 = 14.0;
+const ITEM_HEIGHT: f32 = 24.0;
+const ITEM_PADDING: f32 = 12.0;
+const MIN_WIDTH: f32 = 120.0;
+
+/// Props used by the [`ContextMenuBundle`] widget
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenuProps {
+    /// If true, right-clicking doesn't open the menu
+    pub disabled: bool,
+    /// The items listed in the menu
+    pub items: Vec<String>,
+}
+
+impl Widget for ContextMenuProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct ContextMenuState {
+    pub open: bool,
+    /// The cursor position the menu was opened at
+    pub anchor: Vec2,
+    /// The index of the item under the cursor
+    pub highlighted: Option<usize>,
+}
+
+/// An event sent when the user picks an item from a context menu
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenuSelected {
+    /// The context menu widget entity
+    pub entity: Entity,
+    /// The index of the picked item
+    pub index: usize,
+    /// The picked item
+    pub value: String,
+}
+
+/// Shows a menu at the cursor when its children are right-clicked
+///
+/// The menu is drawn above the rest of the UI and kept within the camera's viewport. Picking an
+/// item closes it and sends a [`ContextMenuSelected`] event, while pressing anywhere outside of
+/// the menu closes it without picking anything.
+#[derive(Bundle)]
+pub struct ContextMenuBundle {
+    pub context_menu: ContextMenuProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub children: KChildren,
+    pub on_event: OnEvent,
+    pub widget_name: WidgetName,
+}
+
+impl Default for ContextMenuBundle {
+    fn default() -> Self {
+        Self {
+            context_menu: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            children: Default::default(),
+            on_event: Default::default(),
+            widget_name: ContextMenuProps::default().get_name(),
+        }
+    }
+}
+
+pub fn context_menu_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(
+        &ContextMenuProps,
+        &KStyle,
+        &KChildren,
+        &mut ComputedStyles,
+        &mut OnEvent,
+    )>,
+    state_query: Query<&ContextMenuState>,
+    font_assets: Res<Assets<KayakFont>>,
+    font_mapping: Res<FontMapping>,
+    window_size: Res<WindowSize>,
+    ui_scale: Res<KayakUiScale>,
+    camera: Query<&Camera>,
+) -> bool {
+    if let Ok((context_menu, styles, menu_children, mut computed_styles, mut on_event)) =
+        query.get_mut(entity)
+    {
+        let state_entity =
+            widget_context.use_state(&mut commands, entity, ContextMenuState::default());
+
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            })
+            .with_style(styles)
+            .into();
+
+        let disabled = context_menu.disabled;
+        *on_event = OnEvent::new(
+            move |In(_entity): In<Entity>,
+                  mut event: ResMut<KEvent>,
+                  mut state_query: Query<&mut ContextMenuState>| {
+                if let EventType::ContextMenu(cursor_event) = event.event_type {
+                    if disabled {
+                        return;
+                    }
+                    // Only the innermost context menu opens
+                    event.stop_propagation();
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        state.open = true;
+                        state.anchor = Vec2::from(cursor_event.position);
+                        state.highlighted = None;
+                    }
+                }
+            },
+        );
+
+        let state = state_query.get(state_entity).cloned().unwrap_or_default();
+        let is_open = state.open && !context_menu.items.is_empty();
+
+        let viewport_size = widget_context
+            .camera_entity
+            .and_then(|camera_entity| camera.get(camera_entity).ok())
+            .and_then(|camera| camera.logical_viewport_size())
+            .unwrap_or(Vec2::new(window_size.0, window_size.1))
+            / ui_scale.get();
+
+        let (overlay_styles, menu_styles) = if is_open {
+            let text_width = font_mapping
+                .get_handle(DEFAULT_FONT.into())
+                .and_then(|handle| font_assets.get(&handle))
+                .map(|font| {
+                    context_menu
+                        .items
+                        .iter()
+                        .map(|item| {
+                            font.measure(
+                                item,
+                                TextProperties {
+                                    font_size: FONT_SIZE,
+                                    line_height: ITEM_HEIGHT,
+                                    max_size: (10000.0, ITEM_HEIGHT),
+                                    ..Default::default()
+                                },
+                            )
+                            .size()
+                            .0
+                        })
+                        .fold(0.0, f32::max)
+                })
+                .unwrap_or_default();
+            let size = Vec2::new(
+                (text_width + ITEM_PADDING * 2.0).max(MIN_WIDTH),
+                ITEM_HEIGHT * context_menu.items.len() as f32,
+            );
+            // Keep the whole menu within the viewport, shifting it up and left when needed
+            let position = state.anchor.min(viewport_size - size).max(Vec2::ZERO);
+
+            // The overlay covers the viewport, so it's positioned relative to this widget
+            let layout = widget_context.get_layout(entity).unwrap_or_default();
+            (
+                KStyle {
+                    position_type: KPositionType::SelfDirected.into(),
+                    left: Units::Pixels(-layout.posx).into(),
+                    top: Units::Pixels(-layout.posy).into(),
+                    width: Units::Pixels(viewport_size.x).into(),
+                    height: Units::Pixels(viewport_size.y).into(),
+                    z_index: MENU_Z_INDEX.into(),
+                    render_layer: 1.into(),
+                    ..Default::default()
+                },
+                KStyle {
+                    render_command: StyleProp::Value(RenderCommand::Quad),
+                    background_color: Color::rgba(0.133, 0.145, 0.2, 1.0).into(),
+                    border: Edge::all(1.0).into(),
+                    border_color: Color::rgba(0.360, 0.380, 0.474, 1.0).into(),
+                    border_radius: Corner::all(3.0).into(),
+                    position_type: KPositionType::SelfDirected.into(),
+                    left: Units::Pixels(position.x).into(),
+                    top: Units::Pixels(position.y).into(),
+                    width: Units::Pixels(size.x).into(),
+                    height: Units::Pixels(size.y).into(),
+                    ..Default::default()
+                },
+            )
+        } else {
+            (KStyle::default(), KStyle::default())
+        };
+
+        // Pressing anywhere outside of the menu closes it
+        let overlay_event = OnEvent::new(
+            move |In(_entity): In<Entity>,
+                  mut event: ResMut<KEvent>,
+                  mut state_query: Query<&mut ContextMenuState>| {
+                if let EventType::MouseDown(..) | EventType::ContextMenu(..) = event.event_type {
+                    event.stop_propagation();
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        state.open = false;
+                    }
+                }
+            },
+        );
+
+        let parent_id = Some(entity);
+        rsx! {
+            <ElementBundle
+                styles={KStyle {
+                    width: Units::Stretch(1.0).into(),
+                    height: Units::Stretch(1.0).into(),
+                    ..Default::default()
+                }}
+            >
+                <ElementBundle
+                    styles={KStyle {
+                        width: Units::Stretch(1.0).into(),
+                        height: Units::Stretch(1.0).into(),
+                        ..Default::default()
+                    }}
+                    children={menu_children.clone()}
+                />
+                {
+                    if is_open {
+                        constructor! {
+                            <ElementBundle styles={overlay_styles} on_event={overlay_event}>
+                                <BackgroundBundle styles={menu_styles}>
+                                    {context_menu.items.iter().enumerate().for_each(|(index, item)| {
+                                        let key = format!("context-menu-item-{}", index);
+                                        let value = item.clone();
+                                        let on_event = OnEvent::new(
+                                            move |In(_entity): In<Entity>,
+                                                  mut event: ResMut<KEvent>,
+                                                  mut state_query: Query<&mut ContextMenuState>,
+                                                  mut selected: EventWriter<ContextMenuSelected>| {
+                                                if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                                    match event.event_type {
+                                                        // Keep the overlay from closing the menu before the click
+                                                        EventType::MouseDown(..) => {
+                                                            event.stop_propagation();
+                                                        }
+                                                        EventType::Click(..) => {
+                                                            event.stop_propagation();
+                                                            state.open = false;
+                                                            selected.send(ContextMenuSelected {
+                                                                entity,
+                                                                index,
+                                                                value: value.clone(),
+                                                            });
+                                                        }
+                                                        EventType::Hover(..) if state.highlighted != Some(index) => {
+                                                            state.highlighted = Some(index);
+                                                        }
+                                                        EventType::MouseOut(..) if state.highlighted == Some(index) => {
+                                                            state.highlighted = None;
+                                                        }
+                                                        _ => {}
+                                                    }
+                                                }
+                                            },
+                                        );
+                                        constructor! {
+                                            <BackgroundBundle
+                                                key={key.as_str()}
+                                                styles={KStyle {
+                                                    background_color: if state.highlighted == Some(index) {
+                                                        Color::rgba(0.254, 0.270, 0.349, 1.0)
+                                                    } else {
+                                                        Color::rgba(0.0, 0.0, 0.0, 0.0)
+                                                    }
+                                                    .into(),
+                                                    height: Units::Pixels(ITEM_HEIGHT).into(),
+                                                    padding_left: Units::Pixels(ITEM_PADDING).into(),
+                                                    ..Default::default()
+                                                }}
+                                                on_event={on_event}
+                                            >
+                                                <TextWidgetBundle
+                                                    styles={KStyle {
+                                                        top: Units::Stretch(1.0).into(),
+                                                        bottom: Units::Stretch(1.0).into(),
+                                                        ..Default::default()
+                                                    }}
+                                                    text={TextProps {
+                                                        content: item.clone(),
+                                                        size: FONT_SIZE,
+                                                        word_wrap: false,
+                                                        ..Default::default()
+                                                    }}
+                                                />
+                                            </BackgroundBundle>
+                                        }
+                                    })}
+                                </BackgroundBundle>
+                            </ElementBundle>
+                        }
+                    }
+                }
+            </ElementBundle>
+        };
+    }
+
+    true
+}
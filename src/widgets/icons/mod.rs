@@ -12,22 +12,65 @@ pub const EXPAND_LESS_HANDLE: HandleUntyped =
 pub const EXPAND_MORE_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Svg::TYPE_UUID, 9116091369991258337);
 
+pub const CHECK_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Svg::TYPE_UUID, 2931806419752306417);
+
+pub const RADIO_DOT_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Svg::TYPE_UUID, 6603247615381994120);
+
+pub const SPINNER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Svg::TYPE_UUID, 7485120368943062913);
+
+pub const CLOSE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Svg::TYPE_UUID, 1730467209855143620);
+
+pub const MINIMIZE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Svg::TYPE_UUID, 8271945063318870451);
+
+pub const MAXIMIZE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Svg::TYPE_UUID, 5089312647720195836);
+
 pub struct IconsPlugin;
 impl Plugin for IconsPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         let expand_less_bytes = include_bytes!("expand_less.svg");
         let expand_more_bytes = include_bytes!("expand_more.svg");
+        let check_bytes = include_bytes!("check.svg");
+        let radio_dot_bytes = include_bytes!("radio_dot.svg");
+        let spinner_bytes = include_bytes!("spinner.svg");
+        let close_bytes = include_bytes!("close.svg");
+        let minimize_bytes = include_bytes!("minimize.svg");
+        let maximize_bytes = include_bytes!("maximize.svg");
         let mut expand_less =
             Svg::from_bytes(expand_less_bytes, Path::new(""), None::<PathBuf>).unwrap();
         let mut expand_more =
             Svg::from_bytes(expand_more_bytes, Path::new(""), None::<PathBuf>).unwrap();
+        let mut check = Svg::from_bytes(check_bytes, Path::new(""), None::<PathBuf>).unwrap();
+        let mut radio_dot =
+            Svg::from_bytes(radio_dot_bytes, Path::new(""), None::<PathBuf>).unwrap();
+        let mut spinner = Svg::from_bytes(spinner_bytes, Path::new(""), None::<PathBuf>).unwrap();
+        let mut close = Svg::from_bytes(close_bytes, Path::new(""), None::<PathBuf>).unwrap();
+        let mut minimize = Svg::from_bytes(minimize_bytes, Path::new(""), None::<PathBuf>).unwrap();
+        let mut maximize = Svg::from_bytes(maximize_bytes, Path::new(""), None::<PathBuf>).unwrap();
 
         let mut meshes = app.world.get_resource_mut::<Assets<Mesh>>().unwrap();
         expand_less.mesh = meshes.add(expand_less.tessellate());
         expand_more.mesh = meshes.add(expand_more.tessellate());
+        check.mesh = meshes.add(check.tessellate());
+        radio_dot.mesh = meshes.add(radio_dot.tessellate());
+        spinner.mesh = meshes.add(spinner.tessellate());
+        close.mesh = meshes.add(close.tessellate());
+        minimize.mesh = meshes.add(minimize.tessellate());
+        maximize.mesh = meshes.add(maximize.tessellate());
 
         let mut svgs = app.world.get_resource_mut::<Assets<Svg>>().unwrap();
         svgs.set_untracked(EXPAND_LESS_HANDLE, expand_less);
         svgs.set_untracked(EXPAND_MORE_HANDLE, expand_more);
+        svgs.set_untracked(CHECK_HANDLE, check);
+        svgs.set_untracked(RADIO_DOT_HANDLE, radio_dot);
+        svgs.set_untracked(SPINNER_HANDLE, spinner);
+        svgs.set_untracked(CLOSE_HANDLE, close);
+        svgs.set_untracked(MINIMIZE_HANDLE, minimize);
+        svgs.set_untracked(MAXIMIZE_HANDLE, maximize);
     }
 }
@@ -5,14 +5,25 @@
 //!
 //! - KayakApp
 //! - Background
+//! - Checkbox
 //! - Clip
+//! - ContextMenu
 //! - Element
 //! - Image
 //! - NinePatch
+//! - NumericInput
+//! - ProgressBar
+//! - RadioButton
+//! - Slider
+//! - Spinner
+//! - Table
 //! - TextBox
 //! - Text
 //! - Texture Atlas
+//! - Tooltip
 //! - Scroll
+//! - VirtualList
+//! - Select
 //!
 //! Widgets like:
 //! - Window
@@ -26,17 +37,27 @@ mod accordion;
 mod app;
 mod background;
 mod button;
+mod checkbox;
 mod clip;
+mod context_menu;
 mod element;
 mod icons;
 mod image;
 mod modal;
 mod nine_patch;
+mod numeric_input;
+mod progress_bar;
+mod radio_button;
 mod scroll;
+mod select;
+mod slider;
+mod spinner;
 mod svg;
+mod table;
 mod text;
 mod text_box;
 mod texture_atlas;
+mod tooltip;
 mod transition;
 mod window;
 mod window_context_provider;
@@ -45,12 +66,24 @@ pub use accordion::*;
 pub use app::{KayakApp, KayakAppBundle};
 pub use background::{Background, BackgroundBundle};
 pub use button::{ButtonState, KButton, KButtonBundle};
+pub use checkbox::{CheckboxBundle, CheckboxChanged, CheckboxProps, CheckboxState};
 pub use clip::{Clip, ClipBundle};
+pub use context_menu::{
+    ContextMenuBundle, ContextMenuProps, ContextMenuSelected, ContextMenuState,
+};
 pub use element::{Element, ElementBundle};
 pub use icons::*;
 pub use image::{KImage, KImageBundle};
-pub use modal::{Modal, ModalBundle};
+pub use modal::{Modal, ModalBundle, ModalDismissed};
 pub use nine_patch::{NinePatch, NinePatchBundle};
+pub use numeric_input::{
+    NumericInputBundle, NumericInputChanged, NumericInputProps, NumericInputState,
+};
+pub use progress_bar::{ProgressBarBundle, ProgressBarProps, ProgressBarState};
+pub use radio_button::{
+    RadioButtonBundle, RadioButtonChanged, RadioButtonProps, RadioButtonState, RadioContext,
+    RadioContextProvider, RadioContextProviderBundle,
+};
 pub use scroll::{
     scroll_bar::{ScrollBarBundle, ScrollBarProps},
     scroll_box::{ScrollBoxBundle, ScrollBoxProps},
@@ -58,16 +91,24 @@ pub use scroll::{
     scroll_context::{
         ScrollContext, ScrollContextProvider, ScrollContextProviderBundle, ScrollMode,
     },
+    virtual_list::{ItemBuilder, VirtualListBundle, VirtualListProps},
 };
+pub use select::{SelectBundle, SelectChanged, SelectProps, SelectState};
+pub use slider::{SliderBundle, SliderChanged, SliderProps, SliderState};
+pub use spinner::{SpinnerBundle, SpinnerProps, SpinnerState};
 pub use svg::{KSvg, KSvgBundle, Svg};
+pub use table::{SortDirection, TableBundle, TableColumn, TableProps, TableSorted, TableState};
 pub use text::{TextProps, TextWidgetBundle};
 pub use text_box::{TextBoxBundle, TextBoxProps, TextBoxState};
 pub use texture_atlas::{TextureAtlasBundle, TextureAtlasProps};
+pub use tooltip::{
+    TooltipBundle, TooltipContext, TooltipProps, TooltipProvider, TooltipProviderBundle,
+};
 pub use transition::{
     create_transition, Transition, TransitionBundle, TransitionEasing, TransitionProps,
     TransitionState,
 };
-pub use window::{KWindow, KWindowState, WindowBundle};
+pub use window::{KWindow, KWindowState, WindowBundle, WindowClosed};
 pub use window_context_provider::{
     WindowContext, WindowContextProvider, WindowContextProviderBundle,
 };
@@ -75,18 +116,29 @@ pub use window_context_provider::{
 use app::{app_render, app_update};
 use background::background_render;
 use button::button_render;
+use checkbox::checkbox_render;
 use clip::clip_render;
+use context_menu::context_menu_render;
 use element::element_render;
 use image::image_render;
 use nine_patch::nine_patch_render;
+use numeric_input::numeric_input_render;
+use progress_bar::progress_bar_render;
+use radio_button::{radio_button_render, radio_context_render};
 use scroll::{
     scroll_bar::scroll_bar_render, scroll_box::scroll_box_render,
     scroll_content::scroll_content_render, scroll_context::scroll_context_render,
+    virtual_list::virtual_list_render,
 };
+use select::select_render;
+use slider::slider_render;
+use spinner::spinner_render;
 use svg::svg_render;
+use table::table_render;
 use text::text_render;
 use text_box::text_box_render;
 use texture_atlas::texture_atlas_render;
+use tooltip::{tooltip_provider_render, tooltip_render};
 use window::window_render;
 
 use crate::{
@@ -102,11 +154,30 @@ pub struct KayakWidgets;
 impl Plugin for KayakWidgets {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_plugins(icons::IconsPlugin);
+        app.add_event::<CheckboxChanged>()
+            .add_event::<NumericInputChanged>()
+            .add_event::<RadioButtonChanged>()
+            .add_event::<SelectChanged>()
+            .add_event::<ContextMenuSelected>()
+            .add_event::<SliderChanged>()
+            .add_event::<TableSorted>()
+            .add_event::<WindowClosed>()
+            .add_event::<ModalDismissed>();
         app.add_systems(
             PostUpdate,
             transition::update_transitions.after(update_widgets_sys),
         )
-        .add_systems(Update, text_box::cursor_animation_system);
+        .add_systems(
+            Update,
+            (
+                text_box::cursor_animation_system,
+                text_box::text_box_ime_system,
+                progress_bar::progress_bar_animation_system,
+                spinner::spinner_animation_system,
+                scroll::scroll_context::elastic_scroll_system,
+                tooltip::update_tooltips,
+            ),
+        );
     }
 }
 
@@ -121,17 +192,30 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
         context.add_widget_data::<KWindow, KWindowState>();
         context.add_widget_data::<WindowContextProvider, EmptyState>();
         context.add_widget_data::<Background, EmptyState>();
+        context.add_widget_data::<CheckboxProps, CheckboxState>();
         context.add_widget_data::<Clip, EmptyState>();
         context.add_widget_data::<KImage, EmptyState>();
         context.add_widget_data::<TextureAtlasProps, EmptyState>();
         context.add_widget_data::<NinePatch, EmptyState>();
         context.add_widget_data::<KSvg, EmptyState>();
         context.add_widget_data::<Element, EmptyState>();
+        context.add_widget_data::<NumericInputProps, NumericInputState>();
+        context.add_widget_data::<ProgressBarProps, ProgressBarState>();
+        context.add_widget_data::<SpinnerProps, SpinnerState>();
+        context.add_widget_data::<RadioContextProvider, EmptyState>();
+        context.add_widget_data::<RadioButtonProps, RadioButtonState>();
         context.add_widget_data::<ScrollBarProps, EmptyState>();
         context.add_widget_data::<ScrollContentProps, EmptyState>();
         context.add_widget_data::<ScrollBoxProps, EmptyState>();
         context.add_widget_data::<ScrollContextProvider, EmptyState>();
+        context.add_widget_data::<VirtualListProps, EmptyState>();
+        context.add_widget_data::<SelectProps, SelectState>();
+        context.add_widget_data::<ContextMenuProps, ContextMenuState>();
+        context.add_widget_data::<SliderProps, SliderState>();
+        context.add_widget_data::<TableProps, TableState>();
         context.add_widget_data::<TextBoxProps, TextBoxState>();
+        context.add_widget_data::<TooltipProvider, EmptyState>();
+        context.add_widget_data::<TooltipProps, EmptyState>();
         context.add_widget_data::<TransitionProps, TransitionState>();
         context.add_widget_data::<Modal, TransitionState>();
 
@@ -161,6 +245,11 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
             widget_update::<Background, EmptyState>,
             background_render,
         );
+        context.add_widget_system(
+            CheckboxProps::default().get_name(),
+            widget_update::<CheckboxProps, CheckboxState>,
+            checkbox_render,
+        );
         context.add_widget_system(
             Clip::default().get_name(),
             widget_update::<Clip, EmptyState>,
@@ -176,6 +265,16 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
             widget_update::<TextureAtlasProps, EmptyState>,
             texture_atlas_render,
         );
+        context.add_widget_system(
+            TooltipProvider::default().get_name(),
+            widget_update_with_context::<TooltipProvider, EmptyState, TooltipContext>,
+            tooltip_provider_render,
+        );
+        context.add_widget_system(
+            TooltipProps::default().get_name(),
+            widget_update::<TooltipProps, EmptyState>,
+            tooltip_render,
+        );
         context.add_widget_system(
             NinePatch::default().get_name(),
             widget_update::<NinePatch, EmptyState>,
@@ -191,6 +290,31 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
             widget_update::<Element, EmptyState>,
             element_render,
         );
+        context.add_widget_system(
+            NumericInputProps::default().get_name(),
+            widget_update::<NumericInputProps, NumericInputState>,
+            numeric_input_render,
+        );
+        context.add_widget_system(
+            ProgressBarProps::default().get_name(),
+            widget_update::<ProgressBarProps, ProgressBarState>,
+            progress_bar_render,
+        );
+        context.add_widget_system(
+            SpinnerProps::default().get_name(),
+            widget_update::<SpinnerProps, SpinnerState>,
+            spinner_render,
+        );
+        context.add_widget_system(
+            RadioContextProvider::default().get_name(),
+            widget_update::<RadioContextProvider, EmptyState>,
+            radio_context_render,
+        );
+        context.add_widget_system(
+            RadioButtonProps::default().get_name(),
+            widget_update_with_context::<RadioButtonProps, RadioButtonState, RadioContext>,
+            radio_button_render,
+        );
         context.add_widget_system(
             ScrollBarProps::default().get_name(),
             widget_update_with_context::<ScrollBarProps, EmptyState, ScrollContext>,
@@ -211,6 +335,31 @@ impl KayakUIPlugin for KayakWidgetsContextPlugin {
             widget_update::<ScrollContextProvider, EmptyState>,
             scroll_context_render,
         );
+        context.add_widget_system(
+            VirtualListProps::default().get_name(),
+            widget_update_with_context::<VirtualListProps, EmptyState, ScrollContext>,
+            virtual_list_render,
+        );
+        context.add_widget_system(
+            SelectProps::default().get_name(),
+            widget_update::<SelectProps, SelectState>,
+            select_render,
+        );
+        context.add_widget_system(
+            ContextMenuProps::default().get_name(),
+            widget_update::<ContextMenuProps, ContextMenuState>,
+            context_menu_render,
+        );
+        context.add_widget_system(
+            SliderProps::default().get_name(),
+            widget_update::<SliderProps, SliderState>,
+            slider_render,
+        );
+        context.add_widget_system(
+            TableProps::default().get_name(),
+            widget_update::<TableProps, TableState>,
+            table_render,
+        );
         context.add_widget_system(
             TextBoxProps::default().get_name(),
             widget_update::<TextBoxProps, TextBoxState>,
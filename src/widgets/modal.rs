@@ -1,5 +1,8 @@
 use bevy::{
-    prelude::{Bundle, Color, Commands, Component, Entity, In, Query, Res},
+    prelude::{
+        Bundle, Color, Commands, Component, Entity, Event, EventWriter, In, KeyCode, Query, Res,
+        ResMut,
+    },
     window::CursorIcon,
 };
 use kayak_ui_macros::rsx;
@@ -7,6 +10,9 @@ use kayak_ui_macros::rsx;
 use crate::{
     children::KChildren,
     context::WidgetName,
+    event::{EventType, KEvent},
+    focus_tree::FocusTrap,
+    on_event::OnEvent,
     prelude::KayakWidgetContext,
     styles::{
         ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, RenderCommand, StyleProp,
@@ -23,6 +29,9 @@ use super::{
     ElementBundle, TransitionState,
 };
 
+/// The render layer of a modal, drawing it above the rest of the UI
+const MODAL_RENDER_LAYER: i32 = 1;
+
 #[derive(Component, PartialEq, Clone, Debug)]
 pub struct Modal {
     /// The text to display in the modal's title bar
@@ -35,6 +44,21 @@ pub struct Modal {
     pub timeout: f32,
     /// The overlay background alpha value
     pub overlay_alpha: f32,
+    /// If true, clicking the overlay sends a [`ModalDismissed`] event
+    ///
+    /// Pressing Escape always sends one while the modal is visible, unless the focused widget
+    /// handles the key itself. Focus moves into the modal when it opens, and only the modal
+    /// holding focus (the topmost one) is dismissed.
+    pub dismiss_on_overlay: bool,
+}
+
+/// An event sent when the user asks to close a visible modal
+///
+/// The modal isn't hidden automatically, set [`Modal::visible`] to false in response.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModalDismissed {
+    /// The modal widget entity
+    pub entity: Entity,
 }
 
 impl Default for Modal {
@@ -45,6 +69,7 @@ impl Default for Modal {
             visible: Default::default(),
             timeout: 250.0,
             overlay_alpha: 0.95,
+            dismiss_on_overlay: false,
         }
     }
 }
@@ -88,6 +113,7 @@ pub fn render(
             position_type: KPositionType::SelfDirected.into(),
             width: Units::Stretch(1.0).into(),
             height: Units::Stretch(1.0).into(),
+            render_layer: MODAL_RENDER_LAYER.into(),
             ..Default::default()
         };
 
@@ -132,6 +158,38 @@ pub fn render(
             }
 
             let title = modal.title.clone();
+            let dismiss_on_overlay = modal.dismiss_on_overlay;
+            // The overlay takes every click, so nothing behind the modal can be clicked
+            let overlay_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut dismissed: EventWriter<ModalDismissed>| {
+                    event.stop_propagation();
+                    if let EventType::Click(..) = event.event_type {
+                        if dismiss_on_overlay {
+                            dismissed.send(ModalDismissed {
+                                entity: modal_entity,
+                            });
+                        }
+                    }
+                },
+            );
+            // Escape bubbles up from the focused widget, so it reaches the innermost modal first
+            let visible = modal.visible;
+            let dialog_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut dismissed: EventWriter<ModalDismissed>| {
+                    if let EventType::KeyDown(key_event) = event.event_type {
+                        if visible && key_event.key() == KeyCode::Escape {
+                            event.stop_propagation();
+                            dismissed.send(ModalDismissed {
+                                entity: modal_entity,
+                            });
+                        }
+                    }
+                },
+            );
             let parent_id = Some(modal_entity);
             rsx! {
                 <ElementBundle>
@@ -140,8 +198,10 @@ pub fn render(
                             background_color: Color::rgba(0.0, 0.0, 0.0, modal.overlay_alpha).into(),
                             ..Default::default()
                         }}
+                        on_event={overlay_event}
                     />
                     <ElementBundle
+                        id={"dialog_entity"}
                         styles={KStyle {
                             background_color: Color::rgba(0.188, 0.203, 0.274, 1.0).into(),
                             border_color: Color::rgba(0.933, 0.745, 0.745, 1.0).into(),
@@ -149,8 +209,13 @@ pub fn render(
                             border_radius: Corner::all(10.0).into(),
                             render_command: RenderCommand::Quad.into(),
                             position_type: KPositionType::SelfDirected.into(),
+                            left: Units::Stretch(1.0).into(),
+                            right: Units::Stretch(1.0).into(),
+                            top: Units::Stretch(1.0).into(),
+                            bottom: Units::Stretch(1.0).into(),
                             ..Default::default()
                         }.with_style(modal_styles)}
+                        on_event={dialog_event}
                     >
                         <BackgroundBundle
                             styles={KStyle {
@@ -202,6 +267,14 @@ pub fn render(
                             children={modal_children.clone()}
                         />
                     </ElementBundle>
+                    {
+                        // Focus and Tab navigation stay within the dialog while the modal is visible
+                        if modal.visible {
+                            commands.entity(dialog_entity).insert(FocusTrap);
+                        } else {
+                            commands.entity(dialog_entity).remove::<FocusTrap>();
+                        }
+                    }
                 </ElementBundle>
             };
         }
@@ -0,0 +1,447 @@
+use bevy::prelude::*;
+use kayak_ui_macros::rsx;
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    event::{EventType, KEvent},
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    styles::{
+        ComputedStyles, Corner, Edge, KCursorIcon, KStyle, LayoutType, RenderCommand, StyleProp,
+        Units,
+    },
+    widget::Widget,
+    widgets::{
+        BackgroundBundle, ElementBundle, KSvg, KSvgBundle, Svg, TextProps, TextWidgetBundle,
+        EXPAND_LESS_HANDLE, EXPAND_MORE_HANDLE,
+    },
+    Focusable,
+};
+
+/// Props used by the [`NumericInputBundle`] widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct NumericInputProps {
+    /// If true, prevents the value from being changed
+    pub disabled: bool,
+    /// The smallest allowed value
+    pub min: f32,
+    /// The largest allowed value
+    pub max: f32,
+    /// How much the arrow buttons, arrow keys and mouse wheel change the value by
+    pub step: f32,
+    /// The number of decimal places the value is rounded to, or `None` to keep it as is
+    pub precision: Option<usize>,
+    /// The initial value
+    ///
+    /// Changing this prop sets the value to the new one.
+    pub value: f32,
+}
+
+impl Default for NumericInputProps {
+    fn default() -> Self {
+        Self {
+            disabled: false,
+            min: f32::MIN,
+            max: f32::MAX,
+            step: 1.0,
+            precision: None,
+            value: 0.0,
+        }
+    }
+}
+
+impl NumericInputProps {
+    /// Clamps the value to the range and rounds it to the precision
+    ///
+    /// A range whose `min` is larger than its `max` is treated as if they were swapped.
+    pub fn clamp(&self, value: f32) -> f32 {
+        let value = value.clamp(self.min.min(self.max), self.min.max(self.max));
+        match self.precision {
+            Some(precision) => {
+                let scale = 10f32.powi(precision as i32);
+                let rounded = (value * scale).round() / scale;
+                // Values too large to scale have no fractional digits left to round anyway
+                if rounded.is_finite() {
+                    rounded
+                } else {
+                    value
+                }
+            }
+            None => value,
+        }
+    }
+
+    /// Formats the value according to the precision
+    pub fn format(&self, value: f32) -> String {
+        match self.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => format!("{}", value),
+        }
+    }
+}
+
+impl Widget for NumericInputProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct NumericInputState {
+    /// The current value
+    pub value: f32,
+    /// The value of [`NumericInputProps::value`] when it was last applied
+    pub prop_value: f32,
+    /// The text typed since the value was last committed, if any
+    pub editing: Option<String>,
+    pub focused: bool,
+}
+
+/// An event sent whenever the user changes the value of a numeric input
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct NumericInputChanged {
+    /// The numeric input widget entity
+    pub entity: Entity,
+    /// The new value
+    pub value: f32,
+}
+
+/// A number field with buttons to step its value up and down
+///
+/// The value is changed by [`NumericInputProps::step`] with the arrow buttons, the mouse wheel
+/// or, while focused, the up and down arrow keys. Typing replaces the value, which is committed
+/// with enter or by moving focus away and discarded with escape. Committed values are clamped
+/// to the range and rounded to the precision, and every change sends a [`NumericInputChanged`]
+/// event.
+#[derive(Bundle)]
+pub struct NumericInputBundle {
+    pub numeric_input: NumericInputProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub on_event: OnEvent,
+    pub focusable: Focusable,
+    pub widget_name: WidgetName,
+}
+
+impl Default for NumericInputBundle {
+    fn default() -> Self {
+        Self {
+            numeric_input: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            on_event: Default::default(),
+            focusable: Default::default(),
+            widget_name: NumericInputProps::default().get_name(),
+        }
+    }
+}
+
+/// Commits a new value, sending an event if it changed
+fn commit(
+    props: &NumericInputProps,
+    state: &mut NumericInputState,
+    value: f32,
+    entity: Entity,
+    changed: &mut EventWriter<NumericInputChanged>,
+) {
+    state.editing = None;
+    let value = props.clamp(value);
+    if value != state.value {
+        state.value = value;
+        changed.send(NumericInputChanged { entity, value });
+    }
+}
+
+/// Commits the typed text, keeping the current value if it isn't a number
+fn commit_editing(
+    props: &NumericInputProps,
+    state: &mut NumericInputState,
+    entity: Entity,
+    changed: &mut EventWriter<NumericInputChanged>,
+) {
+    let value = state
+        .editing
+        .as_ref()
+        .and_then(|text| text.trim().parse::<f32>().ok())
+        .unwrap_or(state.value);
+    commit(props, state, value, entity, changed);
+}
+
+pub fn numeric_input_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(
+        &NumericInputProps,
+        &KStyle,
+        &mut ComputedStyles,
+        &mut OnEvent,
+    )>,
+    mut state_query: ParamSet<(Query<&NumericInputState>, Query<&mut NumericInputState>)>,
+) -> bool {
+    if let Ok((numeric_input, styles, mut computed_styles, mut on_event)) = query.get_mut(entity) {
+        let value = numeric_input.clamp(numeric_input.value);
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            NumericInputState {
+                value,
+                prop_value: numeric_input.value,
+                ..Default::default()
+            },
+        );
+
+        if let Ok(mut state) = state_query.p1().get_mut(state_entity) {
+            if state.prop_value != numeric_input.value {
+                state.prop_value = numeric_input.value;
+                state.value = value;
+                state.editing = None;
+            }
+        }
+
+        if let Ok(state) = state_query.p0().get(state_entity) {
+            *computed_styles = KStyle::default()
+                .with_style(KStyle {
+                    render_command: RenderCommand::Layout.into(),
+                    ..Default::default()
+                })
+                .with_style(styles)
+                .with_style(KStyle {
+                    width: Units::Stretch(1.0).into(),
+                    height: Units::Pixels(28.0).into(),
+                    ..Default::default()
+                })
+                .into();
+
+            let box_styles = KStyle {
+                render_command: StyleProp::Value(RenderCommand::Quad),
+                background_color: Color::rgba(0.160, 0.172, 0.235, 1.0).into(),
+                border: Edge::all(1.0).into(),
+                border_color: if state.focused {
+                    Color::rgba(0.933, 0.745, 0.745, 1.0)
+                } else {
+                    Color::rgba(0.360, 0.380, 0.474, 1.0)
+                }
+                .into(),
+                border_radius: Corner::all(3.0).into(),
+                disabled: numeric_input.disabled.into(),
+                layout_type: LayoutType::Row.into(),
+                padding_left: Units::Pixels(8.0).into(),
+                padding_right: Units::Pixels(2.0).into(),
+                ..Default::default()
+            };
+
+            let text_styles = KStyle {
+                top: Units::Stretch(1.0).into(),
+                bottom: Units::Stretch(1.0).into(),
+                right: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let buttons_styles = KStyle {
+                layout_type: LayoutType::Column.into(),
+                width: Units::Pixels(16.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let button_styles = KStyle {
+                cursor: KCursorIcon(CursorIcon::Hand).into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let icon_styles = KStyle {
+                background_color: Color::WHITE.into(),
+                cursor: KCursorIcon(CursorIcon::Hand).into(),
+                width: Units::Pixels(16.0).into(),
+                height: Units::Pixels(16.0).into(),
+                top: Units::Stretch(1.0).into(),
+                bottom: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let props = numeric_input.clone();
+            *on_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut state_query: Query<&mut NumericInputState>,
+                      mut changed: EventWriter<NumericInputChanged>| {
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        match event.event_type {
+                            EventType::Focus => {
+                                state.focused = true;
+                            }
+                            EventType::Blur => {
+                                state.focused = false;
+                                commit_editing(&props, &mut state, entity, &mut changed);
+                            }
+                            _ if props.disabled => {}
+                            EventType::Scroll(scroll_event) => {
+                                let (_, y) = scroll_event.delta.to_pixels(1.0);
+                                if y != 0.0 {
+                                    event.stop_propagation();
+                                    let value = state.value + props.step * y.signum();
+                                    commit(&props, &mut state, value, entity, &mut changed);
+                                }
+                            }
+                            EventType::CharInput { c } if state.focused => {
+                                if c == '\u{8}' || c == '\u{7f}' {
+                                    // Backspace edits the shown value rather than clearing it
+                                    let text = props.format(state.value);
+                                    state.editing.get_or_insert(text).pop();
+                                } else if c.is_ascii_digit() || c == '.' || c == '-' {
+                                    state.editing.get_or_insert_with(String::new).push(c);
+                                }
+                            }
+                            EventType::KeyDown(key_event) if state.focused => match key_event.key()
+                            {
+                                KeyCode::Return => {
+                                    commit_editing(&props, &mut state, entity, &mut changed);
+                                }
+                                KeyCode::Escape if state.editing.is_some() => {
+                                    // Cancelling the edit handles the key, so a modal around
+                                    // the input stays open
+                                    event.stop_propagation();
+                                    state.editing = None;
+                                }
+                                KeyCode::Up => {
+                                    let value = state.value + props.step;
+                                    commit(&props, &mut state, value, entity, &mut changed);
+                                }
+                                KeyCode::Down => {
+                                    let value = state.value - props.step;
+                                    commit(&props, &mut state, value, entity, &mut changed);
+                                }
+                                _ => {}
+                            },
+                            _ => {}
+                        }
+                    }
+                },
+            );
+
+            let step_event = |step: f32| {
+                let props = numeric_input.clone();
+                OnEvent::new(
+                    move |In(_entity): In<Entity>,
+                          event: Res<KEvent>,
+                          mut state_query: Query<&mut NumericInputState>,
+                          mut changed: EventWriter<NumericInputChanged>| {
+                        if props.disabled {
+                            return;
+                        }
+                        if let EventType::Click(..) = event.event_type {
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                let value = state.value + step;
+                                commit(&props, &mut state, value, entity, &mut changed);
+                            }
+                        }
+                    },
+                )
+            };
+            let increment_event = step_event(numeric_input.step);
+            let decrement_event = step_event(-numeric_input.step);
+
+            let text = state
+                .editing
+                .clone()
+                .unwrap_or_else(|| numeric_input.format(state.value));
+
+            let parent_id = Some(entity);
+            rsx! {
+                <BackgroundBundle styles={box_styles}>
+                    <TextWidgetBundle
+                        styles={text_styles}
+                        text={TextProps {
+                            content: text,
+                            size: 14.0,
+                            word_wrap: false,
+                            ..Default::default()
+                        }}
+                    />
+                    <ElementBundle styles={buttons_styles}>
+                        <ElementBundle styles={button_styles.clone()} on_event={increment_event}>
+                            <KSvgBundle
+                                styles={icon_styles.clone()}
+                                svg={KSvg(EXPAND_LESS_HANDLE.typed::<Svg>())}
+                            />
+                        </ElementBundle>
+                        <ElementBundle styles={button_styles} on_event={decrement_event}>
+                            <KSvgBundle
+                                styles={icon_styles}
+                                svg={KSvg(EXPAND_MORE_HANDLE.typed::<Svg>())}
+                            />
+                        </ElementBundle>
+                    </ElementBundle>
+                </BackgroundBundle>
+            };
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumericInputProps;
+
+    fn props(min: f32, max: f32, precision: Option<usize>) -> NumericInputProps {
+        NumericInputProps {
+            min,
+            max,
+            precision,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn clamps_to_range() {
+        let props = props(0.0, 10.0, None);
+        assert_eq!(props.clamp(5.5), 5.5);
+        assert_eq!(props.clamp(-1.0), 0.0);
+        assert_eq!(props.clamp(11.0), 10.0);
+    }
+
+    #[test]
+    fn reversed_range_is_swapped() {
+        let props = props(10.0, 0.0, None);
+        assert_eq!(props.clamp(5.0), 5.0);
+        assert_eq!(props.clamp(-1.0), 0.0);
+        assert_eq!(props.clamp(11.0), 10.0);
+    }
+
+    #[test]
+    fn rounds_to_precision() {
+        assert_eq!(props(0.0, 10.0, Some(0)).clamp(2.5), 3.0);
+        assert_eq!(props(0.0, 10.0, Some(2)).clamp(1.23456), 1.23);
+        // Rounding happens after clamping, so it can't leave the range
+        assert_eq!(props(0.0, 1.0, Some(0)).clamp(1.4), 1.0);
+    }
+
+    #[test]
+    fn large_values_survive_rounding() {
+        let props = props(f32::MIN, f32::MAX, Some(2));
+        assert_eq!(props.clamp(f32::MAX), f32::MAX);
+        assert_eq!(props.clamp(f32::MIN), f32::MIN);
+        assert_eq!(props.clamp(f32::INFINITY), f32::MAX);
+
+        // So many digits that the scale itself overflows
+        let props = NumericInputProps {
+            precision: Some(100),
+            ..props
+        };
+        assert_eq!(props.clamp(1.5), 1.5);
+        assert_eq!(props.clamp(0.0), 0.0);
+    }
+
+    #[test]
+    fn formats_to_precision() {
+        assert_eq!(props(0.0, 10.0, None).format(1.5), "1.5");
+        assert_eq!(props(0.0, 10.0, None).format(2.0), "2");
+        assert_eq!(props(0.0, 10.0, Some(2)).format(1.5), "1.50");
+        assert_eq!(props(0.0, 10.0, Some(0)).format(2.5), "2");
+
+        let formatted = props(f32::MIN, f32::MAX, Some(2)).format(f32::MAX);
+        assert!(formatted.ends_with(".00"));
+        assert_eq!(formatted.parse::<f32>(), Ok(f32::MAX));
+    }
+}
@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+use kayak_ui_macros::rsx;
+
+use crate::{
+    context::WidgetName,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, Corner, KPositionType, KStyle, RenderCommand, Units},
+    widget::Widget,
+    widgets::BackgroundBundle,
+};
+
+/// The fraction of the track covered by the moving segment of an indeterminate progress bar
+const SEGMENT_LENGTH: f32 = 0.3;
+/// How many times per second the segment of an indeterminate progress bar crosses the track
+const SEGMENT_SPEED: f32 = 0.6;
+
+/// Props used by the [`ProgressBarBundle`] widget
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct ProgressBarProps {
+    /// How far along the progress is, from `0.0` to `1.0`
+    pub value: f32,
+    /// If true, ignores the value and shows a segment moving along the track instead
+    ///
+    /// Useful when the amount of remaining work is unknown.
+    pub indeterminate: bool,
+}
+
+impl Widget for ProgressBarProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct ProgressBarState {
+    /// Whether the progress bar is animating
+    pub indeterminate: bool,
+    /// The position of the moving segment, from `0.0` to `1.0`
+    pub offset: f32,
+}
+
+/// A bar that fills up to show the progress of a task
+///
+/// The track is drawn with the [`background_color`](KStyle::background_color) style and the
+/// filled part with the [`color`](KStyle::color) style.
+#[derive(Bundle)]
+pub struct ProgressBarBundle {
+    pub progress_bar: ProgressBarProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub widget_name: WidgetName,
+}
+
+impl Default for ProgressBarBundle {
+    fn default() -> Self {
+        Self {
+            progress_bar: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            widget_name: ProgressBarProps::default().get_name(),
+        }
+    }
+}
+
+pub fn progress_bar_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&ProgressBarProps, &KStyle, &mut ComputedStyles)>,
+    mut state_query: ParamSet<(Query<&ProgressBarState>, Query<&mut ProgressBarState>)>,
+) -> bool {
+    if let Ok((progress_bar, styles, mut computed_styles)) = query.get_mut(entity) {
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            ProgressBarState {
+                indeterminate: progress_bar.indeterminate,
+                ..Default::default()
+            },
+        );
+
+        if let Ok(mut state) = state_query.p1().get_mut(state_entity) {
+            if state.indeterminate != progress_bar.indeterminate {
+                state.indeterminate = progress_bar.indeterminate;
+                state.offset = 0.0;
+            }
+        }
+
+        if let Ok(state) = state_query.p0().get(state_entity) {
+            *computed_styles = KStyle::default()
+                .with_style(KStyle {
+                    render_command: RenderCommand::Quad.into(),
+                    background_color: Color::rgba(0.160, 0.172, 0.235, 1.0).into(),
+                    border_radius: Corner::all(4.0).into(),
+                    width: Units::Stretch(1.0).into(),
+                    height: Units::Pixels(8.0).into(),
+                    ..Default::default()
+                })
+                .with_style(styles)
+                .into();
+
+            let (start, end) = if state.indeterminate {
+                // The segment enters from the left and leaves on the right.
+                let position = state.offset * (1.0 + SEGMENT_LENGTH) - SEGMENT_LENGTH;
+                (position.max(0.0), (position + SEGMENT_LENGTH).min(1.0))
+            } else {
+                (0.0, progress_bar.value.clamp(0.0, 1.0))
+            };
+
+            let fill_styles = KStyle {
+                background_color: styles
+                    .color
+                    .resolve_or(Color::rgba(0.592, 0.627, 0.749, 1.0))
+                    .into(),
+                border_radius: styles.border_radius.resolve_or(Corner::all(4.0)).into(),
+                position_type: KPositionType::SelfDirected.into(),
+                left: Units::Percentage(start * 100.0).into(),
+                top: Units::Pixels(0.0).into(),
+                width: Units::Percentage((end - start) * 100.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let parent_id = Some(entity);
+            rsx! {
+                <BackgroundBundle styles={fill_styles} />
+            };
+        }
+    }
+
+    true
+}
+
+/// Moves the segment of every indeterminate progress bar along its track
+pub fn progress_bar_animation_system(
+    time: Res<Time>,
+    mut state_query: Query<&mut ProgressBarState>,
+) {
+    for mut state in state_query.iter_mut() {
+        if state.indeterminate {
+            state.offset = (state.offset + time.delta_seconds() * SEGMENT_SPEED).fract();
+        }
+    }
+}
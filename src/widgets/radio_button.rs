@@ -0,0 +1,273 @@
+use bevy::{prelude::*, utils::HashMap};
+use kayak_ui_macros::{constructor, rsx};
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    event::{EventType, KEvent},
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, KCursorIcon, KStyle, RenderCommand, Units},
+    widget::Widget,
+    widgets::{KSvg, KSvgBundle, Svg, RADIO_DOT_HANDLE},
+    Focusable,
+};
+
+use super::{
+    checkbox::{toggle_box_styles, toggle_icon_styles},
+    BackgroundBundle,
+};
+
+/// Context data provided by a [`RadioContextProviderBundle`]
+///
+/// Keeps track of the selected value of each radio button group.
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct RadioContext {
+    selected: HashMap<String, String>,
+}
+
+impl RadioContext {
+    /// Returns the selected value of the given group, if any
+    pub fn selected(&self, group_id: &str) -> Option<&str> {
+        self.selected.get(group_id).map(|value| value.as_str())
+    }
+
+    /// Selects a value within the given group, deselecting any other value in it
+    pub fn select(&mut self, group_id: impl Into<String>, value: impl Into<String>) {
+        self.selected.insert(group_id.into(), value.into());
+    }
+}
+
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct RadioContextProvider;
+
+impl Widget for RadioContextProvider {}
+
+/// Provides a [`RadioContext`] shared by all of the radio buttons within it
+#[derive(Bundle, Debug, Clone, PartialEq)]
+pub struct RadioContextProviderBundle {
+    pub context_provider: RadioContextProvider,
+    pub children: KChildren,
+    pub computed_styles: ComputedStyles,
+    pub widget_name: WidgetName,
+}
+
+impl Default for RadioContextProviderBundle {
+    fn default() -> Self {
+        Self {
+            context_provider: Default::default(),
+            children: Default::default(),
+            computed_styles: ComputedStyles(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                height: Units::Auto.into(),
+                width: Units::Stretch(1.0).into(),
+                ..KStyle::default()
+            }),
+            widget_name: RadioContextProvider::default().get_name(),
+        }
+    }
+}
+
+pub fn radio_context_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    children_query: Query<&KChildren>,
+) -> bool {
+    if let Ok(children) = children_query.get(entity) {
+        let context_entity = if let Some(context_entity) =
+            widget_context.get_context_entity::<RadioContext>(entity)
+        {
+            context_entity
+        } else {
+            commands
+                .spawn(RadioContext::default())
+                .set_parent(entity)
+                .id()
+        };
+        widget_context.set_context_entity::<RadioContext>(Some(entity), context_entity);
+        children.process(&widget_context, &mut commands, Some(entity));
+    }
+
+    true
+}
+
+/// Props used by the [`RadioButtonBundle`] widget
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct RadioButtonProps {
+    /// If true, prevents the radio button from being selected
+    pub disabled: bool,
+    /// The group this radio button belongs to
+    ///
+    /// Only one radio button per group can be selected at a time.
+    pub group_id: String,
+    /// The value this radio button represents within its group
+    pub value: String,
+    /// If true, this radio button starts out selected when nothing else in its group is
+    pub selected: bool,
+}
+
+impl Widget for RadioButtonProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct RadioButtonState {
+    pub focused: bool,
+}
+
+/// An event sent whenever the user selects a radio button
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct RadioButtonChanged {
+    /// The radio button widget entity
+    pub entity: Entity,
+    /// The group of the radio button
+    pub group_id: String,
+    /// The value of the newly selected radio button
+    pub value: String,
+}
+
+/// A button that selects one value out of a group
+///
+/// Radio buttons should be placed within a [`RadioContextProviderBundle`], which makes sure
+/// only one button per [`group_id`](RadioButtonProps::group_id) is selected. Clicking a radio
+/// button, or pressing space while it's focused, selects it and sends a [`RadioButtonChanged`]
+/// event.
+#[derive(Bundle)]
+pub struct RadioButtonBundle {
+    pub radio_button: RadioButtonProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub on_event: OnEvent,
+    pub focusable: Focusable,
+    pub widget_name: WidgetName,
+}
+
+impl Default for RadioButtonBundle {
+    fn default() -> Self {
+        Self {
+            radio_button: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            on_event: Default::default(),
+            focusable: Default::default(),
+            widget_name: RadioButtonProps::default().get_name(),
+        }
+    }
+}
+
+pub fn radio_button_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(
+        &RadioButtonProps,
+        &KStyle,
+        &mut ComputedStyles,
+        &mut OnEvent,
+    )>,
+    state_query: Query<&RadioButtonState>,
+    mut context_query: ParamSet<(Query<&RadioContext>, Query<&mut RadioContext>)>,
+) -> bool {
+    if let Ok((radio_button, styles, mut computed_styles, mut on_event)) = query.get_mut(entity) {
+        if let Some(context_entity) = widget_context.get_context_entity::<RadioContext>(entity) {
+            if radio_button.selected {
+                if let Ok(mut context) = context_query.p1().get_mut(context_entity) {
+                    if context.selected(&radio_button.group_id).is_none() {
+                        context.select(&radio_button.group_id, &radio_button.value);
+                    }
+                }
+            }
+
+            let state_entity =
+                widget_context.use_state(&mut commands, entity, RadioButtonState::default());
+
+            if let (Ok(state), Ok(context)) = (
+                state_query.get(state_entity),
+                context_query.p0().get(context_entity),
+            ) {
+                let selected =
+                    context.selected(&radio_button.group_id) == Some(radio_button.value.as_str());
+
+                *computed_styles = KStyle::default()
+                    .with_style(KStyle {
+                        render_command: RenderCommand::Layout.into(),
+                        ..Default::default()
+                    })
+                    .with_style(styles)
+                    .with_style(KStyle {
+                        cursor: KCursorIcon(CursorIcon::Hand).into(),
+                        width: Units::Pixels(20.0).into(),
+                        height: Units::Pixels(20.0).into(),
+                        ..Default::default()
+                    })
+                    .into();
+
+                let box_styles = toggle_box_styles(state.focused, radio_button.disabled, 10.0);
+
+                let disabled = radio_button.disabled;
+                let group_id = radio_button.group_id.clone();
+                let value = radio_button.value.clone();
+                *on_event = OnEvent::new(
+                    move |In(_entity): In<Entity>,
+                          event: Res<KEvent>,
+                          mut state_query: Query<&mut RadioButtonState>,
+                          mut context_query: Query<&mut RadioContext>,
+                          mut changed: EventWriter<RadioButtonChanged>| {
+                        let select = match event.event_type {
+                            EventType::Click(..) => true,
+                            EventType::KeyDown(key_event) => {
+                                key_event.key() == KeyCode::Space
+                                    && state_query
+                                        .get(state_entity)
+                                        .map(|state| state.focused)
+                                        .unwrap_or_default()
+                            }
+                            EventType::Focus | EventType::Blur => {
+                                if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                    state.focused = matches!(event.event_type, EventType::Focus);
+                                }
+                                false
+                            }
+                            _ => false,
+                        };
+
+                        if select && !disabled {
+                            if let Ok(mut context) = context_query.get_mut(context_entity) {
+                                if context.selected(&group_id) != Some(value.as_str()) {
+                                    context.select(&group_id, &value);
+                                    changed.send(RadioButtonChanged {
+                                        entity,
+                                        group_id: group_id.clone(),
+                                        value: value.clone(),
+                                    });
+                                }
+                            }
+                        }
+                    },
+                );
+
+                let parent_id = Some(entity);
+                rsx! {
+                    <BackgroundBundle styles={box_styles}>
+                        {
+                            if selected {
+                                constructor! {
+                                    <KSvgBundle
+                                        styles={toggle_icon_styles()}
+                                        svg={KSvg(RADIO_DOT_HANDLE.typed::<Svg>())}
+                                    />
+                                }
+                            }
+                        }
+                    </BackgroundBundle>
+                };
+            }
+        } else {
+            log::warn!(
+                "Radio button {:?} is not within a RadioContextProviderBundle",
+                entity
+            );
+        }
+    }
+
+    true
+}
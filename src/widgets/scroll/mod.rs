@@ -2,6 +2,7 @@ pub mod scroll_bar;
 pub mod scroll_box;
 pub mod scroll_content;
 pub mod scroll_context;
+pub mod virtual_list;
 
 /// Maps a value from one range to another range
 fn map_range(value: f32, from_range: (f32, f32), to_range: (f32, f32)) -> f32 {
@@ -3,7 +3,6 @@ use bevy::prelude::{Bundle, Color, Commands, Component, Entity, In, ParamSet, Qu
 use crate::{
     children::KChildren,
     context::WidgetName,
-    cursor::ScrollUnit,
     event::{EventType, KEvent},
     layout::{GeometryChanged, LayoutEvent},
     on_event::OnEvent,
@@ -20,7 +19,7 @@ use crate::{
     },
 };
 
-use super::scroll_context::ScrollContext;
+use super::scroll_context::{ScrollContext, ScrollMode};
 
 #[derive(Component, Default, Clone, PartialEq)]
 pub struct ScrollBoxProps {
@@ -37,6 +36,10 @@ pub struct ScrollBoxProps {
     pub hide_horizontal: bool,
     /// If true, hides the vertical scrollbar
     pub hide_vertical: bool,
+    /// If true, the content can be scrolled slightly past its limits and springs back once released
+    ///
+    /// This switches a [`ScrollMode::Clamped`] scroll context to [`ScrollMode::Elastic`].
+    pub overscroll: bool,
     /// The thickness of the scrollbar
     pub scrollbar_thickness: Option<f32>,
     /// The step to scroll by when `ScrollUnit::Line`
@@ -53,6 +56,12 @@ pub struct ScrollBoxProps {
 
 impl Widget for ScrollBoxProps {}
 
+/// A container that clips its children and lets them be scrolled with the mouse wheel or
+/// by dragging its scrollbars
+///
+/// The scroll offset is stored in the closest [`ScrollContext`], which can be provided by a
+/// [`ScrollContextProviderBundle`](crate::widgets::ScrollContextProviderBundle) to read or
+/// change it from other widgets. If there isn't one, the scroll box creates its own.
 #[derive(Bundle)]
 pub struct ScrollBoxBundle {
     pub scroll_box_props: ScrollBoxProps,
@@ -92,168 +101,192 @@ pub fn scroll_box_render(
     if let Ok((scroll_box, styles, mut computed_styles, scroll_box_children, mut on_layout)) =
         query.get_mut(entity)
     {
-        if let Some(context_entity) = widget_context.get_context_entity::<ScrollContext>(entity) {
-            if let Ok(scroll_context) = context_query.p0().get(context_entity).cloned() {
-                // === Configuration === //
-                let always_show_scrollbar = scroll_box.always_show_scrollbar;
-                let disable_horizontal = scroll_box.disable_horizontal;
-                let disable_vertical = scroll_box.disable_vertical;
-                let hide_horizontal = scroll_box.hide_horizontal;
-                let hide_vertical = scroll_box.hide_vertical;
-                let scrollbar_thickness = scroll_box.scrollbar_thickness.unwrap_or(10.0);
-                let scroll_line = scroll_box.scroll_line.unwrap_or(16.0);
-                let thumb_color = scroll_box.thumb_color;
-                let thumb_styles = scroll_box.thumb_styles.clone();
-                let track_color = scroll_box.track_color;
-                let track_styles = scroll_box.track_styles.clone();
+        // Without a ScrollContextProvider above it, the scroll box keeps its own scroll context.
+        let context_entity = widget_context
+            .get_context_entity::<ScrollContext>(entity)
+            .unwrap_or_else(|| {
+                let context_entity =
+                    widget_context.use_state(&mut commands, entity, ScrollContext::default());
+                widget_context.set_context_entity::<ScrollContext>(Some(entity), context_entity);
+                context_entity
+            });
+        if let Ok(scroll_context) = context_query.p0().get(context_entity).cloned() {
+            // === Configuration === //
+            let always_show_scrollbar = scroll_box.always_show_scrollbar;
+            let disable_horizontal = scroll_box.disable_horizontal;
+            let disable_vertical = scroll_box.disable_vertical;
+            let hide_horizontal = scroll_box.hide_horizontal;
+            let hide_vertical = scroll_box.hide_vertical;
+            let scrollbar_thickness = scroll_box.scrollbar_thickness.unwrap_or(10.0);
+            let scroll_line = scroll_box.scroll_line.unwrap_or(16.0);
+            let thumb_color = scroll_box.thumb_color;
+            let thumb_styles = scroll_box.thumb_styles.clone();
+            let track_color = scroll_box.track_color;
+            let track_styles = scroll_box.track_styles.clone();
 
-                let scroll_x = scroll_context.scroll_x();
-                let scroll_y = scroll_context.scroll_y();
-                let scrollable_width = scroll_context.scrollable_width();
-                let scrollable_height = scroll_context.scrollable_height();
+            let scroll_x = scroll_context.scroll_x();
+            let scroll_y = scroll_context.scroll_y();
+            let scrollable_width = scroll_context.scrollable_width();
+            let scrollable_height = scroll_context.scrollable_height();
 
-                let hori_thickness = scrollbar_thickness;
-                let vert_thickness = scrollbar_thickness;
+            let hori_thickness = scrollbar_thickness;
+            let vert_thickness = scrollbar_thickness;
 
-                let hide_horizontal =
-                    hide_horizontal || !always_show_scrollbar && scrollable_width < f32::EPSILON;
-                let hide_vertical =
-                    hide_vertical || !always_show_scrollbar && scrollable_height < f32::EPSILON;
+            let hide_horizontal =
+                hide_horizontal || !always_show_scrollbar && scrollable_width < f32::EPSILON;
+            let hide_vertical =
+                hide_vertical || !always_show_scrollbar && scrollable_height < f32::EPSILON;
 
-                let pad_x = if hide_vertical { 0.0 } else { vert_thickness };
-                let pad_y = if hide_horizontal { 0.0 } else { hori_thickness };
+            let pad_x = if hide_vertical { 0.0 } else { vert_thickness };
+            let pad_y = if hide_horizontal { 0.0 } else { hori_thickness };
 
-                if pad_x != scroll_context.pad_x || pad_y != scroll_context.pad_y {
-                    if let Ok(mut scroll_context_mut) = context_query.p1().get_mut(context_entity) {
-                        scroll_context_mut.pad_x = pad_x;
-                        scroll_context_mut.pad_y = pad_y;
-                    }
+            if pad_x != scroll_context.pad_x || pad_y != scroll_context.pad_y {
+                if let Ok(mut scroll_context_mut) = context_query.p1().get_mut(context_entity) {
+                    scroll_context_mut.pad_x = pad_x;
+                    scroll_context_mut.pad_y = pad_y;
                 }
+            }
 
-                *on_layout = OnLayout::new(
-                    move |In((event, _entity)): In<(LayoutEvent, Entity)>,
-                          mut query: Query<&mut ScrollContext>| {
-                        if event.flags.intersects(
-                            GeometryChanged::WIDTH_CHANGED | GeometryChanged::HEIGHT_CHANGED,
-                        ) {
-                            if let Ok(mut scroll) = query.get_mut(context_entity) {
-                                scroll.scrollbox_width = event.layout.width;
-                                scroll.scrollbox_height = event.layout.height;
-                            }
-                        }
+            let mode = match (scroll_context.mode(), scroll_box.overscroll) {
+                (ScrollMode::Clamped, true) => Some(ScrollMode::Elastic),
+                (ScrollMode::Elastic, false) => Some(ScrollMode::Clamped),
+                _ => None,
+            };
+            if let Some(mode) = mode {
+                if let Ok(mut scroll_context_mut) = context_query.p1().get_mut(context_entity) {
+                    scroll_context_mut.mode = mode;
+                }
+            }
 
-                        event
-                    },
-                );
+            *on_layout = OnLayout::new(
+                move |In((event, _entity)): In<(LayoutEvent, Entity)>,
+                      mut query: Query<&mut ScrollContext>| {
+                    if event.flags.intersects(
+                        GeometryChanged::WIDTH_CHANGED | GeometryChanged::HEIGHT_CHANGED,
+                    ) {
+                        if let Ok(mut scroll) = query.get_mut(context_entity) {
+                            scroll.scrollbox_width = event.layout.width;
+                            scroll.scrollbox_height = event.layout.height;
+                        }
+                    }
 
-                // === Styles === //
-                *computed_styles = KStyle::default()
-                    .with_style(KStyle {
-                        render_command: RenderCommand::Layout.into(),
-                        ..Default::default()
-                    })
-                    .with_style(styles)
-                    .with_style(KStyle {
-                        width: Units::Stretch(1.0).into(),
-                        height: Units::Stretch(1.0).into(),
-                        ..Default::default()
-                    })
-                    .into();
+                    event
+                },
+            );
 
-                let hbox_styles = KStyle::default().with_style(KStyle {
+            // === Styles === //
+            *computed_styles = KStyle::default()
+                .with_style(KStyle {
                     render_command: RenderCommand::Layout.into(),
-                    layout_type: LayoutType::Row.into(),
-                    width: Units::Stretch(1.0).into(),
                     ..Default::default()
-                });
-                let vbox_styles = KStyle::default().with_style(KStyle {
-                    render_command: RenderCommand::Layout.into(),
-                    layout_type: LayoutType::Column.into(),
+                })
+                .with_style(styles)
+                .with_style(KStyle {
                     width: Units::Stretch(1.0).into(),
+                    height: Units::Stretch(1.0).into(),
                     ..Default::default()
-                });
+                })
+                .into();
 
-                let content_styles = KStyle::default().with_style(KStyle {
-                    position_type: KPositionType::SelfDirected.into(),
-                    top: Units::Pixels(scroll_y).into(),
-                    left: Units::Pixels(scroll_x).into(),
-                    ..Default::default()
-                });
+            let hbox_styles = KStyle::default().with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                layout_type: LayoutType::Row.into(),
+                width: Units::Stretch(1.0).into(),
+                ..Default::default()
+            });
+            let vbox_styles = KStyle::default().with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                layout_type: LayoutType::Column.into(),
+                width: Units::Stretch(1.0).into(),
+                ..Default::default()
+            });
+
+            let content_styles = KStyle::default().with_style(KStyle {
+                position_type: KPositionType::SelfDirected.into(),
+                top: Units::Pixels(scroll_y).into(),
+                left: Units::Pixels(scroll_x).into(),
+                ..Default::default()
+            });
 
-                let event_handler = OnEvent::new(
-                    move |In(_entity): In<Entity>,
-                          mut event: ResMut<KEvent>,
-                          mut query: Query<&mut ScrollContext>| {
-                        if let Ok(mut scroll_context) = query.get_mut(context_entity) {
-                            if let EventType::Scroll(evt) = event.event_type {
-                                match evt.delta {
-                                    ScrollUnit::Line { x, y } => {
-                                        if !disable_horizontal {
-                                            scroll_context.set_scroll_x(scroll_x - x * scroll_line);
-                                        }
-                                        if !disable_vertical {
-                                            scroll_context.set_scroll_y(scroll_y + y * scroll_line);
-                                        }
-                                    }
-                                    ScrollUnit::Pixel { x, y } => {
-                                        if !disable_horizontal {
-                                            scroll_context.set_scroll_x(scroll_x - x);
-                                        }
-                                        if !disable_vertical {
-                                            scroll_context.set_scroll_y(scroll_y + y);
-                                        }
-                                    }
-                                }
+            let event_handler = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut query: Query<&mut ScrollContext>| {
+                    if let Ok(mut scroll_context) = query.get_mut(context_entity) {
+                        if let EventType::Scroll(evt) = event.event_type {
+                            let (x, y) = evt.delta.to_pixels(scroll_line);
+                            // Holding shift turns a vertical wheel into horizontal scrolling
+                            let (x, y) = if evt.modifiers.is_shift_pressed && x == 0.0 {
+                                (-y, 0.0)
+                            } else {
+                                (x, y)
+                            };
+                            // Each axis only moves from its own current offset, so scrolling one
+                            // never resets the other
+                            let previous = *scroll_context;
+                            if !disable_horizontal && x != 0.0 {
+                                let scroll_x = scroll_context.scroll_x();
+                                scroll_context.set_scroll_x(scroll_x - x);
+                            }
+                            if !disable_vertical && y != 0.0 {
+                                let scroll_y = scroll_context.scroll_y();
+                                scroll_context.set_scroll_y(scroll_y + y);
+                            }
+                            // A scroll box that can't scroll any further lets the scroll bubble
+                            // up to the scroll boxes around it
+                            if scroll_context.scroll_x() != previous.scroll_x()
+                                || scroll_context.scroll_y() != previous.scroll_y()
+                            {
                                 event.stop_propagation();
                             }
                         }
-                    },
-                );
+                    }
+                },
+            );
 
-                let parent_id = Some(entity);
-                rsx! {
-                    <ElementBundle on_event={event_handler} styles={hbox_styles}>
-                        <ElementBundle styles={vbox_styles}>
-                            <ClipBundle>
-                                <ScrollContentBundle
-                                    children={scroll_box_children.clone()}
-                                    styles={content_styles}
-                                />
-                            </ClipBundle>
-                            {if !hide_horizontal {
-                                constructor! {
-                                    <ScrollBarBundle
-                                        scrollbar_props={ScrollBarProps {
-                                            disabled: disable_horizontal,
-                                            horizontal: true,
-                                            thickness: hori_thickness,
-                                            thumb_color,
-                                            thumb_styles: thumb_styles.clone(),
-                                            track_color,
-                                            track_styles: track_styles.clone(),
-                                        }}
-                                    />
-                                }
-                            }}
-                        </ElementBundle>
-                        {if !hide_vertical {
+            let parent_id = Some(entity);
+            rsx! {
+                <ElementBundle on_event={event_handler} styles={hbox_styles}>
+                    <ElementBundle styles={vbox_styles}>
+                        <ClipBundle>
+                            <ScrollContentBundle
+                                children={scroll_box_children.clone()}
+                                styles={content_styles}
+                            />
+                        </ClipBundle>
+                        {if !hide_horizontal {
                             constructor! {
                                 <ScrollBarBundle
                                     scrollbar_props={ScrollBarProps {
-                                        disabled: disable_vertical,
+                                        disabled: disable_horizontal,
+                                        horizontal: true,
                                         thickness: hori_thickness,
                                         thumb_color,
-                                        thumb_styles,
+                                        thumb_styles: thumb_styles.clone(),
                                         track_color,
-                                        track_styles,
-                                        ..Default::default()
+                                        track_styles: track_styles.clone(),
                                     }}
                                 />
                             }
                         }}
                     </ElementBundle>
-                };
-            }
+                    {if !hide_vertical {
+                        constructor! {
+                            <ScrollBarBundle
+                                scrollbar_props={ScrollBarProps {
+                                    disabled: disable_vertical,
+                                    thickness: vert_thickness,
+                                    thumb_color,
+                                    thumb_styles,
+                                    track_color,
+                                    track_styles,
+                                    ..Default::default()
+                                }}
+                            />
+                        }
+                    }}
+                </ElementBundle>
+            };
         }
     }
     true
@@ -56,12 +56,13 @@ pub fn scroll_content_render(
             if let Ok(scroll_context) = context_query.get(context_entity) {
                 // === OnLayout === //
                 *on_layout = OnLayout::new(
-                    move |In((event, _entity)): In<(LayoutEvent, Entity)>,
+                    move |In((event, entity)): In<(LayoutEvent, Entity)>,
                           mut query: Query<&mut ScrollContext>| {
                         if event.flags.intersects(
                             GeometryChanged::WIDTH_CHANGED | GeometryChanged::HEIGHT_CHANGED,
                         ) {
                             if let Ok(mut scroll) = query.get_mut(context_entity) {
+                                scroll.content_entity = Some(entity);
                                 scroll.content_width = event.layout.width;
                                 scroll.content_height = event.layout.height;
                                 let scroll_y = scroll.scroll_y;
@@ -1,4 +1,6 @@
-use bevy::prelude::{BuildChildren, Bundle, Commands, Component, Entity, In, Query, Res, Vec2};
+use bevy::prelude::{
+    BuildChildren, Bundle, Commands, Component, Entity, In, Query, Res, Time, Vec2,
+};
 
 use crate::{
     children::KChildren,
@@ -23,6 +25,8 @@ pub struct ScrollContext {
     pub(super) is_dragging: bool,
     pub(super) start_pos: Vec2,
     pub(super) start_offset: Vec2,
+    /// The scroll content widget, once it has been laid out
+    pub(super) content_entity: Option<Entity>,
 }
 
 #[non_exhaustive]
@@ -33,8 +37,17 @@ pub enum ScrollMode {
     Clamped,
     /// Allows infinite scrolling
     Infinite,
+    /// Allows scrolling slightly past the scroll range, springing back once released
+    Elastic,
 }
 
+/// The maximum distance (in pixels) content can be scrolled past its limits in [`ScrollMode::Elastic`]
+const ELASTIC_MAX_OVERSCROLL: f32 = 80.0;
+/// How much of the scroll movement is applied once past the limits in [`ScrollMode::Elastic`]
+const ELASTIC_RESISTANCE: f32 = 0.4;
+/// How quickly an overscrolled [`ScrollMode::Elastic`] context springs back into range
+const ELASTIC_SPRING_SPEED: f32 = 12.0;
+
 impl ScrollContext {
     /// Get the current x-axis scroll offset
     pub fn scroll_x(&self) -> f32 {
@@ -87,6 +100,7 @@ impl ScrollContext {
         self.scroll_x = match self.mode {
             ScrollMode::Clamped => ScrollContext::clamped(x, min, 0.0),
             ScrollMode::Infinite => x,
+            ScrollMode::Elastic => ScrollContext::elastic(self.scroll_x, x, min, 0.0),
         }
     }
 
@@ -98,6 +112,7 @@ impl ScrollContext {
         self.scroll_y = match self.mode {
             ScrollMode::Clamped => ScrollContext::clamped(y, min, 0.0),
             ScrollMode::Infinite => y,
+            ScrollMode::Elastic => ScrollContext::elastic(self.scroll_y, y, min, 0.0),
         };
     }
 
@@ -127,6 +142,59 @@ impl ScrollContext {
     fn clamped(value: f32, min: f32, max: f32) -> f32 {
         value.clamp(min, max)
     }
+
+    /// Moves from `current` towards `value`, resisting any movement past the range
+    fn elastic(current: f32, value: f32, min: f32, max: f32) -> f32 {
+        let next = if value > max && value > current {
+            let start = current.max(max);
+            start + (value - start) * ELASTIC_RESISTANCE
+        } else if value < min && value < current {
+            let start = current.min(min);
+            start + (value - start) * ELASTIC_RESISTANCE
+        } else {
+            value
+        };
+
+        next.clamp(min - ELASTIC_MAX_OVERSCROLL, max + ELASTIC_MAX_OVERSCROLL)
+    }
+
+    /// Eases an overscrolled offset back into range, returning true if it changed
+    fn spring_back(&mut self, delta_seconds: f32) -> bool {
+        let target_x = ScrollContext::clamped(self.scroll_x, -self.scrollable_width(), 0.0);
+        let target_y = ScrollContext::clamped(self.scroll_y, -self.scrollable_height(), 0.0);
+        if target_x == self.scroll_x && target_y == self.scroll_y {
+            return false;
+        }
+
+        let t = 1.0 - (-ELASTIC_SPRING_SPEED * delta_seconds).exp();
+        let spring = |value: f32, target: f32| {
+            let next = value + (target - value) * t;
+            if (target - next).abs() < 0.5 {
+                target
+            } else {
+                next
+            }
+        };
+        self.scroll_x = spring(self.scroll_x, target_x);
+        self.scroll_y = spring(self.scroll_y, target_y);
+
+        true
+    }
+}
+
+/// Springs any overscrolled [`ScrollMode::Elastic`] contexts back into range
+pub fn elastic_scroll_system(time: Res<Time>, mut query: Query<&mut ScrollContext>) {
+    for mut scroll_context in query.iter_mut() {
+        if scroll_context.mode != ScrollMode::Elastic || scroll_context.is_dragging {
+            continue;
+        }
+
+        // Only trigger change detection when the offset actually moves.
+        let mut updated = *scroll_context;
+        if updated.spring_back(time.delta_seconds()) {
+            *scroll_context = updated;
+        }
+    }
 }
 
 #[derive(Component, Default, PartialEq, Clone)]
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use bevy::prelude::{Bundle, Commands, Component, Entity, In, Query, Res};
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    prelude::{constructor, rsx, KayakWidgetContext},
+    styles::{ComputedStyles, KPositionType, KStyle, RenderCommand, Units},
+    widget::Widget,
+    widgets::ElementBundle,
+};
+
+use super::scroll_context::ScrollContext;
+
+/// Builds the widget for a single row of a [`VirtualListBundle`]
+///
+/// The closure is given the same `widget_context`, `commands`, `children` and `parent_id` the
+/// `constructor!` macro expects, along with the index of the row to build.
+#[derive(Clone)]
+pub struct ItemBuilder(
+    Arc<
+        dyn Fn(&KayakWidgetContext, &mut Commands, &mut KChildren, Option<Entity>, usize)
+            + Send
+            + Sync,
+    >,
+);
+
+impl ItemBuilder {
+    pub fn new<F>(builder: F) -> Self
+    where
+        F: Fn(&KayakWidgetContext, &mut Commands, &mut KChildren, Option<Entity>, usize)
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self(Arc::new(builder))
+    }
+
+    fn build(
+        &self,
+        widget_context: &KayakWidgetContext,
+        commands: &mut Commands,
+        children: &mut KChildren,
+        parent_id: Option<Entity>,
+        index: usize,
+    ) {
+        (self.0)(widget_context, commands, children, parent_id, index);
+    }
+}
+
+impl Default for ItemBuilder {
+    fn default() -> Self {
+        Self::new(|_, _, _, _, _| {})
+    }
+}
+
+impl std::fmt::Debug for ItemBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemBuilder").finish()
+    }
+}
+
+/// __Note:__ Only checks if both builders are the same closure
+impl PartialEq for ItemBuilder {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Props used by the [`VirtualListBundle`] widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct VirtualListProps {
+    /// The total number of rows
+    pub item_count: usize,
+    /// The height of every row in pixels
+    pub item_height: f32,
+    /// The number of rows to build above and below the visible ones
+    pub overscan: usize,
+    /// Builds the widget for a row
+    pub item_builder: ItemBuilder,
+}
+
+impl Default for VirtualListProps {
+    fn default() -> Self {
+        Self {
+            item_count: 0,
+            item_height: 24.0,
+            overscan: 2,
+            item_builder: Default::default(),
+        }
+    }
+}
+
+impl Widget for VirtualListProps {}
+
+/// A list that only builds the rows currently scrolled into view
+///
+/// The list must be placed within a [`ScrollBoxBundle`](crate::widgets::ScrollBoxBundle). It
+/// takes up the height of all of its rows, so the scrollbar matches the full list, but only
+/// builds widgets for the visible rows plus [`overscan`](VirtualListProps::overscan) rows on
+/// either side. Rows are keyed by their index, so a row keeps its entity while it stays in view.
+#[derive(Bundle)]
+pub struct VirtualListBundle {
+    pub virtual_list_props: VirtualListProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub widget_name: WidgetName,
+}
+
+impl Default for VirtualListBundle {
+    fn default() -> Self {
+        Self {
+            virtual_list_props: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            widget_name: VirtualListProps::default().get_name(),
+        }
+    }
+}
+
+pub fn virtual_list_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&VirtualListProps, &KStyle, &mut ComputedStyles)>,
+    context_query: Query<&ScrollContext>,
+) -> bool {
+    if let Ok((virtual_list, styles, mut computed_styles)) = query.get_mut(entity) {
+        if let Some(context_entity) = widget_context.get_context_entity::<ScrollContext>(entity) {
+            if let Ok(scroll_context) = context_query.get(context_entity) {
+                let item_count = virtual_list.item_count;
+                let item_height = virtual_list.item_height.max(1.0);
+
+                *computed_styles = KStyle::default()
+                    .with_style(KStyle {
+                        render_command: RenderCommand::Layout.into(),
+                        width: Units::Stretch(1.0).into(),
+                        ..Default::default()
+                    })
+                    .with_style(styles)
+                    .with_style(KStyle {
+                        height: Units::Pixels(item_height * item_count as f32).into(),
+                        ..Default::default()
+                    })
+                    .into();
+
+                // The list's offset within the scroll content doesn't change as it scrolls, so
+                // the visible range only depends on the scroll offset
+                let offset = scroll_context
+                    .content_entity
+                    .and_then(|content_entity| {
+                        let layout = widget_context.get_layout(entity)?;
+                        let content_layout = widget_context.get_layout(content_entity)?;
+                        Some(layout.posy - content_layout.posy)
+                    })
+                    .unwrap_or_default();
+                let view_top = -scroll_context.scroll_y() - offset;
+                let view_bottom = view_top + scroll_context.scrollbox_height;
+                let first = ((view_top / item_height).floor().max(0.0) as usize)
+                    .saturating_sub(virtual_list.overscan)
+                    .min(item_count);
+                let last = ((view_bottom / item_height).ceil().max(0.0) as usize)
+                    .saturating_add(virtual_list.overscan)
+                    .min(item_count);
+
+                let item_builder = virtual_list.item_builder.clone();
+                let parent_id = Some(entity);
+                rsx! {
+                    <ElementBundle
+                        styles={KStyle {
+                            width: Units::Stretch(1.0).into(),
+                            height: Units::Stretch(1.0).into(),
+                            ..Default::default()
+                        }}
+                    >
+                        {(first..last).for_each(|index| {
+                            let key = format!("virtual-list-item-{}", index);
+                            constructor! {
+                                <ElementBundle
+                                    key={key.as_str()}
+                                    styles={KStyle {
+                                        position_type: KPositionType::SelfDirected.into(),
+                                        top: Units::Pixels(index as f32 * item_height).into(),
+                                        width: Units::Stretch(1.0).into(),
+                                        height: Units::Pixels(item_height).into(),
+                                        ..Default::default()
+                                    }}
+                                >
+                                    {item_builder.build(&widget_context, &mut commands, &mut children, parent_id, index)}
+                                </ElementBundle>
+                            }
+                        })}
+                    </ElementBundle>
+                };
+            }
+        }
+    }
+
+    true
+}
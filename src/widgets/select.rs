@@ -0,0 +1,350 @@
+use bevy::prelude::*;
+use kayak_ui_macros::{constructor, rsx};
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    event::{EventType, KEvent},
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    styles::{
+        ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, LayoutType,
+        RenderCommand, StyleProp, Units,
+    },
+    widget::Widget,
+    widgets::{
+        BackgroundBundle, KSvg, KSvgBundle, Svg, TextProps, TextWidgetBundle, EXPAND_LESS_HANDLE,
+        EXPAND_MORE_HANDLE,
+    },
+    Focusable,
+};
+
+/// The z-index of an open popup list, keeping it above the rest of the UI
+const POPUP_Z_INDEX: i32 = 100_000;
+
+/// Props used by the [`SelectBundle`] widget
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct SelectProps {
+    /// If true, prevents the popup list from being opened
+    pub disabled: bool,
+    /// The options to pick from
+    pub options: Vec<String>,
+    /// The index of the initially selected option
+    ///
+    /// Changing this prop selects the new index.
+    pub selected: usize,
+}
+
+impl Widget for SelectProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct SelectState {
+    /// The index of the selected option
+    pub selected: usize,
+    /// The value of [`SelectProps::selected`] when it was last applied
+    pub prop_selected: usize,
+    /// The index of the option highlighted in the popup list
+    pub highlighted: usize,
+    pub open: bool,
+    pub focused: bool,
+}
+
+/// An event sent whenever the user picks an option from a select widget
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct SelectChanged {
+    /// The select widget entity
+    pub entity: Entity,
+    /// The index of the newly selected option
+    pub index: usize,
+    /// The newly selected option
+    pub value: String,
+}
+
+/// A dropdown that lets users pick one option out of a list
+///
+/// Clicking the widget opens a popup list of its options, drawn above the rest of the UI.
+/// Picking an option closes the list and sends a [`SelectChanged`] event. Clicking anywhere
+/// else closes the list without changing the selection. While focused, the up and down arrow
+/// keys move through the options and enter picks the highlighted one.
+#[derive(Bundle)]
+pub struct SelectBundle {
+    pub select: SelectProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub on_event: OnEvent,
+    pub focusable: Focusable,
+    pub widget_name: WidgetName,
+}
+
+impl Default for SelectBundle {
+    fn default() -> Self {
+        Self {
+            select: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            on_event: Default::default(),
+            focusable: Default::default(),
+            widget_name: SelectProps::default().get_name(),
+        }
+    }
+}
+
+pub fn select_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&SelectProps, &KStyle, &mut ComputedStyles, &mut OnEvent)>,
+    mut state_query: ParamSet<(Query<&SelectState>, Query<&mut SelectState>)>,
+) -> bool {
+    if let Ok((select, styles, mut computed_styles, mut on_event)) = query.get_mut(entity) {
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            SelectState {
+                selected: select.selected,
+                prop_selected: select.selected,
+                highlighted: select.selected,
+                ..Default::default()
+            },
+        );
+
+        if let Ok(mut state) = state_query.p1().get_mut(state_entity) {
+            if state.prop_selected != select.selected {
+                state.prop_selected = select.selected;
+                state.selected = select.selected;
+                state.highlighted = select.selected;
+            }
+        }
+
+        if let Ok(state) = state_query.p0().get(state_entity) {
+            *computed_styles = KStyle::default()
+                .with_style(KStyle {
+                    render_command: RenderCommand::Layout.into(),
+                    ..Default::default()
+                })
+                .with_style(styles)
+                .with_style(KStyle {
+                    cursor: KCursorIcon(CursorIcon::Hand).into(),
+                    width: Units::Stretch(1.0).into(),
+                    height: Units::Pixels(28.0).into(),
+                    ..Default::default()
+                })
+                .into();
+
+            let box_styles = KStyle {
+                render_command: StyleProp::Value(RenderCommand::Quad),
+                background_color: Color::rgba(0.160, 0.172, 0.235, 1.0).into(),
+                border: Edge::all(1.0).into(),
+                border_color: if state.focused {
+                    Color::rgba(0.933, 0.745, 0.745, 1.0)
+                } else {
+                    Color::rgba(0.360, 0.380, 0.474, 1.0)
+                }
+                .into(),
+                border_radius: Corner::all(3.0).into(),
+                disabled: select.disabled.into(),
+                layout_type: LayoutType::Row.into(),
+                padding_left: Units::Pixels(8.0).into(),
+                padding_right: Units::Pixels(4.0).into(),
+                ..Default::default()
+            };
+
+            let text_styles = KStyle {
+                top: Units::Stretch(1.0).into(),
+                bottom: Units::Stretch(1.0).into(),
+                right: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let icon_styles = KStyle {
+                background_color: Color::WHITE.into(),
+                width: Units::Pixels(20.0).into(),
+                height: Units::Pixels(20.0).into(),
+                top: Units::Stretch(1.0).into(),
+                bottom: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let popup_styles = KStyle {
+                render_command: StyleProp::Value(RenderCommand::Quad),
+                background_color: Color::rgba(0.133, 0.145, 0.2, 1.0).into(),
+                border: Edge::all(1.0).into(),
+                border_color: Color::rgba(0.360, 0.380, 0.474, 1.0).into(),
+                border_radius: Corner::all(3.0).into(),
+                position_type: KPositionType::SelfDirected.into(),
+                top: Units::Percentage(100.0).into(),
+                left: Units::Pixels(0.0).into(),
+                width: Units::Percentage(100.0).into(),
+                height: Units::Auto.into(),
+                z_index: POPUP_Z_INDEX.into(),
+                render_layer: 1.into(),
+                ..Default::default()
+            };
+
+            let option_count = select.options.len();
+            let options = select.options.clone();
+            let disabled = select.disabled;
+            *on_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event: ResMut<KEvent>,
+                      mut state_query: Query<&mut SelectState>,
+                      mut changed: EventWriter<SelectChanged>| {
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        match event.event_type {
+                            EventType::MouseDown(..) if !disabled => {
+                                state.open = !state.open;
+                                state.highlighted = state.selected;
+                            }
+                            EventType::KeyDown(key_event) if state.focused && !disabled => {
+                                match key_event.key() {
+                                    KeyCode::Up if state.open => {
+                                        state.highlighted = state.highlighted.saturating_sub(1);
+                                    }
+                                    KeyCode::Down if state.open => {
+                                        state.highlighted = (state.highlighted + 1)
+                                            .min(option_count.saturating_sub(1));
+                                    }
+                                    KeyCode::Up | KeyCode::Down | KeyCode::Space => {
+                                        state.open = true;
+                                        state.highlighted = state.selected;
+                                    }
+                                    KeyCode::Return if state.open => {
+                                        let index = state.highlighted;
+                                        state.open = false;
+                                        if index != state.selected && index < option_count {
+                                            state.selected = index;
+                                            changed.send(SelectChanged {
+                                                entity,
+                                                index,
+                                                value: options[index].clone(),
+                                            });
+                                        }
+                                    }
+                                    KeyCode::Escape if state.open => {
+                                        // Closing the list handles the key, so a modal around
+                                        // the select stays open
+                                        event.stop_propagation();
+                                        state.open = false;
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            EventType::Focus => {
+                                state.focused = true;
+                            }
+                            EventType::Blur => {
+                                // Clicking anywhere outside of the widget blurs it
+                                state.focused = false;
+                                state.open = false;
+                            }
+                            _ => {}
+                        }
+                    }
+                },
+            );
+
+            let selected_text = select
+                .options
+                .get(state.selected)
+                .cloned()
+                .unwrap_or_default();
+
+            let parent_id = Some(entity);
+            rsx! {
+                <BackgroundBundle styles={box_styles}>
+                    <TextWidgetBundle
+                        styles={text_styles}
+                        text={TextProps {
+                            content: selected_text,
+                            size: 14.0,
+                            word_wrap: false,
+                            ..Default::default()
+                        }}
+                    />
+                    <KSvgBundle
+                        styles={icon_styles}
+                        svg={KSvg(if state.open {
+                            EXPAND_LESS_HANDLE.typed::<Svg>()
+                        } else {
+                            EXPAND_MORE_HANDLE.typed::<Svg>()
+                        })}
+                    />
+                    {
+                        if state.open {
+                            constructor! {
+                                <BackgroundBundle styles={popup_styles}>
+                                    {select.options.iter().enumerate().for_each(|(index, option)| {
+                                        let key = format!("select-option-{}", index);
+                                        let value = option.clone();
+                                        let on_event = OnEvent::new(
+                                            move |In(_entity): In<Entity>,
+                                                  mut event: ResMut<KEvent>,
+                                                  mut state_query: Query<&mut SelectState>,
+                                                  mut changed: EventWriter<SelectChanged>| {
+                                                if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                                    match event.event_type {
+                                                        // The press also blurs the select, so pick the option right away
+                                                        // instead of waiting for the click.
+                                                        EventType::MouseDown(..) => {
+                                                            event.stop_propagation();
+                                                            state.open = false;
+                                                            if index != state.selected {
+                                                                state.selected = index;
+                                                                changed.send(SelectChanged {
+                                                                    entity,
+                                                                    index,
+                                                                    value: value.clone(),
+                                                                });
+                                                            }
+                                                        }
+                                                        EventType::Hover(..) if state.highlighted != index => {
+                                                            state.highlighted = index;
+                                                        }
+                                                        _ => {}
+                                                    }
+                                                }
+                                            },
+                                        );
+                                        constructor! {
+                                            <BackgroundBundle
+                                                key={key.as_str()}
+                                                styles={KStyle {
+                                                    background_color: if index == state.highlighted {
+                                                        Color::rgba(0.254, 0.270, 0.349, 1.0)
+                                                    } else {
+                                                        Color::rgba(0.0, 0.0, 0.0, 0.0)
+                                                    }
+                                                    .into(),
+                                                    height: Units::Pixels(24.0).into(),
+                                                    padding_left: Units::Pixels(8.0).into(),
+                                                    ..Default::default()
+                                                }}
+                                                on_event={on_event}
+                                            >
+                                                <TextWidgetBundle
+                                                    styles={KStyle {
+                                                        top: Units::Stretch(1.0).into(),
+                                                        bottom: Units::Stretch(1.0).into(),
+                                                        ..Default::default()
+                                                    }}
+                                                    text={TextProps {
+                                                        content: option.clone(),
+                                                        size: 14.0,
+                                                        word_wrap: false,
+                                                        ..Default::default()
+                                                    }}
+                                                />
+                                            </BackgroundBundle>
+                                        }
+                                    })}
+                                </BackgroundBundle>
+                            }
+                        }
+                    }
+                </BackgroundBundle>
+            };
+        }
+    }
+
+    true
+}
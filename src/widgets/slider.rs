@@ -0,0 +1,306 @@
+use bevy::prelude::*;
+use kayak_ui_macros::rsx;
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    cursor::PointerEvents,
+    event::{EventType, KEvent},
+    event_dispatcher::EventDispatcherContext,
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, Corner, Edge, KPositionType, KStyle, RenderCommand, Units},
+    widget::Widget,
+    widgets::{BackgroundBundle, ElementBundle},
+    Focusable,
+};
+
+/// Props used by the [`SliderBundle`] widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SliderProps {
+    /// If true, prevents the value from being changed
+    pub disabled: bool,
+    /// The smallest selectable value
+    pub min: f32,
+    /// The largest selectable value
+    pub max: f32,
+    /// The interval values snap to, starting from [`min`](Self::min)
+    ///
+    /// This is also how much the arrow keys change the value by. A step of `0.0` allows any
+    /// value, in which case the arrow keys move by a tenth of the range.
+    pub step: f32,
+    /// The initial value of the slider
+    ///
+    /// Changing this prop moves the slider to the new value.
+    pub value: f32,
+    /// The height of the track
+    pub track_height: Units,
+    /// The diameter of the thumb in pixels
+    pub thumb_size: f32,
+    /// The color of the thumb
+    pub thumb_color: Option<Color>,
+    /// The color of the track
+    pub track_color: Option<Color>,
+}
+
+impl Default for SliderProps {
+    fn default() -> Self {
+        Self {
+            disabled: false,
+            min: 0.0,
+            max: 1.0,
+            step: 0.0,
+            value: 0.0,
+            track_height: Units::Pixels(4.0),
+            thumb_size: 16.0,
+            thumb_color: None,
+            track_color: None,
+        }
+    }
+}
+
+impl SliderProps {
+    /// Clamps the value to the range and snaps it to the closest step
+    pub fn snap(&self, value: f32) -> f32 {
+        let (min, max) = (self.min.min(self.max), self.min.max(self.max));
+        let value = value.clamp(min, max);
+        if self.step > 0.0 {
+            (min + ((value - min) / self.step).round() * self.step).clamp(min, max)
+        } else {
+            value
+        }
+    }
+
+    /// Returns the value as a fraction of the range, from `0.0` to `1.0`
+    fn percent(&self, value: f32) -> f32 {
+        let range = self.max - self.min;
+        if range.abs() <= f32::EPSILON {
+            // Can't divide by zero
+            0.0
+        } else {
+            ((value - self.min) / range).clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl Widget for SliderProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct SliderState {
+    /// The current value of the slider
+    pub value: f32,
+    /// The value of [`SliderProps::value`] when it was last applied
+    pub prop_value: f32,
+    pub dragging: bool,
+    pub focused: bool,
+}
+
+/// An event sent whenever the user changes the value of a slider
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct SliderChanged {
+    /// The slider widget entity
+    pub entity: Entity,
+    /// The new value
+    pub value: f32,
+}
+
+/// A widget that lets users pick a number from a range by dragging a thumb along a track
+///
+/// Clicking the track moves the thumb to the cursor. While focused, the left and right arrow
+/// keys change the value by [`SliderProps::step`]. Every change sends a [`SliderChanged`] event.
+#[derive(Bundle)]
+pub struct SliderBundle {
+    pub slider: SliderProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub on_event: OnEvent,
+    pub focusable: Focusable,
+    pub widget_name: WidgetName,
+}
+
+impl Default for SliderBundle {
+    fn default() -> Self {
+        Self {
+            slider: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            on_event: Default::default(),
+            focusable: Default::default(),
+            widget_name: SliderProps::default().get_name(),
+        }
+    }
+}
+
+pub fn slider_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&SliderProps, &KStyle, &mut ComputedStyles, &mut OnEvent)>,
+    mut state_query: ParamSet<(Query<&SliderState>, Query<&mut SliderState>)>,
+) -> bool {
+    if let Ok((slider, styles, mut computed_styles, mut on_event)) = query.get_mut(entity) {
+        let value = slider.snap(slider.value);
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            SliderState {
+                value,
+                prop_value: slider.value,
+                ..Default::default()
+            },
+        );
+
+        if let Ok(mut state) = state_query.p1().get_mut(state_entity) {
+            if state.prop_value != slider.value {
+                state.prop_value = slider.value;
+                state.value = value;
+            }
+        }
+
+        if let Ok(state) = state_query.p0().get(state_entity) {
+            let thumb_size = slider.thumb_size;
+
+            *computed_styles = KStyle::default()
+                .with_style(KStyle {
+                    render_command: RenderCommand::Layout.into(),
+                    ..Default::default()
+                })
+                .with_style(styles)
+                .with_style(KStyle {
+                    width: Units::Stretch(1.0).into(),
+                    height: Units::Pixels(thumb_size + 4.0).into(),
+                    // The thumb and track shouldn't take events away from the slider itself.
+                    pointer_events: PointerEvents::SelfOnly.into(),
+                    ..Default::default()
+                })
+                .into();
+
+            let track_styles = KStyle {
+                background_color: slider
+                    .track_color
+                    .unwrap_or_else(|| Color::rgba(0.160, 0.172, 0.235, 1.0))
+                    .into(),
+                border_radius: Corner::all(thumb_size / 4.0).into(),
+                width: Units::Stretch(1.0).into(),
+                height: slider.track_height.into(),
+                top: Units::Stretch(1.0).into(),
+                bottom: Units::Stretch(1.0).into(),
+                left: Units::Pixels(thumb_size / 2.0).into(),
+                right: Units::Pixels(thumb_size / 2.0).into(),
+                ..Default::default()
+            };
+
+            // The thumb moves along a rail that is one thumb shorter than the slider, so the
+            // percentage offset keeps the thumb within the slider at both ends.
+            let rail_styles = KStyle {
+                position_type: KPositionType::SelfDirected.into(),
+                left: Units::Pixels(0.0).into(),
+                right: Units::Pixels(thumb_size).into(),
+                top: Units::Pixels(0.0).into(),
+                bottom: Units::Pixels(0.0).into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let thumb_color = slider
+                .thumb_color
+                .unwrap_or_else(|| Color::rgba(0.592, 0.627, 0.749, 1.0));
+            let thumb_styles = KStyle {
+                render_command: RenderCommand::Quad.into(),
+                position_type: KPositionType::SelfDirected.into(),
+                background_color: if slider.disabled {
+                    thumb_color.with_a(thumb_color.a() * 0.5)
+                } else {
+                    thumb_color
+                }
+                .into(),
+                border: Edge::all(if state.focused { 2.0 } else { 0.0 }).into(),
+                border_color: Color::rgba(0.933, 0.745, 0.745, 1.0).into(),
+                border_radius: Corner::all(thumb_size / 2.0).into(),
+                left: Units::Percentage(slider.percent(state.value) * 100.0).into(),
+                top: Units::Stretch(1.0).into(),
+                bottom: Units::Stretch(1.0).into(),
+                width: Units::Pixels(thumb_size).into(),
+                height: Units::Pixels(thumb_size).into(),
+                ..Default::default()
+            };
+
+            let props = slider.clone();
+            let layout_context = widget_context.clone();
+            *on_event = OnEvent::new(
+                move |In(_entity): In<Entity>,
+                      mut event_dispatcher_context: ResMut<EventDispatcherContext>,
+                      event: Res<KEvent>,
+                      mut state_query: Query<&mut SliderState>,
+                      mut changed: EventWriter<SliderChanged>| {
+                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                        // Converts a cursor position into a value along the track.
+                        let value_at = |x: f32| {
+                            let layout = layout_context.get_layout(entity).unwrap_or_default();
+                            let length = (layout.width - props.thumb_size).max(1.0);
+                            let percent = (x - layout.posx - props.thumb_size / 2.0) / length;
+                            props.min + percent.clamp(0.0, 1.0) * (props.max - props.min)
+                        };
+
+                        let value = match event.event_type {
+                            EventType::MouseDown(data) if !props.disabled => {
+                                event_dispatcher_context.capture_cursor(event.current_target);
+                                state.dragging = true;
+                                Some(value_at(data.position.0))
+                            }
+                            EventType::Hover(data) if state.dragging => {
+                                Some(value_at(data.position.0))
+                            }
+                            EventType::MouseUp(..) if state.dragging => {
+                                event_dispatcher_context.release_cursor(event.current_target);
+                                state.dragging = false;
+                                None
+                            }
+                            EventType::KeyDown(key_event) if state.focused && !props.disabled => {
+                                let nudge = if props.step > 0.0 {
+                                    props.step
+                                } else {
+                                    (props.max - props.min) / 10.0
+                                };
+                                match key_event.key() {
+                                    KeyCode::Left => Some(state.value - nudge),
+                                    KeyCode::Right => Some(state.value + nudge),
+                                    _ => None,
+                                }
+                            }
+                            EventType::Focus => {
+                                state.focused = true;
+                                None
+                            }
+                            EventType::Blur => {
+                                state.focused = false;
+                                None
+                            }
+                            _ => None,
+                        };
+
+                        if let Some(value) = value.map(|value| props.snap(value)) {
+                            if value != state.value {
+                                state.value = value;
+                                changed.send(SliderChanged { entity, value });
+                            }
+                        }
+                    }
+                },
+            );
+
+            let parent_id = Some(entity);
+            rsx! {
+                <ElementBundle>
+                    <BackgroundBundle styles={track_styles} />
+                    <ElementBundle styles={rail_styles}>
+                        <BackgroundBundle styles={thumb_styles} />
+                    </ElementBundle>
+                </ElementBundle>
+            };
+        }
+    }
+
+    true
+}
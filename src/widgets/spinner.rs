@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use kayak_ui_macros::rsx;
+
+use crate::{
+    context::WidgetName,
+    prelude::KayakWidgetContext,
+    styles::{ComputedStyles, KStyle, RenderCommand, Units},
+    widget::Widget,
+    widgets::{KSvg, KSvgBundle, Svg, SPINNER_HANDLE},
+};
+
+/// Props used by the [`SpinnerBundle`] widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SpinnerProps {
+    /// How many full turns the spinner makes per second
+    pub speed: f32,
+}
+
+impl Default for SpinnerProps {
+    fn default() -> Self {
+        Self { speed: 1.0 }
+    }
+}
+
+impl Widget for SpinnerProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct SpinnerState {
+    /// How many turns per second the spinner makes
+    pub speed: f32,
+    /// The current rotation in radians
+    pub angle: f32,
+}
+
+/// A spinning arc used to show that something is loading
+///
+/// The size of the spinner is set with the [`width`](KStyle::width) and
+/// [`height`](KStyle::height) styles and its color with the [`color`](KStyle::color) style.
+#[derive(Bundle)]
+pub struct SpinnerBundle {
+    pub spinner: SpinnerProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub widget_name: WidgetName,
+}
+
+impl Default for SpinnerBundle {
+    fn default() -> Self {
+        Self {
+            spinner: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            widget_name: SpinnerProps::default().get_name(),
+        }
+    }
+}
+
+pub fn spinner_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&SpinnerProps, &KStyle, &mut ComputedStyles)>,
+    mut state_query: ParamSet<(Query<&SpinnerState>, Query<&mut SpinnerState>)>,
+) -> bool {
+    if let Ok((spinner, styles, mut computed_styles)) = query.get_mut(entity) {
+        let state_entity = widget_context.use_state(
+            &mut commands,
+            entity,
+            SpinnerState {
+                speed: spinner.speed,
+                ..Default::default()
+            },
+        );
+
+        if let Ok(mut state) = state_query.p1().get_mut(state_entity) {
+            if state.speed != spinner.speed {
+                state.speed = spinner.speed;
+            }
+        }
+
+        if let Ok(state) = state_query.p0().get(state_entity) {
+            *computed_styles = KStyle::default()
+                .with_style(KStyle {
+                    render_command: RenderCommand::Layout.into(),
+                    width: Units::Pixels(24.0).into(),
+                    height: Units::Pixels(24.0).into(),
+                    ..Default::default()
+                })
+                .with_style(styles)
+                .into();
+
+            let arc_styles = KStyle {
+                background_color: styles
+                    .color
+                    .resolve_or(Color::rgba(0.592, 0.627, 0.749, 1.0))
+                    .into(),
+                rotation: state.angle.into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            };
+
+            let parent_id = Some(entity);
+            rsx! {
+                <KSvgBundle
+                    svg={KSvg(SPINNER_HANDLE.typed::<Svg>())}
+                    styles={arc_styles}
+                />
+            };
+        }
+    }
+
+    true
+}
+
+/// Turns every spinner by the time since the last frame
+pub fn spinner_animation_system(time: Res<Time>, mut state_query: Query<&mut SpinnerState>) {
+    for mut state in state_query.iter_mut() {
+        if state.speed != 0.0 {
+            state.angle = (state.angle
+                + time.delta_seconds() * state.speed * std::f32::consts::TAU)
+                % std::f32::consts::TAU;
+        }
+    }
+}
@@ -3,11 +3,24 @@ use crate::{
     styles::{ComputedStyles, KStyle, RenderCommand},
     widget::Widget,
 };
-use bevy::prelude::{Bundle, Component, Entity, Handle, In, Query};
+use bevy::prelude::{Bundle, Color, Component, Entity, Handle, In, Query};
 
 pub use bevy_svg::prelude::Svg;
 /// Renders a svg asset within the GUI
 /// The rendered svg respects some of the styles.
+///
+/// Any [`Svg`] asset can be used, including ones loaded through the `AssetServer`. The svg is
+/// scaled to fit within the widget's layout while keeping its aspect ratio, and is centered
+/// along the other axis.
+///
+/// The svg's colors are multiplied by the `color` style, so a white icon can be reused in any
+/// color. Unlike text, svgs don't inherit the `color` of their parents and keep their own colors
+/// unless `color` is set on them. Set it to
+/// [`StyleProp::Inherit`](crate::styles::StyleProp::Inherit) for icons that should follow the
+/// text color of their parents. Setting a `background_color` instead replaces all of the svg's
+/// colors with that color.
+///
+/// Nothing is drawn while the asset is still loading.
 #[derive(Component, PartialEq, Eq, Clone, Default)]
 pub struct KSvg(pub Handle<Svg>);
 
@@ -46,6 +59,11 @@ pub fn svg_render(
                 ..Default::default()
             })
             .with_style(style)
+            .with_style(KStyle {
+                // Only tint the svg with a color that was set on it
+                color: Color::WHITE.into(),
+                ..Default::default()
+            })
             .into();
     }
     true
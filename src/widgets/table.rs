@@ -0,0 +1,398 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use bevy::prelude::*;
+use kayak_ui_macros::{constructor, rsx};
+
+use crate::{
+    children::KChildren,
+    context::WidgetName,
+    event::{EventType, KEvent},
+    on_event::OnEvent,
+    prelude::KayakWidgetContext,
+    styles::{
+        ComputedStyles, Edge, KCursorIcon, KStyle, LayoutType, RenderCommand, StyleProp, Units,
+    },
+    widget::Widget,
+    widgets::{
+        BackgroundBundle, ElementBundle, ItemBuilder, KSvg, KSvgBundle, ScrollBoxBundle,
+        ScrollBoxProps, Svg, TextProps, TextWidgetBundle, VirtualListBundle, VirtualListProps,
+        EXPAND_LESS_HANDLE, EXPAND_MORE_HANDLE,
+    },
+};
+
+const HEADER_HEIGHT: f32 = 28.0;
+const CELL_PADDING: f32 = 8.0;
+const FONT_SIZE: f32 = 14.0;
+const SCROLLBAR_THICKNESS: f32 = 10.0;
+
+/// The order a [`TableBundle`] is sorted in
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    /// The opposite direction
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+/// A column of a [`TableBundle`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableColumn {
+    /// The text shown in the column's header
+    pub label: String,
+    /// The width of the column
+    ///
+    /// Stretch widths share whatever space the pixel widths leave.
+    pub width: Units,
+    /// If true, clicking the column's header sorts the table by it
+    pub sortable: bool,
+}
+
+impl Default for TableColumn {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            width: Units::Stretch(1.0),
+            sortable: true,
+        }
+    }
+}
+
+/// Props used by the [`TableBundle`] widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct TableProps {
+    /// The columns of the table
+    pub columns: Vec<TableColumn>,
+    /// The rows of the table, holding the text of each cell in column order
+    pub rows: Vec<Vec<String>>,
+    /// The height of every body row in pixels
+    pub row_height: f32,
+    /// The column and direction the table is initially sorted by
+    pub sort: Option<(usize, SortDirection)>,
+}
+
+impl Default for TableProps {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            row_height: 24.0,
+            sort: None,
+        }
+    }
+}
+
+impl Widget for TableProps {}
+
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct TableState {
+    /// The column and direction the table is currently sorted by
+    pub sort: Option<(usize, SortDirection)>,
+}
+
+/// An event sent whenever the user sorts a table by clicking a column header
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSorted {
+    /// The table widget entity
+    pub entity: Entity,
+    /// The index of the column the table is sorted by
+    pub column: usize,
+    /// The direction the table is sorted in
+    pub direction: SortDirection,
+}
+
+/// A table of text cells with a header row
+///
+/// Clicking the header of a sortable column sorts the rows by it, and clicking it again reverses
+/// the order. Cells are compared as numbers when both parse as one, and as text otherwise. Only
+/// the rows scrolled into view are built, so the table can hold large datasets.
+#[derive(Bundle)]
+pub struct TableBundle {
+    pub table: TableProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub widget_name: WidgetName,
+}
+
+impl Default for TableBundle {
+    fn default() -> Self {
+        Self {
+            table: Default::default(),
+            styles: Default::default(),
+            computed_styles: ComputedStyles::default(),
+            widget_name: TableProps::default().get_name(),
+        }
+    }
+}
+
+/// Compares two cells, sorting numbers before text
+///
+/// Numbers are compared by value and text alphabetically, with ties broken by the text so the
+/// order stays total (`"1"` and `"1.0"` are still ordered).
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    let a_number = a.trim().parse::<f64>();
+    let b_number = b.trim().parse::<f64>();
+    a_number
+        .is_err()
+        .cmp(&b_number.is_err())
+        .then_with(|| match (a_number, b_number) {
+            (Ok(a_number), Ok(b_number)) => a_number.total_cmp(&b_number),
+            _ => Ordering::Equal,
+        })
+        .then_with(|| a.cmp(b))
+}
+
+pub fn table_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&TableProps, &KStyle, &mut ComputedStyles)>,
+    state_query: Query<&TableState>,
+) -> bool {
+    if let Ok((table, styles, mut computed_styles)) = query.get_mut(entity) {
+        let state_entity =
+            widget_context.use_state(&mut commands, entity, TableState { sort: table.sort });
+
+        let sort = state_query
+            .get(state_entity)
+            .map(|state| state.sort)
+            .unwrap_or(table.sort);
+
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                layout_type: LayoutType::Column.into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Stretch(1.0).into(),
+                ..Default::default()
+            })
+            .with_style(styles)
+            .into();
+
+        let columns = Arc::new(table.columns.clone());
+        let rows = Arc::new(table.rows.clone());
+        let mut order = (0..rows.len()).collect::<Vec<_>>();
+        if let Some((column, direction)) = sort {
+            let empty = String::new();
+            order.sort_by(|a, b| {
+                let a = rows[*a].get(column).unwrap_or(&empty);
+                let b = rows[*b].get(column).unwrap_or(&empty);
+                match direction {
+                    SortDirection::Ascending => compare_cells(a, b),
+                    SortDirection::Descending => compare_cells(b, a),
+                }
+            });
+        }
+        let order = Arc::new(order);
+
+        let row_columns = columns.clone();
+        let item_builder = ItemBuilder::new(
+            move |widget_context, mut commands, children, parent_id, index| {
+                let row = &rows[order[index]];
+                let key = format!("table-row-{}", index);
+                constructor! {
+                    <BackgroundBundle
+                        key={key.as_str()}
+                        styles={KStyle {
+                            background_color: if index % 2 == 0 {
+                                Color::rgba(0.160, 0.172, 0.235, 1.0)
+                            } else {
+                                Color::rgba(0.133, 0.145, 0.2, 1.0)
+                            }
+                            .into(),
+                            layout_type: LayoutType::Row.into(),
+                            ..Default::default()
+                        }}
+                    >
+                        {row_columns.iter().enumerate().for_each(|(column_index, column)| {
+                            let key = format!("table-cell-{}", column_index);
+                            constructor! {
+                                <ElementBundle
+                                    key={key.as_str()}
+                                    styles={KStyle {
+                                        width: StyleProp::Value(column.width),
+                                        padding_left: Units::Pixels(CELL_PADDING).into(),
+                                        padding_right: Units::Pixels(CELL_PADDING).into(),
+                                        ..Default::default()
+                                    }}
+                                >
+                                    <TextWidgetBundle
+                                        styles={KStyle {
+                                            top: Units::Stretch(1.0).into(),
+                                            bottom: Units::Stretch(1.0).into(),
+                                            ..Default::default()
+                                        }}
+                                        text={TextProps {
+                                            content: row.get(column_index).cloned().unwrap_or_default(),
+                                            size: FONT_SIZE,
+                                            word_wrap: false,
+                                            ..Default::default()
+                                        }}
+                                    />
+                                </ElementBundle>
+                            }
+                        })}
+                    </BackgroundBundle>
+                }
+            },
+        );
+
+        let parent_id = Some(entity);
+        rsx! {
+            <ElementBundle
+                styles={KStyle {
+                    layout_type: LayoutType::Column.into(),
+                    ..Default::default()
+                }}
+            >
+                <BackgroundBundle
+                    styles={KStyle {
+                        background_color: Color::rgba(0.254, 0.270, 0.349, 1.0).into(),
+                        layout_type: LayoutType::Row.into(),
+                        height: Units::Pixels(HEADER_HEIGHT).into(),
+                        // Line the header up with the body rows, which are inset by the scroll
+                        // content and its scrollbar
+                        padding_right: Units::Pixels(SCROLLBAR_THICKNESS * 2.0).into(),
+                        ..Default::default()
+                    }}
+                >
+                    {columns.iter().enumerate().for_each(|(column_index, column)| {
+                        let key = format!("table-header-{}", column_index);
+                        let sortable = column.sortable;
+                        let direction = sort
+                            .filter(|(sorted_column, _)| *sorted_column == column_index)
+                            .map(|(_, direction)| direction);
+                        let on_event = OnEvent::new(
+                            move |In(_entity): In<Entity>,
+                                  event: Res<KEvent>,
+                                  mut state_query: Query<&mut TableState>,
+                                  mut sorted: EventWriter<TableSorted>| {
+                                if !sortable {
+                                    return;
+                                }
+                                if let EventType::Click(..) = event.event_type {
+                                    if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                        let direction = direction
+                                            .map(SortDirection::reversed)
+                                            .unwrap_or_default();
+                                        state.sort = Some((column_index, direction));
+                                        sorted.send(TableSorted {
+                                            entity,
+                                            column: column_index,
+                                            direction,
+                                        });
+                                    }
+                                }
+                            },
+                        );
+                        constructor! {
+                            <ElementBundle
+                                key={key.as_str()}
+                                styles={KStyle {
+                                    cursor: if sortable {
+                                        StyleProp::Value(KCursorIcon(CursorIcon::Hand))
+                                    } else {
+                                        StyleProp::Unset
+                                    },
+                                    layout_type: LayoutType::Row.into(),
+                                    width: StyleProp::Value(column.width),
+                                    padding: Edge::axis(Units::Pixels(0.0), Units::Pixels(CELL_PADDING)).into(),
+                                    ..Default::default()
+                                }}
+                                on_event={on_event}
+                            >
+                                <TextWidgetBundle
+                                    styles={KStyle {
+                                        top: Units::Stretch(1.0).into(),
+                                        bottom: Units::Stretch(1.0).into(),
+                                        ..Default::default()
+                                    }}
+                                    text={TextProps {
+                                        content: column.label.clone(),
+                                        size: FONT_SIZE,
+                                        word_wrap: false,
+                                        ..Default::default()
+                                    }}
+                                />
+                                {
+                                    if let Some(direction) = direction {
+                                        constructor! {
+                                            <KSvgBundle
+                                                styles={KStyle {
+                                                    background_color: Color::WHITE.into(),
+                                                    width: Units::Pixels(16.0).into(),
+                                                    height: Units::Pixels(16.0).into(),
+                                                    top: Units::Stretch(1.0).into(),
+                                                    bottom: Units::Stretch(1.0).into(),
+                                                    left: Units::Pixels(4.0).into(),
+                                                    ..Default::default()
+                                                }}
+                                                svg={KSvg(match direction {
+                                                    SortDirection::Ascending => EXPAND_LESS_HANDLE.typed::<Svg>(),
+                                                    SortDirection::Descending => EXPAND_MORE_HANDLE.typed::<Svg>(),
+                                                })}
+                                            />
+                                        }
+                                    }
+                                }
+                            </ElementBundle>
+                        }
+                    })}
+                </BackgroundBundle>
+                <ScrollBoxBundle
+                    scroll_box_props={ScrollBoxProps {
+                        always_show_scrollbar: true,
+                        disable_horizontal: true,
+                        hide_horizontal: true,
+                        scrollbar_thickness: Some(SCROLLBAR_THICKNESS),
+                        ..Default::default()
+                    }}
+                >
+                    <VirtualListBundle
+                        virtual_list_props={VirtualListProps {
+                            item_count: table.rows.len(),
+                            item_height: table.row_height,
+                            item_builder,
+                            ..Default::default()
+                        }}
+                    />
+                </ScrollBoxBundle>
+            </ElementBundle>
+        };
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compare_cells;
+
+    #[test]
+    fn mixed_column_sorts_numbers_before_text() {
+        let mut column = vec!["banana", "10", "apple", "2", "-1.5", "1.0", "1", " 3 "];
+        column.sort_by(|a, b| compare_cells(a, b));
+        assert_eq!(
+            column,
+            vec!["-1.5", "1", "1.0", "2", " 3 ", "10", "apple", "banana"]
+        );
+    }
+
+    #[test]
+    fn compare_cells_is_antisymmetric() {
+        let cells = ["banana", "10", "apple", "2", "1.0", "1", "NaN", ""];
+        for a in cells {
+            for b in cells {
+                assert_eq!(compare_cells(a, b), compare_cells(b, a).reverse());
+            }
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use instant::Instant;
 
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 use kayak_font::{KayakFont, TextProperties};
 use kayak_ui_macros::{constructor, rsx};
 
 use crate::{
-    context::WidgetName,
+    clipboard::Clipboard,
+    context::{KayakRootContext, WidgetName},
     event::{EventType, KEvent},
+    input::ImeComposition,
+    node::WrappedIndex,
     on_event::OnEvent,
     on_layout::OnLayout,
     prelude::{KChildren, KayakWidgetContext, OnChange},
@@ -42,6 +45,16 @@ pub struct TextBoxState {
     pub graphemes: Vec<String>,
     pub cursor_x: f32,
     pub cursor_position: usize,
+    /// The grapheme index where the selection started, if any text is selected
+    ///
+    /// The selection spans from here to the [`cursor_position`](Self::cursor_position).
+    pub selection_anchor: Option<usize>,
+    /// The offset of the selection anchor in pixels
+    pub selection_x: f32,
+    /// The text being composed with an input method, shown at the cursor
+    pub composition: String,
+    /// The width of the [`composition`](Self::composition) in pixels
+    pub composition_width: f32,
     pub cursor_visible: bool,
     pub cursor_last_update: Instant,
     pub current_value: String,
@@ -54,6 +67,10 @@ impl Default for TextBoxState {
             graphemes: Default::default(),
             cursor_x: 0.0,
             cursor_position: Default::default(),
+            selection_anchor: None,
+            selection_x: 0.0,
+            composition: String::new(),
+            composition_width: 0.0,
             cursor_visible: Default::default(),
             cursor_last_update: Instant::now(),
             current_value: String::new(),
@@ -61,6 +78,43 @@ impl Default for TextBoxState {
     }
 }
 
+impl TextBoxState {
+    /// Returns the selected range of graphemes, if it isn't empty
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.selection_anchor?;
+        if anchor == self.cursor_position {
+            return None;
+        }
+        Some((
+            anchor.min(self.cursor_position),
+            anchor.max(self.cursor_position),
+        ))
+    }
+
+    /// Returns the selected text, if any
+    pub fn selected_text(&self) -> Option<String> {
+        self.selection()
+            .map(|(start, end)| self.graphemes[start..end].join(""))
+    }
+
+    /// Removes the selected text from the current value
+    ///
+    /// Returns true if anything was removed.
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.selection_anchor = None;
+        if let Some((start, end)) = selection {
+            let byte_start: usize = self.graphemes[0..start].iter().map(|g| g.len()).sum();
+            let byte_end: usize = self.graphemes[0..end].iter().map(|g| g.len()).sum();
+            self.current_value.replace_range(byte_start..byte_end, "");
+            self.cursor_position = start;
+            true
+        } else {
+            false
+        }
+    }
+}
+
 pub struct TextBoxValue(pub String);
 
 impl Widget for TextBoxProps {}
@@ -69,6 +123,13 @@ impl Widget for TextBoxProps {}
 /// A text box allows users to input text.
 /// This text box is fairly simple and only supports basic input.
 ///
+/// Text can be selected with Shift and the arrow keys, or all at once with Ctrl+A. The
+/// selection can be copied and cut with Ctrl+C and Ctrl+X, and text pasted with Ctrl+V,
+/// using the [`Clipboard`].
+///
+/// Text composed with an input method (IME) is shown underlined at the cursor and only added to
+/// the value once the composition is finalized.
+///
 #[derive(Bundle)]
 pub struct TextBoxBundle {
     pub text_box: TextBoxProps,
@@ -139,6 +200,7 @@ pub fn text_box_render(
                 set_graphemes(&mut state, &font_assets, &font_mapping, &style_font);
 
                 state.cursor_position = state.graphemes.len();
+                state.selection_anchor = None;
 
                 set_new_cursor_position(&mut state, &font_assets, &font_mapping, &style_font);
             }
@@ -185,11 +247,31 @@ pub fn text_box_render(
                       mut event: ResMut<KEvent>,
                       font_assets: Res<Assets<KayakFont>>,
                       font_mapping: Res<FontMapping>,
+                      mut clipboard: ResMut<Clipboard>,
+                      ime_composition: Res<ImeComposition>,
                       mut state_query: Query<&mut TextBoxState>| {
                     match event.event_type {
                         EventType::KeyDown(key_event) => {
+                            let is_shortcut =
+                                key_event.is_ctrl_pressed() || key_event.is_meta_pressed();
+                            if key_event.key() == KeyCode::A && is_shortcut {
+                                if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                    state.selection_anchor = Some(0);
+                                    state.cursor_position = state.graphemes.len();
+                                    set_new_cursor_position(
+                                        &mut state,
+                                        &font_assets,
+                                        &font_mapping,
+                                        &style_font,
+                                    );
+                                }
+                            }
                             if key_event.key() == KeyCode::Right {
                                 if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                    update_selection_anchor(
+                                        &mut state,
+                                        key_event.is_shift_pressed(),
+                                    );
                                     if state.cursor_position < state.graphemes.len() {
                                         state.cursor_position += 1;
                                     }
@@ -203,6 +285,10 @@ pub fn text_box_render(
                             }
                             if key_event.key() == KeyCode::Left {
                                 if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                    update_selection_anchor(
+                                        &mut state,
+                                        key_event.is_shift_pressed(),
+                                    );
                                     if state.cursor_position > 0 {
                                         state.cursor_position -= 1;
                                     }
@@ -221,9 +307,13 @@ pub fn text_box_render(
                                 if !state.focused {
                                     return;
                                 }
-                                let cursor_pos = state.cursor_position;
                                 if is_backspace(c) {
-                                    if !state.current_value.is_empty() {
+                                    let cursor_pos = state.cursor_position;
+                                    // Removes the selection instead of a character, if any
+                                    if !state.delete_selection()
+                                        && !state.current_value.is_empty()
+                                        && cursor_pos > 0
+                                    {
                                         let char_pos: usize = state.graphemes[0..cursor_pos - 1]
                                             .iter()
                                             .map(|g| g.len())
@@ -232,6 +322,16 @@ pub fn text_box_render(
                                         state.cursor_position -= 1;
                                     }
                                 } else if !c.is_control() {
+                                    // Typing replaces the selection
+                                    if state.delete_selection() {
+                                        set_graphemes(
+                                            &mut state,
+                                            &font_assets,
+                                            &font_mapping,
+                                            &style_font,
+                                        );
+                                    }
+                                    let cursor_pos = state.cursor_position;
                                     let char_pos: usize = state.graphemes[0..cursor_pos]
                                         .iter()
                                         .map(|g| g.len())
@@ -254,9 +354,57 @@ pub fn text_box_render(
                                 event.add_system(cloned_on_change);
                             }
                         }
+                        EventType::Copy => {
+                            if let Ok(state) = state_query.get(state_entity) {
+                                if let Some(text) = state.selected_text() {
+                                    clipboard.set_text(text);
+                                }
+                            }
+                        }
+                        EventType::Cut => {
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                if !state.focused {
+                                    return;
+                                }
+                                if let Some(text) = state.selected_text() {
+                                    clipboard.set_text(text);
+                                    state.delete_selection();
+                                    set_graphemes(
+                                        &mut state,
+                                        &font_assets,
+                                        &font_mapping,
+                                        &style_font,
+                                    );
+                                    set_new_cursor_position(
+                                        &mut state,
+                                        &font_assets,
+                                        &font_mapping,
+                                        &style_font,
+                                    );
+                                    let cloned_on_change = cloned_on_change.clone();
+                                    cloned_on_change.set_value(state.current_value.clone());
+                                    event.add_system(cloned_on_change);
+                                }
+                            }
+                        }
+                        EventType::ImePreedit => {
+                            if let Ok(mut state) = state_query.get_mut(state_entity) {
+                                if !state.focused {
+                                    return;
+                                }
+                                state.composition = ime_composition.value.clone();
+                                state.composition_width = measure_text(
+                                    &font_assets,
+                                    &font_mapping,
+                                    &style_font,
+                                    &state.composition,
+                                );
+                            }
+                        }
                         EventType::Focus => {
                             if let Ok(mut state) = state_query.get_mut(state_entity) {
                                 state.focused = true;
+                                state.selection_anchor = None;
                                 // Update graphemes
                                 set_graphemes(&mut state, &font_assets, &font_mapping, &style_font);
 
@@ -273,6 +421,8 @@ pub fn text_box_render(
                         EventType::Blur => {
                             if let Ok(mut state) = state_query.get_mut(state_entity) {
                                 state.focused = false;
+                                state.selection_anchor = None;
+                                state.composition.clear();
                             }
                         }
                         _ => {}
@@ -281,7 +431,10 @@ pub fn text_box_render(
             );
 
             let cursor_styles = KStyle {
-                background_color: Color::rgba(0.933, 0.745, 0.745, 1.0).into(),
+                background_color: styles
+                    .caret_color
+                    .resolve_or(Color::rgba(0.933, 0.745, 0.745, 1.0))
+                    .into(),
                 position_type: KPositionType::SelfDirected.into(),
                 top: Units::Pixels(5.0).into(),
                 left: Units::Pixels(state.cursor_x).into(),
@@ -290,6 +443,45 @@ pub fn text_box_render(
                 ..Default::default()
             };
 
+            let selection_styles = KStyle {
+                background_color: styles
+                    .selection_color
+                    .resolve_or(Color::rgba(0.360, 0.380, 0.474, 0.5))
+                    .into(),
+                position_type: KPositionType::SelfDirected.into(),
+                top: Units::Pixels(5.0).into(),
+                left: Units::Pixels(state.cursor_x.min(state.selection_x)).into(),
+                width: Units::Pixels((state.cursor_x - state.selection_x).abs()).into(),
+                height: Units::Pixels(26.0 - 10.0).into(),
+                ..Default::default()
+            };
+
+            let composition_styles = KStyle {
+                background_color: styles
+                    .caret_color
+                    .resolve_or(Color::rgba(0.933, 0.745, 0.745, 1.0))
+                    .into(),
+                position_type: KPositionType::SelfDirected.into(),
+                top: Units::Pixels(26.0 - 6.0).into(),
+                left: Units::Pixels(state.cursor_x).into(),
+                width: Units::Pixels(state.composition_width).into(),
+                height: Units::Pixels(1.0).into(),
+                ..Default::default()
+            };
+
+            // The composed text is shown at the cursor without being part of the value yet.
+            let content = if state.composition.is_empty() {
+                text_box.value.clone()
+            } else {
+                let position = state.cursor_position.min(state.graphemes.len());
+                format!(
+                    "{}{}{}",
+                    state.graphemes[0..position].join(""),
+                    state.composition,
+                    state.graphemes[position..].join("")
+                )
+            };
+
             let text_styles = KStyle {
                 top: Units::Stretch(1.0).into(),
                 bottom: Units::Stretch(1.0).into(),
@@ -297,14 +489,12 @@ pub fn text_box_render(
             };
 
             let shift = if let Some(layout) = widget_context.get_layout(entity) {
-                let font_handle = match &styles.font {
-                    StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
-                    _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
-                };
-                if let Some(font) = font_assets.get(&font_handle) {
+                let fonts = get_fonts(&font_assets, &font_mapping, &styles.font);
+                if !fonts.is_empty() {
                     let string_to_cursor = state.graphemes[0..state.cursor_position].join("");
-                    let measurement = font.measure(
+                    let measurement = KayakFont::measure_with_fallbacks(
                         &string_to_cursor,
+                        &fonts,
                         TextProperties {
                             font_size: 14.0,
                             line_height: 18.0,
@@ -345,10 +535,17 @@ pub fn text_box_render(
                         ..Default::default()
                     }}>
                         <ElementBundle styles={scroll_styles}>
+                            {
+                                if state.focused && state.selection().is_some() {
+                                    constructor! {
+                                        <BackgroundBundle styles={selection_styles} />
+                                    }
+                                }
+                            }
                             <TextWidgetBundle
                                 styles={text_styles}
                                 text={TextProps {
-                                    content: text_box.value.clone(),
+                                    content,
                                     size: 14.0,
                                     line_height: Some(18.0),
                                     word_wrap: false,
@@ -356,7 +553,17 @@ pub fn text_box_render(
                                 }}
                             />
                             {
-                                if state.focused && state.cursor_visible {
+                                if state.focused && !state.composition.is_empty() {
+                                    constructor! {
+                                        <BackgroundBundle styles={composition_styles} />
+                                    }
+                                }
+                            }
+                            {
+                                if state.focused
+                                    && state.cursor_visible
+                                    && state.composition.is_empty()
+                                {
                                     constructor! {
                                         <BackgroundBundle styles={cursor_styles} />
                                     }
@@ -379,18 +586,50 @@ fn is_backspace(c: char) -> bool {
     c == '\u{8}' || c == '\u{7f}'
 }
 
+/// Starts a selection at the cursor when extending, or clears it otherwise
+fn update_selection_anchor(state: &mut TextBoxState, extend: bool) {
+    if !extend {
+        state.selection_anchor = None;
+    } else if state.selection_anchor.is_none() {
+        state.selection_anchor = Some(state.cursor_position);
+    }
+}
+
+/// Gets the loaded fonts of the text box's font stack, in order of preference
+///
+/// Returns nothing until the first font of the stack is loaded, since the text is measured
+/// with it.
+fn get_fonts<'a>(
+    font_assets: &'a Assets<KayakFont>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) -> Vec<&'a KayakFont> {
+    let font = match style_font {
+        StyleProp::Value(font) => font.clone(),
+        _ => DEFAULT_FONT.into(),
+    };
+    let font_handles = font_mapping.get_fallback_handles(font);
+
+    let Some(first_handle) = font_handles.first() else {
+        return Vec::new();
+    };
+    if !font_assets.contains(first_handle) {
+        return Vec::new();
+    }
+
+    font_handles
+        .iter()
+        .filter_map(|handle| font_assets.get(handle))
+        .collect()
+}
+
 fn set_graphemes(
     state: &mut TextBoxState,
     font_assets: &Res<Assets<KayakFont>>,
     font_mapping: &FontMapping,
     style_font: &StyleProp<String>,
 ) {
-    let font_handle = match style_font {
-        StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
-        _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
-    };
-
-    if let Some(font) = font_assets.get(&font_handle) {
+    if let Some(font) = get_fonts(font_assets, font_mapping, style_font).first() {
         state.graphemes = font
             .get_graphemes(&state.current_value)
             .iter()
@@ -405,12 +644,7 @@ fn get_single_grapheme_length(
     style_font: &StyleProp<String>,
     text: &str,
 ) -> usize {
-    let font_handle = match style_font {
-        StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
-        _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
-    };
-
-    if let Some(font) = font_assets.get(&font_handle) {
+    if let Some(font) = get_fonts(font_assets, font_mapping, style_font).first() {
         let graphemes = font.get_graphemes(text);
         return graphemes[0].len();
     }
@@ -418,21 +652,17 @@ fn get_single_grapheme_length(
     0
 }
 
-fn set_new_cursor_position(
-    state: &mut TextBoxState,
+fn measure_text(
     font_assets: &Res<Assets<KayakFont>>,
     font_mapping: &FontMapping,
     style_font: &StyleProp<String>,
-) {
-    let font_handle = match style_font {
-        StyleProp::Value(font) => font_mapping.get_handle(font.clone()).unwrap(),
-        _ => font_mapping.get_handle(DEFAULT_FONT.into()).unwrap(),
-    };
-
-    if let Some(font) = font_assets.get(&font_handle) {
-        let string_to_cursor = state.graphemes[0..state.cursor_position].join("");
-        let measurement = font.measure(
-            &string_to_cursor,
+    text: &str,
+) -> f32 {
+    let fonts = get_fonts(font_assets, font_mapping, style_font);
+    if !fonts.is_empty() {
+        KayakFont::measure_with_fallbacks(
+            text,
+            &fonts,
             TextProperties {
                 font_size: 14.0,
                 line_height: 18.0,
@@ -440,9 +670,43 @@ fn set_new_cursor_position(
                 alignment: kayak_font::Alignment::Start,
                 tab_size: 4,
             },
-        );
+        )
+        .size()
+        .0
+    } else {
+        0.0
+    }
+}
 
-        state.cursor_x = measurement.size().0;
+fn set_new_cursor_position(
+    state: &mut TextBoxState,
+    font_assets: &Res<Assets<KayakFont>>,
+    font_mapping: &FontMapping,
+    style_font: &StyleProp<String>,
+) {
+    let fonts = get_fonts(font_assets, font_mapping, style_font);
+    if !fonts.is_empty() {
+        let measure = |position: usize| {
+            let string_to_position = state.graphemes[0..position].join("");
+            KayakFont::measure_with_fallbacks(
+                &string_to_position,
+                &fonts,
+                TextProperties {
+                    font_size: 14.0,
+                    line_height: 18.0,
+                    max_size: (10000.0, 18.0),
+                    alignment: kayak_font::Alignment::Start,
+                    tab_size: 4,
+                },
+            )
+            .size()
+            .0
+        };
+
+        let cursor_x = measure(state.cursor_position);
+        let selection_x = state.selection_anchor.map(measure).unwrap_or(cursor_x);
+        state.cursor_x = cursor_x;
+        state.selection_x = selection_x;
     }
 }
 
@@ -464,3 +728,65 @@ pub fn cursor_animation_system(
         }
     }
 }
+
+/// Enables the input method editor (IME) while a text box is focused
+///
+/// The IME candidate window is placed right below the caret of the focused text box.
+/// The IME is only switched on or off when a text box gains or loses focus, so other systems
+/// are free to enable it while no text box is focused.
+pub fn text_box_ime_system(
+    roots: Query<&KayakRootContext>,
+    text_box_query: Query<(), With<TextBoxProps>>,
+    state_query: Query<&TextBoxState>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut previous_focus: Local<Option<Entity>>,
+) {
+    let mut focused_text_box = None;
+    let mut caret = None;
+    for context in roots.iter() {
+        if let Some(focused) = context.focus_tree.current() {
+            if !text_box_query.contains(focused) {
+                continue;
+            }
+            let state = context
+                .widget_state
+                .get(focused)
+                .and_then(|state_entity| state_query.get(state_entity).ok());
+            let layout = context
+                .layout_cache
+                .try_read()
+                .ok()
+                .and_then(|cache| cache.rect.get(&WrappedIndex(focused)).cloned());
+            if let (Some(state), Some(layout)) = (state, layout) {
+                if state.focused {
+                    focused_text_box = Some(focused);
+                    // Matches the scrolling applied when rendering, which keeps the caret in view.
+                    let caret_x = if state.cursor_x > layout.width {
+                        layout.width - 20.0
+                    } else {
+                        state.cursor_x
+                    };
+                    caret = Some(Vec2::new(
+                        layout.posx + 5.0 + caret_x,
+                        layout.posy + layout.height,
+                    ));
+                }
+            }
+        }
+    }
+
+    if let Ok(mut window) = windows.get_single_mut() {
+        if *previous_focus != focused_text_box {
+            let ime_enabled = focused_text_box.is_some();
+            if window.ime_enabled != ime_enabled {
+                window.ime_enabled = ime_enabled;
+            }
+            *previous_focus = focused_text_box;
+        }
+        if let Some(caret) = caret {
+            if window.ime_position != caret {
+                window.ime_position = caret;
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use bevy::prelude::*;
+use kayak_font::{KayakFont, TextProperties};
+use kayak_ui_macros::{constructor, rsx};
+
+use crate::{
+    children::KChildren,
+    context::{KayakRootContext, WidgetName},
+    event_dispatcher::EventDispatcher,
+    prelude::KayakWidgetContext,
+    render::font::FontMapping,
+    styles::{
+        ComputedStyles, Corner, Edge, KPositionType, KStyle, RenderCommand, StyleProp, Units,
+    },
+    widget::Widget,
+    widgets::{BackgroundBundle, ElementBundle, TextProps, TextWidgetBundle},
+    KayakUiScale, WindowSize, DEFAULT_FONT,
+};
+
+/// The z-index of a visible tooltip, keeping it above the rest of the UI
+const TOOLTIP_Z_INDEX: i32 = 200_000;
+/// How far the cursor may move, in pixels, before a tooltip is hidden again
+const MOVE_THRESHOLD: f32 = 6.0;
+/// The offset of the tooltip from the cursor
+const CURSOR_OFFSET: Vec2 = Vec2::new(12.0, 16.0);
+const FONT_SIZE: f32 = 14.0;
+const LINE_HEIGHT: f32 = 18.0;
+const PADDING: Vec2 = Vec2::new(8.0, 4.0);
+
+/// Context data provided by a [`TooltipProviderBundle`]
+///
+/// Describes the tooltip shown by the provider, if any.
+#[derive(Component, Default, Debug, Clone, PartialEq)]
+pub struct TooltipContext {
+    /// The [`TooltipBundle`] widget currently under the cursor
+    pub target: Option<Entity>,
+    /// The text of the tooltip
+    pub text: String,
+    /// The cursor position when the hover started
+    pub anchor: Vec2,
+    /// The time the hover started, in seconds since startup
+    pub hover_start: f64,
+    /// Whether the tooltip is currently shown
+    pub visible: bool,
+}
+
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq)]
+pub struct TooltipProvider;
+
+impl Widget for TooltipProvider {}
+
+/// Draws the tooltips of every [`TooltipBundle`] within it
+///
+/// The provider should usually wrap the whole UI, so that tooltips are drawn above everything
+/// else. Tooltips are placed next to the cursor and kept within the window.
+#[derive(Bundle, Debug, Clone, PartialEq)]
+pub struct TooltipProviderBundle {
+    pub context_provider: TooltipProvider,
+    pub children: KChildren,
+    pub computed_styles: ComputedStyles,
+    pub widget_name: WidgetName,
+}
+
+impl Default for TooltipProviderBundle {
+    fn default() -> Self {
+        Self {
+            context_provider: Default::default(),
+            children: Default::default(),
+            computed_styles: ComputedStyles(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                height: Units::Stretch(1.0).into(),
+                width: Units::Stretch(1.0).into(),
+                ..KStyle::default()
+            }),
+            widget_name: TooltipProvider::default().get_name(),
+        }
+    }
+}
+
+pub fn tooltip_provider_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    children_query: Query<&KChildren>,
+    context_query: Query<&TooltipContext>,
+    font_assets: Res<Assets<KayakFont>>,
+    font_mapping: Res<FontMapping>,
+    window_size: Res<WindowSize>,
+    ui_scale: Res<KayakUiScale>,
+    camera: Query<&Camera>,
+) -> bool {
+    if let Ok(passed_children) = children_query.get(entity) {
+        let context_entity = if let Some(context_entity) =
+            widget_context.get_context_entity::<TooltipContext>(entity)
+        {
+            context_entity
+        } else {
+            commands
+                .spawn(TooltipContext::default())
+                .set_parent(entity)
+                .id()
+        };
+        widget_context.set_context_entity::<TooltipContext>(Some(entity), context_entity);
+
+        let tooltip = context_query
+            .get(context_entity)
+            .cloned()
+            .unwrap_or_default();
+
+        let label_styles = if tooltip.visible {
+            let text_width = font_mapping
+                .get_handle(DEFAULT_FONT.into())
+                .and_then(|handle| font_assets.get(&handle))
+                .map(|font| {
+                    font.measure(
+                        &tooltip.text,
+                        TextProperties {
+                            font_size: FONT_SIZE,
+                            line_height: LINE_HEIGHT,
+                            max_size: (10000.0, LINE_HEIGHT),
+                            ..Default::default()
+                        },
+                    )
+                    .size()
+                    .0
+                })
+                .unwrap_or_default();
+            let size = Vec2::new(text_width, LINE_HEIGHT) + PADDING * 2.0;
+
+            // Prefer the bottom right of the cursor, flipping to the other side when the
+            // tooltip would leave the camera's viewport.
+            let viewport_size = widget_context
+                .camera_entity
+                .and_then(|camera_entity| camera.get(camera_entity).ok())
+                .and_then(|camera| camera.logical_viewport_size())
+                .unwrap_or(Vec2::new(window_size.0, window_size.1))
+                / ui_scale.get();
+            let mut position = tooltip.anchor + CURSOR_OFFSET;
+            if position.x + size.x > viewport_size.x {
+                position.x = tooltip.anchor.x - CURSOR_OFFSET.x - size.x;
+            }
+            if position.y + size.y > viewport_size.y {
+                position.y = tooltip.anchor.y - CURSOR_OFFSET.y - size.y;
+            }
+            let position = position.min(viewport_size - size).max(Vec2::ZERO);
+
+            // The tooltip is positioned relative to the provider.
+            let layout = widget_context.get_layout(entity).unwrap_or_default();
+            KStyle {
+                render_command: StyleProp::Value(RenderCommand::Quad),
+                background_color: Color::rgba(0.133, 0.145, 0.2, 0.95).into(),
+                border: Edge::all(1.0).into(),
+                border_color: Color::rgba(0.360, 0.380, 0.474, 1.0).into(),
+                border_radius: Corner::all(3.0).into(),
+                position_type: KPositionType::SelfDirected.into(),
+                left: Units::Pixels(position.x - layout.posx).into(),
+                top: Units::Pixels(position.y - layout.posy).into(),
+                width: Units::Pixels(size.x).into(),
+                height: Units::Pixels(size.y).into(),
+                padding: Edge::axis(Units::Pixels(PADDING.y), Units::Pixels(PADDING.x)).into(),
+                z_index: TOOLTIP_Z_INDEX.into(),
+                render_layer: 1.into(),
+                ..Default::default()
+            }
+        } else {
+            KStyle::default()
+        };
+
+        let parent_id = Some(entity);
+        rsx! {
+            <ElementBundle
+                styles={KStyle {
+                    width: Units::Stretch(1.0).into(),
+                    height: Units::Stretch(1.0).into(),
+                    ..Default::default()
+                }}
+            >
+                <ElementBundle
+                    styles={KStyle {
+                        width: Units::Stretch(1.0).into(),
+                        height: Units::Stretch(1.0).into(),
+                        ..Default::default()
+                    }}
+                    children={passed_children.clone()}
+                />
+                {
+                    if tooltip.visible {
+                        constructor! {
+                            <BackgroundBundle styles={label_styles}>
+                                <TextWidgetBundle
+                                    text={TextProps {
+                                        content: tooltip.text.clone(),
+                                        size: FONT_SIZE,
+                                        line_height: Some(LINE_HEIGHT),
+                                        word_wrap: false,
+                                        ..Default::default()
+                                    }}
+                                />
+                            </BackgroundBundle>
+                        }
+                    }
+                }
+            </ElementBundle>
+        };
+    }
+
+    true
+}
+
+/// Props used by the [`TooltipBundle`] widget
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct TooltipProps {
+    /// The text shown in the tooltip
+    pub text: String,
+    /// How long the cursor has to rest on the widget before the tooltip appears, in seconds
+    pub delay: f32,
+}
+
+impl Default for TooltipProps {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            delay: 0.5,
+        }
+    }
+}
+
+impl Widget for TooltipProps {}
+
+/// Shows a tooltip while the cursor rests on its children
+///
+/// The tooltip appears once the cursor has stayed still over the children for
+/// [`TooltipProps::delay`] seconds, and disappears when the cursor leaves them or moves away.
+/// It's drawn by the closest [`TooltipProviderBundle`] above this widget.
+#[derive(Bundle, Debug, Clone, PartialEq)]
+pub struct TooltipBundle {
+    pub tooltip: TooltipProps,
+    pub styles: KStyle,
+    pub computed_styles: ComputedStyles,
+    pub children: KChildren,
+    pub widget_name: WidgetName,
+}
+
+impl Default for TooltipBundle {
+    fn default() -> Self {
+        Self {
+            tooltip: Default::default(),
+            styles: Default::default(),
+            computed_styles: Default::default(),
+            children: Default::default(),
+            widget_name: TooltipProps::default().get_name(),
+        }
+    }
+}
+
+pub fn tooltip_render(
+    In(entity): In<Entity>,
+    widget_context: Res<KayakWidgetContext>,
+    mut commands: Commands,
+    mut query: Query<(&KStyle, &mut ComputedStyles, &KChildren)>,
+) -> bool {
+    if let Ok((styles, mut computed_styles, children)) = query.get_mut(entity) {
+        *computed_styles = KStyle::default()
+            .with_style(KStyle {
+                render_command: RenderCommand::Layout.into(),
+                width: Units::Stretch(1.0).into(),
+                height: Units::Auto.into(),
+                ..Default::default()
+            })
+            .with_style(styles)
+            .into();
+        children.process(&widget_context, &mut commands, Some(entity));
+    }
+
+    true
+}
+
+/// Shows and hides tooltips based on what is under the cursor
+pub fn update_tooltips(
+    time: Res<Time>,
+    roots: Query<(&KayakRootContext, &EventDispatcher)>,
+    tooltip_query: Query<&TooltipProps>,
+    provider_query: Query<(), With<TooltipProvider>>,
+    mut context_query: Query<(Entity, &mut TooltipContext)>,
+) {
+    // Finds the innermost tooltip under the cursor along with the context of its provider.
+    let mut hovered = Vec::new();
+    for (context, event_dispatcher) in roots.iter() {
+        if let Ok(tree) = context.tree.try_read() {
+            let mut target = None;
+            let mut node = event_dispatcher.pointer_over();
+            while let Some(index) = node {
+                if target.is_none() && tooltip_query.contains(index.0) {
+                    target = Some(index.0);
+                } else if target.is_some() && provider_query.contains(index.0) {
+                    if let Some(context_entity) = context
+                        .context_entities
+                        .get_context_entity::<TooltipContext>(Some(index.0))
+                    {
+                        let (x, y) = event_dispatcher.current_mouse_position();
+                        hovered.push((context_entity, target.unwrap(), Vec2::new(x, y)));
+                    }
+                    break;
+                }
+                node = tree.get_parent(index);
+            }
+        }
+    }
+
+    let now = time.elapsed_seconds_f64();
+    for (context_entity, mut context) in context_query.iter_mut() {
+        let entry = hovered
+            .iter()
+            .find(|(hovered_context, ..)| *hovered_context == context_entity);
+        if let Some((_, target, cursor)) = entry {
+            if let Ok(tooltip) = tooltip_query.get(*target) {
+                if context.target != Some(*target)
+                    || context.anchor.distance(*cursor) > MOVE_THRESHOLD
+                {
+                    // Restart the delay whenever the cursor moves on.
+                    *context = TooltipContext {
+                        target: Some(*target),
+                        text: tooltip.text.clone(),
+                        anchor: *cursor,
+                        hover_start: now,
+                        visible: false,
+                    };
+                } else if !context.visible && now - context.hover_start >= tooltip.delay as f64 {
+                    context.visible = true;
+                }
+            }
+        } else if context.target.is_some() {
+            *context = TooltipContext::default();
+        }
+    }
+}
@@ -11,8 +11,9 @@ use crate::{
     widget::Widget,
 };
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Reflect, Debug, Default, Copy, Clone, PartialEq)]
 pub enum TransitionEasing {
+    #[default]
     Linear,
     QuadraticIn,
     QuadraticOut,
@@ -47,6 +48,16 @@ pub enum TransitionEasing {
 }
 
 impl TransitionEasing {
+    /// Applies the easing to the interpolation factor `x`, from `0.0` to `1.0`
+    pub fn ease(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        if let Some(easing) = self.try_into_easing_function() {
+            Ease::calc(x, easing)
+        } else {
+            x
+        }
+    }
+
     fn try_into_easing_function(&self) -> Option<EaseFunction> {
         match self {
             TransitionEasing::QuadraticIn => Some(EaseFunction::QuadraticIn),
@@ -127,11 +138,7 @@ impl Transition {
         let elapsed_time = self.start.elapsed().as_secs_f32() * 1000.0; // as Milliseconds
                                                                         // dbg!(elapsed_time, self.timeout, self.reversing, self.playing);
         if (elapsed_time < self.timeout) && self.playing {
-            let mut x = if let Some(easing) = self.easing.try_into_easing_function() {
-                Ease::calc((elapsed_time / self.timeout).clamp(0.0, 1.0), easing)
-            } else {
-                (elapsed_time / self.timeout).clamp(0.0, 1.0)
-            };
+            let mut x = self.easing.ease(elapsed_time / self.timeout);
             if self.reversing {
                 x = 1.0 - x;
             }
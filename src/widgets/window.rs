@@ -1,19 +1,22 @@
 use bevy::{
-    prelude::{Bundle, Color, Commands, Component, Entity, In, Query, Res, ResMut, Vec2},
+    prelude::{
+        Bundle, Color, Commands, Component, Entity, Event, EventWriter, In, Query, Res, ResMut,
+        Vec2,
+    },
     window::CursorIcon,
 };
-use kayak_ui_macros::rsx;
+use kayak_ui_macros::{constructor, rsx};
 
 use crate::{
     children::KChildren,
     context::WidgetName,
-    event::{EventType, KEvent},
-    event_dispatcher::EventDispatcherContext,
+    cursor::Draggable,
+    event::{EventCategory, EventType, KEvent},
     on_event::OnEvent,
     prelude::KayakWidgetContext,
     styles::{
-        BoxShadow, ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, RenderCommand,
-        StyleProp, Units,
+        BoxShadow, ComputedStyles, Corner, Edge, KCursorIcon, KPositionType, KStyle, LayoutType,
+        RenderCommand, StyleProp, Units,
     },
     widget::Widget,
     Focusable,
@@ -24,17 +27,45 @@ use super::{
     clip::ClipBundle,
     text::{TextProps, TextWidgetBundle},
     window_context_provider::WindowContext,
-    ElementBundle,
+    ElementBundle, KSvg, KSvgBundle, Svg, CLOSE_HANDLE, MAXIMIZE_HANDLE, MINIMIZE_HANDLE,
 };
 
+/// The thickness of the grips along the edges of a resizable window
+const RESIZE_GRIP_SIZE: f32 = 6.0;
+/// The smallest size a resizable window can be given when it has no `min_size`
+const DEFAULT_MIN_SIZE: Vec2 = Vec2::new(100.0, 50.0);
+/// The height of the title bar
+const TITLE_BAR_HEIGHT: f32 = 24.0;
+/// The size of the buttons in the title bar
+const TITLE_BUTTON_SIZE: f32 = 16.0;
+/// The space between the buttons in the title bar
+const TITLE_BUTTON_SPACING: f32 = 4.0;
+
 #[derive(Component, PartialEq, Clone, Debug, Default)]
 pub struct KWindow {
     /// If true, allows the window to be draggable by its title bar
     pub draggable: bool,
+    /// If true, allows the window to be resized by dragging its right and bottom edges
+    pub resizable: bool,
     /// The initial position at which to display the window in pixels
     pub initial_position: Vec2,
     /// The size of the window in pixels
+    ///
+    /// For resizable windows, this is the initial size.
     pub size: Vec2,
+    /// The smallest size a resizable window can be resized to
+    pub min_size: Option<Vec2>,
+    /// The largest size a resizable window can be resized to
+    pub max_size: Option<Vec2>,
+    /// If true, shows a button in the title bar that closes the window
+    ///
+    /// Closing a window hides it and sends a [`WindowClosed`] event. It can be shown again by
+    /// clearing [`KWindowState::closed`], or despawned in response to the event.
+    pub closable: bool,
+    /// If true, shows a button in the title bar that collapses the window to its title bar
+    pub minimizable: bool,
+    /// If true, shows a button in the title bar that makes the window fill its parent
+    pub maximizable: bool,
     /// The text to display in the window's title bar
     pub title: String,
     /// Styles for the main window quad.
@@ -49,6 +80,31 @@ pub struct KWindowState {
     pub offset: Vec2,
     pub position: Vec2,
     pub focused: bool,
+    /// The current size of a resizable window
+    pub size: Vec2,
+    /// The size of the window and the cursor position when a resize started, if resizing
+    pub resize_start: Option<(Vec2, Vec2)>,
+    /// Whether the window has been closed and is hidden
+    pub closed: bool,
+    /// Whether the window is collapsed to its title bar
+    pub minimized: bool,
+    /// Whether the window fills its parent
+    pub maximized: bool,
+}
+
+/// An event sent when the user closes a window with its close button
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowClosed {
+    /// The window widget entity
+    pub entity: Entity,
+}
+
+/// A button in the title bar of a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TitleButton {
+    Close,
+    Minimize,
+    Maximize,
 }
 
 impl Widget for KWindow {}
@@ -123,11 +179,70 @@ pub fn window_render(
                 offset: Vec2::ZERO,
                 is_dragging: false,
                 focused: false,
+                size: window.size,
+                resize_start: None,
+                closed: false,
+                minimized: false,
+                maximized: false,
             },
         );
 
         if let Ok(state) = state_query.get(state_entity) {
             let parent_id = Some(window_entity);
+            let size = if window.resizable {
+                state.size
+            } else {
+                window.size
+            };
+            let min_size = window.min_size.unwrap_or(DEFAULT_MIN_SIZE);
+            let max_size = window
+                .max_size
+                .unwrap_or(Vec2::splat(f32::MAX))
+                .max(min_size);
+
+            if state.closed {
+                return true;
+            }
+
+            // A maximized window fills its parent, a minimized one only shows its title bar
+            let (left, top, width, height) = if state.maximized {
+                (
+                    Units::Pixels(0.0),
+                    Units::Pixels(0.0),
+                    Units::Stretch(1.0),
+                    Units::Stretch(1.0),
+                )
+            } else if state.minimized {
+                // The title bar and the window's border
+                let height = TITLE_BAR_HEIGHT + 4.0;
+                (
+                    Units::Pixels(state.position.x),
+                    Units::Pixels(state.position.y),
+                    Units::Pixels(size.x),
+                    Units::Pixels(height),
+                )
+            } else {
+                (
+                    Units::Pixels(state.position.x),
+                    Units::Pixels(state.position.y),
+                    Units::Pixels(size.x),
+                    Units::Pixels(size.y),
+                )
+            };
+            let (min_width, min_height) = if state.maximized {
+                (StyleProp::Default, StyleProp::Default)
+            } else {
+                (StyleProp::Value(width), StyleProp::Value(height))
+            };
+            let title_buttons = [
+                (window.minimizable, TitleButton::Minimize, MINIMIZE_HANDLE),
+                (window.maximizable, TitleButton::Maximize, MAXIMIZE_HANDLE),
+                (window.closable, TitleButton::Close, CLOSE_HANDLE),
+            ]
+            .into_iter()
+            .filter(|(enabled, ..)| *enabled)
+            .map(|(_, button, handle)| (button, handle))
+            .collect::<Vec<_>>();
 
             let focus_event = OnEvent::new(
                 move |In(_entity): In<Entity>,
@@ -135,7 +250,11 @@ pub fn window_render(
                       mut query: Query<&mut KWindowState>,
                       mut context_query: Query<&mut WindowContext>| {
                     if let Ok(mut window) = query.get_mut(state_entity) {
-                        event.stop_propagation();
+                        // Keyboard input keeps bubbling up, so shortcuts handled around the
+                        // window still work while one of its widgets is focused
+                        if event.event_type.event_category() != EventCategory::Keyboard {
+                            event.stop_propagation();
+                        }
                         event.prevent_default();
                         match event.event_type {
                             EventType::Focus => {
@@ -168,12 +287,12 @@ pub fn window_render(
                         border_radius: StyleProp::Value(Corner::all(10.0)),
                         render_command: StyleProp::Value(RenderCommand::Quad),
                         position_type: StyleProp::Value(KPositionType::SelfDirected),
-                        left: StyleProp::Value(Units::Pixels(state.position.x)),
-                        top: StyleProp::Value(Units::Pixels(state.position.y)),
-                        width: StyleProp::Value(Units::Pixels(window.size.x)),
-                        height: StyleProp::Value(Units::Pixels(window.size.y)),
-                        min_width: StyleProp::Value(Units::Pixels(window.size.x)),
-                        min_height: StyleProp::Value(Units::Pixels(window.size.y)),
+                        left: StyleProp::Value(left),
+                        top: StyleProp::Value(top),
+                        width: StyleProp::Value(width),
+                        height: StyleProp::Value(height),
+                        min_width,
+                        min_height,
                         box_shadow: vec![BoxShadow {
                             color: Color::rgba(0.0, 0.0, 0.0, 0.5),
                             radius: 8.0,
@@ -192,7 +311,7 @@ pub fn window_render(
                             render_command: StyleProp::Value(RenderCommand::Quad),
                             background_color: StyleProp::Value(Color::rgba(0.188, 0.203, 0.274, 1.0)),
                             border_radius: Corner::all(10.0).into(),
-                            height: StyleProp::Value(Units::Pixels(24.0)),
+                            height: StyleProp::Value(Units::Pixels(TITLE_BAR_HEIGHT)),
                             width: StyleProp::Value(Units::Stretch(1.0)),
                             left: StyleProp::Value(Units::Pixels(0.0)),
                             right: StyleProp::Value(Units::Pixels(0.0)),
@@ -216,6 +335,54 @@ pub fn window_render(
                                 ..Default::default()
                             }}
                         />
+                        {
+                            if !title_buttons.is_empty() {
+                                let count = title_buttons.len() as f32;
+                                constructor! {
+                                    <ElementBundle
+                                        styles={KStyle {
+                                            layout_type: LayoutType::Row.into(),
+                                            position_type: KPositionType::SelfDirected.into(),
+                                            col_between: Units::Pixels(TITLE_BUTTON_SPACING).into(),
+                                            left: Units::Stretch(1.0).into(),
+                                            right: Units::Pixels(TITLE_BUTTON_SPACING).into(),
+                                            top: Units::Stretch(1.0).into(),
+                                            bottom: Units::Stretch(1.0).into(),
+                                            width: Units::Pixels(count * TITLE_BUTTON_SIZE + (count - 1.0) * TITLE_BUTTON_SPACING).into(),
+                                            height: Units::Pixels(TITLE_BUTTON_SIZE).into(),
+                                            ..Default::default()
+                                        }}
+                                    >
+                                        {
+                                            for (button, handle) in title_buttons.iter() {
+                                                let on_event = title_button_event(state_entity, window_entity, *button);
+                                                constructor! {
+                                                    <ElementBundle
+                                                        styles={KStyle {
+                                                            cursor: StyleProp::Value(KCursorIcon(CursorIcon::Hand)),
+                                                            width: Units::Pixels(TITLE_BUTTON_SIZE).into(),
+                                                            height: Units::Pixels(TITLE_BUTTON_SIZE).into(),
+                                                            ..Default::default()
+                                                        }}
+                                                        on_event={on_event}
+                                                    >
+                                                        <KSvgBundle
+                                                            styles={KStyle {
+                                                                background_color: Color::rgba(0.781, 0.789, 0.836, 1.0).into(),
+                                                                width: Units::Stretch(1.0).into(),
+                                                                height: Units::Stretch(1.0).into(),
+                                                                ..Default::default()
+                                                            }}
+                                                            svg={KSvg(handle.clone().typed::<Svg>())}
+                                                        />
+                                                    </ElementBundle>
+                                                }
+                                            }
+                                        }
+                                    </ElementBundle>
+                                }
+                            }
+                        }
                     </BackgroundBundle>
                     {
                         // This code needs to go after the closing tag for the background bundle as that is when the
@@ -224,34 +391,31 @@ pub fn window_render(
                         if window.draggable {
                             commands
                                 .entity(title_bar_entity)
+                                .insert(Draggable)
                                 .insert(OnEvent::new(
-                                    move |In(entity): In<Entity>,
-                                    mut event_dispatcher_context: ResMut<EventDispatcherContext>,
+                                    move |In(_entity): In<Entity>,
                                     mut event: ResMut<KEvent>,
                                         mut query: Query<&mut KWindowState>| {
                                         if let Ok(mut window) = query.get_mut(state_entity) {
                                             event.prevent_default();
                                             event.stop_propagation();
                                             match event.event_type {
-                                                EventType::MouseDown(data) => {
-                                                    event_dispatcher_context.capture_cursor(entity);
+                                                EventType::DragStart(data) => {
                                                     window.is_dragging = true;
-                                                    window.offset = Vec2::new(
-                                                        window.position.x - data.position.0,
-                                                        window.position.y - data.position.1,
+                                                    window.offset = window.position;
+                                                    window.position = Vec2::new(
+                                                        window.offset.x + data.delta.0,
+                                                        window.offset.y + data.delta.1,
                                                     );
                                                 }
-                                                EventType::MouseUp(..) => {
-                                                    event_dispatcher_context.release_cursor(entity);
-                                                    window.is_dragging = false;
+                                                EventType::DragMove(data) => {
+                                                    window.position = Vec2::new(
+                                                        window.offset.x + data.delta.0,
+                                                        window.offset.y + data.delta.1,
+                                                    );
                                                 }
-                                                EventType::Hover(data) => {
-                                                    if window.is_dragging {
-                                                        window.position = Vec2::new(
-                                                            window.offset.x + data.position.0,
-                                                            window.offset.y + data.position.1,
-                                                        );
-                                                    }
+                                                EventType::DragEnd(..) => {
+                                                    window.is_dragging = false;
                                                 }
                                                 _ => {}
                                             }
@@ -260,24 +424,77 @@ pub fn window_render(
                                 ));
                         }
                     }
-                    <BackgroundBundle
-                        styles={KStyle {
-                            background_color: StyleProp::Value(Color::rgba(0.239, 0.258, 0.337, 1.0)),
-                            width: Units::Stretch(1.0).into(),
-                            height: Units::Pixels(2.0).into(),
-                            ..Default::default()
-                        }}
-                    />
-                    <ClipBundle
-                        styles={window.children_styles.clone().with_style(KStyle {
-                            top: Units::Pixels(10.0).into(),
-                            left: Units::Pixels(10.0).into(),
-                            right: Units::Pixels(10.0).into(),
-                            bottom: Units::Pixels(10.0).into(),
-                            ..Default::default()
-                        })}
-                        children={window_children.clone()}
-                    />
+                    {
+                        if !state.minimized {
+                            constructor! {
+                                <BackgroundBundle
+                                    styles={KStyle {
+                                        background_color: StyleProp::Value(Color::rgba(0.239, 0.258, 0.337, 1.0)),
+                                        width: Units::Stretch(1.0).into(),
+                                        height: Units::Pixels(2.0).into(),
+                                        ..Default::default()
+                                    }}
+                                />
+                            }
+                            constructor! {
+                                <ClipBundle
+                                    styles={window.children_styles.clone().with_style(KStyle {
+                                        top: Units::Pixels(10.0).into(),
+                                        left: Units::Pixels(10.0).into(),
+                                        right: Units::Pixels(10.0).into(),
+                                        bottom: Units::Pixels(10.0).into(),
+                                        ..Default::default()
+                                    })}
+                                    children={window_children.clone()}
+                                />
+                            }
+                        }
+                    }
+                    {
+                        // The grips are drawn after the content so they're above it
+                        if window.resizable && !state.minimized && !state.maximized {
+                            let grips = [
+                                // Right edge
+                                (
+                                    Vec2::X,
+                                    Vec2::new(size.x - RESIZE_GRIP_SIZE, 0.0),
+                                    Vec2::new(RESIZE_GRIP_SIZE, size.y),
+                                    CursorIcon::EwResize,
+                                ),
+                                // Bottom edge
+                                (
+                                    Vec2::Y,
+                                    Vec2::new(0.0, size.y - RESIZE_GRIP_SIZE),
+                                    Vec2::new(size.x, RESIZE_GRIP_SIZE),
+                                    CursorIcon::NsResize,
+                                ),
+                                // Bottom right corner
+                                (
+                                    Vec2::ONE,
+                                    size - RESIZE_GRIP_SIZE * 2.0,
+                                    Vec2::splat(RESIZE_GRIP_SIZE * 2.0),
+                                    CursorIcon::NwseResize,
+                                ),
+                            ];
+                            for (axes, position, grip_size, cursor) in grips {
+                                let on_event = resize_grip_event(state_entity, axes, min_size, max_size);
+                                constructor! {
+                                    <ElementBundle
+                                        styles={KStyle {
+                                            cursor: StyleProp::Value(KCursorIcon(cursor)),
+                                            position_type: KPositionType::SelfDirected.into(),
+                                            left: Units::Pixels(position.x).into(),
+                                            top: Units::Pixels(position.y).into(),
+                                            width: Units::Pixels(grip_size.x).into(),
+                                            height: Units::Pixels(grip_size.y).into(),
+                                            ..Default::default()
+                                        }}
+                                        on_event={on_event}
+                                    />
+                                }
+                            }
+                        }
+                    }
                 </ElementBundle>
             };
         }
@@ -285,3 +502,69 @@ pub fn window_render(
 
     true
 }
+
+/// Closes, minimizes or maximizes the window when a title bar button is clicked
+fn title_button_event(state_entity: Entity, window_entity: Entity, button: TitleButton) -> OnEvent {
+    OnEvent::new(
+        move |In(_entity): In<Entity>,
+              mut event: ResMut<KEvent>,
+              mut query: Query<&mut KWindowState>,
+              mut closed: EventWriter<WindowClosed>| {
+            if let EventType::Click(..) = event.event_type {
+                event.stop_propagation();
+                if let Ok(mut window) = query.get_mut(state_entity) {
+                    match button {
+                        TitleButton::Close => {
+                            window.closed = true;
+                            closed.send(WindowClosed {
+                                entity: window_entity,
+                            });
+                        }
+                        TitleButton::Minimize => {
+                            window.minimized = !window.minimized;
+                            window.maximized = false;
+                        }
+                        TitleButton::Maximize => {
+                            window.maximized = !window.maximized;
+                            window.minimized = false;
+                        }
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Resizes the window along the given axes while its grip is pressed
+///
+/// The pressed grip captures the cursor, so it keeps receiving hover events when the cursor
+/// moves faster than the window grows.
+fn resize_grip_event(state_entity: Entity, axes: Vec2, min_size: Vec2, max_size: Vec2) -> OnEvent {
+    OnEvent::new(
+        move |In(_entity): In<Entity>,
+              mut event: ResMut<KEvent>,
+              mut query: Query<&mut KWindowState>| {
+            if let Ok(mut window) = query.get_mut(state_entity) {
+                match event.event_type {
+                    EventType::MouseDown(data) => {
+                        window.resize_start = Some((window.size, data.position.into()));
+                    }
+                    EventType::Hover(data) if data.pressed => {
+                        if let Some((start_size, start_position)) = window.resize_start {
+                            let delta = Vec2::from(data.position) - start_position;
+                            let size = (start_size + delta * axes).clamp(min_size, max_size);
+                            if size != window.size {
+                                window.size = size;
+                            }
+                        }
+                    }
+                    EventType::MouseUp(..) => {
+                        window.resize_start = None;
+                    }
+                    _ => {}
+                }
+                event.stop_propagation();
+            }
+        },
+    )
+}
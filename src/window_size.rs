@@ -7,6 +7,36 @@ use bevy::{
 #[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
 pub struct WindowSize(pub f32, pub f32);
 
+/// A scale applied to the whole UI on top of the window's scale factor
+///
+/// At a scale of `2.0` everything is drawn twice as large, and the root of the UI is laid out in
+/// half as many pixels. Useful for accessibility zoom or pixel art games. Changing it relayouts
+/// every context.
+///
+/// Text is drawn from distance fields, so it stays sharp at any scale. Fractional scales can
+/// still place glyph edges between physical pixels, which makes small text look slightly soft.
+///
+/// Scales that aren't positive are ignored, and the UI is drawn at a scale of `1.0` instead.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct KayakUiScale(pub f32);
+
+impl Default for KayakUiScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl KayakUiScale {
+    /// Get the scale to apply, falling back to `1.0` if it isn't a positive number
+    pub fn get(&self) -> f32 {
+        if self.0.is_finite() && self.0 > 0.0 {
+            self.0
+        } else {
+            1.0
+        }
+    }
+}
+
 pub fn update_window_size(
     mut window_resized_events: EventReader<WindowResized>,
     mut window_created_events: EventReader<WindowCreated>,